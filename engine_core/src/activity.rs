@@ -0,0 +1,268 @@
+// Activity analysis: finds low-activity (idle) spans in a recording.
+// Drives "speed up the boring parts": idle spans become suggested speed ramps the user can accept.
+// See steering.md: Auto-Zoom Strategy Rules
+
+use serde::{Deserialize, Serialize};
+
+use crate::time_remap::SpeedRampConfig;
+use crate::types::*;
+
+/// Tuning for idle detection and the ramps suggested over idle spans.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivitySettings {
+    /// Shortest span (after padding) worth speeding up, in microseconds.
+    #[serde(default = "default_min_idle")]
+    pub min_idle_us: u64,
+    /// Cursor speed (normalized units per second) below which the cursor counts as still.
+    #[serde(default = "default_motion_threshold")]
+    pub motion_threshold: f32,
+    /// Time kept at 1x on each side of an activity, in microseconds.
+    #[serde(default = "default_padding")]
+    pub padding_us: u64,
+    /// Speed suggested over idle spans.
+    #[serde(default = "default_idle_speed")]
+    pub idle_speed: f32,
+    /// Ease-in/out window of suggested ramps, in microseconds.
+    #[serde(default = "default_ease")]
+    pub ease_us: u64,
+}
+
+fn default_min_idle() -> u64 {
+    3_000_000
+}
+fn default_motion_threshold() -> f32 {
+    0.05
+}
+fn default_padding() -> u64 {
+    500_000
+}
+fn default_idle_speed() -> f32 {
+    3.0
+}
+fn default_ease() -> u64 {
+    300_000
+}
+
+impl Default for ActivitySettings {
+    fn default() -> Self {
+        ActivitySettings {
+            min_idle_us: default_min_idle(),
+            motion_threshold: default_motion_threshold(),
+            padding_us: default_padding(),
+            idle_speed: default_idle_speed(),
+            ease_us: default_ease(),
+        }
+    }
+}
+
+/// Whether an event is deliberate user input (as opposed to motion samples or frames).
+pub(crate) fn is_discrete_activity(event_type: &EventType) -> bool {
+    matches!(
+        event_type,
+        EventType::MouseClick { .. }
+            | EventType::MouseDown { .. }
+            | EventType::MouseUp { .. }
+            | EventType::FocusChange { .. }
+            | EventType::TextSelection { .. }
+            | EventType::Scroll { .. }
+            | EventType::KeyPress { .. }
+            | EventType::KeyDown { .. }
+            | EventType::Touch {
+                phase: PointerPhase::Down,
+                ..
+            }
+            | EventType::Pen {
+                phase: PointerPhase::Down,
+                ..
+            }
+    )
+}
+
+/// A span with no clicks, focus changes, scrolling, or typing and little cursor motion.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IdleSpan {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+impl IdleSpan {
+    pub fn duration_us(&self) -> u64 {
+        self.end.as_micros() - self.start.as_micros()
+    }
+}
+
+/// Finds idle spans from input events and the cursor track.
+pub struct ActivityAnalyzer {
+    settings: ActivitySettings,
+}
+
+impl ActivityAnalyzer {
+    pub fn new(settings: ActivitySettings) -> Self {
+        ActivityAnalyzer { settings }
+    }
+
+    /// Instants where something happened: discrete input events, and both ends of
+    /// every cursor step faster than the motion threshold. Sorted.
+    fn activity_instants(
+        &self,
+        signals: &SignalBatch,
+        cursor_track: &[CursorTrackPoint],
+    ) -> Vec<u64> {
+        let mut instants: Vec<u64> = signals
+            .events
+            .iter()
+            .filter(|event| is_discrete_activity(&event.event_type))
+            .map(|event| event.timestamp.as_micros())
+            .collect();
+
+        for pair in cursor_track.windows(2) {
+            let dt_us = pair[1]
+                .timestamp
+                .as_micros()
+                .saturating_sub(pair[0].timestamp.as_micros());
+            if dt_us == 0 {
+                continue;
+            }
+            let dx = pair[1].position.x - pair[0].position.x;
+            let dy = pair[1].position.y - pair[0].position.y;
+            let speed = (dx * dx + dy * dy).sqrt() / (dt_us as f32 / 1_000_000.0);
+            if speed > self.settings.motion_threshold {
+                instants.push(pair[0].timestamp.as_micros());
+                instants.push(pair[1].timestamp.as_micros());
+            }
+        }
+
+        instants.sort_unstable();
+        instants.dedup();
+        instants
+    }
+
+    /// Idle spans between activity, each shrunk by `padding_us` on both sides.
+    /// The recording bounds come from the earliest and latest signal.
+    pub fn find_idle_spans(
+        &self,
+        signals: &SignalBatch,
+        cursor_track: &[CursorTrackPoint],
+    ) -> Vec<IdleSpan> {
+        let bounds = signals
+            .events
+            .iter()
+            .map(|event| event.timestamp.as_micros())
+            .chain(cursor_track.iter().map(|point| point.timestamp.as_micros()));
+        let (Some(first), Some(last)) = (bounds.clone().min(), bounds.max()) else {
+            return Vec::new();
+        };
+
+        let instants = self.activity_instants(signals, cursor_track);
+        let padding = self.settings.padding_us;
+
+        // Gaps between consecutive activity, plus the lead-in and tail. The recording
+        // edges aren't activity, so no padding is kept against them.
+        let mut gaps = Vec::with_capacity(instants.len() + 1);
+        match (instants.first(), instants.last()) {
+            (Some(&head), Some(&tail)) => {
+                gaps.push((first, head.saturating_sub(padding)));
+                gaps.extend(
+                    instants
+                        .windows(2)
+                        .map(|pair| (pair[0] + padding, pair[1].saturating_sub(padding))),
+                );
+                gaps.push((tail + padding, last));
+            }
+            _ => gaps.push((first, last)),
+        }
+
+        gaps.into_iter()
+            .filter(|&(start, end)| end > start && end - start >= self.settings.min_idle_us)
+            .map(|(start, end)| IdleSpan {
+                start: Timestamp::from_micros(start),
+                end: Timestamp::from_micros(end),
+            })
+            .collect()
+    }
+
+    /// Suggested speed ramps over idle spans, ready to pass to a TimeRemapper.
+    pub fn suggest_speed_ramps(
+        &self,
+        signals: &SignalBatch,
+        cursor_track: &[CursorTrackPoint],
+    ) -> Vec<SpeedRampConfig> {
+        self.find_idle_spans(signals, cursor_track)
+            .into_iter()
+            .map(|span| SpeedRampConfig {
+                start_us: span.start.as_micros(),
+                end_us: span.end.as_micros(),
+                speed: self.settings.idle_speed,
+                ramp_in_us: self.settings.ease_us,
+                ramp_out_us: self.settings.ease_us,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(us: u64) -> InputEvent {
+        InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::MouseClick {
+                position: NormalizedCoord::center(),
+                button: 0,
+                element: None,
+            },
+        }
+    }
+
+    fn still_point(us: u64) -> CursorTrackPoint {
+        CursorTrackPoint {
+            timestamp: Timestamp::from_micros(us),
+            position: NormalizedCoord::center(),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        }
+    }
+
+    #[test]
+    fn idle_gap_between_clicks_becomes_ramp() {
+        let signals = SignalBatch {
+            events: vec![click(1_000_000), click(11_000_000), click(12_000_000)],
+        };
+        let track: Vec<CursorTrackPoint> = (0..=12).map(|s| still_point(s * 1_000_000)).collect();
+
+        let analyzer = ActivityAnalyzer::new(ActivitySettings::default());
+        let spans = analyzer.find_idle_spans(&signals, &track);
+        assert_eq!(
+            spans,
+            vec![IdleSpan {
+                start: Timestamp::from_micros(1_500_000),
+                end: Timestamp::from_micros(10_500_000),
+            }]
+        );
+
+        let ramps = analyzer.suggest_speed_ramps(&signals, &track);
+        assert_eq!(ramps.len(), 1);
+        assert_eq!(ramps[0].speed, 3.0);
+    }
+
+    #[test]
+    fn cursor_motion_counts_as_activity() {
+        let signals = SignalBatch {
+            events: vec![click(0), click(12_000_000)],
+        };
+        let mut track: Vec<CursorTrackPoint> =
+            (0..=12).map(|s| still_point(s * 1_000_000)).collect();
+        // A fast move from 5s to 7s splits the 12s gap in two.
+        track[6].position = NormalizedCoord::new(0.9, 0.9);
+
+        let analyzer = ActivityAnalyzer::new(ActivitySettings::default());
+        let spans = analyzer.find_idle_spans(&signals, &track);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].end.as_micros(), 4_500_000);
+        assert_eq!(spans[1].start.as_micros(), 7_500_000);
+        assert_eq!(spans[1].end.as_micros(), 11_500_000);
+    }
+}
@@ -0,0 +1,195 @@
+// After Effects keyframe data: the camera and click rings as text that pastes onto a
+// layer's properties (AE's clipboard "Keyframe Data" format), for users who finish
+// their edits in After Effects.
+// See steering.md: Rust Rules (Engine)
+
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+
+use crate::camera::CameraEngine;
+use crate::types::{EffectTrack, EffectType, Timestamp};
+
+/// Keyframe data for each layer to paste, one string per layer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AfterEffectsExport {
+    /// Position and Scale for the layer holding the recording.
+    pub camera: String,
+    /// Position, Scale, and Opacity for one ring layer per click ring.
+    pub click_rings: Vec<String>,
+}
+
+/// Camera and click-ring keyframe data for a `width`x`height` comp at `fps`, covering
+/// `duration_us` of the recording from 0. The camera is sampled every frame (so its
+/// easing survives pasting as linear keys), keeping only the ends of runs that hold
+/// still. Ring scale is the effect's envelope, for a ring drawn at its style's radius.
+pub fn after_effects_export(
+    camera: &CameraEngine,
+    track: &EffectTrack,
+    fps: f64,
+    duration_us: u64,
+    width: u32,
+    height: u32,
+) -> AfterEffectsExport {
+    let (w, h) = (width as f32, height as f32);
+    let frame_count = (duration_us as f64 * fps / 1_000_000.0).ceil() as u64;
+
+    // The layer keeps its anchor at its center; moving and scaling it frames the
+    // viewport in the comp.
+    let mut position = Vec::new();
+    let mut scale = Vec::new();
+    for frame in 0..frame_count {
+        let viewport = camera.get_viewport_at(Timestamp::from_frame(frame, fps));
+        let zoom = viewport.zoom;
+        position.push((
+            frame,
+            vec![
+                w / 2.0 + (0.5 - viewport.center.x) * w * zoom,
+                h / 2.0 + (0.5 - viewport.center.y) * h * zoom,
+                0.0,
+            ],
+        ));
+        scale.push((frame, vec![zoom * 100.0, zoom * 100.0, 100.0]));
+    }
+    let mut camera_data = header(fps, width, height);
+    push_property(&mut camera_data, "Position", PIXELS, &drop_holds(position));
+    push_property(&mut camera_data, "Scale", PERCENT, &drop_holds(scale));
+    camera_data.push_str(FOOTER);
+
+    let click_rings = track
+        .effects
+        .iter()
+        .filter(|effect| matches!(effect.effect_type, EffectType::ClickRing))
+        .map(|effect| {
+            let start = effect.timestamp.frame_at(fps);
+            let end = Timestamp::from_micros(effect.timestamp.as_micros() + effect.duration_us)
+                .frame_at(fps);
+            let states: Vec<(u64, f32, f32)> = (start..=end)
+                .filter_map(|frame| {
+                    let at = Timestamp::from_frame(frame, fps).max(effect.timestamp);
+                    let state = effect.state_at(at)?;
+                    Some((frame, state.scale, state.opacity))
+                })
+                .collect();
+
+            let mut data = header(fps, width, height);
+            let at = vec![(
+                start,
+                vec![effect.position.x * w, effect.position.y * h, 0.0],
+            )];
+            push_property(&mut data, "Position", PIXELS, &at);
+            let scale: Vec<_> = states
+                .iter()
+                .map(|&(frame, scale, _)| (frame, vec![scale * 100.0, scale * 100.0, 100.0]))
+                .collect();
+            push_property(&mut data, "Scale", PERCENT, &scale);
+            let opacity: Vec<_> = states
+                .iter()
+                .map(|&(frame, _, opacity)| (frame, vec![opacity * 100.0]))
+                .collect();
+            push_property(&mut data, "Opacity", &["percent"], &opacity);
+            data.push_str(FOOTER);
+            data
+        })
+        .collect();
+
+    AfterEffectsExport {
+        camera: camera_data,
+        click_rings,
+    }
+}
+
+const PIXELS: &[&str] = &["X pixels", "Y pixels", "Z pixels"];
+const PERCENT: &[&str] = &["X percent", "Y percent", "Z percent"];
+const FOOTER: &str = "\nEnd of Keyframe Data\n";
+
+fn header(fps: f64, width: u32, height: u32) -> String {
+    format!(
+        "Adobe After Effects 8.0 Keyframe Data\n\n\
+         \tUnits Per Second\t{}\n\
+         \tSource Width\t{}\n\
+         \tSource Height\t{}\n\
+         \tSource Pixel Aspect Ratio\t1\n\
+         \tComp Pixel Aspect Ratio\t1\n",
+        fps, width, height
+    )
+}
+
+fn push_property(data: &mut String, name: &str, columns: &[&str], keys: &[(u64, Vec<f32>)]) {
+    if keys.is_empty() {
+        return;
+    }
+    let _ = write!(data, "\nTransform\t{}\n\tFrame\t", name);
+    for column in columns {
+        let _ = write!(data, "{}\t", column);
+    }
+    data.push('\n');
+    for (frame, values) in keys {
+        let _ = write!(data, "\t{}\t", frame);
+        for value in values {
+            let _ = write!(data, "{}\t", round3(*value));
+        }
+        data.push('\n');
+    }
+}
+
+/// Drop keys in the middle of runs of equal values; linear keys at the run's ends
+/// hold the same value in between.
+fn drop_holds(keys: Vec<(u64, Vec<f32>)>) -> Vec<(u64, Vec<f32>)> {
+    let mut kept: Vec<(u64, Vec<f32>)> = Vec::with_capacity(keys.len());
+    for (i, key) in keys.iter().enumerate() {
+        let same_as = |other: Option<&(u64, Vec<f32>)>| other.is_some_and(|other| other.1 == key.1);
+        if i > 0 && same_as(keys.get(i - 1)) && same_as(keys.get(i + 1)) {
+            continue;
+        }
+        kept.push(key.clone());
+    }
+    kept
+}
+
+/// Values to three decimals, without trailing zeros.
+fn round3(value: f32) -> f32 {
+    (value * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CameraSettings, Effect, NormalizedCoord};
+
+    #[test]
+    fn exports_camera_and_ring_keyframes() {
+        let camera = CameraEngine::new(CameraSettings::default());
+        let ring = EffectType::ClickRing;
+        let track = EffectTrack {
+            effects: vec![Effect {
+                timestamp: Timestamp::from_micros(1_000_000),
+                duration_us: 100_000,
+                position: NormalizedCoord::new(0.25, 0.5),
+                style: ring.default_style(),
+                envelope: ring.default_envelope(),
+                effect_type: ring,
+            }],
+        };
+
+        let export = after_effects_export(&camera, &track, 30.0, 2_000_000, 1920, 1080);
+        assert!(export
+            .camera
+            .starts_with("Adobe After Effects 8.0 Keyframe Data\n\n\tUnits Per Second\t30\n"));
+        // A still camera keeps only the first and last frames.
+        assert!(export.camera.contains(
+            "Transform\tPosition\n\tFrame\tX pixels\tY pixels\tZ pixels\t\n\
+             \t0\t960\t540\t0\t\n\t59\t960\t540\t0\t\n"
+        ));
+        assert!(export.camera.ends_with("\nEnd of Keyframe Data\n"));
+
+        assert_eq!(export.click_rings.len(), 1);
+        let ring = &export.click_rings[0];
+        assert!(ring.contains(
+            "Transform\tPosition\n\tFrame\tX pixels\tY pixels\tZ pixels\t\n\t30\t480\t540\t0\t\n"
+        ));
+        // Grows from 30% and fades over frames 30-33.
+        assert!(ring.contains("\t30\t30\t30\t100\t\n"));
+        assert!(ring.contains("Transform\tOpacity\n\tFrame\tpercent\t\n\t30\t90\t\n"));
+    }
+}
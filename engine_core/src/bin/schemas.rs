@@ -0,0 +1,28 @@
+// Writes the engine's JSON Schemas: all of them as one object to stdout, or with a
+// directory argument, one `<Type>.schema.json` file per type.
+// cargo run --features schema --bin schemas [-- <dir>]
+
+use std::{env, fs, path::Path, process};
+
+fn main() {
+    let schemas = engine_core::json_schemas();
+    let Some(dir) = env::args().nth(1) else {
+        println!("{:#}", schemas);
+        return;
+    };
+
+    let dir = Path::new(&dir);
+    let written = fs::create_dir_all(dir).and_then(|_| {
+        for (name, schema) in schemas.as_object().into_iter().flatten() {
+            fs::write(
+                dir.join(format!("{}.schema.json", name)),
+                format!("{:#}\n", schema),
+            )?;
+        }
+        Ok(())
+    });
+    if let Err(e) = written {
+        eprintln!("Could not write schemas to {}: {}", dir.display(), e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,723 @@
+// Binary signal ingestion and result encoding. Fixed-stride little-endian records instead of JSON.
+// Lets JS hand a whole Uint8Array of events across the boundary in one copy, and read results back
+// with a DataView instead of parsing a multi-megabyte JSON string.
+// See steering.md: Rust Rules (Engine)
+
+use crate::error::EngineError;
+use crate::types::*;
+
+/// Size of one encoded event record in bytes.
+///
+/// Record layout (all values little-endian):
+///
+/// | offset | size | field                                            |
+/// |--------|------|--------------------------------------------------|
+/// | 0      | 8    | timestamp in microseconds (u64)                  |
+/// | 8      | 1    | event kind (see `EVENT_KIND_*`)                  |
+/// | 9      | 1    | mouse button (MouseClick/Down/Up), confidence    |
+/// |        |      | (CursorDetected), key modifiers (Key*; bit 0     |
+/// |        |      | shift, 1 ctrl, 2 alt, 3 meta), or 1 if playing   |
+/// |        |      | (VideoPlaying)                                   |
+/// | 10     | 2    | reserved, must be zero                           |
+/// | 12     | 4    | a: x, bounds.x, scroll delta_y, or window dx     |
+/// |        |      | (f32)                                            |
+/// | 16     | 4    | b: y, bounds.y, or window dy (f32)               |
+/// | 20     | 4    | c: bounds.width (f32)                            |
+/// | 24     | 4    | d: bounds.height (f32)                           |
+/// | 28     | 4    | frame index (u32, FrameCaptured only)            |
+///
+/// Touch and pen records put the phase (`PointerPhase` declaration order) in byte 9,
+/// the pressure in c, and the pointer id in bytes 28..32.
+///
+/// FocusChange and MouseClick element metadata is not carried; send it as JSON when
+/// needed.
+/// Key events store the key as UTF-8 in bytes 12..28 instead of a-d, zero-padded
+/// (keys longer than 16 bytes are truncated at a character boundary).
+pub const SIGNAL_RECORD_SIZE: usize = 32;
+
+pub const EVENT_KIND_MOUSE_MOVE: u8 = 0;
+pub const EVENT_KIND_MOUSE_CLICK: u8 = 1;
+pub const EVENT_KIND_FOCUS_CHANGE: u8 = 2;
+pub const EVENT_KIND_SCROLL: u8 = 3;
+pub const EVENT_KIND_FRAME_CAPTURED: u8 = 4;
+pub const EVENT_KIND_CURSOR_DETECTED: u8 = 5;
+pub const EVENT_KIND_MOUSE_DOWN: u8 = 6;
+pub const EVENT_KIND_MOUSE_UP: u8 = 7;
+pub const EVENT_KIND_KEY_PRESS: u8 = 8;
+pub const EVENT_KIND_KEY_DOWN: u8 = 9;
+pub const EVENT_KIND_KEY_UP: u8 = 10;
+pub const EVENT_KIND_TOUCH: u8 = 11;
+pub const EVENT_KIND_PEN: u8 = 12;
+pub const EVENT_KIND_PAUSE: u8 = 13;
+pub const EVENT_KIND_RESUME: u8 = 14;
+pub const EVENT_KIND_TEXT_SELECTION: u8 = 15;
+pub const EVENT_KIND_VIDEO_PLAYING: u8 = 16;
+pub const EVENT_KIND_WINDOW_MOVED: u8 = 17;
+
+const KEY_OFFSET: usize = 12;
+const KEY_MAX_BYTES: usize = 16;
+
+/// Decode a buffer of fixed-size event records into a signal batch.
+pub fn decode_signal_buffer(buffer: &[u8]) -> Result<SignalBatch, EngineError> {
+    if !buffer.len().is_multiple_of(SIGNAL_RECORD_SIZE) {
+        return Err(EngineError::SignalProcessing(format!(
+            "buffer length {} is not a multiple of the {}-byte record size",
+            buffer.len(),
+            SIGNAL_RECORD_SIZE
+        )));
+    }
+
+    let events = buffer
+        .chunks_exact(SIGNAL_RECORD_SIZE)
+        .enumerate()
+        .map(|(index, record)| decode_record(index, record))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(SignalBatch { events })
+}
+
+/// Encode a single event as a fixed-size record.
+/// Mirrors the layout JS writes; used by tests and by tooling that replays batches.
+pub fn encode_signal_record(event: &InputEvent) -> [u8; SIGNAL_RECORD_SIZE] {
+    let mut record = [0u8; SIGNAL_RECORD_SIZE];
+    record[0..8].copy_from_slice(&event.timestamp.as_micros().to_le_bytes());
+
+    let (kind, button, fields, frame_index) = match &event.event_type {
+        EventType::MouseMove { position } => (
+            EVENT_KIND_MOUSE_MOVE,
+            0,
+            [position.x, position.y, 0.0, 0.0],
+            0,
+        ),
+        EventType::MouseClick {
+            position, button, ..
+        } => (
+            EVENT_KIND_MOUSE_CLICK,
+            *button,
+            [position.x, position.y, 0.0, 0.0],
+            0,
+        ),
+        EventType::MouseDown { position, button } => (
+            EVENT_KIND_MOUSE_DOWN,
+            *button,
+            [position.x, position.y, 0.0, 0.0],
+            0,
+        ),
+        EventType::MouseUp { position, button } => (
+            EVENT_KIND_MOUSE_UP,
+            *button,
+            [position.x, position.y, 0.0, 0.0],
+            0,
+        ),
+        EventType::FocusChange { bounds, .. } => (
+            EVENT_KIND_FOCUS_CHANGE,
+            0,
+            [bounds.x, bounds.y, bounds.width, bounds.height],
+            0,
+        ),
+        EventType::Scroll { delta_y } => (EVENT_KIND_SCROLL, 0, [*delta_y, 0.0, 0.0, 0.0], 0),
+        EventType::FrameCaptured { frame_index } => {
+            (EVENT_KIND_FRAME_CAPTURED, 0, [0.0; 4], frame_index.as_u32())
+        }
+        EventType::CursorDetected {
+            position,
+            confidence,
+        } => (
+            EVENT_KIND_CURSOR_DETECTED,
+            *confidence,
+            [position.x, position.y, 0.0, 0.0],
+            0,
+        ),
+        EventType::Touch {
+            position,
+            pointer_id,
+            pressure,
+            phase,
+        } => (
+            EVENT_KIND_TOUCH,
+            *phase as u8,
+            [position.x, position.y, *pressure, 0.0],
+            *pointer_id,
+        ),
+        EventType::Pen {
+            position,
+            pointer_id,
+            pressure,
+            phase,
+        } => (
+            EVENT_KIND_PEN,
+            *phase as u8,
+            [position.x, position.y, *pressure, 0.0],
+            *pointer_id,
+        ),
+        EventType::Pause => (EVENT_KIND_PAUSE, 0, [0.0; 4], 0),
+        EventType::Resume => (EVENT_KIND_RESUME, 0, [0.0; 4], 0),
+        EventType::TextSelection { bounds } => (
+            EVENT_KIND_TEXT_SELECTION,
+            0,
+            [bounds.x, bounds.y, bounds.width, bounds.height],
+            0,
+        ),
+        EventType::VideoPlaying { bounds, playing } => (
+            EVENT_KIND_VIDEO_PLAYING,
+            *playing as u8,
+            [bounds.x, bounds.y, bounds.width, bounds.height],
+            0,
+        ),
+        EventType::WindowMoved { dx, dy } => (EVENT_KIND_WINDOW_MOVED, 0, [*dx, *dy, 0.0, 0.0], 0),
+        EventType::KeyPress { key, modifiers } => {
+            return encode_key_record(record, EVENT_KIND_KEY_PRESS, key, modifiers);
+        }
+        EventType::KeyDown { key, modifiers } => {
+            return encode_key_record(record, EVENT_KIND_KEY_DOWN, key, modifiers);
+        }
+        EventType::KeyUp { key, modifiers } => {
+            return encode_key_record(record, EVENT_KIND_KEY_UP, key, modifiers);
+        }
+    };
+
+    record[8] = kind;
+    record[9] = button;
+    for (i, value) in fields.iter().enumerate() {
+        let offset = 12 + i * 4;
+        record[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+    record[28..32].copy_from_slice(&frame_index.to_le_bytes());
+    record
+}
+
+fn encode_key_record(
+    mut record: [u8; SIGNAL_RECORD_SIZE],
+    kind: u8,
+    key: &str,
+    modifiers: &KeyModifiers,
+) -> [u8; SIGNAL_RECORD_SIZE] {
+    let mut len = key.len().min(KEY_MAX_BYTES);
+    while !key.is_char_boundary(len) {
+        len -= 1;
+    }
+    record[8] = kind;
+    record[9] = u8::from(modifiers.shift)
+        | u8::from(modifiers.ctrl) << 1
+        | u8::from(modifiers.alt) << 2
+        | u8::from(modifiers.meta) << 3;
+    record[KEY_OFFSET..KEY_OFFSET + len].copy_from_slice(&key.as_bytes()[..len]);
+    record
+}
+
+/// Magic bytes at the start of an encoded `AnalysisResult`.
+pub const ANALYSIS_MAGIC: [u8; 4] = *b"SMAR";
+/// Version of the encoded `AnalysisResult` layout.
+pub const ANALYSIS_FORMAT_VERSION: u16 = 2;
+
+const ANALYSIS_HEADER_SIZE: usize = 24;
+const CURSOR_POINT_RECORD_SIZE: usize = 24;
+const FOCUS_REGION_RECORD_SIZE: usize = 32;
+const KEYFRAME_RECORD_SIZE: usize = 32;
+const EFFECT_RECORD_SIZE: usize = 32;
+
+/// Encode an analysis result into the compact binary layout.
+///
+/// Layout (all values little-endian, every record 8-byte aligned):
+///
+/// Header (24 bytes):
+/// `magic "SMAR" | version u16 | reserved u16 | cursor_count u32 | focus_count u32 |
+///  keyframe_count u32 | effect_count u32`
+///
+/// Followed by the four sections in that order:
+/// - cursor point (24 bytes): `ts u64 | x f32 | y f32 | state u8 | confidence u8 |
+///   reason u8 | reason_detail u8 | reserved u32`
+/// - focus region (32 bytes): `ts u64 | x f32 | y f32 | width f32 | height f32 |
+///   importance f32 | reserved u32`
+/// - keyframe (32 bytes): `ts u64 | center_x f32 | center_y f32 | zoom f32 |
+///   easing u8 | flags u8 | reason u8 | reserved u8 | hold_us u64` (flags bit 0: manual)
+/// - effect (32 bytes): `ts u64 | duration_us u64 | x f32 | y f32 | effect_type u8 |
+///   reserved [u8; 7]`
+///
+/// Enum codes follow declaration order in `types.rs` (e.g. `CursorState::Visible = 0`).
+/// `reason_detail` carries the detection confidence for `CursorDetection`, otherwise 0.
+/// Drags, audio cues, and the blur track are not encoded; read them with
+/// `Engine::get_drags`, `Engine::get_audio_cues`, and `Engine::get_blur_track`. Neither
+/// are effect styles, envelopes, keystroke and label text, trail and ribbon points,
+/// custom effect names and parameters, or watermark assets; read them from the JSON or
+/// object outputs.
+///
+/// JS decode sketch:
+/// ```js
+/// const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+/// const cursorCount = view.getUint32(8, true);
+/// let offset = 24;
+/// for (let i = 0; i < cursorCount; i++, offset += 24) {
+///   const ts = Number(view.getBigUint64(offset, true));
+///   const x = view.getFloat32(offset + 8, true);
+///   const y = view.getFloat32(offset + 12, true);
+///   // ...
+/// }
+/// ```
+pub fn encode_analysis_result(result: &AnalysisResult) -> Vec<u8> {
+    let effects = &result.effect_tracks.effects;
+    let mut out = Vec::with_capacity(
+        ANALYSIS_HEADER_SIZE
+            + result.cursor_track.len() * CURSOR_POINT_RECORD_SIZE
+            + result.focus_regions.len() * FOCUS_REGION_RECORD_SIZE
+            + result.camera_keyframes.len() * KEYFRAME_RECORD_SIZE
+            + effects.len() * EFFECT_RECORD_SIZE,
+    );
+
+    out.extend_from_slice(&ANALYSIS_MAGIC);
+    out.extend_from_slice(&ANALYSIS_FORMAT_VERSION.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    for count in [
+        result.cursor_track.len(),
+        result.focus_regions.len(),
+        result.camera_keyframes.len(),
+        effects.len(),
+    ] {
+        out.extend_from_slice(&(count as u32).to_le_bytes());
+    }
+
+    for point in &result.cursor_track {
+        let (reason, reason_detail) = match point.reason {
+            InferenceReason::DirectInput => (0, 0),
+            InferenceReason::CursorDetection { confidence } => (1, confidence),
+            InferenceReason::MotionTracking => (2, 0),
+            InferenceReason::UiChange => (3, 0),
+            InferenceReason::SaliencyFallback => (4, 0),
+            InferenceReason::Outlier => (5, 0),
+        };
+        let state = match point.state {
+            CursorState::Visible => 0,
+            CursorState::Hidden => 1,
+            CursorState::Inferred => 2,
+        };
+        out.extend_from_slice(&point.timestamp.as_micros().to_le_bytes());
+        out.extend_from_slice(&point.position.x.to_le_bytes());
+        out.extend_from_slice(&point.position.y.to_le_bytes());
+        out.extend_from_slice(&[state, point.confidence, reason, reason_detail]);
+        out.extend_from_slice(&[0u8; 4]);
+    }
+
+    for region in &result.focus_regions {
+        out.extend_from_slice(&region.timestamp.as_micros().to_le_bytes());
+        for value in [
+            region.bounds.x,
+            region.bounds.y,
+            region.bounds.width,
+            region.bounds.height,
+            region.importance,
+        ] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        out.extend_from_slice(&[0u8; 4]);
+    }
+
+    for keyframe in &result.camera_keyframes {
+        let easing = match keyframe.easing {
+            EasingType::Linear => 0,
+            EasingType::EaseOut => 1,
+            EasingType::EaseInOut => 2,
+            EasingType::Spring => 3,
+            // Control points are only carried by the JSON/object outputs.
+            EasingType::CubicBezier(..) => 4,
+        };
+        out.extend_from_slice(&keyframe.timestamp.as_micros().to_le_bytes());
+        out.extend_from_slice(&keyframe.viewport.center.x.to_le_bytes());
+        out.extend_from_slice(&keyframe.viewport.center.y.to_le_bytes());
+        out.extend_from_slice(&keyframe.viewport.zoom.to_le_bytes());
+        let flags = keyframe.is_manual as u8;
+        let reason = match keyframe.reason {
+            KeyframeReason::Initial => 0,
+            KeyframeReason::Cursor => 1,
+            KeyframeReason::ClickTarget => 2,
+            KeyframeReason::FocusRegion => 3,
+            KeyframeReason::MultiRegionFit => 4,
+            KeyframeReason::CursorAndFocus => 5,
+            KeyframeReason::VelocityZoomOut => 6,
+            KeyframeReason::Manual => 7,
+            KeyframeReason::Smoothing => 8,
+            KeyframeReason::SceneChange => 9,
+        };
+        out.extend_from_slice(&[easing, flags, reason, 0]);
+        out.extend_from_slice(&keyframe.hold_us.to_le_bytes());
+    }
+
+    for effect in effects {
+        let effect_type = match effect.effect_type {
+            EffectType::ClickRing => 0,
+            EffectType::CursorHighlight => 1,
+            EffectType::Spotlight => 2,
+            EffectType::Keystrokes { .. } => 3,
+            EffectType::Trail { .. } => 4,
+            EffectType::DragRibbon { .. } => 5,
+            EffectType::Custom { .. } => 6,
+            EffectType::Watermark { .. } => 7,
+            EffectType::ProgressBar { .. } => 8,
+            EffectType::ClickLabel { .. } => 9,
+        };
+        out.extend_from_slice(&effect.timestamp.as_micros().to_le_bytes());
+        out.extend_from_slice(&effect.duration_us.to_le_bytes());
+        out.extend_from_slice(&effect.position.x.to_le_bytes());
+        out.extend_from_slice(&effect.position.y.to_le_bytes());
+        out.extend_from_slice(&[effect_type, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    out
+}
+
+fn decode_record(index: usize, record: &[u8]) -> Result<InputEvent, EngineError> {
+    let timestamp = Timestamp::from_micros(read_u64(record, 0));
+    let a = read_f32(record, 12);
+    let b = read_f32(record, 16);
+
+    let event_type = match record[8] {
+        EVENT_KIND_MOUSE_MOVE => EventType::MouseMove {
+            position: NormalizedCoord::new(a, b),
+        },
+        EVENT_KIND_MOUSE_CLICK => EventType::MouseClick {
+            position: NormalizedCoord::new(a, b),
+            button: record[9],
+            element: None,
+        },
+        EVENT_KIND_MOUSE_DOWN => EventType::MouseDown {
+            position: NormalizedCoord::new(a, b),
+            button: record[9],
+        },
+        EVENT_KIND_MOUSE_UP => EventType::MouseUp {
+            position: NormalizedCoord::new(a, b),
+            button: record[9],
+        },
+        EVENT_KIND_FOCUS_CHANGE => EventType::FocusChange {
+            bounds: NormalizedRect::new(a, b, read_f32(record, 20), read_f32(record, 24)),
+            element: None,
+        },
+        EVENT_KIND_SCROLL => EventType::Scroll { delta_y: a },
+        EVENT_KIND_FRAME_CAPTURED => EventType::FrameCaptured {
+            frame_index: FrameIndex::new(read_u32(record, 28)),
+        },
+        EVENT_KIND_CURSOR_DETECTED => EventType::CursorDetected {
+            position: NormalizedCoord::new(a, b),
+            confidence: record[9].min(100),
+        },
+        EVENT_KIND_PAUSE => EventType::Pause,
+        EVENT_KIND_RESUME => EventType::Resume,
+        EVENT_KIND_TEXT_SELECTION => EventType::TextSelection {
+            bounds: NormalizedRect::new(a, b, read_f32(record, 20), read_f32(record, 24)),
+        },
+        EVENT_KIND_VIDEO_PLAYING => EventType::VideoPlaying {
+            bounds: NormalizedRect::new(a, b, read_f32(record, 20), read_f32(record, 24)),
+            playing: record[9] != 0,
+        },
+        EVENT_KIND_WINDOW_MOVED => EventType::WindowMoved { dx: a, dy: b },
+        EVENT_KIND_TOUCH | EVENT_KIND_PEN => {
+            let position = NormalizedCoord::new(a, b);
+            let pointer_id = read_u32(record, 28);
+            let pressure = read_f32(record, 20);
+            let phase = match record[9] {
+                0 => PointerPhase::Down,
+                1 => PointerPhase::Move,
+                2 => PointerPhase::Up,
+                phase => {
+                    return Err(EngineError::SignalProcessing(format!(
+                        "unknown pointer phase {} in record {}",
+                        phase, index
+                    )))
+                }
+            };
+            if record[8] == EVENT_KIND_TOUCH {
+                EventType::Touch {
+                    position,
+                    pointer_id,
+                    pressure,
+                    phase,
+                }
+            } else {
+                EventType::Pen {
+                    position,
+                    pointer_id,
+                    pressure,
+                    phase,
+                }
+            }
+        }
+        EVENT_KIND_KEY_PRESS | EVENT_KIND_KEY_DOWN | EVENT_KIND_KEY_UP => {
+            let (key, modifiers) = decode_key(index, record)?;
+            match record[8] {
+                EVENT_KIND_KEY_PRESS => EventType::KeyPress { key, modifiers },
+                EVENT_KIND_KEY_DOWN => EventType::KeyDown { key, modifiers },
+                _ => EventType::KeyUp { key, modifiers },
+            }
+        }
+        kind => {
+            return Err(EngineError::SignalProcessing(format!(
+                "unknown event kind {} in record {}",
+                kind, index
+            )))
+        }
+    };
+
+    Ok(InputEvent {
+        timestamp,
+        event_type,
+    })
+}
+
+fn decode_key(index: usize, record: &[u8]) -> Result<(String, KeyModifiers), EngineError> {
+    let bytes = &record[KEY_OFFSET..KEY_OFFSET + KEY_MAX_BYTES];
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(KEY_MAX_BYTES);
+    let key = std::str::from_utf8(&bytes[..len]).map_err(|_| {
+        EngineError::SignalProcessing(format!("key in record {} is not valid UTF-8", index))
+    })?;
+    let bits = record[9];
+    let modifiers = KeyModifiers {
+        shift: bits & 1 != 0,
+        ctrl: bits & 2 != 0,
+        alt: bits & 4 != 0,
+        meta: bits & 8 != 0,
+    };
+    Ok((key.to_string(), modifiers))
+}
+
+fn read_u64(record: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&record[offset..offset + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn read_u32(record: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&record[offset..offset + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+fn read_f32(record: &[u8], offset: usize) -> f32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&record[offset..offset + 4]);
+    f32::from_le_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_all_event_kinds() {
+        let events = [
+            InputEvent {
+                timestamp: Timestamp::from_micros(1000),
+                event_type: EventType::MouseMove {
+                    position: NormalizedCoord::new(0.25, 0.75),
+                },
+            },
+            InputEvent {
+                timestamp: Timestamp::from_micros(2000),
+                event_type: EventType::MouseClick {
+                    position: NormalizedCoord::new(0.5, 0.5),
+                    button: 2,
+                    element: None,
+                },
+            },
+            InputEvent {
+                timestamp: Timestamp::from_micros(3000),
+                event_type: EventType::FocusChange {
+                    bounds: NormalizedRect::new(0.1, 0.2, 0.3, 0.4),
+                    element: None,
+                },
+            },
+            InputEvent {
+                timestamp: Timestamp::from_micros(4000),
+                event_type: EventType::Scroll { delta_y: -120.0 },
+            },
+            InputEvent {
+                timestamp: Timestamp::from_micros(5000),
+                event_type: EventType::FrameCaptured {
+                    frame_index: FrameIndex::new(42),
+                },
+            },
+            InputEvent {
+                timestamp: Timestamp::from_micros(6000),
+                event_type: EventType::MouseUp {
+                    position: NormalizedCoord::new(0.2, 0.3),
+                    button: 1,
+                },
+            },
+            InputEvent {
+                timestamp: Timestamp::from_micros(7000),
+                event_type: EventType::CursorDetected {
+                    position: NormalizedCoord::new(0.6, 0.4),
+                    confidence: 85,
+                },
+            },
+            InputEvent {
+                timestamp: Timestamp::from_micros(8000),
+                event_type: EventType::KeyDown {
+                    key: "ArrowLeft".to_string(),
+                    modifiers: KeyModifiers {
+                        shift: true,
+                        ctrl: true,
+                        ..Default::default()
+                    },
+                },
+            },
+            InputEvent {
+                timestamp: Timestamp::from_micros(8500),
+                event_type: EventType::TextSelection {
+                    bounds: NormalizedRect::new(0.2, 0.3, 0.4, 0.05),
+                },
+            },
+            InputEvent {
+                timestamp: Timestamp::from_micros(9000),
+                event_type: EventType::Pen {
+                    position: NormalizedCoord::new(0.3, 0.7),
+                    pointer_id: 3,
+                    pressure: 0.5,
+                    phase: PointerPhase::Up,
+                },
+            },
+        ];
+
+        let buffer: Vec<u8> = events.iter().flat_map(encode_signal_record).collect();
+        let batch = decode_signal_buffer(&buffer).expect("Should decode valid buffer");
+
+        assert_eq!(batch.events.len(), events.len());
+        assert_eq!(batch.events[1].timestamp.as_micros(), 2000);
+        assert!(matches!(
+            batch.events[1].event_type,
+            EventType::MouseClick { button: 2, .. }
+        ));
+        match batch.events[2].event_type {
+            EventType::FocusChange { bounds, .. } => {
+                assert_eq!(bounds, NormalizedRect::new(0.1, 0.2, 0.3, 0.4))
+            }
+            _ => panic!("Expected FocusChange"),
+        }
+        assert!(matches!(
+            batch.events[4].event_type,
+            EventType::FrameCaptured { frame_index } if frame_index.as_u32() == 42
+        ));
+        assert!(matches!(
+            batch.events[6].event_type,
+            EventType::CursorDetected { confidence: 85, .. }
+        ));
+        assert!(matches!(
+            batch.events[5].event_type,
+            EventType::MouseUp { button: 1, .. }
+        ));
+        match &batch.events[7].event_type {
+            EventType::KeyDown { key, modifiers } => {
+                assert_eq!(key, "ArrowLeft");
+                assert!(modifiers.ctrl && modifiers.shift && !modifiers.alt && !modifiers.meta);
+            }
+            _ => panic!("Expected KeyDown"),
+        }
+        assert!(matches!(
+            batch.events[8].event_type,
+            EventType::TextSelection { bounds } if bounds == NormalizedRect::new(0.2, 0.3, 0.4, 0.05)
+        ));
+        assert!(matches!(
+            batch.events[9].event_type,
+            EventType::Pen {
+                pointer_id: 3,
+                pressure: 0.5,
+                phase: PointerPhase::Up,
+                ..
+            }
+        ));
+        // Long keys are cut to whole characters.
+        let long = InputEvent {
+            timestamp: Timestamp::from_micros(0),
+            event_type: EventType::KeyPress {
+                key: "éééééééééé".to_string(),
+                modifiers: KeyModifiers::default(),
+            },
+        };
+        let decoded = decode_signal_buffer(&encode_signal_record(&long)).unwrap();
+        assert!(matches!(
+            &decoded.events[0].event_type,
+            EventType::KeyPress { key, .. } if key == "éééééééé"
+        ));
+    }
+
+    #[test]
+    fn rejects_truncated_buffer() {
+        let result = decode_signal_buffer(&[0u8; SIGNAL_RECORD_SIZE + 3]);
+        assert!(matches!(result, Err(EngineError::SignalProcessing(_))));
+    }
+
+    #[test]
+    fn encodes_analysis_result_layout() {
+        let result = AnalysisResult {
+            cursor_track: vec![CursorTrackPoint {
+                timestamp: Timestamp::from_micros(1000),
+                position: NormalizedCoord::new(0.25, 0.5),
+                state: CursorState::Inferred,
+                confidence: 80,
+                reason: InferenceReason::CursorDetection { confidence: 65 },
+                smoothed: None,
+            }],
+            focus_regions: vec![],
+            camera_keyframes: vec![CameraKeyframe {
+                timestamp: Timestamp::from_micros(2000),
+                viewport: Viewport::default(),
+                easing: EasingType::EaseInOut,
+                is_manual: true,
+                reason: KeyframeReason::Manual,
+                hold_us: 1_500_000,
+            }],
+            effect_tracks: EffectTrack {
+                effects: vec![Effect {
+                    timestamp: Timestamp::from_micros(3000),
+                    duration_us: 300_000,
+                    effect_type: EffectType::CursorHighlight,
+                    position: NormalizedCoord::center(),
+                    style: EffectStyle::default(),
+                    envelope: EffectEnvelope::default(),
+                }],
+            },
+            drags: vec![],
+            pinches: vec![],
+            shakes: vec![],
+            sessions: vec![],
+            warnings: vec![],
+            audio_cues: vec![],
+            blur_track: vec![],
+        };
+
+        let bytes = encode_analysis_result(&result);
+        assert_eq!(
+            bytes.len(),
+            ANALYSIS_HEADER_SIZE
+                + CURSOR_POINT_RECORD_SIZE
+                + KEYFRAME_RECORD_SIZE
+                + EFFECT_RECORD_SIZE
+        );
+        assert_eq!(&bytes[0..4], &ANALYSIS_MAGIC);
+        assert_eq!(read_u32(&bytes, 8), 1);
+        assert_eq!(read_u32(&bytes, 12), 0);
+        assert_eq!(read_u32(&bytes, 16), 1);
+        assert_eq!(read_u32(&bytes, 20), 1);
+
+        let cursor = &bytes[ANALYSIS_HEADER_SIZE..];
+        assert_eq!(read_u64(cursor, 0), 1000);
+        assert_eq!(read_f32(cursor, 8), 0.25);
+        assert_eq!(&cursor[16..20], &[2, 80, 1, 65]);
+
+        let keyframe = &cursor[CURSOR_POINT_RECORD_SIZE..];
+        assert_eq!(read_u64(keyframe, 0), 2000);
+        assert_eq!(read_f32(keyframe, 16), 1.0);
+        assert_eq!(keyframe[20], 2);
+        assert_eq!(keyframe[21], 1);
+        assert_eq!(keyframe[22], 7);
+        assert_eq!(read_u64(keyframe, 24), 1_500_000);
+
+        let effect = &keyframe[KEYFRAME_RECORD_SIZE..];
+        assert_eq!(read_u64(effect, 8), 300_000);
+        assert_eq!(effect[24], 1);
+    }
+
+    #[test]
+    fn rejects_unknown_kind() {
+        let mut record = [0u8; SIGNAL_RECORD_SIZE];
+        record[8] = 0xFF;
+        assert!(decode_signal_buffer(&record).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+// Privacy blur: user-drawn rectangles kept over the content they hide as the page scrolls
+// and the window moves, sampled into per-time rectangle lists for the renderer.
+// See steering.md: Rust Rules (Engine)
+
+use crate::types::*;
+
+/// First id given to automatically added blur regions.
+pub const AUTO_BLUR_ID_BASE: u32 = 1 << 31;
+
+/// Page scroll and window position at one moment, relative to the start of the recording.
+#[derive(Debug, Clone, Copy, Default)]
+struct Offset {
+    /// Cumulative scroll, in normalized viewport heights.
+    scroll_y: f32,
+    window_x: f32,
+    window_y: f32,
+}
+
+/// Where each blur region is over the recording. A new sample starts whenever a region
+/// appears, disappears, or moves; regions scrolled or moved fully out of frame are left
+/// out, and the rest are clipped to the frame.
+pub fn track_blur_regions(
+    regions: &[BlurRegion],
+    signals: &SignalBatch,
+    scroll: &ScrollSettings,
+) -> Vec<BlurSample> {
+    if regions.is_empty() {
+        return Vec::new();
+    }
+    let offsets = offset_timeline(signals, scroll);
+    let offset_at = |ts: Timestamp| {
+        let i = offsets.partition_point(|(at, _)| *at <= ts);
+        i.checked_sub(1).map_or(Offset::default(), |i| offsets[i].1)
+    };
+
+    let mut times: Vec<Timestamp> = offsets.iter().map(|(at, _)| *at).collect();
+    times.push(Timestamp::default());
+    for region in regions {
+        times.extend(region.start);
+        times.extend(region.end);
+    }
+    times.sort();
+    times.dedup();
+
+    let frame = NormalizedRect::new(0.0, 0.0, 1.0, 1.0);
+    let mut samples: Vec<BlurSample> = Vec::new();
+    for ts in times {
+        let now = offset_at(ts);
+        let rects: Vec<BlurRect> = regions
+            .iter()
+            .filter(|region| {
+                region.start.is_none_or(|start| start <= ts)
+                    && region.end.is_none_or(|end| ts < end)
+            })
+            .filter_map(|region| {
+                let drawn = offset_at(region.start.unwrap_or_default());
+                let mut bounds = region.bounds;
+                bounds.x += now.window_x - drawn.window_x;
+                bounds.y += now.window_y - drawn.window_y;
+                if region.follow_scroll {
+                    bounds.y -= now.scroll_y - drawn.scroll_y;
+                }
+                bounds.intersection(&frame).map(|bounds| BlurRect {
+                    id: region.id,
+                    bounds,
+                })
+            })
+            .collect();
+
+        let unchanged = match samples.last() {
+            Some(last) => last.rects == rects,
+            None => rects.is_empty(),
+        };
+        if !unchanged {
+            samples.push(BlurSample {
+                timestamp: ts,
+                rects,
+            });
+        }
+    }
+    samples
+}
+
+/// Blur regions over sensitive inputs (password fields, or a sensitive `autocomplete`
+/// token) for as long as they have focus, widened by the privacy margin. Ids count up
+/// from `AUTO_BLUR_ID_BASE` so they don't collide with user-drawn regions.
+pub fn sensitive_regions(signals: &SignalBatch, privacy: &PrivacySettings) -> Vec<BlurRegion> {
+    if !privacy.auto_blur {
+        return Vec::new();
+    }
+    let focus_changes: Vec<(Timestamp, NormalizedRect, Option<&ElementInfo>)> = signals
+        .events
+        .iter()
+        .filter_map(|event| match &event.event_type {
+            EventType::FocusChange { bounds, element } => {
+                Some((event.timestamp, *bounds, element.as_ref()))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let mut regions = Vec::new();
+    for (i, (ts, bounds, element)) in focus_changes.iter().enumerate() {
+        if !element.is_some_and(|element| is_sensitive(element, privacy)) {
+            continue;
+        }
+        // Focus lasts until it moves somewhere else.
+        let end = focus_changes
+            .get(i + 1)
+            .map(|(next, _, _)| Timestamp::from_micros(next.as_micros() + privacy.margin_us));
+        regions.push(BlurRegion {
+            id: AUTO_BLUR_ID_BASE + regions.len() as u32,
+            bounds: *bounds,
+            start: Some(Timestamp::from_micros(
+                ts.as_micros().saturating_sub(privacy.margin_us),
+            )),
+            end,
+            follow_scroll: true,
+        });
+    }
+    regions
+}
+
+/// Password fields and inputs whose `autocomplete` hint has a sensitive token.
+pub(crate) fn is_sensitive(element: &ElementInfo, privacy: &PrivacySettings) -> bool {
+    element.is_password
+        || element.autocomplete.as_deref().is_some_and(|hint| {
+            hint.split_whitespace().any(|token| {
+                privacy
+                    .sensitive_autocomplete
+                    .iter()
+                    .any(|sensitive| sensitive.eq_ignore_ascii_case(token))
+            })
+        })
+}
+
+/// Every change of scroll or window position, in time order.
+fn offset_timeline(signals: &SignalBatch, scroll: &ScrollSettings) -> Vec<(Timestamp, Offset)> {
+    let scale = if scroll.delta_scale > 0.0 {
+        scroll.delta_scale
+    } else {
+        1.0
+    };
+    let mut offset = Offset::default();
+    let mut timeline = Vec::new();
+    for event in &signals.events {
+        match event.event_type {
+            // The page can't scroll above its top.
+            EventType::Scroll { delta_y } => {
+                offset.scroll_y = (offset.scroll_y + delta_y / scale).max(0.0)
+            }
+            EventType::WindowMoved { dx, dy } => {
+                offset.window_x += dx;
+                offset.window_y += dy;
+            }
+            _ => continue,
+        }
+        timeline.push((event.timestamp, offset));
+    }
+    timeline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(us: u64, event_type: EventType) -> InputEvent {
+        InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type,
+        }
+    }
+
+    #[test]
+    fn regions_follow_scrolling_and_window_moves() {
+        let regions = vec![
+            BlurRegion {
+                id: 1,
+                bounds: NormalizedRect::new(0.1, 0.5, 0.3, 0.1),
+                start: None,
+                end: None,
+                follow_scroll: true,
+            },
+            BlurRegion {
+                id: 2,
+                bounds: NormalizedRect::new(0.8, 0.0, 0.2, 0.05),
+                start: Some(Timestamp::from_micros(1_000_000)),
+                end: Some(Timestamp::from_micros(3_000_000)),
+                follow_scroll: false,
+            },
+        ];
+        let signals = SignalBatch {
+            events: vec![
+                event(2_000_000, EventType::Scroll { delta_y: 0.3 }),
+                event(4_000_000, EventType::WindowMoved { dx: 0.0, dy: 0.2 }),
+                event(5_000_000, EventType::Scroll { delta_y: 1.0 }),
+            ],
+        };
+
+        let samples = track_blur_regions(&regions, &signals, &ScrollSettings::default());
+        let summary: Vec<(u64, Vec<(u32, f32)>)> = samples
+            .iter()
+            .map(|sample| {
+                let rects = sample
+                    .rects
+                    .iter()
+                    .map(|rect| (rect.id, (rect.bounds.y * 100.0).round() / 100.0))
+                    .collect();
+                (sample.timestamp.as_micros(), rects)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, vec![(1, 0.5)]),
+                (1_000_000, vec![(1, 0.5), (2, 0.0)]),
+                // Page content scrolls up; the fixed toolbar region stays put.
+                (2_000_000, vec![(1, 0.2), (2, 0.0)]),
+                (3_000_000, vec![(1, 0.2)]),
+                (4_000_000, vec![(1, 0.4)]),
+                // Scrolled out of frame.
+                (5_000_000, vec![]),
+            ]
+        );
+    }
+}
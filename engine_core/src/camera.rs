@@ -2,12 +2,53 @@
 // Rule: A stable, slightly-wrong zoom feels better than a jittery "correct" zoom.
 // See steering.md: Auto-Zoom Strategy Rules
 
+use std::cell::Cell;
+
 use crate::types::*;
 
+/// Number of f32 values per frame in a baked camera track: `[center_x, center_y, zoom]`.
+pub const BAKED_VIEWPORT_STRIDE: usize = 3;
+
+/// Number of f32 values per frame in a baked matrix track (a column-major 3x3 matrix).
+pub const BAKED_MATRIX_STRIDE: usize = 9;
+
 /// Camera engine: generates keyframes for zoom/pan based on cursor and focus data.
 pub struct CameraEngine {
     settings: CameraSettings,
     keyframes: Vec<CameraKeyframe>,
+    /// Timestamp of the last cursor-driven keyframe (for min hold time across chunks).
+    last_cursor_keyframe_ts: Timestamp,
+    /// Target of the last initial/cursor keyframe (for the dead zone check and travel
+    /// direction across chunks).
+    last_cursor_center: Option<NormalizedCoord>,
+    /// Result of the last keyframe lookup in `get_viewport_at`. Export queries are
+    /// sequential, so the next lookup usually lands on the same or the next keyframe.
+    lookup_hint: Cell<usize>,
+    /// Hand-placed keyframes, sorted by timestamp. Kept across regeneration.
+    manual: Vec<CameraKeyframe>,
+    /// Manual keyframes up to and including this time have been merged into `keyframes`.
+    manual_merged_until: Option<Timestamp>,
+    /// Previous cursor point (for velocity across chunks).
+    last_cursor_point: Option<(Timestamp, NormalizedCoord)>,
+    /// Pulled back to full view by fast motion and waiting for the cursor to settle.
+    pulled_back: bool,
+    /// Zoom level the hysteresis pass last accepted, and when it changed.
+    zoom_level: f32,
+    zoom_changed_at: Option<Timestamp>,
+    /// When the zoom last went down (starts the zoom-in cooldown).
+    last_zoom_out: Option<Timestamp>,
+    /// Latest important focus region seen (for smart framing across chunks).
+    active_focus: Option<FocusRegion>,
+    /// Important focus regions from the last `focus_hold_us`, oldest first (for
+    /// multi-region fitting across chunks).
+    recent_focus: Vec<FocusRegion>,
+    /// Track and bounds of the last focus region framed; later samples of the same
+    /// track at the same place keep the camera where it is.
+    last_focus_track: Option<(u32, NormalizedRect)>,
+    /// Scene boundaries, sorted. Kept across regeneration like manual keyframes.
+    scene_changes: Vec<Timestamp>,
+    /// Scene boundaries before this index already have a reset keyframe.
+    next_scene: usize,
 }
 
 impl CameraEngine {
@@ -15,45 +56,288 @@ impl CameraEngine {
         CameraEngine {
             settings,
             keyframes: Vec::new(),
+            last_cursor_keyframe_ts: Timestamp::from_micros(0),
+            last_cursor_center: None,
+            lookup_hint: Cell::new(0),
+            manual: Vec::new(),
+            manual_merged_until: None,
+            last_cursor_point: None,
+            pulled_back: false,
+            zoom_level: 1.0,
+            zoom_changed_at: None,
+            last_zoom_out: None,
+            active_focus: None,
+            recent_focus: Vec::new(),
+            last_focus_track: None,
+            scene_changes: Vec::new(),
+            next_scene: 0,
+        }
+    }
+
+    /// Replace camera settings. Existing keyframes are kept until regenerated.
+    pub fn set_settings(&mut self, settings: CameraSettings) {
+        self.settings = settings;
+    }
+
+    /// Drop all generated keyframes and incremental state. Manual keyframes are kept.
+    pub fn reset(&mut self) {
+        self.keyframes.clear();
+        self.last_cursor_keyframe_ts = Timestamp::from_micros(0);
+        self.last_cursor_center = None;
+        self.manual_merged_until = None;
+        self.last_cursor_point = None;
+        self.pulled_back = false;
+        self.zoom_level = 1.0;
+        self.zoom_changed_at = None;
+        self.last_zoom_out = None;
+        self.active_focus = None;
+        self.recent_focus.clear();
+        self.last_focus_track = None;
+        self.next_scene = 0;
+    }
+
+    /// Cut back to full view at each scene boundary, forgetting focus regions from the
+    /// previous scene. Replaces earlier boundaries; takes effect on the next regeneration.
+    pub fn set_scene_changes(&mut self, mut scene_changes: Vec<Timestamp>) {
+        scene_changes.sort();
+        scene_changes.dedup();
+        self.scene_changes = scene_changes;
+    }
+
+    /// Start of the scene showing at `timestamp` (zero in the first scene).
+    fn scene_start(&self, timestamp: Timestamp) -> Timestamp {
+        let index = self.scene_changes.partition_point(|&t| t <= timestamp);
+        index
+            .checked_sub(1)
+            .map_or(Timestamp::from_micros(0), |i| self.scene_changes[i])
+    }
+
+    /// Full-view keyframes for scene boundaries up to `until` not yet reached.
+    fn reset_for_scenes(&mut self, keyframes: &mut Vec<CameraKeyframe>, until: Timestamp) {
+        while let Some(&timestamp) = self.scene_changes.get(self.next_scene) {
+            if timestamp > until {
+                break;
+            }
+            let viewport = Viewport::default();
+            self.last_cursor_center = Some(viewport.center);
+            self.last_cursor_keyframe_ts = timestamp;
+            self.pulled_back = false;
+            keyframes.push(CameraKeyframe {
+                timestamp,
+                viewport,
+                easing: EasingType::EaseOut,
+                is_manual: false,
+                reason: KeyframeReason::SceneChange,
+                hold_us: 0,
+            });
+            self.next_scene += 1;
+        }
+    }
+
+    /// Pin the camera to `viewport` at `timestamp`, replacing any manual keyframe
+    /// already there. Takes effect on the next regeneration.
+    pub fn set_manual_keyframe(&mut self, timestamp: Timestamp, viewport: Viewport) {
+        self.set_manual_hold(timestamp, viewport, 0);
+    }
+
+    /// Like `set_manual_keyframe`, locking the camera on `viewport` for `hold_us` before
+    /// it is released to the next keyframe. Generated keyframes inside the hold are dropped.
+    pub fn set_manual_hold(&mut self, timestamp: Timestamp, viewport: Viewport, hold_us: u64) {
+        let keyframe = CameraKeyframe {
+            timestamp,
+            viewport: viewport.clamped_to_frame(self.settings.bounds),
+            easing: EasingType::EaseInOut,
+            is_manual: true,
+            reason: KeyframeReason::Manual,
+            hold_us,
+        };
+        match self
+            .manual
+            .binary_search_by_key(&timestamp, |kf| kf.timestamp)
+        {
+            Ok(index) => self.manual[index] = keyframe,
+            Err(index) => self.manual.insert(index, keyframe),
+        }
+    }
+
+    /// Remove the manual keyframe at `timestamp`. Returns whether one was removed.
+    /// Takes effect on the next regeneration.
+    pub fn clear_manual_keyframe(&mut self, timestamp: Timestamp) -> bool {
+        match self
+            .manual
+            .binary_search_by_key(&timestamp, |kf| kf.timestamp)
+        {
+            Ok(index) => {
+                self.manual.remove(index);
+                true
+            }
+            Err(_) => false,
         }
     }
 
+    /// Remove every manual keyframe.
+    pub fn clear_manual_keyframes(&mut self) {
+        self.manual.clear();
+    }
+
+    /// Replace every manual keyframe. Takes effect on the next regeneration.
+    pub fn set_manual_keyframes(&mut self, mut manual: Vec<CameraKeyframe>) {
+        manual.sort_by_key(|kf| kf.timestamp);
+        self.manual = manual;
+    }
+
+    /// Hand-placed keyframes, sorted by timestamp.
+    pub fn manual_keyframes(&self) -> &[CameraKeyframe] {
+        &self.manual
+    }
+
+    /// Scene boundaries, sorted.
+    pub fn scene_changes(&self) -> &[Timestamp] {
+        &self.scene_changes
+    }
+
+    /// Replace the keyframes and manual keyframes with saved ones, as if the keyframes
+    /// had just been generated with the manual ones merged in. Incremental state starts
+    /// over, so streaming further chunks begins a new camera path.
+    pub fn restore_keyframes(
+        &mut self,
+        mut keyframes: Vec<CameraKeyframe>,
+        manual: Vec<CameraKeyframe>,
+    ) {
+        self.reset();
+        keyframes.sort_by_key(|kf| kf.timestamp);
+        self.manual_merged_until = keyframes.last().map(|kf| kf.timestamp);
+        self.keyframes = keyframes;
+        self.set_manual_keyframes(manual);
+        self.lookup_hint.set(0);
+    }
+
     /// Generate camera keyframes from cursor track and focus regions.
     pub fn generate_keyframes(
         &mut self,
         cursor_track: &[CursorTrackPoint],
         focus_regions: &[FocusRegion],
+    ) -> Vec<CameraKeyframe> {
+        self.generate_keyframes_with_clicks(cursor_track, focus_regions, &[])
+    }
+
+    /// Like `generate_keyframes`, starting click-driven moves `look_ahead_us` early.
+    /// `clicks` are the sorted timestamps of the recording's clicks.
+    pub fn generate_keyframes_with_clicks(
+        &mut self,
+        cursor_track: &[CursorTrackPoint],
+        focus_regions: &[FocusRegion],
+        clicks: &[Timestamp],
+    ) -> Vec<CameraKeyframe> {
+        self.reset();
+        self.extend_keyframes_with_clicks(cursor_track, focus_regions, clicks)
+    }
+
+    /// Extend the existing keyframes with a later chunk of cursor points and focus regions.
+    /// Returns only the newly added keyframes. Chunks must arrive in timestamp order;
+    /// feeding a whole recording in order produces the same keyframes as `generate_keyframes`.
+    pub fn extend_keyframes(
+        &mut self,
+        cursor_track: &[CursorTrackPoint],
+        focus_regions: &[FocusRegion],
+    ) -> Vec<CameraKeyframe> {
+        self.extend_keyframes_with_clicks(cursor_track, focus_regions, &[])
+    }
+
+    /// Like `extend_keyframes`, starting click-driven moves `look_ahead_us` early.
+    /// `clicks` are the sorted click timestamps of this chunk.
+    pub fn extend_keyframes_with_clicks(
+        &mut self,
+        cursor_track: &[CursorTrackPoint],
+        focus_regions: &[FocusRegion],
+        clicks: &[Timestamp],
     ) -> Vec<CameraKeyframe> {
         let mut keyframes = Vec::new();
 
         // Start with a full-screen view.
-        if !cursor_track.is_empty() || !focus_regions.is_empty() {
+        if self.last_cursor_center.is_none()
+            && (!cursor_track.is_empty() || !focus_regions.is_empty())
+        {
             let first_ts = cursor_track
                 .first()
                 .map(|p| p.timestamp)
                 .or_else(|| focus_regions.first().map(|r| r.timestamp))
                 .unwrap_or_else(|| Timestamp::from_micros(0));
 
+            let viewport = Viewport::default();
+            self.last_cursor_center = Some(viewport.center);
             keyframes.push(CameraKeyframe {
                 timestamp: first_ts,
-                viewport: Viewport::default(),
+                viewport,
                 easing: EasingType::EaseOut,
+                is_manual: false,
+                reason: KeyframeReason::Initial,
+                hold_us: 0,
             });
         }
 
-        // Process cursor track to generate zoom keyframes.
-        let mut last_keyframe_ts = Timestamp::from_micros(0);
+        // Important focus regions in time order, for smart framing.
+        let mut important: Vec<&FocusRegion> = focus_regions
+            .iter()
+            .filter(|region| region.importance >= 0.8)
+            .collect();
+        important.sort_by_key(|region| region.timestamp);
+        let mut next_region = 0;
 
+        // Process cursor track to generate zoom keyframes.
         for point in cursor_track {
-            // Apply min hold time constraint.
-            let time_since_last = point.timestamp.as_micros() - last_keyframe_ts.as_micros();
+            self.reset_for_scenes(&mut keyframes, point.timestamp);
+            while let Some(region) = important.get(next_region) {
+                if region.timestamp > point.timestamp {
+                    break;
+                }
+                self.active_focus = Some((*region).clone());
+                next_region += 1;
+            }
+
+            let speed = self
+                .last_cursor_point
+                .replace((point.timestamp, point.position))
+                .map_or(0.0, |(ts, position)| cursor_speed(ts, &position, point));
+
+            // Apply min hold time constraint. Resampled points can arrive behind a focus
+            // keyframe placed in an earlier chunk.
+            let time_since_last = point
+                .timestamp
+                .as_micros()
+                .saturating_sub(self.last_cursor_keyframe_ts.as_micros());
             if time_since_last < self.settings.min_hold_time_us {
                 continue;
             }
 
-            // Check if cursor moved outside dead zone.
-            if let Some(last_kf) = keyframes.last() {
-                if self.is_within_dead_zone(&last_kf.viewport.center, &point.position) {
+            // Fast motion: pull back to full view until the cursor settles.
+            let fast_motion = self.settings.fast_motion_speed;
+            let mut settled = false;
+            if self.pulled_back {
+                if speed > self.settings.settle_speed {
+                    continue;
+                }
+                self.pulled_back = false;
+                settled = true;
+            } else if fast_motion > 0.0 && speed > fast_motion {
+                let viewport = Viewport::default();
+                self.pulled_back = true;
+                self.last_cursor_center = Some(viewport.center);
+                keyframes.push(CameraKeyframe {
+                    timestamp: point.timestamp,
+                    viewport,
+                    easing: EasingType::EaseOut,
+                    is_manual: false,
+                    reason: KeyframeReason::VelocityZoomOut,
+                    hold_us: 0,
+                });
+                self.last_cursor_keyframe_ts = point.timestamp;
+                continue;
+            }
+
+            // Check if cursor moved outside dead zone (always re-zoom after settling).
+            if let Some(last_center) = &self.last_cursor_center {
+                if !settled && self.is_within_dead_zone(last_center, &point.position) {
                     continue;
                 }
             }
@@ -63,38 +347,156 @@ impl CameraEngine {
                 continue;
             }
 
-            let viewport = self.calculate_viewport(point);
+            // Never follow the cursor into an exclusion zone.
+            if self.is_excluded(&point.position) {
+                continue;
+            }
+
+            let mut viewport = self.calculate_viewport(point, self.last_cursor_center);
+            let mut reason = KeyframeReason::Cursor;
+            self.last_cursor_center = Some(point.position);
+            if let Some(region) = &self.active_focus {
+                // The region can be ahead of a resampled point trailing the last chunk.
+                let age = point
+                    .timestamp
+                    .as_micros()
+                    .saturating_sub(region.timestamp.as_micros());
+                if viewport.zoom > 1.0
+                    && age <= self.settings.focus_hold_us
+                    && region.timestamp >= self.scene_start(point.timestamp)
+                {
+                    if let Some(framed) = self.frame_targets(&point.position, &region.bounds) {
+                        viewport = framed;
+                        if !region.bounds.contains(&point.position) {
+                            reason = KeyframeReason::CursorAndFocus;
+                        }
+                    }
+                }
+            }
             keyframes.push(CameraKeyframe {
                 timestamp: point.timestamp,
                 viewport,
                 easing: EasingType::EaseInOut,
+                is_manual: false,
+                reason,
+                hold_us: 0,
             });
 
-            last_keyframe_ts = point.timestamp;
+            self.last_cursor_keyframe_ts = point.timestamp;
+        }
+        if let Some(region) = important.last() {
+            self.active_focus = Some((*region).clone());
+        }
+
+        // Pin manual keyframes and scene cuts that fall in this chunk.
+        let chunk_end = cursor_track
+            .iter()
+            .map(|p| p.timestamp)
+            .chain(focus_regions.iter().map(|r| r.timestamp))
+            .max();
+        if let Some(chunk_end) = chunk_end {
+            self.reset_for_scenes(&mut keyframes, chunk_end);
         }
 
         // Supplement with focus region keyframes.
-        for region in focus_regions {
-            if region.importance >= 0.8 {
-                let center = region.bounds.center();
-                let zoom = self.calculate_zoom_for_bounds(&region.bounds);
+        for region in important {
+            let hold = self.settings.focus_hold_us;
+            let scene_start = self.scene_start(region.timestamp);
+            // Only the latest sample of a tracked region takes part in fitting.
+            self.recent_focus.retain(|recent| {
+                region
+                    .timestamp
+                    .as_micros()
+                    .saturating_sub(recent.timestamp.as_micros())
+                    <= hold
+                    && recent.timestamp >= scene_start
+                    && (recent.track_id.is_none() || recent.track_id != region.track_id)
+            });
+            self.recent_focus.push(region.clone());
 
-                keyframes.push(CameraKeyframe {
-                    timestamp: region.timestamp,
-                    viewport: Viewport { center, zoom },
-                    easing: EasingType::EaseOut,
-                });
+            // A tracked region that hasn't moved holds the framing it already has.
+            let track = region.track_id.map(|id| (id, region.bounds));
+            if track.is_some() && track == self.last_focus_track {
+                continue;
             }
+            self.last_focus_track = track;
+
+            let center = region.bounds.center();
+            if self.is_excluded(&center) {
+                continue;
+            }
+
+            // Fit other regions that are still active, newest first.
+            let own_zoom = self.calculate_zoom_for_bounds(&region.bounds);
+            let mut bounds = region.bounds;
+            for recent in self.recent_focus.iter().rev().skip(1) {
+                let candidate = bounds.union(&recent.bounds);
+                if self.fit_zoom(&candidate) * self.settings.max_fit_zoom_out >= own_zoom {
+                    bounds = candidate;
+                }
+            }
+            let (fitted, mut reason) = if bounds == region.bounds {
+                let viewport = Viewport {
+                    center,
+                    zoom: own_zoom,
+                };
+                (viewport, KeyframeReason::FocusRegion)
+            } else {
+                let viewport = Viewport {
+                    center: bounds.center(),
+                    zoom: self.fit_zoom(&bounds),
+                };
+                (viewport, KeyframeReason::MultiRegionFit)
+            };
+
+            // Keep the cursor in frame too, if it's close enough.
+            let cursor = cursor_track
+                [..cursor_track.partition_point(|p| p.timestamp <= region.timestamp)]
+                .last()
+                .filter(|p| p.confidence >= 70);
+            let framed = cursor.and_then(|p| {
+                let framed = self.frame_targets(&p.position, &bounds)?;
+                if !bounds.contains(&p.position) {
+                    reason = KeyframeReason::CursorAndFocus;
+                }
+                Some(framed)
+            });
+
+            keyframes.push(CameraKeyframe {
+                timestamp: region.timestamp,
+                viewport: framed.unwrap_or(fitted),
+                easing: EasingType::EaseOut,
+                is_manual: false,
+                reason,
+                hold_us: 0,
+            });
         }
 
         // Sort by timestamp.
         keyframes.sort_by_key(|kf| kf.timestamp);
+        self.anticipate_clicks(&mut keyframes, clicks);
+
+        if let Some(chunk_end) = chunk_end {
+            self.merge_manual(&mut keyframes, chunk_end);
+        }
+        self.apply_zoom_hysteresis(&mut keyframes);
+
+        // Apply smoothing pass, continuing from the last existing keyframe.
+        let first_new = self.keyframes.len();
+        self.keyframes.extend(keyframes);
+        self.apply_smoothing(first_new);
+        self.apply_zoom_quantization(first_new);
 
-        // Apply smoothing pass.
-        let smoothed = self.apply_smoothing(&keyframes);
+        self.keyframes[first_new..].to_vec()
+    }
 
-        self.keyframes = smoothed.clone();
-        smoothed
+    /// Viewport of the most recent keyframe (the camera's current target).
+    /// O(1); intended for live preview while signals are still streaming in.
+    pub fn current_viewport(&self) -> Viewport {
+        self.keyframes
+            .last()
+            .map(|kf| kf.viewport.clone())
+            .unwrap_or_default()
     }
 
     /// Get viewport at a specific timestamp (interpolated).
@@ -103,32 +505,72 @@ impl CameraEngine {
             return Viewport::default();
         }
 
-        // Find surrounding keyframes.
-        let mut prev_kf = &self.keyframes[0];
-        let mut next_kf = &self.keyframes[0];
+        // Find surrounding keyframes: `prev_kf` is the last one at or before `timestamp`.
+        let index = self.keyframes_through(timestamp);
+        let viewport = if index == 0 {
+            self.keyframes[0].viewport.clone()
+        } else {
+            let prev_kf = &self.keyframes[index - 1];
+            let next_kf = self.keyframes.get(index).unwrap_or(prev_kf);
+            self.interpolate(prev_kf, next_kf, timestamp)
+        };
 
-        for (i, kf) in self.keyframes.iter().enumerate() {
-            if kf.timestamp <= timestamp {
-                prev_kf = kf;
-                next_kf = self.keyframes.get(i + 1).unwrap_or(kf);
-            }
+        self.breathe(viewport, timestamp)
+    }
+
+    /// Add the breathing drift at `timestamp`. The drift grows with zoom (none at full
+    /// view) and depends only on the time and seed, so previews and exports agree.
+    fn breathe(&self, viewport: Viewport, timestamp: Timestamp) -> Viewport {
+        let amplitude = self.settings.breathing_amplitude;
+        let period = self.settings.breathing_period_us;
+        if amplitude <= 0.0 || period == 0 || viewport.zoom <= 1.0 {
+            return viewport;
         }
 
+        let seed = splitmix64(self.settings.breathing_seed);
+        let phase = |bits: u64| (bits & 0xffff) as f64 / 65_536.0 * std::f64::consts::TAU;
+        let t = timestamp.as_micros() as f64 / period as f64 * std::f64::consts::TAU;
+        // Incommensurate x/y/zoom rates so the drift traces a slow, non-repeating loop.
+        let dx = (t + phase(seed)).sin() as f32;
+        let dy = (t * 0.77 + phase(seed >> 16)).sin() as f32;
+        let dz = (t * 0.53 + phase(seed >> 32)).sin() as f32;
+
+        let strength = amplitude * (viewport.zoom - 1.0).min(1.0);
+        let visible = 1.0 / viewport.zoom;
+        Viewport {
+            center: NormalizedCoord::new(
+                viewport.center.x + dx * strength * visible,
+                viewport.center.y + dy * strength * visible,
+            ),
+            zoom: viewport.zoom * (1.0 + dz * strength * 0.5),
+        }
+        .clamped_to_frame(ViewportBounds::BiasCenter)
+    }
+
+    /// Eased viewport at `timestamp` between two keyframes.
+    fn interpolate(
+        &self,
+        prev_kf: &CameraKeyframe,
+        next_kf: &CameraKeyframe,
+        timestamp: Timestamp,
+    ) -> Viewport {
         // If past the last keyframe, return the last viewport.
         if timestamp >= next_kf.timestamp {
             return next_kf.viewport.clone();
         }
 
-        // Interpolate between keyframes.
-        let duration = next_kf.timestamp.as_micros() - prev_kf.timestamp.as_micros();
-        if duration == 0 {
+        // Interpolate between keyframes, once `prev_kf`'s hold is over.
+        let start =
+            (prev_kf.timestamp.as_micros() + prev_kf.hold_us).min(next_kf.timestamp.as_micros());
+        let duration = next_kf.timestamp.as_micros() - start;
+        if duration == 0 || timestamp.as_micros() <= start {
             return prev_kf.viewport.clone();
         }
 
-        let progress =
-            (timestamp.as_micros() - prev_kf.timestamp.as_micros()) as f32 / duration as f32;
-        let eased_progress = self.apply_easing(progress, next_kf.easing);
+        let progress = (timestamp.as_micros() - start) as f32 / duration as f32;
+        let eased_progress = apply_easing(progress, next_kf.easing);
 
+        // Keyframes are already in frame; spring overshoot may not be.
         Viewport {
             center: NormalizedCoord::new(
                 lerp(
@@ -144,6 +586,234 @@ impl CameraEngine {
             ),
             zoom: lerp(prev_kf.viewport.zoom, next_kf.viewport.zoom, eased_progress),
         }
+        .clamped_to_frame(self.settings.bounds)
+    }
+
+    /// Move keyframes that target a click earlier by the look-ahead, without passing
+    /// the keyframe before them, so the camera arrives as the click happens.
+    fn anticipate_clicks(&self, keyframes: &mut [CameraKeyframe], clicks: &[Timestamp]) {
+        let look_ahead = self.settings.look_ahead_us;
+        if clicks.is_empty() {
+            return;
+        }
+
+        let mut floor = self
+            .keyframes
+            .last()
+            .map_or(0, |kf| kf.timestamp.as_micros() + 1);
+        for keyframe in keyframes.iter_mut() {
+            let ts = keyframe.timestamp.as_micros();
+            if clicks.binary_search(&keyframe.timestamp).is_ok() {
+                let anticipated = ts.saturating_sub(look_ahead).max(floor).min(ts);
+                keyframe.timestamp = Timestamp::from_micros(anticipated);
+                if keyframe.reason == KeyframeReason::Cursor {
+                    keyframe.reason = KeyframeReason::ClickTarget;
+                }
+            }
+            floor = keyframe.timestamp.as_micros() + 1;
+        }
+    }
+
+    /// Hold the zoom level unless a change is big enough, the current level has been
+    /// held long enough, and (for zooming in) the cooldown after a zoom-out has passed.
+    /// Rejected keyframes keep their pan but take the held zoom. Manual keyframes
+    /// always apply.
+    fn apply_zoom_hysteresis(&mut self, keyframes: &mut [CameraKeyframe]) {
+        for keyframe in keyframes.iter_mut() {
+            let ts = keyframe.timestamp;
+            let target = keyframe.viewport.zoom;
+            let since = |t: Timestamp| ts.as_micros().saturating_sub(t.as_micros());
+
+            let accept = keyframe.is_manual
+                || keyframe.reason == KeyframeReason::SceneChange
+                || ((target - self.zoom_level).abs() >= self.settings.min_zoom_delta
+                    && self
+                        .zoom_changed_at
+                        .is_none_or(|t| since(t) >= self.settings.min_zoom_dwell_us)
+                    && (target < self.zoom_level
+                        || self
+                            .last_zoom_out
+                            .is_none_or(|t| since(t) >= self.settings.zoom_in_cooldown_us)));
+
+            if !accept {
+                keyframe.viewport.zoom = self.zoom_level;
+                continue;
+            }
+            if target != self.zoom_level {
+                if target < self.zoom_level {
+                    self.last_zoom_out = Some(ts);
+                }
+                self.zoom_level = target;
+                self.zoom_changed_at = Some(ts);
+            }
+        }
+    }
+
+    /// Merge manual keyframes up to `until` (not merged by an earlier chunk) into
+    /// `keyframes`. Auto keyframes within the min hold time of a manual one are dropped,
+    /// so the camera eases into and out of it instead of fighting it.
+    fn merge_manual(&mut self, keyframes: &mut Vec<CameraKeyframe>, until: Timestamp) {
+        let merged_until = self.manual_merged_until;
+        let pinned: Vec<&CameraKeyframe> = self
+            .manual
+            .iter()
+            .filter(|kf| merged_until.is_none_or(|t| kf.timestamp > t) && kf.timestamp <= until)
+            .collect();
+        self.manual_merged_until = Some(merged_until.map_or(until, |t| t.max(until)));
+        if pinned.is_empty() {
+            return;
+        }
+
+        let hold = self.settings.min_hold_time_us;
+        keyframes.retain(|kf| {
+            pinned.iter().all(|manual| {
+                let ts = kf.timestamp.as_micros();
+                let manual_ts = manual.timestamp.as_micros();
+                let before = ts + hold.max(1) <= manual_ts;
+                let after = ts >= manual_ts + hold.max(1) && ts > manual_ts + manual.hold_us;
+                before || after
+            })
+        });
+        keyframes.extend(pinned.into_iter().cloned());
+        keyframes.sort_by_key(|kf| kf.timestamp);
+    }
+
+    /// Drop keyframes the camera path passes close to anyway (within
+    /// `keyframe_epsilon`), then the least significant ones beyond `max_keyframes`.
+    /// The first, last, and manual keyframes are always kept. Returns the number
+    /// removed. Run after generation finishes; streamed chunks are not compacted.
+    pub fn compact_keyframes(&mut self) -> usize {
+        let before = self.keyframes.len();
+        if before <= 2 {
+            return 0;
+        }
+
+        // Greedy pass: extend the span from the last kept keyframe while every skipped
+        // keyframe stays within epsilon of the curve that would replace it.
+        let epsilon = self.settings.keyframe_epsilon;
+        let keyframes = &self.keyframes;
+        let mut kept: Vec<usize> = vec![0];
+        for k in 1..keyframes.len() - 1 {
+            let anchor = &keyframes[*kept.last().unwrap()];
+            let next = &keyframes[k + 1];
+            let removable = !keyframes[k].is_manual
+                && (kept.last().unwrap() + 1..=k).all(|j| {
+                    viewport_error(
+                        &keyframes[j].viewport,
+                        &self.interpolate(anchor, next, keyframes[j].timestamp),
+                    ) <= epsilon
+                });
+            if !removable {
+                kept.push(k);
+            }
+        }
+        kept.push(keyframes.len() - 1);
+
+        // Budget: repeatedly drop the keyframe whose removal changes the path least.
+        if let Some(max) = self.settings.max_keyframes {
+            while kept.len() > max.max(2) {
+                let cheapest = (1..kept.len() - 1)
+                    .filter(|&i| !keyframes[kept[i]].is_manual)
+                    .map(|i| {
+                        let kf = &keyframes[kept[i]];
+                        let curve = self.interpolate(
+                            &keyframes[kept[i - 1]],
+                            &keyframes[kept[i + 1]],
+                            kf.timestamp,
+                        );
+                        (i, viewport_error(&kf.viewport, &curve))
+                    })
+                    .min_by(|a, b| a.1.total_cmp(&b.1));
+                match cheapest {
+                    Some((i, _)) => {
+                        kept.remove(i);
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        self.keyframes = kept
+            .into_iter()
+            .map(|i| self.keyframes[i].clone())
+            .collect();
+        self.lookup_hint.set(0);
+        before - self.keyframes.len()
+    }
+
+    /// All current keyframes, sorted by timestamp.
+    pub fn keyframes(&self) -> &[CameraKeyframe] {
+        &self.keyframes
+    }
+
+    /// Number of keyframes at or before `timestamp`. Tries the cached index from the
+    /// previous call (and the one after it) before falling back to a binary search.
+    fn keyframes_through(&self, timestamp: Timestamp) -> usize {
+        let keyframes = &self.keyframes;
+        let splits_at = |index: usize| {
+            index <= keyframes.len()
+                && (index == 0 || keyframes[index - 1].timestamp <= timestamp)
+                && keyframes
+                    .get(index)
+                    .is_none_or(|next| next.timestamp > timestamp)
+        };
+
+        let hint = self.lookup_hint.get();
+        let index = if splits_at(hint) {
+            hint
+        } else if splits_at(hint + 1) {
+            hint + 1
+        } else {
+            keyframes.partition_point(|kf| kf.timestamp <= timestamp)
+        };
+        self.lookup_hint.set(index);
+        index
+    }
+
+    /// Bake the eased viewport for every output frame into a flat buffer.
+    /// Frame `i` samples `start + i / fps`; each frame is `BAKED_VIEWPORT_STRIDE` values
+    /// (`center_x, center_y, zoom`). The frame count is `ceil(duration * fps)` so the
+    /// final partial frame is covered.
+    pub fn bake_track(&self, fps: f64, start: Timestamp, duration_us: u64) -> Vec<f32> {
+        if fps <= 0.0 || !fps.is_finite() {
+            return Vec::new();
+        }
+
+        self.bake_with(fps, start, duration_us, BAKED_VIEWPORT_STRIDE, |viewport| {
+            vec![viewport.center.x, viewport.center.y, viewport.zoom]
+        })
+    }
+
+    /// Like `bake_track`, with each frame's `Viewport::uv_matrix` (`BAKED_MATRIX_STRIDE`
+    /// floats per frame) for GPU compositors.
+    pub fn bake_matrix_track(&self, fps: f64, start: Timestamp, duration_us: u64) -> Vec<f32> {
+        if fps <= 0.0 || !fps.is_finite() {
+            return Vec::new();
+        }
+        self.bake_with(fps, start, duration_us, BAKED_MATRIX_STRIDE, |viewport| {
+            viewport.uv_matrix().to_vec()
+        })
+    }
+
+    fn bake_with(
+        &self,
+        fps: f64,
+        start: Timestamp,
+        duration_us: u64,
+        stride: usize,
+        values: impl Fn(&Viewport) -> Vec<f32>,
+    ) -> Vec<f32> {
+        let frame_count = (duration_us as f64 * fps / 1_000_000.0).ceil() as u64;
+        let mut baked = Vec::with_capacity(frame_count as usize * stride);
+
+        for frame in 0..frame_count {
+            let offset_us = (frame as f64 * 1_000_000.0 / fps).round() as u64;
+            let viewport =
+                self.get_viewport_at(Timestamp::from_micros(start.as_micros() + offset_us));
+            baked.extend(values(&viewport));
+        }
+
+        baked
     }
 
     fn is_within_dead_zone(&self, center: &NormalizedCoord, target: &NormalizedCoord) -> bool {
@@ -153,19 +823,93 @@ impl CameraEngine {
         distance < self.settings.dead_zone
     }
 
-    fn calculate_viewport(&self, point: &CursorTrackPoint) -> Viewport {
+    fn is_excluded(&self, point: &NormalizedCoord) -> bool {
+        self.settings
+            .exclusion_zones
+            .iter()
+            .any(|zone| zone.contains(point))
+    }
+
+    /// Viewport following the cursor, composed per `composition`. `from` is the previous
+    /// target, which gives the direction of travel for `CompositionAnchor::Lead`.
+    fn calculate_viewport(
+        &self,
+        point: &CursorTrackPoint,
+        from: Option<NormalizedCoord>,
+    ) -> Viewport {
         let zoom = match point.state {
             CursorState::Visible => self.settings.zoom_strength,
             CursorState::Inferred => self.settings.zoom_strength * 0.8,
             CursorState::Hidden => 1.0,
         };
+        if zoom <= 1.0 {
+            return Viewport {
+                center: point.position,
+                zoom,
+            };
+        }
+
+        let target = point.position;
+        let (ox, oy) = match self.settings.composition {
+            CompositionAnchor::Center => (0.0, 0.0),
+            CompositionAnchor::RuleOfThirds => {
+                let third = |v: f32| if v < 0.5 { -1.0 / 6.0 } else { 1.0 / 6.0 };
+                (third(target.x), third(target.y))
+            }
+            CompositionAnchor::Lead { amount } => {
+                let (dx, dy) =
+                    from.map_or((0.0, 0.0), |from| (target.x - from.x, target.y - from.y));
+                let len = (dx * dx + dy * dy).sqrt();
+                if len > f32::EPSILON {
+                    (-dx / len * amount, -dy / len * amount)
+                } else {
+                    (0.0, 0.0)
+                }
+            }
+            CompositionAnchor::Offset { x, y } => (x, y),
+        };
 
+        // Place the target at (ox, oy) of the visible rect; smoothing then limits and
+        // clamps this center like any other.
+        let visible = 1.0 / zoom;
         Viewport {
-            center: point.position,
+            center: NormalizedCoord::new(target.x - ox * visible, target.y - oy * visible),
             zoom,
         }
     }
 
+    /// Smallest viewport showing both the cursor and `bounds` with padding, or `None`
+    /// if smart framing is off or they're too far apart to frame together.
+    fn frame_targets(&self, cursor: &NormalizedCoord, bounds: &NormalizedRect) -> Option<Viewport> {
+        if !self.settings.smart_framing {
+            return None;
+        }
+        let pad = self.settings.framing_padding;
+        let x0 = cursor.x.min(bounds.x) - pad;
+        let x1 = cursor.x.max(bounds.x + bounds.width) + pad;
+        let y0 = cursor.y.min(bounds.y) - pad;
+        let y1 = cursor.y.max(bounds.y + bounds.height) + pad;
+        let size = (x1 - x0).max(y1 - y0);
+        if size <= 0.0 {
+            return None;
+        }
+
+        let zoom = (1.0 / size).min(self.settings.zoom_strength);
+        if zoom < self.settings.min_framing_zoom {
+            return None;
+        }
+        Some(Viewport {
+            center: NormalizedCoord::new((x0 + x1) / 2.0, (y0 + y1) / 2.0),
+            zoom,
+        })
+    }
+
+    /// Zoom that shows `bounds` with framing padding (never below 1.0).
+    fn fit_zoom(&self, bounds: &NormalizedRect) -> f32 {
+        let size = bounds.width.max(bounds.height) + 2.0 * self.settings.framing_padding;
+        (1.0 / size).clamp(1.0, self.settings.zoom_strength.max(1.0))
+    }
+
     fn calculate_zoom_for_bounds(&self, bounds: &NormalizedRect) -> f32 {
         // Zoom to fit bounds with some padding.
         let max_dim = bounds.width.max(bounds.height);
@@ -176,11 +920,24 @@ impl CameraEngine {
         }
     }
 
-    fn apply_smoothing(&self, keyframes: &[CameraKeyframe]) -> Vec<CameraKeyframe> {
-        // Simple smoothing: enforce max pan speed.
-        let mut smoothed = keyframes.to_vec();
+    /// Enforce max pan speed on keyframes from `start` onwards, then keep each one
+    /// inside the frame. Each keyframe is clamped relative to its (already smoothed)
+    /// predecessor.
+    fn apply_smoothing(&mut self, start: usize) {
+        let bounds = self.settings.bounds;
+        let zones = &self.settings.exclusion_zones;
+        let smoothed = &mut self.keyframes;
+
+        for i in start..smoothed.len() {
+            // Scene changes cut to full view; there is no pan to limit.
+            if smoothed[i].is_manual || smoothed[i].reason == KeyframeReason::SceneChange {
+                continue;
+            }
+            if i == 0 {
+                smoothed[0].viewport = smoothed[0].viewport.clamped_to_frame(bounds);
+                continue;
+            }
 
-        for i in 1..smoothed.len() {
             let prev_center = smoothed[i - 1].viewport.center;
             let curr_center = smoothed[i].viewport.center;
 
@@ -188,9 +945,11 @@ impl CameraEngine {
             let dy = curr_center.y - prev_center.y;
             let distance = (dx * dx + dy * dy).sqrt();
 
-            let duration_secs = (smoothed[i].timestamp.as_micros()
-                - smoothed[i - 1].timestamp.as_micros()) as f32
-                / 1_000_000.0;
+            let duration_secs =
+                (smoothed[i].timestamp.as_micros().saturating_sub(
+                    smoothed[i - 1].timestamp.as_micros() + smoothed[i - 1].hold_us,
+                )) as f32
+                    / 1_000_000.0;
 
             if duration_secs > 0.0 {
                 let speed = distance / duration_secs;
@@ -201,37 +960,188 @@ impl CameraEngine {
                         prev_center.x + dx * scale,
                         prev_center.y + dy * scale,
                     );
+                    smoothed[i].reason = KeyframeReason::Smoothing;
                 }
             }
-        }
 
-        smoothed
+            let center = smoothed[i].viewport.center;
+            smoothed[i].viewport.center = move_out_of_zones(center, zones);
+            smoothed[i].viewport = smoothed[i].viewport.clamped_to_frame(bounds);
+        }
     }
 
-    fn apply_easing(&self, t: f32, easing: EasingType) -> f32 {
-        match easing {
-            EasingType::Linear => t,
-            EasingType::EaseOut => 1.0 - (1.0 - t).powi(3),
-            EasingType::EaseInOut => {
-                if t < 0.5 {
-                    4.0 * t * t * t
-                } else {
-                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
-                }
+    /// Snap zooms to `zoom_levels`. Runs after smoothing; centers are only moved as far
+    /// as needed to keep the snapped viewport in frame (per `bounds`) and out of
+    /// exclusion zones, so pans stay continuous.
+    fn apply_zoom_quantization(&mut self, start: usize) {
+        let levels = &self.settings.zoom_levels;
+        if levels.is_empty() {
+            return;
+        }
+        let bounds = self.settings.bounds;
+        let zones = &self.settings.exclusion_zones;
+        let tolerance = self.settings.zoom_snap_tolerance;
+        for keyframe in &mut self.keyframes[start..] {
+            if keyframe.is_manual {
+                continue;
             }
-            EasingType::Spring => {
-                // Simple spring approximation.
-                let c4 = (2.0 * std::f32::consts::PI) / 3.0;
-                if t == 0.0 {
-                    0.0
-                } else if t == 1.0 {
-                    1.0
-                } else {
-                    2.0_f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * c4).sin() + 1.0
+            let zoom = keyframe.viewport.zoom;
+            let nearest = levels
+                .iter()
+                .copied()
+                .min_by(|a, b| (a - zoom).abs().total_cmp(&(b - zoom).abs()))
+                .unwrap_or(zoom);
+            if tolerance.is_some_and(|tolerance| (nearest - zoom).abs() > tolerance) {
+                continue;
+            }
+            keyframe.viewport.zoom = nearest;
+            let mut viewport = keyframe.viewport.clamped_to_frame(bounds);
+            if viewport.zoom > nearest {
+                // `Clip` zoomed in at a frame edge; the next level up still fits around
+                // the clipped center.
+                match levels
+                    .iter()
+                    .copied()
+                    .filter(|&level| level >= viewport.zoom)
+                    .min_by(f32::total_cmp)
+                {
+                    Some(level) => viewport.zoom = level,
+                    None => viewport.zoom = nearest.max(1.0),
                 }
             }
+            viewport.center = move_out_of_zones(viewport.center, zones);
+            // Shift back into frame if that left it, keeping the level.
+            keyframe.viewport = viewport.clamped_to_frame(ViewportBounds::BiasCenter);
+        }
+    }
+}
+
+/// Eased progress for linear progress `t` in 0..=1.
+pub(crate) fn apply_easing(t: f32, easing: EasingType) -> f32 {
+    match easing {
+        EasingType::Linear => t,
+        EasingType::EaseOut => 1.0 - (1.0 - t).powi(3),
+        EasingType::EaseInOut => {
+            if t < 0.5 {
+                4.0 * t * t * t
+            } else {
+                1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+            }
+        }
+        EasingType::Spring => {
+            // Simple spring approximation.
+            let c4 = (2.0 * std::f32::consts::PI) / 3.0;
+            if t == 0.0 {
+                0.0
+            } else if t == 1.0 {
+                1.0
+            } else {
+                2.0_f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * c4).sin() + 1.0
+            }
+        }
+        EasingType::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+    }
+}
+
+/// Evaluate a CSS `cubic-bezier(x1, y1, x2, y2)` timing curve at progress `x`:
+/// solve the curve's x(s) = x for s, then return y(s).
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+    // Bernstein form with P0 = (0, 0) and P3 = (1, 1).
+    let curve = |p1: f32, p2: f32, s: f32| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * s * p1 + 3.0 * inv * s * s * p2 + s * s * s
+    };
+    let slope = |p1: f32, p2: f32, s: f32| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * p1 + 6.0 * inv * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+    };
+
+    let x = x.clamp(0.0, 1.0);
+    // Newton's method, falling back to bisection where the slope is flat.
+    let mut s = x;
+    for _ in 0..8 {
+        let error = curve(x1, x2, s) - x;
+        if error.abs() < 1e-6 {
+            return curve(y1, y2, s);
+        }
+        let d = slope(x1, x2, s);
+        if d.abs() < 1e-6 {
+            break;
+        }
+        s = (s - error / d).clamp(0.0, 1.0);
+    }
+    let (mut lo, mut hi) = (0.0_f32, 1.0_f32);
+    s = x;
+    for _ in 0..32 {
+        let value = curve(x1, x2, s);
+        if (value - x).abs() < 1e-6 {
+            break;
+        }
+        if value < x {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
+    }
+    curve(y1, y2, s)
+}
+
+/// How far apart two viewports are: center distance, or relative zoom difference,
+/// whichever is larger.
+fn viewport_error(a: &Viewport, b: &Viewport) -> f32 {
+    let dx = a.center.x - b.center.x;
+    let dy = a.center.y - b.center.y;
+    let zoom = (a.zoom - b.zoom).abs() / a.zoom.max(b.zoom);
+    (dx * dx + dy * dy).sqrt().max(zoom)
+}
+
+/// Cursor speed in normalized units per second from an earlier position to `point`.
+fn cursor_speed(from_ts: Timestamp, from: &NormalizedCoord, point: &CursorTrackPoint) -> f32 {
+    let dt_us = point
+        .timestamp
+        .as_micros()
+        .saturating_sub(from_ts.as_micros());
+    if dt_us == 0 {
+        return 0.0;
+    }
+    let dx = point.position.x - from.x;
+    let dy = point.position.y - from.y;
+    (dx * dx + dy * dy).sqrt() / (dt_us as f32 / 1_000_000.0)
+}
+
+/// SplitMix64 step: spreads a small seed over all 64 bits.
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Move a point that lies inside any zone to the nearest edge of that zone.
+fn move_out_of_zones(point: NormalizedCoord, zones: &[NormalizedRect]) -> NormalizedCoord {
+    let mut point = point;
+    for zone in zones {
+        if !zone.contains(&point) {
+            continue;
         }
+        let to_left = point.x - zone.x;
+        let to_right = zone.x + zone.width - point.x;
+        let to_top = point.y - zone.y;
+        let to_bottom = zone.y + zone.height - point.y;
+        let nearest = to_left.min(to_right).min(to_top).min(to_bottom);
+        point = if nearest == to_left {
+            NormalizedCoord::new(zone.x, point.y)
+        } else if nearest == to_right {
+            NormalizedCoord::new(zone.x + zone.width, point.y)
+        } else if nearest == to_top {
+            NormalizedCoord::new(point.x, zone.y)
+        } else {
+            NormalizedCoord::new(point.x, zone.y + zone.height)
+        };
     }
+    point
 }
 
 fn lerp(a: f32, b: f32, t: f32) -> f32 {
@@ -239,6 +1149,7 @@ fn lerp(a: f32, b: f32, t: f32) -> f32 {
 }
 
 #[cfg(test)]
+#[allow(clippy::manual_range_contains)]
 mod tests {
     use super::*;
 
@@ -251,23 +1162,803 @@ mod tests {
             state: CursorState::Visible,
             confidence: 100,
             reason: InferenceReason::DirectInput,
+            smoothed: None,
         }];
 
         let keyframes = engine.generate_keyframes(&cursor_track, &[]);
         assert!(!keyframes.is_empty());
     }
 
+    #[test]
+    fn chunked_generation_matches_batch() {
+        let cursor_track: Vec<CursorTrackPoint> = (0..8)
+            .map(|i| CursorTrackPoint {
+                timestamp: Timestamp::from_micros(i * 400_000),
+                position: NormalizedCoord::new(0.1 * i as f32, 0.9 - 0.1 * i as f32),
+                state: CursorState::Visible,
+                confidence: 100,
+                reason: InferenceReason::DirectInput,
+                smoothed: None,
+            })
+            .collect();
+
+        let settings: CameraSettings = serde_json::from_str("{}").unwrap();
+        let mut batch = CameraEngine::new(settings.clone());
+        let expected = batch.generate_keyframes(&cursor_track, &[]);
+
+        let mut chunked = CameraEngine::new(settings);
+        let mut actual = chunked.extend_keyframes(&cursor_track[..3], &[]);
+        actual.extend(chunked.extend_keyframes(&cursor_track[3..], &[]));
+
+        assert_eq!(expected.len(), actual.len());
+        for (a, b) in expected.iter().zip(&actual) {
+            assert_eq!(a.timestamp, b.timestamp);
+            assert_eq!(a.viewport.center, b.viewport.center);
+        }
+    }
+
+    #[test]
+    fn current_viewport_tracks_latest_keyframe() {
+        let settings: CameraSettings = serde_json::from_str("{}").unwrap();
+        let mut engine = CameraEngine::new(settings.clone());
+        assert_eq!(engine.current_viewport().zoom, 1.0);
+
+        let point = CursorTrackPoint {
+            timestamp: Timestamp::from_micros(600_000),
+            position: NormalizedCoord::new(0.55, 0.5),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        };
+        engine.extend_keyframes(&[point], &[]);
+
+        let viewport = engine.current_viewport();
+        assert_eq!(viewport.zoom, settings.zoom_strength);
+    }
+
+    #[test]
+    fn bake_track_samples_every_frame() {
+        let mut engine = CameraEngine::new(serde_json::from_str("{}").unwrap());
+        let point = CursorTrackPoint {
+            timestamp: Timestamp::from_micros(1_000_000),
+            position: NormalizedCoord::new(0.6, 0.5),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        };
+        engine.generate_keyframes(&[point], &[]);
+
+        let baked = engine.bake_track(30.0, Timestamp::from_micros(0), 2_000_000);
+        assert_eq!(baked.len(), 60 * BAKED_VIEWPORT_STRIDE);
+
+        for frame in [0, 29, 59] {
+            let i = frame * BAKED_VIEWPORT_STRIDE;
+            let expected = engine.get_viewport_at(Timestamp::from_micros(
+                (frame as f64 * 1e6 / 30.0).round() as u64,
+            ));
+            assert_eq!(baked[i], expected.center.x);
+            assert_eq!(baked[i + 2], expected.zoom);
+        }
+
+        let matrices = engine.bake_matrix_track(30.0, Timestamp::from_micros(0), 2_000_000);
+        assert_eq!(matrices.len(), 60 * BAKED_MATRIX_STRIDE);
+        let last = engine.get_viewport_at(Timestamp::from_frame(59, 30.0));
+        assert_eq!(&matrices[59 * BAKED_MATRIX_STRIDE..], &last.uv_matrix());
+
+        assert!(engine
+            .bake_track(0.0, Timestamp::from_micros(0), 1_000_000)
+            .is_empty());
+    }
+
+    #[test]
+    fn keyframes_near_edge_stay_in_frame() {
+        let cursor_track = vec![CursorTrackPoint {
+            timestamp: Timestamp::from_micros(1_000_000),
+            position: NormalizedCoord::new(0.95, 0.95),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        }];
+
+        for bounds in [ViewportBounds::Clip, ViewportBounds::BiasCenter] {
+            let mut engine = CameraEngine::new(CameraSettings {
+                zoom_strength: 2.0,
+                max_pan_speed: 10.0,
+                bounds,
+                ..Default::default()
+            });
+            let keyframes = engine.generate_keyframes(&cursor_track, &[]);
+            for keyframe in &keyframes {
+                let rect = keyframe.viewport.visible_rect();
+                assert!(rect.x + rect.width <= 1.0 + 1e-6);
+                assert!(rect.y + rect.height <= 1.0 + 1e-6);
+            }
+            if bounds == ViewportBounds::BiasCenter {
+                assert_eq!(keyframes.last().unwrap().viewport.zoom, 2.0);
+            }
+        }
+    }
+
+    #[test]
+    fn composition_anchor_places_the_target() {
+        let point = |us: u64, x: f32, y: f32| CursorTrackPoint {
+            timestamp: Timestamp::from_micros(us),
+            position: NormalizedCoord::new(x, y),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        };
+        let settings = |composition| CameraSettings {
+            zoom_strength: 2.0,
+            max_pan_speed: 10.0,
+            fast_motion_speed: 0.0,
+            min_zoom_dwell_us: 0,
+            composition,
+            ..Default::default()
+        };
+
+        // Cursor left of and below center sits on the lower-left thirds point.
+        let mut engine = CameraEngine::new(settings(CompositionAnchor::RuleOfThirds));
+        let keyframes = engine.generate_keyframes(&[point(1_000_000, 0.4, 0.6)], &[]);
+        let center = keyframes.last().unwrap().viewport.center;
+        assert!((center.x - (0.4 + 1.0 / 12.0)).abs() < 1e-5);
+        assert!((center.y - (0.6 - 1.0 / 12.0)).abs() < 1e-5);
+
+        // Moving left from the initial full view, the view leads the cursor.
+        let mut engine = CameraEngine::new(settings(CompositionAnchor::Lead { amount: 0.2 }));
+        let keyframes =
+            engine.generate_keyframes(&[point(0, 0.5, 0.5), point(1_000_000, 0.4, 0.5)], &[]);
+        let center = keyframes.last().unwrap().viewport.center;
+        assert!((center.x - 0.3).abs() < 1e-5);
+        assert!((center.y - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn zooms_snap_to_levels() {
+        let cursor_track = vec![CursorTrackPoint {
+            timestamp: Timestamp::from_micros(1_000_000),
+            position: NormalizedCoord::new(0.3, 0.5),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        }];
+        let settings = |zoom_snap_tolerance| CameraSettings {
+            zoom_strength: 1.8,
+            max_pan_speed: 10.0,
+            zoom_levels: vec![1.0, 1.5, 2.0],
+            zoom_snap_tolerance,
+            ..Default::default()
+        };
+
+        let mut engine = CameraEngine::new(settings(None));
+        let zooms: Vec<f32> = engine
+            .generate_keyframes(&cursor_track, &[])
+            .iter()
+            .map(|kf| kf.viewport.zoom)
+            .collect();
+        assert_eq!(zooms, vec![1.0, 2.0]);
+
+        let mut engine = CameraEngine::new(settings(Some(0.1)));
+        let keyframes = engine.generate_keyframes(&cursor_track, &[]);
+        assert_eq!(keyframes.last().unwrap().viewport.zoom, 1.8);
+    }
+
+    #[test]
+    fn snapped_zooms_respect_bounds_and_exclusion_zones() {
+        let cursor_track = vec![CursorTrackPoint {
+            timestamp: Timestamp::from_micros(1_000_000),
+            position: NormalizedCoord::new(0.23, 0.5),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        }];
+        let zone = NormalizedRect::new(0.235, 0.3, 0.2, 0.4);
+        let mut engine = CameraEngine::new(CameraSettings {
+            zoom_strength: 2.4,
+            max_pan_speed: 10.0,
+            zoom_levels: vec![1.0, 2.0, 3.0],
+            bounds: ViewportBounds::Clip,
+            exclusion_zones: vec![zone],
+            ..Default::default()
+        });
+
+        // Snapping to 2x runs off the left edge; clipping zooms in to the next level
+        // instead of shifting the center into the zone.
+        let keyframes = engine.generate_keyframes(&cursor_track, &[]);
+        let viewport = &keyframes.last().unwrap().viewport;
+        assert_eq!(viewport.zoom, 3.0);
+        assert!(!zone.contains(&viewport.center));
+        let rect = viewport.visible_rect();
+        assert!(rect.x >= 0.0 && rect.x + rect.width <= 1.0);
+    }
+
+    #[test]
+    fn viewport_lookup_matches_in_any_order() {
+        let cursor_track: Vec<CursorTrackPoint> = (0..20)
+            .map(|i| CursorTrackPoint {
+                timestamp: Timestamp::from_micros(i * 600_000),
+                position: NormalizedCoord::new(0.2 + 0.03 * i as f32, 0.5),
+                state: CursorState::Visible,
+                confidence: 100,
+                reason: InferenceReason::DirectInput,
+                smoothed: None,
+            })
+            .collect();
+        let mut engine = CameraEngine::new(CameraSettings::default());
+        let keyframes = engine.generate_keyframes(&cursor_track, &[]);
+        assert!(keyframes.len() > 10);
+
+        // Sequential queries (cached path) and scattered ones (binary search) agree.
+        let times: Vec<u64> = (0..130).map(|i| i * 100_000).collect();
+        let sequential: Vec<Viewport> = times
+            .iter()
+            .map(|&t| engine.get_viewport_at(Timestamp::from_micros(t)))
+            .collect();
+        for (i, &t) in times.iter().enumerate().rev().step_by(7) {
+            let viewport = engine.get_viewport_at(Timestamp::from_micros(t));
+            assert_eq!(viewport.center, sequential[i].center);
+            assert_eq!(viewport.zoom, sequential[i].zoom);
+        }
+
+        // Exactly on a keyframe returns that keyframe.
+        let kf = &keyframes[3];
+        assert_eq!(
+            engine.get_viewport_at(kf.timestamp).center,
+            kf.viewport.center
+        );
+    }
+
+    #[test]
+    fn manual_keyframes_survive_regeneration() {
+        let cursor_track: Vec<CursorTrackPoint> = (0..10)
+            .map(|i| CursorTrackPoint {
+                timestamp: Timestamp::from_micros(i * 600_000),
+                position: NormalizedCoord::new(0.2 + 0.06 * i as f32, 0.4),
+                state: CursorState::Visible,
+                confidence: 100,
+                reason: InferenceReason::DirectInput,
+                smoothed: None,
+            })
+            .collect();
+        let mut engine = CameraEngine::new(CameraSettings::default());
+        let pinned = Viewport {
+            center: NormalizedCoord::new(0.3, 0.7),
+            zoom: 2.0,
+        };
+        engine.set_manual_keyframe(Timestamp::from_micros(2_500_000), pinned.clone());
+
+        let keyframes = engine.generate_keyframes(&cursor_track, &[]);
+        let manual: Vec<&CameraKeyframe> = keyframes.iter().filter(|kf| kf.is_manual).collect();
+        assert_eq!(manual.len(), 1);
+        assert_eq!(manual[0].viewport.center, pinned.center);
+        // Auto keyframes around it are cleared for the min hold time.
+        assert!(keyframes
+            .iter()
+            .all(|kf| kf.is_manual || kf.timestamp.as_micros().abs_diff(2_500_000) >= 500_000));
+        assert_eq!(
+            engine
+                .get_viewport_at(Timestamp::from_micros(2_500_000))
+                .center,
+            pinned.center
+        );
+
+        // Chunked generation places it too.
+        let mut chunked = CameraEngine::new(CameraSettings::default());
+        chunked.set_manual_keyframe(Timestamp::from_micros(2_500_000), pinned);
+        let mut actual = chunked.extend_keyframes(&cursor_track[..4], &[]);
+        actual.extend(chunked.extend_keyframes(&cursor_track[4..], &[]));
+        assert_eq!(actual.iter().filter(|kf| kf.is_manual).count(), 1);
+
+        assert!(engine.clear_manual_keyframe(Timestamp::from_micros(2_500_000)));
+        assert!(!engine.clear_manual_keyframe(Timestamp::from_micros(2_500_000)));
+        let keyframes = engine.generate_keyframes(&cursor_track, &[]);
+        assert!(keyframes.iter().all(|kf| !kf.is_manual));
+    }
+
+    #[test]
+    fn manual_hold_keeps_camera_still_then_releases() {
+        let cursor_track: Vec<CursorTrackPoint> = (0..15)
+            .map(|i| CursorTrackPoint {
+                timestamp: Timestamp::from_micros(i * 600_000),
+                position: NormalizedCoord::new(0.2 + 0.04 * i as f32, 0.4),
+                state: CursorState::Visible,
+                confidence: 100,
+                reason: InferenceReason::DirectInput,
+                smoothed: None,
+            })
+            .collect();
+        let mut engine = CameraEngine::new(CameraSettings::default());
+        let pinned = Viewport {
+            center: NormalizedCoord::new(0.3, 0.7),
+            zoom: 2.0,
+        };
+        engine.set_manual_hold(Timestamp::from_micros(1_000_000), pinned.clone(), 3_000_000);
+
+        let keyframes = engine.generate_keyframes(&cursor_track, &[]);
+        assert!(keyframes.iter().all(|kf| {
+            let ts = kf.timestamp.as_micros();
+            kf.is_manual || !(1_000_000..=4_000_000).contains(&ts)
+        }));
+        for us in [1_000_000, 2_500_000, 4_000_000] {
+            let viewport = engine.get_viewport_at(Timestamp::from_micros(us));
+            assert_eq!(viewport.center, pinned.center);
+            assert_eq!(viewport.zoom, pinned.zoom);
+        }
+        let released = engine.get_viewport_at(Timestamp::from_micros(4_100_000));
+        assert_ne!(released.center, pinned.center);
+    }
+
+    #[test]
+    fn breathing_drift_is_small_and_seeded() {
+        let cursor_track = vec![CursorTrackPoint {
+            timestamp: Timestamp::from_micros(1_000_000),
+            position: NormalizedCoord::new(0.4, 0.5),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        }];
+        let engine_with = |breathing_amplitude, breathing_seed| {
+            let mut engine = CameraEngine::new(CameraSettings {
+                breathing_amplitude,
+                breathing_seed,
+                ..Default::default()
+            });
+            engine.generate_keyframes(&cursor_track, &[]);
+            engine
+        };
+        let still = engine_with(0.0, 0);
+        let a = engine_with(0.02, 7);
+        let b = engine_with(0.02, 7);
+        let c = engine_with(0.02, 8);
+
+        let ts = Timestamp::from_micros(6_000_000);
+        let base = still.get_viewport_at(ts);
+        let drifted = a.get_viewport_at(ts);
+        assert_eq!(drifted, b.get_viewport_at(ts));
+        assert_ne!(drifted, c.get_viewport_at(ts));
+        assert_ne!(drifted, base);
+        assert!((drifted.center.x - base.center.x).abs() <= 0.02 / base.zoom + 1e-6);
+        assert!((drifted.zoom / base.zoom - 1.0).abs() <= 0.01 + 1e-6);
+
+        // No drift at full view.
+        let start = Timestamp::from_micros(0);
+        assert_eq!(a.get_viewport_at(start), still.get_viewport_at(start));
+    }
+
+    #[test]
+    fn exclusion_zones_suppress_and_redirect() {
+        let point = |us: u64, x: f32, y: f32| CursorTrackPoint {
+            timestamp: Timestamp::from_micros(us),
+            position: NormalizedCoord::new(x, y),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        };
+        let corner = NormalizedRect::new(0.75, 0.75, 0.25, 0.25);
+        let mut engine = CameraEngine::new(CameraSettings {
+            exclusion_zones: vec![corner],
+            ..Default::default()
+        });
+
+        // The cursor visits the notification corner, then comes back.
+        let keyframes = engine.generate_keyframes(
+            &[
+                point(0, 0.3, 0.3),
+                point(1_000_000, 0.9, 0.9),
+                point(2_000_000, 0.4, 0.5),
+            ],
+            &[FocusRegion {
+                timestamp: Timestamp::from_micros(1_500_000),
+                bounds: NormalizedRect::new(0.8, 0.8, 0.1, 0.1),
+                importance: 1.0,
+                element: None,
+                track_id: None,
+            }],
+        );
+        assert!(keyframes
+            .iter()
+            .all(|kf| !corner.contains(&kf.viewport.center)));
+        assert!(keyframes.iter().all(
+            |kf| kf.timestamp.as_micros() != 1_000_000 && kf.timestamp.as_micros() != 1_500_000
+        ));
+
+        let moved = move_out_of_zones(NormalizedCoord::new(0.78, 0.9), &[corner]);
+        assert_eq!(moved, NormalizedCoord::new(0.75, 0.9));
+    }
+
+    #[test]
+    fn fast_motion_pulls_back_until_settled() {
+        let point = |us: u64, x: f32| CursorTrackPoint {
+            timestamp: Timestamp::from_micros(us),
+            position: NormalizedCoord::new(x, 0.5),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        };
+        let mut engine = CameraEngine::new(CameraSettings {
+            max_pan_speed: 10.0,
+            bounds: ViewportBounds::BiasCenter,
+            fast_motion_speed: 0.8,
+            min_zoom_dwell_us: 0,
+            zoom_in_cooldown_us: 0,
+            ..Default::default()
+        });
+        let keyframes = engine.generate_keyframes(
+            &[
+                point(0, 0.3),
+                point(1_000_000, 0.4),
+                // Fling across the screen, still moving afterwards, then settled.
+                point(1_600_000, 0.9),
+                point(2_200_000, 0.7),
+                point(3_200_000, 0.7),
+            ],
+            &[],
+        );
+        let zooms: Vec<(u64, f32)> = keyframes
+            .iter()
+            .map(|kf| (kf.timestamp.as_micros(), kf.viewport.zoom))
+            .collect();
+        assert_eq!(
+            zooms,
+            vec![
+                (0, 1.0),
+                (1_000_000, 1.5),
+                (1_600_000, 1.0),
+                (3_200_000, 1.5)
+            ]
+        );
+    }
+
+    #[test]
+    fn keyframes_record_their_reason() {
+        let point = |us: u64, x: f32| CursorTrackPoint {
+            timestamp: Timestamp::from_micros(us),
+            position: NormalizedCoord::new(x, 0.5),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        };
+        let mut engine = CameraEngine::new(CameraSettings {
+            max_pan_speed: 10.0,
+            bounds: ViewportBounds::BiasCenter,
+            fast_motion_speed: 0.8,
+            min_zoom_dwell_us: 0,
+            zoom_in_cooldown_us: 0,
+            ..Default::default()
+        });
+        engine.set_manual_keyframe(Timestamp::from_micros(10_000_000), Viewport::default());
+        let keyframes = engine.generate_keyframes_with_clicks(
+            &[
+                point(0, 0.3),
+                point(1_000_000, 0.4),
+                point(1_600_000, 0.9),
+                point(2_200_000, 0.7),
+                point(3_200_000, 0.7),
+                point(10_000_000, 0.7),
+            ],
+            &[],
+            &[Timestamp::from_micros(3_200_000)],
+        );
+        let reasons: Vec<KeyframeReason> = keyframes.iter().map(|kf| kf.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                KeyframeReason::Initial,
+                KeyframeReason::Cursor,
+                KeyframeReason::VelocityZoomOut,
+                KeyframeReason::ClickTarget,
+                KeyframeReason::Manual,
+            ]
+        );
+    }
+
+    #[test]
+    fn click_targets_are_anticipated() {
+        let point = |us: u64, x: f32| CursorTrackPoint {
+            timestamp: Timestamp::from_micros(us),
+            position: NormalizedCoord::new(x, 0.5),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        };
+        let track = [point(0, 0.3), point(1_000_000, 0.4), point(2_000_000, 0.6)];
+        let mut engine = CameraEngine::new(CameraSettings::default());
+
+        let plain = engine.generate_keyframes(&track, &[]);
+        let anticipated = engine.generate_keyframes_with_clicks(
+            &track,
+            &[],
+            &[Timestamp::from_micros(2_000_000)],
+        );
+        let times = |kfs: &[CameraKeyframe]| -> Vec<u64> {
+            kfs.iter().map(|kf| kf.timestamp.as_micros()).collect()
+        };
+        assert_eq!(times(&plain), vec![0, 1_000_000, 2_000_000]);
+        assert_eq!(times(&anticipated), vec![0, 1_000_000, 1_750_000]);
+
+        // Never moved before the preceding keyframe.
+        let mut engine = CameraEngine::new(CameraSettings {
+            look_ahead_us: 5_000_000,
+            ..Default::default()
+        });
+        let anticipated = engine.generate_keyframes_with_clicks(
+            &track,
+            &[],
+            &[Timestamp::from_micros(2_000_000)],
+        );
+        assert_eq!(times(&anticipated), vec![0, 1_000_000, 1_000_001]);
+    }
+
+    #[test]
+    fn zoom_hysteresis_prevents_pumping() {
+        let focus = |us: u64, size: f32| FocusRegion {
+            timestamp: Timestamp::from_micros(us),
+            bounds: NormalizedRect::new(0.4, 0.4, size, size),
+            importance: 1.0,
+            element: None,
+            track_id: None,
+        };
+        let mut engine = CameraEngine::new(CameraSettings {
+            zoom_strength: 3.0,
+            max_pan_speed: 10.0,
+            bounds: ViewportBounds::BiasCenter,
+            max_fit_zoom_out: 1.0,
+            ..Default::default()
+        });
+        // Zoom in, out, straight back in, then a tiny change.
+        let keyframes = engine.generate_keyframes(
+            &[],
+            &[
+                focus(0, 0.5),
+                focus(1_000_000, 1.0),
+                focus(1_500_000, 0.5),
+                focus(3_000_000, 0.5),
+                focus(4_000_000, 0.48),
+            ],
+        );
+        let zooms: Vec<f32> = keyframes.iter().map(|kf| kf.viewport.zoom).collect();
+        // Initial full view, 2x, out to 1x, in-cooldown hold at 1x, 2x, 2x held.
+        assert_eq!(zooms, vec![1.0, 2.0, 1.0, 1.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn compaction_drops_redundant_keyframes() {
+        let keyframe = |us: u64, x: f32, is_manual: bool| CameraKeyframe {
+            timestamp: Timestamp::from_micros(us),
+            viewport: Viewport {
+                center: NormalizedCoord::new(x, 0.5),
+                zoom: 1.5,
+            },
+            easing: EasingType::Linear,
+            is_manual,
+            reason: KeyframeReason::Cursor,
+            hold_us: 0,
+        };
+        let mut engine = CameraEngine::new(CameraSettings::default());
+        // A linear pan sampled every 100ms, a hold, and a manual keyframe on the hold.
+        engine.keyframes = (0..=10)
+            .map(|i| keyframe(i * 100_000, 0.4 + 0.01 * i as f32, false))
+            .chain([
+                keyframe(1_500_000, 0.5, true),
+                keyframe(2_000_000, 0.5, false),
+                keyframe(3_000_000, 0.6, false),
+            ])
+            .collect();
+        let before: Vec<Viewport> = (0..30)
+            .map(|i| engine.get_viewport_at(Timestamp::from_micros(i * 100_000)))
+            .collect();
+
+        let removed = engine.compact_keyframes();
+        assert!(removed >= 9, "removed {}", removed);
+        assert!(engine.keyframes().iter().any(|kf| kf.is_manual));
+        for (i, viewport) in before.iter().enumerate() {
+            let after = engine.get_viewport_at(Timestamp::from_micros(i as u64 * 100_000));
+            assert!(viewport_error(viewport, &after) <= 0.005);
+        }
+
+        engine.set_settings(CameraSettings {
+            max_keyframes: Some(3),
+            ..Default::default()
+        });
+        engine.compact_keyframes();
+        let times: Vec<u64> = engine
+            .keyframes()
+            .iter()
+            .map(|kf| kf.timestamp.as_micros())
+            .collect();
+        assert_eq!(times, vec![0, 1_500_000, 3_000_000]);
+    }
+
+    #[test]
+    fn smart_framing_keeps_cursor_and_focus_visible() {
+        let point = |us: u64, x: f32, y: f32| CursorTrackPoint {
+            timestamp: Timestamp::from_micros(us),
+            position: NormalizedCoord::new(x, y),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        };
+        let field = FocusRegion {
+            timestamp: Timestamp::from_micros(500_000),
+            bounds: NormalizedRect::new(0.3, 0.4, 0.2, 0.1),
+            importance: 1.0,
+            element: None,
+            track_id: None,
+        };
+        let settings = CameraSettings {
+            zoom_strength: 3.0,
+            max_pan_speed: 10.0,
+            min_zoom_dwell_us: 0,
+            zoom_in_cooldown_us: 0,
+            ..Default::default()
+        };
+
+        // Cursor just right of the focused field: both are framed.
+        let mut engine = CameraEngine::new(settings.clone());
+        let keyframes =
+            engine.generate_keyframes(&[point(1_000_000, 0.6, 0.45)], std::slice::from_ref(&field));
+        let cursor_kf = keyframes
+            .iter()
+            .rfind(|kf| kf.timestamp.as_micros() == 1_000_000)
+            .unwrap();
+        let rect = cursor_kf.viewport.visible_rect();
+        assert!(rect.x <= 0.3 && rect.x + rect.width >= 0.6);
+        assert!(cursor_kf.viewport.zoom > 1.0 && cursor_kf.viewport.zoom < 3.0);
+
+        // Cursor across the screen: too far apart, so the cursor is framed alone.
+        let corner_field = FocusRegion {
+            bounds: NormalizedRect::new(0.05, 0.05, 0.1, 0.1),
+            ..field
+        };
+        let mut engine = CameraEngine::new(settings);
+        let keyframes = engine.generate_keyframes(&[point(1_000_000, 0.8, 0.8)], &[corner_field]);
+        let cursor_kf = keyframes
+            .iter()
+            .rfind(|kf| kf.timestamp.as_micros() == 1_000_000)
+            .unwrap();
+        assert_eq!(cursor_kf.viewport.zoom, 3.0);
+    }
+
+    #[test]
+    fn held_focus_from_an_earlier_chunk_can_lead_the_cursor() {
+        let point = |us: u64, x: f32, y: f32| CursorTrackPoint {
+            timestamp: Timestamp::from_micros(us),
+            position: NormalizedCoord::new(x, y),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        };
+        let field = FocusRegion {
+            timestamp: Timestamp::from_micros(1_000_000),
+            bounds: NormalizedRect::new(0.3, 0.4, 0.2, 0.1),
+            importance: 1.0,
+            element: None,
+            track_id: None,
+        };
+        let settings = CameraSettings {
+            zoom_strength: 3.0,
+            max_pan_speed: 10.0,
+            min_zoom_dwell_us: 0,
+            zoom_in_cooldown_us: 0,
+            min_hold_time_us: 0,
+            ..Default::default()
+        };
+
+        // Resampled points can land in a later chunk but before the chunk's last region.
+        let mut engine = CameraEngine::new(settings);
+        engine.extend_keyframes(&[point(500_000, 0.9, 0.9)], std::slice::from_ref(&field));
+        let keyframes = engine.extend_keyframes(&[point(900_000, 0.6, 0.45)], &[]);
+        let cursor_kf = keyframes
+            .iter()
+            .rfind(|kf| kf.timestamp.as_micros() == 900_000)
+            .unwrap();
+        let rect = cursor_kf.viewport.visible_rect();
+        assert!(rect.x <= 0.3 && rect.x + rect.width >= 0.6);
+    }
+
+    #[test]
+    fn fits_simultaneous_focus_regions() {
+        let region = |us: u64, bounds: NormalizedRect| FocusRegion {
+            timestamp: Timestamp::from_micros(us),
+            bounds,
+            importance: 1.0,
+            element: None,
+            track_id: None,
+        };
+        let form = NormalizedRect::new(0.1, 0.2, 0.3, 0.3);
+        let toast = NormalizedRect::new(0.5, 0.1, 0.2, 0.1);
+        let regions = [region(1_000_000, form), region(1_500_000, toast)];
+        let settings = CameraSettings {
+            max_pan_speed: 10.0,
+            bounds: ViewportBounds::BiasCenter,
+            min_zoom_dwell_us: 0,
+            zoom_in_cooldown_us: 0,
+            ..Default::default()
+        };
+
+        let mut engine = CameraEngine::new(settings.clone());
+        let keyframes = engine.generate_keyframes(&[], &regions);
+        let last = keyframes.last().unwrap();
+        let rect = last.viewport.visible_rect();
+        for target in [form, toast] {
+            assert!(rect.x <= target.x && rect.x + rect.width >= target.x + target.width);
+            assert!(rect.y <= target.y && rect.y + rect.height >= target.y + target.height);
+        }
+
+        // Not allowed to widen: jump to the toast alone.
+        let mut engine = CameraEngine::new(CameraSettings {
+            max_fit_zoom_out: 1.0,
+            ..settings
+        });
+        let keyframes = engine.generate_keyframes(&[], &regions);
+        let last = keyframes.last().unwrap();
+        assert_eq!(last.viewport.zoom, 1.5);
+        assert!((last.viewport.center.x - 0.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn tracked_region_holds_its_framing() {
+        let dialog = NormalizedRect::new(0.3, 0.3, 0.3, 0.3);
+        let regions: Vec<FocusRegion> = (0..5)
+            .map(|i| FocusRegion {
+                timestamp: Timestamp::from_micros(i * 400_000),
+                bounds: dialog,
+                importance: 1.0,
+                element: None,
+                track_id: Some(7),
+            })
+            .collect();
+
+        let mut engine = CameraEngine::new(CameraSettings::default());
+        let keyframes = engine.generate_keyframes(&[], &regions);
+        let framed = keyframes
+            .iter()
+            .filter(|kf| kf.reason == KeyframeReason::FocusRegion)
+            .count();
+        assert_eq!(framed, 1);
+    }
+
+    #[test]
+    fn cubic_bezier_matches_css_curves() {
+        // cubic-bezier(0, 0, 1, 1) is linear.
+        for t in [0.1, 0.35, 0.8] {
+            assert!((cubic_bezier(0.0, 0.0, 1.0, 1.0, t) - t).abs() < 1e-4);
+        }
+        // CSS `ease-in-out` (0.42, 0, 0.58, 1) is symmetric about the midpoint.
+        let ease_in_out = |t| cubic_bezier(0.42, 0.0, 0.58, 1.0, t);
+        assert!((ease_in_out(0.5) - 0.5).abs() < 1e-4);
+        assert!((ease_in_out(0.2) + ease_in_out(0.8) - 1.0).abs() < 1e-4);
+        assert!(ease_in_out(0.2) < 0.2);
+
+        let easing: EasingType =
+            serde_json::from_str(r#"{"CubicBezier":[0.25,0.1,0.25,1.0]}"#).unwrap();
+        assert_eq!(easing, EasingType::CubicBezier(0.25, 0.1, 0.25, 1.0));
+    }
+
     #[test]
     fn easing_bounds() {
-        let engine = CameraEngine::new(CameraSettings::default());
         for easing in [
             EasingType::Linear,
             EasingType::EaseOut,
             EasingType::EaseInOut,
             EasingType::Spring,
+            EasingType::CubicBezier(0.25, 0.1, 0.25, 1.0),
         ] {
-            let start = engine.apply_easing(0.0, easing);
-            let end = engine.apply_easing(1.0, easing);
+            let start = apply_easing(0.0, easing);
+            let end = apply_easing(1.0, easing);
             assert!(start >= -0.1 && start <= 0.1, "Easing start should be ~0");
             assert!(end >= 0.9 && end <= 1.1, "Easing end should be ~1");
         }
@@ -288,6 +1979,7 @@ mod tests {
                 state: CursorState::Visible,
                 confidence: 100,
                 reason: InferenceReason::DirectInput,
+                smoothed: None,
             },
             CursorTrackPoint {
                 timestamp: Timestamp::from_micros(100_000), // 100ms later, should be skipped
@@ -295,6 +1987,7 @@ mod tests {
                 state: CursorState::Visible,
                 confidence: 100,
                 reason: InferenceReason::DirectInput,
+                smoothed: None,
             },
         ];
 
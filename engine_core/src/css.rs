@@ -0,0 +1,160 @@
+// CSS keyframe export of the camera track, for DOM-based playback previews: the
+// preview layer plays a `@keyframes` transform animation instead of re-deriving the
+// interpolation in JS.
+// See steering.md: Rust Rules (Engine)
+
+use std::fmt::Write as _;
+
+use crate::camera::apply_easing;
+use crate::types::{CameraKeyframe, EasingType, Timestamp, Viewport};
+
+/// Points sampled from curves CSS has no function for (the spring), for `linear()`.
+const SPRING_SAMPLES: usize = 24;
+
+/// A `@keyframes` rule named `name` animating the camera over `duration` (the
+/// recording from 0), plus a `.name` rule that plays it. The animated element is the
+/// full-frame content layer inside a clipping box: each stop scales it by the zoom and
+/// translates the visible rect's top-left to the origin. Holds become repeated stops,
+/// and each move carries its keyframe's easing (springs as `linear()`). CSS
+/// interpolates scale and translation separately, so while zoom changes the framing
+/// differs slightly from `get_viewport_at`; breathing drift is not included.
+pub fn camera_keyframes_css(
+    keyframes: &[CameraKeyframe],
+    name: &str,
+    duration: Timestamp,
+) -> String {
+    let total = duration
+        .as_micros()
+        .max(keyframes.last().map_or(0, |kf| kf.timestamp.as_micros()))
+        .max(1) as f64;
+    let percent = |us: u64| us as f64 / total * 100.0;
+
+    let mut css = format!("@keyframes {} {{\n", name);
+    for (i, keyframe) in keyframes.iter().enumerate() {
+        let next = keyframes.get(i + 1);
+        let at = keyframe.timestamp.as_micros();
+        let transform = transform(&keyframe.viewport);
+        let timing = |next: Option<&CameraKeyframe>| {
+            next.map_or_else(|| "linear".to_string(), |next| timing_function(next.easing))
+        };
+
+        // The move to the next keyframe starts once the hold is over.
+        let move_at = next.map_or(at, |next| {
+            (at + keyframe.hold_us).min(next.timestamp.as_micros())
+        });
+        if move_at > at {
+            let _ = writeln!(
+                css,
+                "  {:.4}% {{ transform: {}; animation-timing-function: linear; }}",
+                percent(at),
+                transform
+            );
+        }
+        let _ = writeln!(
+            css,
+            "  {:.4}% {{ transform: {}; animation-timing-function: {}; }}",
+            percent(move_at),
+            transform,
+            timing(next)
+        );
+    }
+    let _ = writeln!(
+        css,
+        "}}\n.{} {{ transform-origin: 0 0; animation: {} {:.6}s linear forwards; }}",
+        name,
+        name,
+        total / 1_000_000.0
+    );
+    css
+}
+
+/// CSS transform showing `viewport` from a full-frame layer.
+fn transform(viewport: &Viewport) -> String {
+    let rect = viewport.visible_rect();
+    format!(
+        "scale({:.4}) translate({:.4}%, {:.4}%)",
+        viewport.zoom,
+        // `0.0 -` so a zero offset prints as 0 rather than -0.
+        0.0 - rect.x * 100.0,
+        0.0 - rect.y * 100.0
+    )
+}
+
+/// CSS timing function matching the camera's easing curve.
+fn timing_function(easing: EasingType) -> String {
+    match easing {
+        EasingType::Linear => "linear".to_string(),
+        // Cubic ease-out and ease-in-out.
+        EasingType::EaseOut => "cubic-bezier(0.33, 1, 0.68, 1)".to_string(),
+        EasingType::EaseInOut => "cubic-bezier(0.65, 0, 0.35, 1)".to_string(),
+        EasingType::CubicBezier(x1, y1, x2, y2) => format!(
+            "cubic-bezier({}, {}, {}, {})",
+            x1.clamp(0.0, 1.0),
+            y1,
+            x2.clamp(0.0, 1.0),
+            y2
+        ),
+        EasingType::Spring => {
+            let points: Vec<String> = (0..=SPRING_SAMPLES)
+                .map(|i| {
+                    let t = i as f32 / SPRING_SAMPLES as f32;
+                    format!("{:.4}", apply_easing(t, easing))
+                })
+                .collect();
+            format!("linear({})", points.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::NormalizedCoord;
+
+    fn keyframe(us: u64, x: f32, zoom: f32, easing: EasingType, hold_us: u64) -> CameraKeyframe {
+        CameraKeyframe {
+            timestamp: Timestamp::from_micros(us),
+            viewport: Viewport {
+                center: NormalizedCoord::new(x, 0.5),
+                zoom,
+            },
+            easing,
+            is_manual: false,
+            reason: Default::default(),
+            hold_us,
+        }
+    }
+
+    #[test]
+    fn keyframes_become_eased_transform_stops() {
+        let keyframes = vec![
+            keyframe(0, 0.5, 1.0, EasingType::Linear, 1_000_000),
+            keyframe(2_000_000, 0.25, 2.0, EasingType::EaseOut, 0),
+            keyframe(3_000_000, 0.5, 1.0, EasingType::Spring, 0),
+        ];
+
+        let css = camera_keyframes_css(&keyframes, "camera", Timestamp::from_micros(4_000_000));
+        let lines: Vec<&str> = css.lines().collect();
+        assert_eq!(lines[0], "@keyframes camera {");
+        assert_eq!(
+            lines[1],
+            "  0.0000% { transform: scale(1.0000) translate(0.0000%, 0.0000%); \
+             animation-timing-function: linear; }"
+        );
+        // The hold ends at 1s, then the ease-out move to 2x on the left.
+        assert_eq!(
+            lines[2],
+            "  25.0000% { transform: scale(1.0000) translate(0.0000%, 0.0000%); \
+             animation-timing-function: cubic-bezier(0.33, 1, 0.68, 1); }"
+        );
+        assert!(lines[3].starts_with(
+            "  50.0000% { transform: scale(2.0000) translate(0.0000%, -25.0000%); \
+             animation-timing-function: linear(0.0000, "
+        ));
+        assert!(lines[3].ends_with(", 1.0000); }"));
+        assert_eq!(
+            lines[6],
+            ".camera { transform-origin: 0 0; animation: camera 4.000000s linear forwards; }"
+        );
+    }
+}
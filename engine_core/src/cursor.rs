@@ -2,37 +2,435 @@
 // Tab Mode: real mouse + click targets. Desktop Mode: cursor-from-video detection.
 // See steering.md: Auto-Zoom Strategy Rules
 
+use std::borrow::Cow;
+
 use crate::types::*;
 
 /// Cursor tracker that processes input signals and generates cursor track with confidence.
 pub struct CursorTracker {
     _capture_mode: CaptureMode,
+    settings: CursorSettings,
     last_position: Option<NormalizedCoord>,
     last_timestamp: Option<Timestamp>,
+    /// Smoothing filter state, carried across batches.
+    filter: Option<PointFilter>,
+    /// Last accepted direct-input point (the reference for outlier detection; focus
+    /// changes move `last_position` without the mouse moving).
+    last_direct: Option<(Timestamp, NormalizedCoord)>,
+    /// Last direct-input point flagged as an outlier. If the next point continues from
+    /// it, the cursor really moved there and tracking follows.
+    last_outlier: Option<(Timestamp, NormalizedCoord)>,
+    /// Last two points of the previous batch, so resampling continues across batches.
+    resample_tail: Vec<CursorTrackPoint>,
+    /// Time of the last resampled point emitted.
+    resampled_until: Option<Timestamp>,
+    /// Latest reports from each source, for fusion: input events, and frame
+    /// detection (with its confidence).
+    last_event_report: Option<(Timestamp, NormalizedCoord)>,
+    last_detection: Option<(Timestamp, NormalizedCoord, u8)>,
+    /// Drag in progress (button held), carried across batches.
+    drag: Option<DragSegment>,
+    /// Drags finished since the last `take_drags`.
+    drags: Vec<DragSegment>,
+    /// Fingers on the screen in touch-down order; the first drives the cursor.
+    touches: Vec<(u32, NormalizedCoord)>,
+    /// Pinch in progress between the first two fingers.
+    pinch: Option<PinchGesture>,
+    /// Pinches finished since the last `take_pinches`.
+    pinches: Vec<PinchGesture>,
+    /// Direct-input positions from the last `SHAKE_WINDOW_US`.
+    shake_window: Vec<(Timestamp, NormalizedCoord)>,
+    /// Whether the cursor is still shaking since the last reported shake.
+    shaking: bool,
+    /// Shakes recognized since the last `take_shakes`.
+    shakes: Vec<ShakeGesture>,
 }
 
 impl CursorTracker {
     pub fn new(capture_mode: CaptureMode) -> Self {
+        CursorTracker::with_settings(capture_mode, CursorSettings::default())
+    }
+
+    pub fn with_settings(capture_mode: CaptureMode, settings: CursorSettings) -> Self {
         CursorTracker {
             _capture_mode: capture_mode,
+            settings,
             last_position: None,
             last_timestamp: None,
+            filter: None,
+            last_direct: None,
+            last_outlier: None,
+            resample_tail: Vec::new(),
+            resampled_until: None,
+            last_event_report: None,
+            last_detection: None,
+            drag: None,
+            drags: Vec::new(),
+            touches: Vec::new(),
+            pinch: None,
+            pinches: Vec::new(),
+            shake_window: Vec::new(),
+            shaking: false,
+            shakes: Vec::new(),
         }
     }
 
+    /// Replace tracker settings. Applies to signals processed after this call.
+    pub fn set_settings(&mut self, settings: CursorSettings) {
+        if settings.filter != self.settings.filter {
+            self.filter = None;
+        }
+        self.settings = settings;
+    }
+
+    /// Forget the last known position so the next batch starts fresh.
+    pub fn reset(&mut self) {
+        self.last_position = None;
+        self.last_timestamp = None;
+        self.filter = None;
+        self.last_direct = None;
+        self.last_outlier = None;
+        self.resample_tail.clear();
+        self.resampled_until = None;
+        self.last_event_report = None;
+        self.last_detection = None;
+        self.drag = None;
+        self.drags.clear();
+        self.touches.clear();
+        self.pinch = None;
+        self.pinches.clear();
+        self.shake_window.clear();
+        self.shaking = false;
+        self.shakes.clear();
+    }
+
+    /// Drags finished in the batches processed since the last call.
+    pub fn take_drags(&mut self) -> Vec<DragSegment> {
+        std::mem::take(&mut self.drags)
+    }
+
+    /// Pinches finished in the batches processed since the last call.
+    pub fn take_pinches(&mut self) -> Vec<PinchGesture> {
+        std::mem::take(&mut self.pinches)
+    }
+
+    /// Shakes recognized in the batches processed since the last call.
+    pub fn take_shakes(&mut self) -> Vec<ShakeGesture> {
+        std::mem::take(&mut self.shakes)
+    }
+
     /// Process a batch of signals and return cursor track points.
     pub fn process(&mut self, signals: &SignalBatch) -> Vec<CursorTrackPoint> {
         let mut track = Vec::with_capacity(signals.events.len());
 
         for event in &signals.events {
-            if let Some(point) = self.process_event(event) {
+            self.track_drag(event);
+            if !self.track_touch(event) || self.is_redundant(event) {
+                continue;
+            }
+            if let Some(mut point) = self.process_event(event) {
+                if point.reason == InferenceReason::DirectInput && self.is_outlier(&point) {
+                    self.last_outlier = Some((point.timestamp, point.position));
+                    if self.settings.drop_outliers {
+                        continue;
+                    }
+                    point.state = CursorState::Inferred;
+                    point.confidence = OUTLIER_CONFIDENCE;
+                    point.reason = InferenceReason::Outlier;
+                    track.push(point);
+                    continue;
+                }
                 self.last_position = Some(point.position);
                 self.last_timestamp = Some(point.timestamp);
+                if point.reason == InferenceReason::DirectInput {
+                    self.last_direct = Some((point.timestamp, point.position));
+                    self.last_outlier = None;
+                    point.smoothed = self.smooth(point.timestamp, point.position);
+                    self.detect_shake(point.timestamp, point.position);
+                }
+                self.fuse(&mut point);
                 track.push(point);
             }
         }
 
-        track
+        match self.settings.resample_hz {
+            Some(hz) if hz > 0.0 => self.resample(track, hz),
+            _ => track,
+        }
+    }
+
+    /// Resample a batch onto the fixed grid, continuing from the previous batch. Samples
+    /// after a batch's last point come with the next batch.
+    fn resample(&mut self, track: Vec<CursorTrackPoint>, hz: f32) -> Vec<CursorTrackPoint> {
+        let mut points = std::mem::take(&mut self.resample_tail);
+        points.extend(
+            track
+                .into_iter()
+                .filter(|point| point.reason != InferenceReason::Outlier),
+        );
+
+        let resampled = resample_points(
+            &points,
+            1_000_000.0 / hz as f64,
+            self.resampled_until,
+            self.settings.max_gap_fill_us,
+        );
+        if let Some(last) = resampled.last() {
+            self.resampled_until = Some(last.timestamp);
+        }
+        self.resample_tail = points.split_off(points.len().saturating_sub(2));
+        resampled
+    }
+
+    /// The track the camera should follow: `track` itself, or with each point moved to
+    /// its smoothed position when `camera_input` is `Smoothed`.
+    pub fn camera_track<'a>(&self, track: &'a [CursorTrackPoint]) -> Cow<'a, [CursorTrackPoint]> {
+        match self.settings.camera_input {
+            CursorInput::Raw => Cow::Borrowed(track),
+            CursorInput::Smoothed => Cow::Owned(
+                track
+                    .iter()
+                    .map(|point| CursorTrackPoint {
+                        position: point.smoothed.unwrap_or(point.position),
+                        ..point.clone()
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Blend a direct-input or detected point with the latest report from the other
+    /// source. Each report is weighted by its confidence, halved every
+    /// `fusion_half_life_us` of age; reports older than 5 half-lives are ignored.
+    fn fuse(&mut self, point: &mut CursorTrackPoint) {
+        let half_life = self.settings.fusion_half_life_us;
+        let other = match point.reason {
+            InferenceReason::DirectInput => {
+                self.last_event_report = Some((point.timestamp, point.position));
+                self.last_detection
+            }
+            InferenceReason::CursorDetection { confidence } => {
+                self.last_detection = Some((point.timestamp, point.position, confidence));
+                self.last_event_report
+                    .map(|(ts, position)| (ts, position, DIRECT_INPUT_CONFIDENCE))
+            }
+            _ => return,
+        };
+        let Some((ts, position, confidence)) = other else {
+            return;
+        };
+        let age = point.timestamp.as_micros().saturating_sub(ts.as_micros());
+        if half_life == 0 || age > half_life * 5 {
+            return;
+        }
+
+        let own = point.confidence as f32;
+        let other = confidence as f32 * 0.5f32.powf(age as f32 / half_life as f32);
+        let total = own + other;
+        if total <= 0.0 {
+            return;
+        }
+        point.position = NormalizedCoord::new(
+            (point.position.x * own + position.x * other) / total,
+            (point.position.y * own + position.y * other) / total,
+        );
+        point.confidence = point.confidence.max(other.round() as u8);
+    }
+
+    /// Follow a press-move-release with one button. Releases that barely moved are
+    /// clicks and are not recorded.
+    fn track_drag(&mut self, event: &InputEvent) {
+        let extend = |drag: &mut DragSegment, position: NormalizedCoord| {
+            drag.end = event.timestamp;
+            drag.end_position = position;
+            drag.bounds = drag
+                .bounds
+                .union(&NormalizedRect::new(position.x, position.y, 0.0, 0.0));
+        };
+        match &event.event_type {
+            EventType::MouseDown { position, button } => {
+                self.drag = Some(DragSegment {
+                    start: event.timestamp,
+                    end: event.timestamp,
+                    button: *button,
+                    start_position: *position,
+                    end_position: *position,
+                    bounds: NormalizedRect::new(position.x, position.y, 0.0, 0.0),
+                });
+            }
+            EventType::MouseMove { position } => {
+                if let Some(drag) = &mut self.drag {
+                    extend(drag, *position);
+                }
+            }
+            EventType::MouseUp { position, button } => {
+                let Some(mut drag) = self.drag.take_if(|drag| drag.button == *button) else {
+                    return;
+                };
+                extend(&mut drag, *position);
+                if drag.bounds.width.max(drag.bounds.height) >= self.settings.min_drag_distance {
+                    self.drags.push(drag);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Follow the fingers on the screen and any pinch between the first two. Returns
+    /// false for touches by other fingers than the first, which don't move the cursor.
+    fn track_touch(&mut self, event: &InputEvent) -> bool {
+        let EventType::Touch {
+            position,
+            pointer_id,
+            phase,
+            ..
+        } = &event.event_type
+        else {
+            return true;
+        };
+
+        if *phase == PointerPhase::Down {
+            self.touches.retain(|(id, _)| id != pointer_id);
+            self.touches.push((*pointer_id, *position));
+        }
+        let Some(index) = self.touches.iter().position(|(id, _)| id == pointer_id) else {
+            return false;
+        };
+        self.touches[index].1 = *position;
+
+        if let [(_, a), (_, b), ..] = self.touches[..] {
+            let spread = (b.x - a.x).hypot(b.y - a.y);
+            let pinch = self.pinch.get_or_insert_with(|| PinchGesture {
+                start: event.timestamp,
+                end: event.timestamp,
+                center: NormalizedCoord::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0),
+                bounds: NormalizedRect::new(a.x.min(b.x), a.y.min(b.y), 0.0, 0.0)
+                    .union(&NormalizedRect::new(a.x.max(b.x), a.y.max(b.y), 0.0, 0.0)),
+                scale: 1.0,
+            });
+            let start_spread = pinch.bounds.width.hypot(pinch.bounds.height);
+            if start_spread > 0.0 {
+                pinch.scale = spread / start_spread;
+            }
+            pinch.end = event.timestamp;
+        }
+
+        if *phase == PointerPhase::Up {
+            self.touches.remove(index);
+            // Lifting either pinching finger ends the pinch.
+            if index < 2 {
+                if let Some(pinch) = self.pinch.take() {
+                    let min_scale = self.settings.min_pinch_scale;
+                    if pinch.scale >= min_scale || pinch.scale * min_scale <= 1.0 {
+                        self.pinches.push(pinch);
+                    }
+                }
+            }
+        }
+        index == 0
+    }
+
+    /// Recognize a shake: at least `shake_min_reversals` direction changes on one axis,
+    /// each after a swing of `SHAKE_MIN_SWING`, within `SHAKE_WINDOW_US` and a small area.
+    fn detect_shake(&mut self, timestamp: Timestamp, position: NormalizedCoord) {
+        let min_reversals = self.settings.shake_min_reversals;
+        if min_reversals == 0 {
+            return;
+        }
+        let ts = timestamp.as_micros();
+        self.shake_window
+            .retain(|(t, _)| ts.saturating_sub(t.as_micros()) <= SHAKE_WINDOW_US);
+        self.shake_window.push((timestamp, position));
+
+        let (first_ts, first) = self.shake_window[0];
+        let bounds = self.shake_window.iter().fold(
+            NormalizedRect::new(first.x, first.y, 0.0, 0.0),
+            |bounds, (_, p)| bounds.union(&NormalizedRect::new(p.x, p.y, 0.0, 0.0)),
+        );
+        let reversals = |axis: fn(&NormalizedCoord) -> f32| {
+            let mut reversals = 0;
+            let mut direction = 0.0;
+            let mut extreme = axis(&first);
+            for (_, p) in &self.shake_window {
+                let delta = axis(p) - extreme;
+                if delta * direction > 0.0 {
+                    // Still swinging the same way.
+                    extreme = axis(p);
+                } else if delta.abs() >= SHAKE_MIN_SWING {
+                    if direction != 0.0 {
+                        reversals += 1;
+                    }
+                    direction = delta.signum();
+                    extreme = axis(p);
+                }
+            }
+            reversals
+        };
+        let shaking = bounds.width.max(bounds.height) <= SHAKE_MAX_SPAN
+            && reversals(|p| p.x).max(reversals(|p| p.y)) >= min_reversals;
+
+        if shaking && !self.shaking {
+            self.shakes.push(ShakeGesture {
+                start: first_ts,
+                end: timestamp,
+                center: bounds.center(),
+                bounds,
+            });
+        }
+        self.shaking = shaking;
+    }
+
+    /// Whether a mouse move is too close in time or space to the last kept point to be
+    /// worth storing (per `min_move_interval_us` / `min_move_distance`).
+    fn is_redundant(&self, event: &InputEvent) -> bool {
+        let EventType::MouseMove { position } = &event.event_type else {
+            return false;
+        };
+        let Some((ts, last)) = self.last_direct else {
+            return false;
+        };
+        let dt_us = event.timestamp.as_micros().saturating_sub(ts.as_micros());
+        let dx = position.x - last.x;
+        let dy = position.y - last.y;
+        dt_us < self.settings.min_move_interval_us
+            || (dx * dx + dy * dy).sqrt() < self.settings.min_move_distance
+    }
+
+    /// Whether `point` is a physically implausible jump from the last good position
+    /// (and doesn't continue from a previous outlier).
+    fn is_outlier(&self, point: &CursorTrackPoint) -> bool {
+        let max_speed = self.settings.max_cursor_speed;
+        if max_speed <= 0.0 {
+            return false;
+        }
+        let too_fast = |from_ts: Timestamp, from: NormalizedCoord| {
+            // Events sharing a timestamp count as 1ms apart.
+            let dt_us = point
+                .timestamp
+                .as_micros()
+                .saturating_sub(from_ts.as_micros())
+                .max(1_000);
+            let dx = point.position.x - from.x;
+            let dy = point.position.y - from.y;
+            (dx * dx + dy * dy).sqrt() / (dt_us as f32 / 1_000_000.0) > max_speed
+        };
+        let Some((ts, position)) = self.last_direct else {
+            return false;
+        };
+        too_fast(ts, position) && self.last_outlier.is_none_or(|(ts, pos)| too_fast(ts, pos))
+    }
+
+    fn smooth(
+        &mut self,
+        timestamp: Timestamp,
+        position: NormalizedCoord,
+    ) -> Option<NormalizedCoord> {
+        let kind = self.settings.filter;
+        if kind == CursorFilter::None {
+            return None;
+        }
+        let filter = self.filter.get_or_insert_with(|| PointFilter::new(kind));
+        Some(filter.apply(timestamp, position))
     }
 
     fn process_event(&self, event: &InputEvent) -> Option<CursorTrackPoint> {
@@ -43,17 +441,23 @@ impl CursorTracker {
                 state: CursorState::Visible,
                 confidence: 100,
                 reason: InferenceReason::DirectInput,
+                smoothed: None,
             }),
 
-            EventType::MouseClick { position, .. } => Some(CursorTrackPoint {
+            EventType::MouseClick { position, .. }
+            | EventType::MouseDown { position, .. }
+            | EventType::MouseUp { position, .. }
+            | EventType::Touch { position, .. }
+            | EventType::Pen { position, .. } => Some(CursorTrackPoint {
                 timestamp: event.timestamp,
                 position: *position,
                 state: CursorState::Visible,
                 confidence: 100,
                 reason: InferenceReason::DirectInput,
+                smoothed: None,
             }),
 
-            EventType::FocusChange { bounds } => {
+            EventType::FocusChange { bounds, .. } => {
                 // Use center of focused element as cursor proxy.
                 Some(CursorTrackPoint {
                     timestamp: event.timestamp,
@@ -61,6 +465,7 @@ impl CursorTracker {
                     state: CursorState::Inferred,
                     confidence: 80,
                     reason: InferenceReason::UiChange,
+                    smoothed: None,
                 })
             }
 
@@ -74,12 +479,380 @@ impl CursorTracker {
                     state: CursorState::Inferred,
                     confidence: 50,
                     reason: InferenceReason::SaliencyFallback,
+                    smoothed: None,
                 })
             }
 
-            EventType::Scroll { .. } => None, // Scroll doesn't produce cursor points
+            EventType::CursorDetected {
+                position,
+                confidence,
+            } => Some(CursorTrackPoint {
+                timestamp: event.timestamp,
+                position: *position,
+                state: CursorState::Inferred,
+                confidence: *confidence,
+                reason: InferenceReason::CursorDetection {
+                    confidence: *confidence,
+                },
+                smoothed: None,
+            }),
+
+            // Scrolling, window moves, selections, typing, and recording control don't
+            // produce cursor points (the mouse events around a selection already do).
+            EventType::Pause
+            | EventType::Resume
+            | EventType::Scroll { .. }
+            | EventType::WindowMoved { .. }
+            | EventType::TextSelection { .. }
+            | EventType::VideoPlaying { .. }
+            | EventType::KeyPress { .. }
+            | EventType::KeyDown { .. }
+            | EventType::KeyUp { .. } => None,
+        }
+    }
+}
+
+/// Velocity and acceleration for each point of a cursor track, by central differences
+/// (one-sided at the ends). Outliers are skipped; points sharing a timestamp with the
+/// previous one repeat its motion.
+pub fn derive_motion(track: &[CursorTrackPoint]) -> Vec<CursorMotion> {
+    let points: Vec<&CursorTrackPoint> = track
+        .iter()
+        .filter(|point| point.reason != InferenceReason::Outlier)
+        .collect();
+    let rate = |a: &CursorTrackPoint, b: &CursorTrackPoint| {
+        let dt = b
+            .timestamp
+            .as_micros()
+            .saturating_sub(a.timestamp.as_micros()) as f32
+            / 1_000_000.0;
+        if dt > 0.0 {
+            Some((
+                (b.position.x - a.position.x) / dt,
+                (b.position.y - a.position.y) / dt,
+            ))
+        } else {
+            None
+        }
+    };
+
+    let mut velocities: Vec<(f32, f32)> = Vec::with_capacity(points.len());
+    for i in 0..points.len() {
+        let before = points[i.saturating_sub(1)];
+        let after = points[(i + 1).min(points.len() - 1)];
+        let velocity = rate(before, after)
+            .or_else(|| velocities.last().copied())
+            .unwrap_or((0.0, 0.0));
+        velocities.push(velocity);
+    }
+
+    (0..points.len())
+        .map(|i| {
+            let before = i.saturating_sub(1);
+            let after = (i + 1).min(points.len() - 1);
+            let dt = points[after]
+                .timestamp
+                .as_micros()
+                .saturating_sub(points[before].timestamp.as_micros()) as f32
+                / 1_000_000.0;
+            let (ax, ay) = if dt > 0.0 {
+                (
+                    (velocities[after].0 - velocities[before].0) / dt,
+                    (velocities[after].1 - velocities[before].1) / dt,
+                )
+            } else {
+                (0.0, 0.0)
+            };
+            let (vx, vy) = velocities[i];
+            CursorMotion {
+                timestamp: points[i].timestamp,
+                vx,
+                vy,
+                speed: (vx * vx + vy * vy).sqrt(),
+                ax,
+                ay,
+            }
+        })
+        .collect()
+}
+
+/// Reduce a cursor track to the points needed to draw its path within `tolerance`
+/// (normalized units) of the original, by Ramer-Douglas-Peucker. Outliers are dropped
+/// and points where the cursor's state changes are always kept, so hide/show
+/// transitions survive simplification.
+pub fn simplify_path(track: &[CursorTrackPoint], tolerance: f32) -> Vec<CursorTrackPoint> {
+    let points: Vec<&CursorTrackPoint> = track
+        .iter()
+        .filter(|point| point.reason != InferenceReason::Outlier)
+        .collect();
+    let mut keep = vec![false; points.len()];
+
+    let mut run_start = 0;
+    for i in 1..=points.len() {
+        if i == points.len() || points[i].state != points[run_start].state {
+            keep[run_start] = true;
+            keep[i - 1] = true;
+            // Explicit stack: recordings can run to tens of thousands of points.
+            let mut pending = vec![(run_start, i - 1)];
+            while let Some((first, last)) = pending.pop() {
+                let farthest = (first + 1..last)
+                    .map(|j| {
+                        let distance = segment_distance(
+                            points[j].position,
+                            points[first].position,
+                            points[last].position,
+                        );
+                        (j, distance)
+                    })
+                    .max_by(|a, b| a.1.total_cmp(&b.1));
+                if let Some((j, distance)) = farthest {
+                    if distance > tolerance {
+                        keep[j] = true;
+                        pending.push((first, j));
+                        pending.push((j, last));
+                    }
+                }
+            }
+            run_start = i;
+        }
+    }
+
+    points
+        .into_iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(point, _)| point.clone())
+        .collect()
+}
+
+/// Distance from `p` to the segment `a`-`b`.
+fn segment_distance(p: NormalizedCoord, a: NormalizedCoord, b: NormalizedCoord) -> f32 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length_sq = dx * dx + dy * dy;
+    let t = if length_sq > 0.0 {
+        (((p.x - a.x) * dx + (p.y - a.y) * dy) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let (ex, ey) = (p.x - (a.x + t * dx), p.y - (a.y + t * dy));
+    (ex * ex + ey * ey).sqrt()
+}
+
+/// Time window (microseconds) a shake's reversals must fall within.
+const SHAKE_WINDOW_US: u64 = 800_000;
+/// Smallest swing (normalized) that counts toward a shake.
+const SHAKE_MIN_SWING: f32 = 0.02;
+/// Largest area (normalized width or height) a shake may cover.
+const SHAKE_MAX_SPAN: f32 = 0.3;
+
+/// Resampled points this far (microseconds) from the nearest reported point have half
+/// its confidence.
+const GAP_CONFIDENCE_HALF_LIFE_US: f32 = 250_000.0;
+
+/// Sample sorted `points` at multiples of `period_us` after `after` (or from the first
+/// point), with Catmull-Rom interpolation. A sample within one period of a reported
+/// point takes that point's state and confidence; farther samples are gap fill
+/// (`Inferred`, confidence decaying with distance). Gaps over `max_gap_us` are skipped.
+fn resample_points(
+    points: &[CursorTrackPoint],
+    period_us: f64,
+    after: Option<Timestamp>,
+    max_gap_us: u64,
+) -> Vec<CursorTrackPoint> {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    let mut k = match after {
+        Some(after) => (after.as_micros() as f64 / period_us).floor() as u64 + 1,
+        None => (first.timestamp.as_micros() as f64 / period_us).ceil() as u64,
+    };
+
+    let mut resampled = Vec::new();
+    let mut i = 0;
+    loop {
+        let t = (k as f64 * period_us).round() as u64;
+        if t > last.timestamp.as_micros() {
+            break;
+        }
+        k += 1;
+        while i + 1 < points.len() && points[i + 1].timestamp.as_micros() <= t {
+            i += 1;
+        }
+        let p1 = &points[i];
+        let t1 = p1.timestamp.as_micros();
+        if t1 > t {
+            continue;
+        }
+        let timestamp = Timestamp::from_micros(t);
+        let Some(p2) = points.get(i + 1) else {
+            resampled.push(CursorTrackPoint {
+                timestamp,
+                ..p1.clone()
+            });
+            continue;
+        };
+        let t2 = p2.timestamp.as_micros();
+        if t2 - t1 > max_gap_us {
+            continue;
+        }
+
+        let p0 = if i > 0 { &points[i - 1] } else { p1 };
+        let p3 = points.get(i + 2).unwrap_or(p2);
+        let u = (t - t1) as f32 / (t2 - t1) as f32;
+        let position = NormalizedCoord::new(
+            catmull_rom(
+                p0.position.x,
+                p1.position.x,
+                p2.position.x,
+                p3.position.x,
+                u,
+            ),
+            catmull_rom(
+                p0.position.y,
+                p1.position.y,
+                p2.position.y,
+                p3.position.y,
+                u,
+            ),
+        );
+        let smoothed = match (p1.smoothed, p2.smoothed) {
+            (Some(a), Some(b)) => Some(NormalizedCoord::new(
+                lerp_by(a.x, b.x, u),
+                lerp_by(a.y, b.y, u),
+            )),
+            _ => None,
+        };
+
+        let (nearest, distance_us) = if t - t1 <= t2 - t {
+            (p1, t - t1)
+        } else {
+            (p2, t2 - t)
+        };
+        resampled.push(if distance_us as f64 <= period_us {
+            CursorTrackPoint {
+                timestamp,
+                position,
+                smoothed,
+                ..nearest.clone()
+            }
+        } else {
+            let confidence = p1.confidence.min(p2.confidence) as f32
+                * 0.5f32.powf(distance_us as f32 / GAP_CONFIDENCE_HALF_LIFE_US);
+            CursorTrackPoint {
+                timestamp,
+                position,
+                state: CursorState::Inferred,
+                confidence: confidence.round() as u8,
+                reason: InferenceReason::MotionTracking,
+                smoothed,
+            }
+        });
+    }
+    resampled
+}
+
+/// Uniform Catmull-Rom spline through `p1` (u = 0) and `p2` (u = 1).
+fn catmull_rom(p0: f32, p1: f32, p2: f32, p3: f32, u: f32) -> f32 {
+    let u2 = u * u;
+    let u3 = u2 * u;
+    0.5 * (2.0 * p1
+        + (p2 - p0) * u
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * u2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * u3)
+}
+
+/// Confidence of input-event positions when fusing with detection.
+const DIRECT_INPUT_CONFIDENCE: u8 = 100;
+
+/// Confidence given to outliers; below the camera's 70 threshold so it never follows them.
+const OUTLIER_CONFIDENCE: u8 = 10;
+
+/// Per-axis filter state for one smoothing filter.
+struct PointFilter {
+    kind: CursorFilter,
+    last_timestamp: Option<Timestamp>,
+    x: AxisState,
+    y: AxisState,
+}
+
+/// One axis: filtered value plus the One-Euro derivative or the Kalman variance.
+#[derive(Clone, Copy, Default)]
+struct AxisState {
+    value: f32,
+    aux: f32,
+}
+
+impl PointFilter {
+    fn new(kind: CursorFilter) -> Self {
+        PointFilter {
+            kind,
+            last_timestamp: None,
+            x: AxisState::default(),
+            y: AxisState::default(),
         }
     }
+
+    fn apply(&mut self, timestamp: Timestamp, position: NormalizedCoord) -> NormalizedCoord {
+        let Some(last) = self.last_timestamp.replace(timestamp) else {
+            self.x = AxisState {
+                value: position.x,
+                aux: 0.0,
+            };
+            self.y = AxisState {
+                value: position.y,
+                aux: 0.0,
+            };
+            return position;
+        };
+        let dt = timestamp.as_micros().saturating_sub(last.as_micros()) as f32 / 1_000_000.0;
+        if dt <= 0.0 {
+            return NormalizedCoord::new(self.x.value, self.y.value);
+        }
+
+        let step = |state: AxisState, measured: f32| match self.kind {
+            CursorFilter::None => AxisState {
+                value: measured,
+                aux: 0.0,
+            },
+            CursorFilter::OneEuro {
+                min_cutoff,
+                beta,
+                d_cutoff,
+            } => {
+                let derivative = (measured - state.value) / dt;
+                let derivative = lerp_by(state.aux, derivative, smoothing_factor(d_cutoff, dt));
+                let cutoff = min_cutoff + beta * derivative.abs();
+                AxisState {
+                    value: lerp_by(state.value, measured, smoothing_factor(cutoff, dt)),
+                    aux: derivative,
+                }
+            }
+            CursorFilter::Kalman {
+                process_noise,
+                measurement_noise,
+            } => {
+                let predicted = state.aux + process_noise * dt;
+                let gain = predicted / (predicted + measurement_noise).max(f32::EPSILON);
+                AxisState {
+                    value: state.value + gain * (measured - state.value),
+                    aux: (1.0 - gain) * predicted,
+                }
+            }
+        };
+        self.x = step(self.x, position.x);
+        self.y = step(self.y, position.y);
+        NormalizedCoord::new(self.x.value, self.y.value)
+    }
+}
+
+/// Exponential smoothing factor for a low-pass filter at `cutoff` Hz.
+fn smoothing_factor(cutoff: f32, dt: f32) -> f32 {
+    let tau = 1.0 / (std::f32::consts::TAU * cutoff.max(f32::EPSILON));
+    1.0 / (1.0 + tau / dt)
+}
+
+fn lerp_by(from: f32, to: f32, alpha: f32) -> f32 {
+    from + (to - from) * alpha
 }
 
 #[cfg(test)]
@@ -112,6 +885,7 @@ mod tests {
                 timestamp: Timestamp::from_micros(1000),
                 event_type: EventType::FocusChange {
                     bounds: NormalizedRect::new(0.2, 0.2, 0.4, 0.2),
+                    element: None,
                 },
             }],
         };
@@ -122,4 +896,408 @@ mod tests {
         // Center of bounds: x=0.2+0.2=0.4, y=0.2+0.1=0.3
         assert!((track[0].position.x - 0.4).abs() < 0.01);
     }
+
+    #[test]
+    fn filters_reduce_jitter() {
+        // A still cursor reported with +-0.01 of alternating noise at 100 Hz.
+        let events = (0..100)
+            .map(|i| InputEvent {
+                timestamp: Timestamp::from_micros(i * 10_000),
+                event_type: EventType::MouseMove {
+                    position: NormalizedCoord::new(if i % 2 == 0 { 0.49 } else { 0.51 }, 0.5),
+                },
+            })
+            .collect();
+        let signals = SignalBatch { events };
+
+        for filter in [
+            CursorFilter::OneEuro {
+                min_cutoff: 1.0,
+                beta: 0.0,
+                d_cutoff: 1.0,
+            },
+            CursorFilter::Kalman {
+                process_noise: 0.001,
+                measurement_noise: 0.01,
+            },
+        ] {
+            let settings = CursorSettings {
+                filter,
+                camera_input: CursorInput::Smoothed,
+                ..Default::default()
+            };
+            let mut tracker = CursorTracker::with_settings(CaptureMode::Tab, settings);
+            let track = tracker.process(&signals);
+            let last = track.last().unwrap();
+            assert_eq!(last.position.x, 0.51);
+            let smoothed = last.smoothed.unwrap();
+            assert!(
+                (smoothed.x - 0.5).abs() < 0.003,
+                "{:?}: {}",
+                filter,
+                smoothed.x
+            );
+
+            let camera_track = tracker.camera_track(&track);
+            assert_eq!(camera_track.last().unwrap().position, smoothed);
+        }
+
+        // Off by default: no smoothed positions, and the camera follows the raw track.
+        let mut tracker = CursorTracker::new(CaptureMode::Tab);
+        let track = tracker.process(&signals);
+        assert!(track.iter().all(|point| point.smoothed.is_none()));
+        assert!(matches!(tracker.camera_track(&track), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn implausible_jumps_are_outliers() {
+        let mv = |us: u64, x: f32, y: f32| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::MouseMove {
+                position: NormalizedCoord::new(x, y),
+            },
+        };
+        let signals = SignalBatch {
+            events: vec![
+                mv(0, 0.5, 0.5),
+                mv(10_000, 0.51, 0.5),
+                // Bogus 0,0 report, then back where the cursor was.
+                mv(20_000, 0.0, 0.0),
+                mv(30_000, 0.52, 0.5),
+                // A real teleport: the next point continues from the new place.
+                mv(40_000, 0.1, 0.9),
+                mv(50_000, 0.11, 0.9),
+            ],
+        };
+
+        let mut tracker = CursorTracker::new(CaptureMode::Tab);
+        let track = tracker.process(&signals);
+        let reasons: Vec<&InferenceReason> = track.iter().map(|p| &p.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                &InferenceReason::DirectInput,
+                &InferenceReason::DirectInput,
+                &InferenceReason::Outlier,
+                &InferenceReason::DirectInput,
+                &InferenceReason::Outlier,
+                &InferenceReason::DirectInput,
+            ]
+        );
+        assert!(track[2].confidence < 70);
+
+        let mut tracker = CursorTracker::with_settings(
+            CaptureMode::Tab,
+            CursorSettings {
+                drop_outliers: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(tracker.process(&signals).len(), 4);
+    }
+
+    #[test]
+    fn resamples_to_fixed_rate_and_fills_gaps() {
+        let mv = |us: u64, x: f32| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::MouseMove {
+                position: NormalizedCoord::new(x, 0.5),
+            },
+        };
+        let events = vec![
+            mv(0, 0.3),
+            mv(7_000, 0.31),
+            mv(20_000, 0.32),
+            // Stall, then the cursor resumes further along.
+            mv(520_000, 0.42),
+            mv(530_000, 0.43),
+        ];
+        let settings = CursorSettings {
+            resample_hz: Some(100.0),
+            ..Default::default()
+        };
+
+        let mut tracker = CursorTracker::with_settings(CaptureMode::Tab, settings.clone());
+        let track = tracker.process(&SignalBatch {
+            events: events.clone(),
+        });
+        assert_eq!(track.len(), 54);
+        assert!(track
+            .iter()
+            .enumerate()
+            .all(|(i, point)| point.timestamp.as_micros() == i as u64 * 10_000));
+        assert_eq!(track[1].state, CursorState::Visible);
+        let middle = &track[27];
+        assert_eq!(middle.state, CursorState::Inferred);
+        assert_eq!(middle.confidence, 50);
+        assert!(middle.position.x > 0.32 && middle.position.x < 0.42);
+
+        // Chunked input lines up with the whole batch.
+        let mut chunked = CursorTracker::with_settings(CaptureMode::Tab, settings);
+        let mut actual = chunked.process(&SignalBatch {
+            events: events[..2].to_vec(),
+        });
+        actual.extend(chunked.process(&SignalBatch {
+            events: events[2..].to_vec(),
+        }));
+        let times = |track: &[CursorTrackPoint]| -> Vec<u64> {
+            track.iter().map(|p| p.timestamp.as_micros()).collect()
+        };
+        assert_eq!(times(&actual), times(&track));
+    }
+
+    #[test]
+    fn downsamples_high_rate_moves() {
+        // 1 kHz mouse moving steadily right.
+        let events: Vec<InputEvent> = (0..100)
+            .map(|i| InputEvent {
+                timestamp: Timestamp::from_micros(i * 1_000),
+                event_type: EventType::MouseMove {
+                    position: NormalizedCoord::new(0.2 + 0.001 * i as f32, 0.5),
+                },
+            })
+            .collect();
+        let signals = SignalBatch { events };
+
+        let downsampled = |min_move_interval_us, min_move_distance| {
+            let settings = CursorSettings {
+                min_move_interval_us,
+                min_move_distance,
+                ..Default::default()
+            };
+            CursorTracker::with_settings(CaptureMode::Tab, settings)
+                .process(&signals)
+                .len()
+        };
+        assert_eq!(downsampled(0, 0.0), 100);
+        assert_eq!(downsampled(10_000, 0.0), 10);
+        assert_eq!(downsampled(0, 0.0045), 20);
+    }
+
+    #[test]
+    fn derives_velocity_and_acceleration() {
+        // x = 0.1 + 0.5 t^2: velocity t, acceleration 1 (per second).
+        let track: Vec<CursorTrackPoint> = (0..=10)
+            .map(|i| {
+                let t = i as f32 * 0.1;
+                CursorTrackPoint {
+                    timestamp: Timestamp::from_micros(i * 100_000),
+                    position: NormalizedCoord::new(0.1 + 0.5 * t * t, 0.5),
+                    state: CursorState::Visible,
+                    confidence: 100,
+                    reason: InferenceReason::DirectInput,
+                    smoothed: None,
+                }
+            })
+            .collect();
+        let motion = derive_motion(&track);
+        assert_eq!(motion.len(), track.len());
+        assert!((motion[5].vx - 0.5).abs() < 1e-4);
+        assert!((motion[5].ax - 1.0).abs() < 1e-3);
+        assert_eq!(motion[5].vy, 0.0);
+        assert!((motion[5].speed - motion[5].vx).abs() < 1e-6);
+    }
+
+    #[test]
+    fn detects_drags() {
+        let event = |us: u64, event_type: EventType| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type,
+        };
+        let at = |x: f32, y: f32| NormalizedCoord::new(x, y);
+        let mut tracker = CursorTracker::new(CaptureMode::Tab);
+        tracker.process(&SignalBatch {
+            events: vec![
+                // A click: press and release in place.
+                event(
+                    0,
+                    EventType::MouseDown {
+                        position: at(0.5, 0.5),
+                        button: 0,
+                    },
+                ),
+                event(
+                    50_000,
+                    EventType::MouseUp {
+                        position: at(0.5, 0.5),
+                        button: 0,
+                    },
+                ),
+                // A drag that dips below both ends, finishing in the next batch.
+                event(
+                    100_000,
+                    EventType::MouseDown {
+                        position: at(0.2, 0.3),
+                        button: 0,
+                    },
+                ),
+                event(
+                    200_000,
+                    EventType::MouseMove {
+                        position: at(0.4, 0.6),
+                    },
+                ),
+            ],
+        });
+        assert!(tracker.take_drags().is_empty());
+
+        tracker.process(&SignalBatch {
+            events: vec![event(
+                300_000,
+                EventType::MouseUp {
+                    position: at(0.6, 0.4),
+                    button: 0,
+                },
+            )],
+        });
+        let drags = tracker.take_drags();
+        assert_eq!(drags.len(), 1);
+        assert_eq!(drags[0].start.as_micros(), 100_000);
+        assert_eq!(drags[0].end.as_micros(), 300_000);
+        assert_eq!(drags[0].end_position, at(0.6, 0.4));
+        let bounds = drags[0].bounds;
+        assert!((bounds.x - 0.2).abs() < 1e-6 && (bounds.y - 0.3).abs() < 1e-6);
+        assert!((bounds.width - 0.4).abs() < 1e-6 && (bounds.height - 0.3).abs() < 1e-6);
+        assert!(tracker.take_drags().is_empty());
+    }
+
+    #[test]
+    fn tracks_primary_touch_and_pinches() {
+        let touch = |us: u64, id: u32, x: f32, phase: PointerPhase| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::Touch {
+                position: NormalizedCoord::new(x, 0.5),
+                pointer_id: id,
+                pressure: 0.5,
+                phase,
+            },
+        };
+        let mut tracker = CursorTracker::new(CaptureMode::Tab);
+        let track = tracker.process(&SignalBatch {
+            events: vec![
+                // Fingers 0.1 apart spread to 0.3 apart: a pinch-out.
+                touch(0, 7, 0.45, PointerPhase::Down),
+                touch(10_000, 8, 0.55, PointerPhase::Down),
+                touch(100_000, 7, 0.35, PointerPhase::Move),
+                touch(100_000, 8, 0.65, PointerPhase::Move),
+                touch(200_000, 8, 0.65, PointerPhase::Up),
+                // A wobble well under the minimum scale.
+                touch(300_000, 9, 0.55, PointerPhase::Down),
+                touch(400_000, 9, 0.56, PointerPhase::Up),
+                touch(500_000, 7, 0.35, PointerPhase::Up),
+            ],
+        });
+
+        // Only the first finger moves the cursor.
+        let xs: Vec<f32> = track.iter().map(|p| p.position.x).collect();
+        assert_eq!(xs, vec![0.45, 0.35, 0.35]);
+
+        let pinches = tracker.take_pinches();
+        assert_eq!(pinches.len(), 1);
+        assert_eq!(pinches[0].start.as_micros(), 10_000);
+        assert_eq!(pinches[0].end.as_micros(), 200_000);
+        assert!((pinches[0].scale - 3.0).abs() < 1e-4);
+        assert!((pinches[0].center.x - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn detects_shakes() {
+        let mv = |us: u64, x: f32| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::MouseMove {
+                position: NormalizedCoord::new(x, 0.5),
+            },
+        };
+        // A slow sweep, then six quick swings of 0.1 around x = 0.65.
+        let mut events: Vec<InputEvent> =
+            (0..=5).map(|i| mv(i * 200_000, 0.1 * i as f32)).collect();
+        events.extend(
+            (1..=6).map(|i| mv(1_000_000 + i * 80_000, if i % 2 == 0 { 0.6 } else { 0.7 })),
+        );
+        // Then the cursor leaves.
+        events.push(mv(2_000_000, 0.2));
+
+        let mut tracker = CursorTracker::new(CaptureMode::Tab);
+        tracker.process(&SignalBatch { events });
+        let shakes = tracker.take_shakes();
+        assert_eq!(shakes.len(), 1);
+        assert_eq!(shakes[0].end.as_micros(), 1_480_000);
+        assert!((shakes[0].center.x - 0.6).abs() < 0.06);
+        assert!(shakes[0].bounds.width <= SHAKE_MAX_SPAN);
+    }
+
+    #[test]
+    fn simplifies_path_within_tolerance() {
+        let point = |i: u64, x: f32, y: f32, state: CursorState| CursorTrackPoint {
+            timestamp: Timestamp::from_micros(i * 10_000),
+            position: NormalizedCoord::new(x, y),
+            state,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        };
+        // A right angle traced by 21 slightly jittered points, then a hidden tail.
+        let mut track: Vec<CursorTrackPoint> = (0..=10)
+            .map(|i| {
+                let jitter = if i % 2 == 0 { 0.001 } else { -0.001 };
+                point(i, 0.1 + i as f32 * 0.05, 0.2 + jitter, CursorState::Visible)
+            })
+            .collect();
+        track.extend(
+            (11..=20).map(|i| point(i, 0.6, 0.2 + (i - 10) as f32 * 0.05, CursorState::Visible)),
+        );
+        track.push(point(21, 0.6, 0.75, CursorState::Hidden));
+
+        let simplified = simplify_path(&track, 0.005);
+        let times: Vec<u64> = simplified
+            .iter()
+            .map(|p| p.timestamp.as_micros() / 10_000)
+            .collect();
+        assert_eq!(times, vec![0, 10, 20, 21]);
+
+        // Zero tolerance keeps every point off the straight lines.
+        assert!(simplify_path(&track, 0.0).len() > times.len());
+        assert!(simplify_path(&[], 0.01).is_empty());
+    }
+
+    #[test]
+    fn fuses_events_with_detection() {
+        let mv = |us: u64| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::MouseMove {
+                position: NormalizedCoord::new(0.5, 0.5),
+            },
+        };
+        let detected = |us: u64| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::CursorDetected {
+                position: NormalizedCoord::new(0.6, 0.5),
+                confidence: 100,
+            },
+        };
+
+        let mut tracker = CursorTracker::new(CaptureMode::Tab);
+        let track = tracker.process(&SignalBatch {
+            events: vec![mv(0), detected(0), mv(100_000), detected(2_000_000)],
+        });
+        // The first report of each source stands alone only until the other arrives.
+        assert_eq!(track[0].position.x, 0.5);
+        assert!((track[1].position.x - 0.55).abs() < 1e-5);
+        // Detection one half-life old counts half as much as the fresh event.
+        assert!((track[2].position.x - (0.5 + 0.1 / 3.0)).abs() < 1e-5);
+        // Stale input events are ignored.
+        assert_eq!(track[3].position.x, 0.6);
+
+        let mut unfused = CursorTracker::with_settings(
+            CaptureMode::Tab,
+            CursorSettings {
+                fusion_half_life_us: 0,
+                ..Default::default()
+            },
+        );
+        let track = unfused.process(&SignalBatch {
+            events: vec![mv(0), detected(0)],
+        });
+        assert_eq!(track[1].position.x, 0.6);
+    }
 }
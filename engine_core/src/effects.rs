@@ -1,16 +1,142 @@
-// Effect tracks: click rings, cursor highlights, safe margins.
+// Effect tracks: click rings, cursor highlights, keystroke chips, cursor trails, drag
+// ribbons, safe margins.
 // See steering.md: Rust Rules (Engine)
 
+use crate::cursor::simplify_path;
 use crate::types::*;
 
+/// Keys this close together (microseconds) share one keystroke chip.
+const KEYSTROKE_GROUP_US: u64 = 800_000;
+/// A chip holding this many characters starts a new one.
+const KEYSTROKE_MAX_CHARS: usize = 24;
+/// Two clicks this close in time (microseconds) and space make a double click.
+const DOUBLE_CLICK_US: u64 = 400_000;
+const DOUBLE_CLICK_DISTANCE: f32 = 0.02;
+/// Click labels sit this far below and right of the click.
+const CLICK_LABEL_OFFSET: f32 = 0.03;
+/// Longer names are shortened with an ellipsis.
+const CLICK_LABEL_MAX_CHARS: usize = 32;
+/// How closely (normalized units) a drag ribbon follows the cursor path.
+const RIBBON_TOLERANCE: f32 = 0.002;
+/// Highlights less than this far apart (microseconds) join into one span.
+const HIGHLIGHT_JOIN_GAP_US: u64 = 50_000;
+/// Rings starting within this long of the previous one are dropped unless they merge.
+const RING_MIN_INTERVAL_US: u64 = 100_000;
+/// Still-visible rings closer than this restart instead of stacking.
+const RING_MERGE_DISTANCE: f32 = 0.05;
+/// Ring colors for right and middle clicks without a configured style.
+const RIGHT_CLICK_COLOR: &str = "#ff9f0a";
+const MIDDLE_CLICK_COLOR: &str = "#30d158";
+
 /// Generates visual effect tracks from input signals and cursor data.
 pub struct EffectGenerator {
     settings: EffectSettings,
+    /// Custom effects by name, in registration order.
+    custom: Vec<(String, Vec<CustomEffectSpec>)>,
 }
 
 impl EffectGenerator {
     pub fn new(settings: EffectSettings) -> Self {
-        EffectGenerator { settings }
+        EffectGenerator {
+            settings,
+            custom: Vec::new(),
+        }
+    }
+
+    /// Register the instances of a custom effect, replacing any earlier registration
+    /// under the same name.
+    pub fn register_custom(&mut self, name: &str, specs: Vec<CustomEffectSpec>) {
+        match self
+            .custom
+            .iter_mut()
+            .find(|(existing, _)| existing == name)
+        {
+            Some((_, existing)) => *existing = specs,
+            None => self.custom.push((name.to_string(), specs)),
+        }
+    }
+
+    /// Drop one custom effect's registration, or all of them with `None`.
+    pub fn clear_custom(&mut self, name: Option<&str>) {
+        match name {
+            Some(name) => self.custom.retain(|(existing, _)| existing != name),
+            None => self.custom.clear(),
+        }
+    }
+
+    /// Registered custom effect instances, by name in registration order.
+    pub fn registered_custom(&self) -> &[(String, Vec<CustomEffectSpec>)] {
+        &self.custom
+    }
+
+    /// Effects for every registered custom effect instance, in time order.
+    pub fn custom_effects(&self) -> Vec<Effect> {
+        let mut effects: Vec<Effect> = self
+            .custom
+            .iter()
+            .flat_map(|(name, specs)| {
+                specs.iter().map(move |spec| {
+                    let effect_type = EffectType::Custom {
+                        name: name.clone(),
+                        params_json: spec.params_json.clone(),
+                    };
+                    Effect {
+                        timestamp: spec.timestamp,
+                        duration_us: spec.duration_us,
+                        position: spec.position,
+                        style: spec
+                            .style
+                            .clone()
+                            .unwrap_or_else(|| effect_type.default_style()),
+                        envelope: spec.envelope.clone(),
+                        effect_type,
+                    }
+                })
+            })
+            .collect();
+        effects.sort_by_key(|effect| effect.timestamp);
+        effects
+    }
+
+    /// Replace effect settings. Takes effect on the next `generate` call.
+    pub fn set_settings(&mut self, settings: EffectSettings) {
+        self.settings = settings;
+    }
+
+    /// Appearance of `effect_type`: the configured override, else the theme's style.
+    pub fn style_for(&self, effect_type: &EffectType) -> EffectStyle {
+        let configured = match effect_type {
+            EffectType::ClickRing => &self.settings.click_ring_style,
+            EffectType::CursorHighlight => &self.settings.cursor_highlight_style,
+            EffectType::Spotlight => &self.settings.spotlight_style,
+            EffectType::Keystrokes { .. } => &self.settings.keystroke_style,
+            EffectType::Trail { .. } => &self.settings.trail_style,
+            EffectType::DragRibbon { .. } => &self.settings.drag_ribbon_style,
+            EffectType::ProgressBar { .. } => &self.settings.progress_bar_style,
+            EffectType::ClickLabel { .. } => &self.settings.click_label_style,
+            // Watermarks take their opacity from `WatermarkSettings`.
+            EffectType::Custom { .. } | EffectType::Watermark { .. } => &None,
+        };
+        configured.clone().unwrap_or_else(|| {
+            effect_type.themed_style(self.settings.theme, &self.settings.brand_color)
+        })
+    }
+
+    /// Ring style for a click with `button`. Right and middle clicks use their own
+    /// configured style, else the click ring's recolored, so they read differently from
+    /// ordinary clicks.
+    pub fn ring_style_for(&self, button: MouseButton) -> EffectStyle {
+        let (configured, color) = match button {
+            MouseButton::Right => (&self.settings.right_click_style, RIGHT_CLICK_COLOR),
+            MouseButton::Middle => (&self.settings.middle_click_style, MIDDLE_CLICK_COLOR),
+            MouseButton::Left | MouseButton::Other => {
+                return self.style_for(&EffectType::ClickRing)
+            }
+        };
+        configured.clone().unwrap_or_else(|| EffectStyle {
+            color: color.to_string(),
+            ..self.style_for(&EffectType::ClickRing)
+        })
     }
 
     /// Generate effect tracks from signals and cursor track.
@@ -18,23 +144,91 @@ impl EffectGenerator {
         &self,
         signals: &SignalBatch,
         cursor_track: &[CursorTrackPoint],
+    ) -> EffectTrack {
+        self.generate_with_gestures(signals, cursor_track, &[], &[])
+    }
+
+    /// Like `generate`, also drawing drag ribbons and spotlighting recognized shakes.
+    pub fn generate_with_gestures(
+        &self,
+        signals: &SignalBatch,
+        cursor_track: &[CursorTrackPoint],
+        drags: &[DragSegment],
+        shakes: &[ShakeGesture],
     ) -> EffectTrack {
         let mut effects = Vec::new();
+        let spotlight_style = self.style_for(&EffectType::Spotlight);
+        let highlight_style = self.style_for(&EffectType::CursorHighlight);
+
+        // Spotlight shakes from the moment they're recognized.
+        if self.settings.shake_spotlight {
+            for shake in shakes {
+                effects.push(Effect {
+                    timestamp: shake.end,
+                    duration_us: 1_500_000, // 1.5s
+                    effect_type: EffectType::Spotlight,
+                    position: shake.center,
+                    style: spotlight_style.clone(),
+                    envelope: EffectType::Spotlight.default_envelope(),
+                });
+            }
+        }
 
         // Generate click ring effects.
         if self.settings.click_rings {
             for event in &signals.events {
-                if let EventType::MouseClick { position, .. } = &event.event_type {
+                if let EventType::MouseClick {
+                    position, button, ..
+                } = &event.event_type
+                {
                     effects.push(Effect {
                         timestamp: event.timestamp,
                         duration_us: 300_000, // 300ms
                         effect_type: EffectType::ClickRing,
                         position: *position,
+                        style: self.ring_style_for(MouseButton::from_code(*button)),
+                        envelope: EffectType::ClickRing.default_envelope(),
                     });
                 }
             }
         }
 
+        // Label clicks on named elements.
+        if self.settings.click_labels {
+            let label_style = self.style_for(&EffectType::ClickLabel {
+                text: String::new(),
+            });
+            for event in &signals.events {
+                let EventType::MouseClick {
+                    position,
+                    element: Some(element),
+                    ..
+                } = &event.event_type
+                else {
+                    continue;
+                };
+                let Some(text) = element.name.as_deref().and_then(label_text) else {
+                    continue;
+                };
+                let effect_type = EffectType::ClickLabel { text };
+                effects.push(Effect {
+                    timestamp: event.timestamp,
+                    duration_us: self.settings.click_label_duration_us,
+                    envelope: effect_type.default_envelope(),
+                    effect_type,
+                    position: NormalizedCoord::new(
+                        (position.x + CLICK_LABEL_OFFSET).clamp(0.05, 0.95),
+                        (position.y + CLICK_LABEL_OFFSET).clamp(0.05, 0.95),
+                    ),
+                    style: label_style.clone(),
+                });
+            }
+        }
+
+        if self.settings.keystroke_overlay {
+            effects.extend(self.keystroke_chips(signals));
+        }
+
         // Generate cursor highlight effects.
         if self.settings.cursor_highlight {
             for point in cursor_track {
@@ -45,12 +239,373 @@ impl EffectGenerator {
                         duration_us: 100_000, // 100ms per frame
                         effect_type: EffectType::CursorHighlight,
                         position: point.position,
+                        style: highlight_style.clone(),
+                        envelope: EffectType::CursorHighlight.default_envelope(),
                     });
                 }
             }
         }
 
-        EffectTrack { effects }
+        if self.settings.cursor_trail {
+            effects.extend(self.trails(cursor_track));
+        }
+
+        if self.settings.drag_ribbons {
+            let style = self.style_for(&EffectType::DragRibbon { points: Vec::new() });
+            for drag in drags {
+                effects.push(Effect {
+                    timestamp: drag.start,
+                    duration_us: drag.end.as_micros() - drag.start.as_micros(),
+                    effect_type: EffectType::DragRibbon {
+                        points: ribbon_points(drag, cursor_track),
+                    },
+                    position: drag.start_position,
+                    style: style.clone(),
+                    envelope: EffectEnvelope::default(),
+                });
+            }
+        }
+
+        EffectTrack {
+            effects: limit_density(effects),
+        }
+    }
+
+    /// The progress bar over a recording running from `start` to `end`, if enabled.
+    /// Project it onto the export timeline (`TimeRemapper::project_effects`) to get
+    /// export progress.
+    pub fn progress_bar(&self, start: Timestamp, end: Timestamp) -> Option<Effect> {
+        let settings = self.settings.progress_bar?;
+        let effect_type = EffectType::ProgressBar {
+            edge: settings.edge,
+            thickness: settings.thickness,
+        };
+        let y = match settings.edge {
+            FrameEdge::Top => settings.thickness / 2.0,
+            FrameEdge::Bottom => 1.0 - settings.thickness / 2.0,
+        };
+        Some(Effect {
+            timestamp: start,
+            duration_us: end.as_micros().saturating_sub(start.as_micros()),
+            position: NormalizedCoord::new(0.5, y),
+            style: self.style_for(&effect_type),
+            envelope: effect_type.default_envelope(),
+            effect_type,
+        })
+    }
+
+    /// Click sound cues lined up with the click rings, so they follow the same flag.
+    /// A click right after another with the same button on the same spot is cued as a
+    /// double click, and
+    /// drags get a softer cue on release.
+    pub fn audio_cues(&self, signals: &SignalBatch, drags: &[DragSegment]) -> Vec<AudioCue> {
+        if !self.settings.click_rings {
+            return Vec::new();
+        }
+
+        let mut cues = Vec::new();
+        let mut previous: Option<(Timestamp, NormalizedCoord, u8)> = None;
+        for event in &signals.events {
+            let EventType::MouseClick {
+                position, button, ..
+            } = &event.event_type
+            else {
+                continue;
+            };
+            let double = previous.is_some_and(|(at, last, last_button)| {
+                last_button == *button
+                    && event.timestamp.as_micros().saturating_sub(at.as_micros()) <= DOUBLE_CLICK_US
+                    && (last.x - position.x).hypot(last.y - position.y) <= DOUBLE_CLICK_DISTANCE
+            });
+            let (kind, intensity) = if double {
+                (AudioCueKind::DoubleClick, 1.0)
+            } else {
+                (AudioCueKind::Click, 0.8)
+            };
+            cues.push(AudioCue {
+                timestamp: event.timestamp,
+                kind,
+                intensity,
+            });
+            // A third click starts over rather than making another double.
+            previous = (!double).then_some((event.timestamp, *position, *button));
+        }
+        cues.extend(drags.iter().map(|drag| AudioCue {
+            timestamp: drag.end,
+            kind: AudioCueKind::DragEnd,
+            intensity: 0.5,
+        }));
+        cues.sort_by_key(|cue| cue.timestamp);
+        cues
+    }
+
+    /// One trail per track point, covering the path of the last `trail_length_us`.
+    /// Uses smoothed positions where the tracker has them, and only sees the track it
+    /// is given, so trails at the start of a chunk are short until history builds up.
+    fn trails(&self, cursor_track: &[CursorTrackPoint]) -> Vec<Effect> {
+        let length = self.settings.trail_length_us;
+        let style = self.style_for(&EffectType::Trail { points: Vec::new() });
+        let mut effects = Vec::new();
+        let mut start = 0;
+
+        for (i, point) in cursor_track.iter().enumerate() {
+            let now = point.timestamp.as_micros();
+            while now.saturating_sub(cursor_track[start].timestamp.as_micros()) > length {
+                start += 1;
+            }
+            if i == start || point.state == CursorState::Hidden {
+                continue;
+            }
+            let points = cursor_track[start..=i]
+                .iter()
+                .filter(|p| p.state != CursorState::Hidden)
+                .map(|p| TrailPoint {
+                    position: p.smoothed.unwrap_or(p.position),
+                    weight: 1.0
+                        - now.saturating_sub(p.timestamp.as_micros()) as f32 / length.max(1) as f32,
+                })
+                .collect();
+            let duration_us = cursor_track
+                .get(i + 1)
+                .map_or(0, |next| next.timestamp.as_micros() - now);
+            effects.push(Effect {
+                timestamp: point.timestamp,
+                duration_us,
+                effect_type: EffectType::Trail { points },
+                position: point.smoothed.unwrap_or(point.position),
+                style: style.clone(),
+                envelope: EffectEnvelope::default(),
+            });
+        }
+        effects
+    }
+
+    /// One chip per run of keys typed in quick succession. Each `KeyDown` counts; a
+    /// `KeyPress` only counts when no `KeyDown` for the same key came just before it,
+    /// so sources that send both don't double up.
+    fn keystroke_chips(&self, signals: &SignalBatch) -> Vec<Effect> {
+        let position = self.settings.keystroke_position.anchor();
+        let style = self.style_for(&EffectType::Keystrokes {
+            text: String::new(),
+        });
+        let mut chips: Vec<Effect> = Vec::new();
+        // Time of the last counted key, and whether it was typed text.
+        let mut last: Option<(Timestamp, bool)> = None;
+        // Key of a `KeyDown` still waiting for its `KeyPress`.
+        let mut pending_press: Option<&str> = None;
+
+        for event in &signals.events {
+            let (key, modifiers) = match &event.event_type {
+                EventType::KeyDown { key, modifiers } => {
+                    pending_press = Some(key);
+                    (key, modifiers)
+                }
+                EventType::KeyPress { key, modifiers } => {
+                    if pending_press.take() == Some(key.as_str()) {
+                        continue;
+                    }
+                    (key, modifiers)
+                }
+                _ => continue,
+            };
+            let Some((label, typed)) = key_label(key, modifiers) else {
+                continue;
+            };
+            let ts = event.timestamp;
+            let previous = last.replace((ts, typed));
+
+            let joins =
+                previous.filter(|(at, _)| ts.as_micros() - at.as_micros() <= KEYSTROKE_GROUP_US);
+            if let (Some((_, previous_typed)), Some(chip)) = (joins, chips.last_mut()) {
+                if let EffectType::Keystrokes { text } = &mut chip.effect_type {
+                    if text.chars().count() < KEYSTROKE_MAX_CHARS {
+                        // Runs of typed characters read as text; anything else is spaced out.
+                        if !(typed && previous_typed) {
+                            text.push(' ');
+                        }
+                        text.push_str(&label);
+                        chip.duration_us = ts.as_micros() - chip.timestamp.as_micros()
+                            + self.settings.keystroke_duration_us;
+                        continue;
+                    }
+                }
+            }
+            chips.push(self.keystroke_chip(ts, label, position, &style));
+        }
+        chips
+    }
+
+    fn keystroke_chip(
+        &self,
+        timestamp: Timestamp,
+        text: String,
+        position: NormalizedCoord,
+        style: &EffectStyle,
+    ) -> Effect {
+        let effect_type = EffectType::Keystrokes { text };
+        Effect {
+            timestamp,
+            duration_us: self.settings.keystroke_duration_us,
+            envelope: effect_type.default_envelope(),
+            effect_type,
+            position,
+            style: style.clone(),
+        }
+    }
+}
+
+/// The drag's path from the cursor track, simplified, pinned to the press and release
+/// positions. A drag with no track points in between is a straight line.
+fn ribbon_points(drag: &DragSegment, cursor_track: &[CursorTrackPoint]) -> Vec<RibbonPoint> {
+    let start = drag.start.as_micros();
+    let end = drag.end.as_micros();
+    let first = cursor_track.partition_point(|p| p.timestamp.as_micros() <= start);
+    let last = cursor_track.partition_point(|p| p.timestamp.as_micros() < end);
+
+    let mut points = vec![RibbonPoint {
+        offset_us: 0,
+        position: drag.start_position,
+    }];
+    if first < last {
+        points.extend(
+            simplify_path(&cursor_track[first..last], RIBBON_TOLERANCE)
+                .iter()
+                .filter(|p| p.state != CursorState::Hidden)
+                .map(|p| RibbonPoint {
+                    offset_us: p.timestamp.as_micros() - start,
+                    position: p.smoothed.unwrap_or(p.position),
+                }),
+        );
+    }
+    points.push(RibbonPoint {
+        offset_us: end - start,
+        position: drag.end_position,
+    });
+    points
+}
+
+/// Thin out effects that would otherwise flood the track. Back-to-back cursor highlights
+/// become one span (positioned where it starts; renderers follow the cursor track within
+/// it). A click ring still showing when another lands nearby is restarted to cover both,
+/// and any other ring too soon after the last is dropped.
+fn limit_density(effects: Vec<Effect>) -> Vec<Effect> {
+    let mut kept: Vec<Effect> = Vec::with_capacity(effects.len());
+    let mut last_highlight: Option<usize> = None;
+    // Index of the last kept ring, and when a click last started or restarted it.
+    let mut last_ring: Option<(usize, u64)> = None;
+
+    for effect in effects {
+        let end = |e: &Effect| e.timestamp.as_micros() + e.duration_us;
+        let start = effect.timestamp.as_micros();
+        match effect.effect_type {
+            EffectType::CursorHighlight => {
+                if let Some(span) = last_highlight.map(|i| &mut kept[i]) {
+                    if start <= end(span) + HIGHLIGHT_JOIN_GAP_US && span.style == effect.style {
+                        span.duration_us = end(&effect).max(end(span)) - span.timestamp.as_micros();
+                        continue;
+                    }
+                }
+                last_highlight = Some(kept.len());
+            }
+            EffectType::ClickRing => {
+                if let Some((i, clicked_at)) = last_ring {
+                    let ring = &mut kept[i];
+                    let near = (ring.position.x - effect.position.x)
+                        .hypot(ring.position.y - effect.position.y)
+                        <= RING_MERGE_DISTANCE;
+                    if near && start < end(ring) && ring.style == effect.style {
+                        ring.duration_us = end(&effect).max(end(ring)) - ring.timestamp.as_micros();
+                        last_ring = Some((i, start));
+                        continue;
+                    }
+                    if start < clicked_at + RING_MIN_INTERVAL_US {
+                        continue;
+                    }
+                }
+                last_ring = Some((kept.len(), start));
+            }
+            _ => {}
+        }
+        kept.push(effect);
+    }
+    kept
+}
+
+/// An accessible name as a label: whitespace collapsed, shortened if long, `None` if
+/// empty.
+fn label_text(name: &str) -> Option<String> {
+    let words: Vec<&str> = name.split_whitespace().collect();
+    if words.is_empty() {
+        return None;
+    }
+    let text = words.join(" ");
+    if text.chars().count() <= CLICK_LABEL_MAX_CHARS {
+        return Some(text);
+    }
+    let mut short: String = text.chars().take(CLICK_LABEL_MAX_CHARS - 1).collect();
+    short.truncate(short.trim_end().len());
+    short.push('…');
+    Some(short)
+}
+
+/// How a key reads on a chip: typed characters as themselves, named keys as symbols,
+/// and shortcuts with their modifiers ("⌘S" when Meta is held, "Ctrl+C" otherwise).
+/// Bare modifier keys have no label. The flag is set for plain typed characters.
+fn key_label(key: &str, modifiers: &KeyModifiers) -> Option<(String, bool)> {
+    let name = match key {
+        "Shift" | "Control" | "Alt" | "Meta" | "CapsLock" | "" => return None,
+        " " => "Space",
+        "Enter" => "⏎",
+        "Backspace" => "⌫",
+        "Delete" => "⌦",
+        "Escape" => "Esc",
+        "Tab" => "⇥",
+        "ArrowLeft" => "←",
+        "ArrowRight" => "→",
+        "ArrowUp" => "↑",
+        "ArrowDown" => "↓",
+        other => other,
+    };
+    let single = name.chars().count() == 1 && key != "Enter" && key != "Tab";
+    let shortcut = modifiers.ctrl || modifiers.alt || modifiers.meta;
+    if !shortcut && (single || !modifiers.shift) {
+        let typed = key.chars().count() == 1 && key != " ";
+        return Some((name.to_string(), typed));
+    }
+
+    let name = if single {
+        name.to_uppercase()
+    } else {
+        name.to_string()
+    };
+    if modifiers.meta {
+        // macOS order: Control, Option, Shift, Command.
+        let mut label = String::new();
+        for (held, symbol) in [
+            (modifiers.ctrl, '⌃'),
+            (modifiers.alt, '⌥'),
+            (modifiers.shift, '⇧'),
+            (modifiers.meta, '⌘'),
+        ] {
+            if held {
+                label.push(symbol);
+            }
+        }
+        label.push_str(&name);
+        Some((label, false))
+    } else {
+        let mut parts = Vec::new();
+        for (held, part) in [
+            (modifiers.ctrl, "Ctrl"),
+            (modifiers.alt, "Alt"),
+            (modifiers.shift, "Shift"),
+        ] {
+            if held {
+                parts.push(part);
+            }
+        }
+        parts.push(&name);
+        Some((parts.join("+"), false))
     }
 }
 
@@ -63,6 +618,14 @@ mod tests {
         let generator = EffectGenerator::new(EffectSettings {
             click_rings: true,
             cursor_highlight: false,
+            shake_spotlight: false,
+            click_ring_style: Some(EffectStyle {
+                color: "#ff0000".to_string(),
+                radius: 0.05,
+                stroke_width: 0.01,
+                opacity: 1.0,
+            }),
+            ..Default::default()
         });
 
         let signals = SignalBatch {
@@ -71,6 +634,7 @@ mod tests {
                 event_type: EventType::MouseClick {
                     position: NormalizedCoord::new(0.5, 0.5),
                     button: 0,
+                    element: None,
                 },
             }],
         };
@@ -81,6 +645,374 @@ mod tests {
             track.effects[0].effect_type,
             EffectType::ClickRing
         ));
+        assert_eq!(track.effects[0].style.color, "#ff0000");
+        assert_eq!(
+            generator.style_for(&EffectType::Spotlight),
+            EffectType::Spotlight.default_style()
+        );
+    }
+
+    #[test]
+    fn rings_and_cues_tell_buttons_apart() {
+        let generator = EffectGenerator::new(EffectSettings {
+            cursor_highlight: false,
+            middle_click_style: Some(EffectStyle {
+                color: "#5e5ce6".to_string(),
+                ..EffectType::ClickRing.default_style()
+            }),
+            ..Default::default()
+        });
+        let click = |us: u64, button: u8| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::MouseClick {
+                position: NormalizedCoord::new(0.5, 0.5),
+                button,
+                element: None,
+            },
+        };
+        let signals = SignalBatch {
+            events: vec![click(0, 0), click(200_000, 2), click(1_000_000, 1)],
+        };
+
+        let colors: Vec<String> = generator
+            .generate(&signals, &[])
+            .effects
+            .into_iter()
+            .map(|effect| effect.style.color)
+            .collect();
+        assert_eq!(colors, vec!["#ffffff", "#ff9f0a", "#5e5ce6"]);
+
+        // A right click straight after a left click isn't a double click.
+        let cues = generator.audio_cues(&signals, &[]);
+        assert!(cues.iter().all(|cue| cue.kind == AudioCueKind::Click));
+    }
+
+    #[test]
+    fn themes_resolve_to_concrete_styles() {
+        let mut generator = EffectGenerator::new(EffectSettings {
+            theme: EffectTheme::Dark,
+            ..Default::default()
+        });
+        let chip = EffectType::Keystrokes {
+            text: String::new(),
+        };
+        assert_eq!(generator.style_for(&EffectType::ClickRing).color, "#ffffff");
+        assert_eq!(generator.style_for(&chip).color, "#2c2c2e");
+
+        generator.set_settings(EffectSettings {
+            theme: EffectTheme::Brand,
+            brand_color: "#ff2d55".to_string(),
+            cursor_highlight_style: Some(EffectType::CursorHighlight.default_style()),
+            ..Default::default()
+        });
+        let ring = generator.style_for(&EffectType::ClickRing);
+        assert_eq!(ring.color, "#ff2d55");
+        assert_eq!(ring.radius, EffectType::ClickRing.default_style().radius);
+        assert_eq!(generator.style_for(&chip), chip.default_style());
+        // Overrides win over the theme.
+        assert_eq!(
+            generator.style_for(&EffectType::CursorHighlight),
+            EffectType::CursorHighlight.default_style()
+        );
+    }
+
+    #[test]
+    fn merges_highlights_and_limits_rings() {
+        let generator = EffectGenerator::new(EffectSettings::default());
+        let click = |us: u64, x: f32| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::MouseClick {
+                position: NormalizedCoord::new(x, 0.5),
+                button: 0,
+                element: None,
+            },
+        };
+        let signals = SignalBatch {
+            events: vec![
+                click(0, 0.5),
+                // Still showing and close by: extends the first ring.
+                click(200_000, 0.52),
+                // Far away but too soon after the last ring.
+                click(250_000, 0.9),
+                click(1_000_000, 0.9),
+            ],
+        };
+        let track: Vec<CursorTrackPoint> = [0, 100_000, 200_000, 1_000_000]
+            .into_iter()
+            .map(|us| CursorTrackPoint {
+                timestamp: Timestamp::from_micros(us),
+                position: NormalizedCoord::new(0.5, 0.5),
+                state: CursorState::Visible,
+                confidence: 100,
+                reason: InferenceReason::DirectInput,
+                smoothed: None,
+            })
+            .collect();
+
+        let effects = generator.generate(&signals, &track).effects;
+        let spans: Vec<(&str, u64, u64)> = effects
+            .iter()
+            .map(|effect| {
+                let kind = match effect.effect_type {
+                    EffectType::ClickRing => "ring",
+                    EffectType::CursorHighlight => "highlight",
+                    _ => panic!("Unexpected effect"),
+                };
+                (kind, effect.timestamp.as_micros(), effect.duration_us)
+            })
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("ring", 0, 500_000),
+                ("ring", 1_000_000, 300_000),
+                ("highlight", 0, 300_000),
+                ("highlight", 1_000_000, 100_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn drag_ribbons_follow_the_path() {
+        let generator = EffectGenerator::new(EffectSettings {
+            click_rings: false,
+            cursor_highlight: false,
+            drag_ribbons: true,
+            ..Default::default()
+        });
+        let point = |us: u64, x: f32, y: f32| CursorTrackPoint {
+            timestamp: Timestamp::from_micros(us),
+            position: NormalizedCoord::new(x, y),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        };
+        // An L-shaped drag: right, then down.
+        let track = vec![
+            point(0, 0.2, 0.2),
+            point(100_000, 0.3, 0.2),
+            point(200_000, 0.4, 0.2),
+            point(300_000, 0.4, 0.3),
+            point(400_000, 0.4, 0.4),
+        ];
+        let drag = DragSegment {
+            start: Timestamp::from_micros(0),
+            end: Timestamp::from_micros(400_000),
+            button: 0,
+            start_position: NormalizedCoord::new(0.2, 0.2),
+            end_position: NormalizedCoord::new(0.4, 0.4),
+            bounds: NormalizedRect::new(0.2, 0.2, 0.2, 0.2),
+        };
+
+        let track =
+            generator.generate_with_gestures(&SignalBatch { events: vec![] }, &track, &[drag], &[]);
+        assert_eq!(track.effects.len(), 1);
+        assert_eq!(track.effects[0].duration_us, 400_000);
+        let EffectType::DragRibbon { points } = &track.effects[0].effect_type else {
+            panic!("Expected a drag ribbon");
+        };
+        let offsets: Vec<u64> = points.iter().map(|p| p.offset_us).collect();
+        assert_eq!(offsets, vec![0, 100_000, 200_000, 300_000, 400_000]);
+    }
+
+    #[test]
+    fn labels_clicks_on_named_elements() {
+        let generator = EffectGenerator::new(EffectSettings {
+            click_rings: false,
+            cursor_highlight: false,
+            click_labels: true,
+            ..Default::default()
+        });
+        let click = |us: u64, name: Option<&str>| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::MouseClick {
+                position: NormalizedCoord::new(0.95, 0.5),
+                button: 0,
+                element: Some(ElementInfo {
+                    tag: "button".to_string(),
+                    role: Some("button".to_string()),
+                    name: name.map(str::to_string),
+                    is_password: false,
+                    autocomplete: None,
+                }),
+            },
+        };
+        let signals = SignalBatch {
+            events: vec![
+                click(0, Some("  Deploy\n  to production ")),
+                click(1_000_000, None),
+                click(
+                    2_000_000,
+                    Some("Accept all cookies and continue to the dashboard"),
+                ),
+            ],
+        };
+
+        let effects = generator.generate(&signals, &[]).effects;
+        let labels: Vec<&str> = effects
+            .iter()
+            .map(|effect| match &effect.effect_type {
+                EffectType::ClickLabel { text } => text.as_str(),
+                _ => panic!("Expected click labels"),
+            })
+            .collect();
+        assert_eq!(
+            labels,
+            vec!["Deploy to production", "Accept all cookies and continue…"]
+        );
+        assert_eq!(effects[0].position, NormalizedCoord::new(0.95, 0.53));
+    }
+
+    #[test]
+    fn groups_keystrokes_into_chips() {
+        let generator = EffectGenerator::new(EffectSettings {
+            click_rings: false,
+            cursor_highlight: false,
+            keystroke_overlay: true,
+            keystroke_position: OverlayPosition::TopRight,
+            ..Default::default()
+        });
+        let key = |us: u64, key: &str, modifiers: KeyModifiers| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::KeyDown {
+                key: key.to_string(),
+                modifiers,
+            },
+        };
+        let plain = KeyModifiers::default();
+        let signals = SignalBatch {
+            events: vec![
+                key(0, "h", plain),
+                // A matching KeyPress is the same keystroke.
+                InputEvent {
+                    timestamp: Timestamp::from_micros(10_000),
+                    event_type: EventType::KeyPress {
+                        key: "h".to_string(),
+                        modifiers: plain,
+                    },
+                },
+                key(200_000, "i", plain),
+                key(400_000, "Enter", plain),
+                key(3_000_000, "Meta", plain),
+                key(
+                    3_100_000,
+                    "s",
+                    KeyModifiers {
+                        meta: true,
+                        ..plain
+                    },
+                ),
+                key(
+                    6_000_000,
+                    "c",
+                    KeyModifiers {
+                        ctrl: true,
+                        ..plain
+                    },
+                ),
+            ],
+        };
+
+        let track = generator.generate(&signals, &[]);
+        let texts: Vec<&str> = track
+            .effects
+            .iter()
+            .map(|effect| match &effect.effect_type {
+                EffectType::Keystrokes { text } => text.as_str(),
+                _ => panic!("Expected keystrokes"),
+            })
+            .collect();
+        assert_eq!(texts, vec!["hi ⏎", "⌘S", "Ctrl+C"]);
+        assert_eq!(track.effects[0].duration_us, 400_000 + 1_500_000);
+        assert_eq!(track.effects[0].position, NormalizedCoord::new(0.9, 0.1));
+    }
+
+    #[test]
+    fn trails_fade_over_recent_path() {
+        let generator = EffectGenerator::new(EffectSettings {
+            click_rings: false,
+            cursor_highlight: false,
+            cursor_trail: true,
+            trail_length_us: 100_000,
+            ..Default::default()
+        });
+        let track: Vec<CursorTrackPoint> = (0..5)
+            .map(|i| CursorTrackPoint {
+                timestamp: Timestamp::from_micros(i * 50_000),
+                position: NormalizedCoord::new(0.1 * i as f32, 0.5),
+                state: CursorState::Visible,
+                confidence: 100,
+                reason: InferenceReason::DirectInput,
+                smoothed: None,
+            })
+            .collect();
+
+        let track = generator.generate(&SignalBatch { events: vec![] }, &track);
+        // The first point has no history yet.
+        assert_eq!(track.effects.len(), 4);
+        let last = track.effects.last().unwrap();
+        assert_eq!(last.duration_us, 0);
+        let EffectType::Trail { points } = &last.effect_type else {
+            panic!("Expected a trail");
+        };
+        let weights: Vec<f32> = points.iter().map(|p| p.weight).collect();
+        assert_eq!(weights, vec![0.0, 0.5, 1.0]);
+        assert_eq!(points[0].position, NormalizedCoord::new(0.2, 0.5));
+        assert_eq!(track.effects[0].duration_us, 50_000);
+    }
+
+    #[test]
+    fn cues_double_clicks_and_drag_ends() {
+        let click = |us: u64, x: f32| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::MouseClick {
+                position: NormalizedCoord::new(x, 0.5),
+                button: 0,
+                element: None,
+            },
+        };
+        let signals = SignalBatch {
+            events: vec![
+                click(0, 0.5),
+                click(200_000, 0.5),
+                click(300_000, 0.5),
+                click(2_000_000, 0.5),
+                click(2_100_000, 0.8),
+            ],
+        };
+        let drag = DragSegment {
+            start: Timestamp::from_micros(1_000_000),
+            end: Timestamp::from_micros(1_500_000),
+            button: 0,
+            start_position: NormalizedCoord::new(0.2, 0.2),
+            end_position: NormalizedCoord::new(0.4, 0.4),
+            bounds: NormalizedRect::new(0.2, 0.2, 0.2, 0.2),
+        };
+
+        let generator = EffectGenerator::new(EffectSettings::default());
+        let kinds: Vec<AudioCueKind> = generator
+            .audio_cues(&signals, &[drag])
+            .iter()
+            .map(|cue| cue.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                AudioCueKind::Click,
+                AudioCueKind::DoubleClick,
+                AudioCueKind::Click,
+                AudioCueKind::DragEnd,
+                AudioCueKind::Click,
+                AudioCueKind::Click,
+            ]
+        );
+
+        let muted = EffectGenerator::new(EffectSettings {
+            click_rings: false,
+            ..Default::default()
+        });
+        assert!(muted.audio_cues(&signals, &[drag]).is_empty());
     }
 
     #[test]
@@ -88,6 +1020,8 @@ mod tests {
         let generator = EffectGenerator::new(EffectSettings {
             click_rings: false,
             cursor_highlight: false,
+            shake_spotlight: false,
+            ..Default::default()
         });
 
         let signals = SignalBatch {
@@ -96,6 +1030,7 @@ mod tests {
                 event_type: EventType::MouseClick {
                     position: NormalizedCoord::new(0.5, 0.5),
                     button: 0,
+                    element: None,
                 },
             }],
         };
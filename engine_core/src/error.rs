@@ -21,6 +21,9 @@ pub enum EngineError {
     #[error("Keyframe generation error: {0}")]
     KeyframeGeneration(String),
 
+    #[error("Invalid timecode: {0}")]
+    InvalidTimecode(String),
+
     #[error("Serialization error: {0}")]
     Serialization(String),
 }
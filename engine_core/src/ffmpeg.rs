@@ -0,0 +1,160 @@
+// FFmpeg filter graphs: the camera track and the edit as one `-filter_complex` string,
+// so the original recording can be re-exported headless outside the browser.
+// See design.md: TimeRemapper (Rust)
+
+use std::fmt::Write as _;
+
+use crate::camera::CameraEngine;
+use crate::time_remap::TimeRemapper;
+
+/// Camera values (zoom, normalized left/top) may stray this far from the exported
+/// piecewise-linear curve.
+const CAMERA_TOLERANCE: f32 = 0.001;
+
+/// Video filter graph reproducing the export from input `0:v`, labelled `[v]`:
+/// `select`/`setpts` keep the uncut source and retime it (eased ramps at their average
+/// speed), `fps` restores a constant rate, and `zoompan` follows the camera, output at
+/// `out_w`x`out_h`. The camera is sampled per export frame and written as
+/// piecewise-linear expressions of the output frame number. Audio is not included;
+/// see `TimeRemapper::audio_segments`.
+pub fn ffmpeg_filter_graph(
+    camera: &CameraEngine,
+    remapper: &TimeRemapper,
+    fps: f64,
+    out_w: u32,
+    out_h: u32,
+) -> String {
+    let clips = remapper.audio_segments();
+    let secs = |us: u64| us as f64 / 1_000_000.0;
+
+    let mut select = String::new();
+    let mut setpts = String::new();
+    for clip in &clips {
+        let (start, end) = (
+            secs(clip.source_range.start.as_micros()),
+            secs(clip.source_range.end.as_micros()),
+        );
+        if !select.is_empty() {
+            select.push('+');
+            setpts.push('+');
+        }
+        let _ = write!(select, "gte(t,{:.6})*lt(t,{:.6})", start, end);
+        let _ = write!(
+            setpts,
+            "gte(T,{:.6})*lt(T,{:.6})*({:.6}+(T-{:.6})/{:.6})",
+            start,
+            end,
+            secs(clip.export_range.start.as_micros()),
+            start,
+            clip.speed
+        );
+    }
+    if select.is_empty() {
+        select.push('0');
+        setpts.push('0');
+    }
+
+    // Camera per export frame: zoom and the visible rect's top-left.
+    let frames = remapper.export_frame_count(fps);
+    let samples: Vec<[f32; 3]> = (0..frames.max(1))
+        .map(|frame| {
+            let source = remapper.source_time_for_export_frame(frame, fps);
+            let viewport = camera.get_viewport_at(source);
+            let rect = viewport.visible_rect();
+            [viewport.zoom, rect.x, rect.y]
+        })
+        .collect();
+    let breaks = linear_breakpoints(&samples);
+    let [zoom, x, y] = [0, 1, 2].map(|channel| piecewise(&samples, &breaks, channel));
+
+    format!(
+        "[0:v]select='{select}',setpts='({setpts})/TB',fps={fps},\
+         zoompan=z='{zoom}':x='({x})*iw':y='({y})*ih':d=1:s={out_w}x{out_h}:fps={fps}[v]"
+    )
+}
+
+/// Indices of the samples where a piecewise-linear curve through them must bend to stay
+/// within `CAMERA_TOLERANCE` of every sample. Always includes the first and last.
+fn linear_breakpoints(samples: &[[f32; 3]]) -> Vec<usize> {
+    let mut breaks = vec![0];
+    let mut start = 0;
+    let mut end = 1;
+    while end < samples.len() {
+        let fits = (start + 1..end).all(|i| {
+            let t = (i - start) as f32 / (end - start) as f32;
+            (0..3).all(|c| {
+                let line = samples[start][c] + (samples[end][c] - samples[start][c]) * t;
+                (samples[i][c] - line).abs() <= CAMERA_TOLERANCE
+            })
+        });
+        if fits {
+            end += 1;
+        } else {
+            start = end - 1;
+            breaks.push(start);
+        }
+    }
+    if samples.len() > 1 {
+        breaks.push(samples.len() - 1);
+    }
+    breaks
+}
+
+/// FFmpeg expression in `on` interpolating `channel` linearly between breakpoints and
+/// holding the last value.
+fn piecewise(samples: &[[f32; 3]], breaks: &[usize], channel: usize) -> String {
+    let mut expr = String::new();
+    for pair in breaks.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let (va, vb) = (samples[a][channel], samples[b][channel]);
+        let slope = (vb - va) / (b - a) as f32;
+        let _ = write!(
+            expr,
+            "gte(on,{a})*lt(on,{b})*({va:.5}+(on-{a})*{slope:.7})+"
+        );
+    }
+    let last = *breaks.last().unwrap_or(&0);
+    let value = samples.get(last).map_or(0.0, |sample| sample[channel]);
+    let _ = write!(expr, "gte(on,{last})*{value:.5}");
+    expr
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_remap::{SpeedRamp, TimeRange};
+    use crate::types::Timestamp;
+
+    #[test]
+    fn selects_and_retimes_the_uncut_source() {
+        let s = |secs: u64| Timestamp::from_micros(secs * 1_000_000);
+        let remapper = TimeRemapper::new(
+            vec![TimeRange::new(s(2), s(4))],
+            vec![SpeedRamp::new(TimeRange::new(s(6), s(10)), 2.0)],
+            s(0),
+            s(10),
+        );
+        let camera = CameraEngine::new(Default::default());
+
+        let graph = ffmpeg_filter_graph(&camera, &remapper, 30.0, 1280, 720);
+        assert!(graph.starts_with(
+            "[0:v]select='gte(t,0.000000)*lt(t,2.000000)+gte(t,4.000000)*lt(t,6.000000)+\
+             gte(t,6.000000)*lt(t,10.000000)'"
+        ));
+        assert!(graph.contains("gte(T,6.000000)*lt(T,10.000000)*(4.000000+(T-6.000000)/2.000000)"));
+        // A still camera is one segment held at full view.
+        assert!(graph.contains(
+            "zoompan=z='gte(on,0)*lt(on,179)*(1.00000+(on-0)*0.0000000)+gte(on,179)*1.00000'"
+        ));
+        assert!(graph.ends_with(":d=1:s=1280x720:fps=30[v]"));
+    }
+
+    #[test]
+    fn breakpoints_follow_bends() {
+        let samples: Vec<[f32; 3]> = [1.0, 1.5, 2.0, 2.0, 2.0]
+            .iter()
+            .map(|&zoom| [zoom, 0.0, 0.0])
+            .collect();
+        assert_eq!(linear_breakpoints(&samples), vec![0, 2, 4]);
+    }
+}
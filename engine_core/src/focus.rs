@@ -2,18 +2,293 @@
 // Tab Mode: focused element bounds. Desktop Mode: UI-change detection, motion saliency.
 // See steering.md: Auto-Zoom Strategy Rules
 
+use crate::error::EngineError;
+use crate::saliency::{GrayFrame, SaliencyAnalyzer};
 use crate::types::*;
 
+/// Keys this close together (microseconds) count as sustained typing.
+const TYPING_GAP_US: u64 = 1_000_000;
+/// While typing continues, the focused field is re-announced at most this often.
+const TYPING_REFRESH_US: u64 = 500_000;
+/// A region continues a track if it arrives within this long (microseconds) of the
+/// track's latest region...
+const TRACK_MAX_GAP_US: u64 = 1_000_000;
+/// ...and overlaps it at least this much (intersection over union).
+const TRACK_MIN_OVERLAP: f32 = 0.5;
+/// Scroll events this close together (microseconds) are one scroll.
+const SCROLL_GAP_US: u64 = 500_000;
+/// A scroll this long stops the camera from holding on what was framed before it.
+const LONG_SCROLL_US: u64 = 1_000_000;
+/// While a long scroll continues, its region is re-announced at most this often.
+const SCROLL_REFRESH_US: u64 = 500_000;
+/// Size of the area framed during a long scroll (normalized width and height).
+const SCROLL_REGION_SIZE: f32 = 0.6;
+
+/// Area framed on a right click, where the context menu will open (normalized width
+/// and height, from just above-left of the click).
+const CONTEXT_MENU_WIDTH: f32 = 0.25;
+const CONTEXT_MENU_HEIGHT: f32 = 0.35;
+const CONTEXT_MENU_INSET: f32 = 0.02;
+
+/// After a click inside a playing video, regions on it count again for this long.
+const VIDEO_INTERACTION_US: u64 = 5_000_000;
+
+/// A playing video, from a `VideoPlaying` hint or detected as persistent motion.
+#[derive(Debug, Clone)]
+struct VideoArea {
+    bounds: NormalizedRect,
+    detected: bool,
+    /// Last click inside it.
+    clicked: Option<Timestamp>,
+}
+
 /// Analyzes signals to detect focus regions (areas of interest).
 pub struct FocusAnalyzer {
+    weights: ImportanceWeights,
+    scroll_settings: ScrollSettings,
+    /// Start, latest event, and latest region of the scroll in progress.
+    scroll_run: Option<(Timestamp, Timestamp, Option<Timestamp>)>,
     active_regions: Vec<FocusRegion>,
+    /// Region of the focused element, carried across batches.
+    focused: Option<FocusRegion>,
+    last_key: Option<Timestamp>,
+    last_typing_region: Option<Timestamp>,
+    /// Motion/contrast saliency for captures without telemetry.
+    saliency: SaliencyAnalyzer,
+    /// Every track so far, in order of first appearance.
+    tracks: Vec<TrackedRegion>,
+    /// Indices of tracks still within `TRACK_MAX_GAP_US` of the latest region.
+    open_tracks: Vec<usize>,
+    /// Videos currently playing; motion and dwell on them don't make regions.
+    videos: Vec<VideoArea>,
 }
 
 impl FocusAnalyzer {
     pub fn new() -> Self {
+        Self::with_weights(ImportanceWeights::default())
+    }
+
+    pub fn with_weights(weights: ImportanceWeights) -> Self {
         FocusAnalyzer {
+            weights,
+            scroll_settings: ScrollSettings::default(),
+            scroll_run: None,
             active_regions: Vec::new(),
+            focused: None,
+            last_key: None,
+            last_typing_region: None,
+            saliency: SaliencyAnalyzer::new(),
+            tracks: Vec::new(),
+            open_tracks: Vec::new(),
+            videos: Vec::new(),
+        }
+    }
+
+    /// Replace the importance weights. Applies to batches analyzed after this call.
+    pub fn set_weights(&mut self, weights: ImportanceWeights) {
+        self.weights = weights;
+    }
+
+    /// Replace the scroll settings (for following long scrolls). Applies to batches
+    /// analyzed after this call.
+    pub fn set_scroll_settings(&mut self, settings: ScrollSettings) {
+        self.scroll_settings = settings;
+    }
+
+    /// Forget the focused element and typing state so the next batch starts fresh.
+    pub fn reset(&mut self) {
+        self.scroll_run = None;
+        self.active_regions.clear();
+        self.focused = None;
+        self.last_key = None;
+        self.last_typing_region = None;
+        self.saliency.reset();
+        self.tracks.clear();
+        self.open_tracks.clear();
+        self.videos.clear();
+    }
+
+    /// During a long scroll, frame the side new content scrolls in from (or the full
+    /// view), so the camera doesn't stay on something that has scrolled away.
+    fn follow_scroll(&mut self, timestamp: Timestamp, delta_y: f32) -> Option<FocusRegion> {
+        let settings = &self.scroll_settings;
+        if settings.pan_sensitivity <= 0.0 && !settings.zoom_out {
+            return None;
+        }
+
+        let ts = timestamp.as_micros();
+        let (start, _, last_region) = match self.scroll_run {
+            Some((start, last, last_region))
+                if ts.saturating_sub(last.as_micros()) <= SCROLL_GAP_US =>
+            {
+                (start, last, last_region)
+            }
+            _ => (timestamp, timestamp, None),
+        };
+        let due = ts - start.as_micros() >= LONG_SCROLL_US
+            && last_region
+                .is_none_or(|last| ts.saturating_sub(last.as_micros()) >= SCROLL_REFRESH_US);
+        self.scroll_run = Some((
+            start,
+            timestamp,
+            if due { Some(timestamp) } else { last_region },
+        ));
+        if !due {
+            return None;
+        }
+
+        let bounds = if settings.zoom_out {
+            NormalizedRect::new(0.0, 0.0, 1.0, 1.0)
+        } else {
+            // Content scrolls in from the bottom when scrolling down.
+            let lean = settings.pan_sensitivity.clamp(0.0, 1.0) * (1.0 - SCROLL_REGION_SIZE) / 2.0;
+            let center_y = 0.5 + lean * delta_y.signum();
+            NormalizedRect::new(
+                (1.0 - SCROLL_REGION_SIZE) / 2.0,
+                center_y - SCROLL_REGION_SIZE / 2.0,
+                SCROLL_REGION_SIZE,
+                SCROLL_REGION_SIZE,
+            )
+        };
+        Some(FocusRegion {
+            timestamp,
+            bounds,
+            importance: 1.0,
+            element: None,
+            track_id: None,
+        })
+    }
+
+    /// Whether a region sits on a playing video the user hasn't clicked recently.
+    fn on_idle_video(&self, region: &FocusRegion) -> bool {
+        let center = region.bounds.center();
+        self.videos.iter().any(|video| {
+            video.bounds.contains(&center)
+                && video.clicked.is_none_or(|clicked| {
+                    region
+                        .timestamp
+                        .as_micros()
+                        .saturating_sub(clicked.as_micros())
+                        > VIDEO_INTERACTION_US
+                })
+        })
+    }
+
+    /// Start or stop suppressing a video's area.
+    fn set_video(&mut self, bounds: NormalizedRect, playing: bool, detected: bool) {
+        let mut clicked = None;
+        self.videos.retain(|video| {
+            let same = video.detected == detected
+                && (detected || video.bounds.overlap(&bounds) >= TRACK_MIN_OVERLAP);
+            if same {
+                clicked = clicked.max(video.clicked);
+            }
+            !same
+        });
+        if playing {
+            self.videos.push(VideoArea {
+                bounds,
+                detected,
+                clicked,
+            });
+        }
+    }
+
+    fn click_videos(&mut self, position: &NormalizedCoord, timestamp: Timestamp) {
+        for video in &mut self.videos {
+            if video.bounds.contains(position) {
+                video.clicked = Some(timestamp);
+            }
+        }
+    }
+
+    /// Regions merged across time, in order of first appearance. A track's `end`
+    /// moves forward as later batches keep landing on it.
+    pub fn tracked_regions(&self) -> &[TrackedRegion] {
+        &self.tracks
+    }
+
+    /// Continue from tracks saved with a project; those ending within the track gap of
+    /// the latest one stay open for later regions.
+    pub fn restore_tracks(&mut self, tracks: Vec<TrackedRegion>) {
+        let latest = tracks.iter().map(|track| track.end.as_micros()).max();
+        self.open_tracks = (0..tracks.len())
+            .filter(|&index| {
+                latest.is_some_and(|latest| {
+                    latest - tracks[index].end.as_micros() <= TRACK_MAX_GAP_US
+                })
+            })
+            .collect();
+        self.tracks = tracks;
+    }
+
+    /// Assign each region to a track (continuing one or starting a new one), in time
+    /// order. Regions must not be older than those tracked before.
+    fn track(&mut self, regions: &mut [FocusRegion]) {
+        let mut order: Vec<usize> = (0..regions.len()).collect();
+        order.sort_by_key(|&i| regions[i].timestamp);
+        for i in order {
+            let region = &mut regions[i];
+            let ts = region.timestamp.as_micros();
+            let tracks = &self.tracks;
+            self.open_tracks.retain(|&index| {
+                ts.saturating_sub(tracks[index].end.as_micros()) <= TRACK_MAX_GAP_US
+            });
+
+            // Continue the best-overlapping open track.
+            let best = self
+                .open_tracks
+                .iter()
+                .map(|&index| (index, tracks[index].bounds.overlap(&region.bounds)))
+                .filter(|&(_, overlap)| overlap >= TRACK_MIN_OVERLAP)
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            match best {
+                Some((index, _)) => {
+                    let track = &mut self.tracks[index];
+                    track.end = track.end.max(region.timestamp);
+                    track.bounds = region.bounds;
+                    track.importance = track.importance.max(region.importance);
+                    track.samples += 1;
+                    region.track_id = Some(track.id);
+                }
+                None => {
+                    let id = self.tracks.len() as u32;
+                    self.tracks.push(TrackedRegion {
+                        id,
+                        start: region.timestamp,
+                        end: region.timestamp,
+                        bounds: region.bounds,
+                        importance: region.importance,
+                        samples: 1,
+                    });
+                    self.open_tracks.push(id as usize);
+                    region.track_id = Some(id);
+                }
+            }
+        }
+    }
+
+    /// Tier B fallback: focus regions from downscaled grayscale frames, for
+    /// screen/window captures without content-script signals. Frames must be in order.
+    pub fn analyze_frames(
+        &mut self,
+        frames: Vec<GrayFrame>,
+    ) -> Result<Vec<FocusRegion>, EngineError> {
+        let mut regions = Vec::new();
+        for frame in frames {
+            let region = self.saliency.analyze_frame(frame)?;
+            match self.saliency.persistent_motion() {
+                Some(bounds) => self.set_video(bounds, true, true),
+                None => self.videos.retain(|video| !video.detected),
+            }
+            regions.extend(region.filter(|region| !self.on_idle_video(region)));
+        }
+        for region in &mut regions {
+            region.importance = weighted(region.importance, self.weights.saliency);
         }
+        self.track(&mut regions);
+        self.active_regions.extend_from_slice(&regions);
+        Ok(regions)
     }
 
     /// Analyze signals and cursor track to detect focus regions.
@@ -21,29 +296,180 @@ impl FocusAnalyzer {
         &mut self,
         signals: &SignalBatch,
         cursor_track: &[CursorTrackPoint],
+    ) -> Vec<FocusRegion> {
+        self.analyze_with_gestures(signals, cursor_track, &[], &[], &[], &[])
+    }
+
+    /// Like `analyze`, also turning gestures into focus regions from the moment they
+    /// start: a drag's whole path is framed, a pinch-out zooms to what was between the
+    /// fingers, a pinch-in zooms out to full view, and a shake zooms to where it
+    /// happened. Gestures that started before this batch are placed at its first event.
+    /// Every click of a multi-click session re-announces the session's whole area, so
+    /// the camera holds one framing for it. A right click frames the area its context
+    /// menu is about to open in.
+    pub fn analyze_with_gestures(
+        &mut self,
+        signals: &SignalBatch,
+        cursor_track: &[CursorTrackPoint],
+        drags: &[DragSegment],
+        pinches: &[PinchGesture],
+        shakes: &[ShakeGesture],
+        sessions: &[InteractionSession],
     ) -> Vec<FocusRegion> {
         let mut regions = Vec::new();
+        let weights = self.weights.clone();
+
+        let batch_start = signals.events.iter().map(|e| e.timestamp).min();
+        let clamp = |ts: Timestamp| batch_start.map_or(ts, |start| ts.max(start));
+        for drag in drags {
+            regions.push(FocusRegion {
+                timestamp: clamp(drag.start),
+                bounds: drag.bounds,
+                importance: weighted(1.0, weights.clicks),
+                element: None,
+                track_id: None,
+            });
+        }
+        for pinch in pinches {
+            let bounds = if pinch.scale > 1.0 {
+                pinch.bounds
+            } else {
+                NormalizedRect::new(0.0, 0.0, 1.0, 1.0)
+            };
+            regions.push(FocusRegion {
+                timestamp: clamp(pinch.start),
+                bounds,
+                importance: weighted(1.0, weights.clicks),
+                element: None,
+                track_id: None,
+            });
+        }
+        for shake in shakes {
+            regions.push(FocusRegion {
+                timestamp: clamp(shake.start),
+                bounds: shake.bounds,
+                importance: weighted(1.0, weights.clicks),
+                element: None,
+                track_id: None,
+            });
+        }
+        for session in sessions.iter().filter(|session| session.clicks > 1) {
+            for event in &signals.events {
+                let in_session = event.timestamp >= session.start && event.timestamp <= session.end;
+                if in_session && matches!(event.event_type, EventType::MouseClick { .. }) {
+                    regions.push(FocusRegion {
+                        timestamp: event.timestamp,
+                        bounds: session.bounds,
+                        importance: weighted(1.0, weights.clicks),
+                        element: None,
+                        track_id: None,
+                    });
+                }
+            }
+        }
 
-        // Process focus change events directly.
         for event in &signals.events {
-            if let EventType::FocusChange { bounds } = &event.event_type {
+            if let EventType::MouseClick {
+                position,
+                button: 2,
+                ..
+            } = &event.event_type
+            {
                 regions.push(FocusRegion {
                     timestamp: event.timestamp,
-                    bounds: *bounds,
-                    importance: 1.0,
+                    bounds: context_menu_bounds(position),
+                    importance: weighted(1.0, weights.clicks),
+                    element: None,
+                    track_id: None,
                 });
             }
         }
 
+        // Process focus change events directly. Sustained typing keeps the focused
+        // field important, so the camera stays zoomed on it.
+        for event in &signals.events {
+            match &event.event_type {
+                EventType::FocusChange { bounds, element } => {
+                    let region = FocusRegion {
+                        timestamp: event.timestamp,
+                        bounds: *bounds,
+                        importance: weighted(
+                            element_importance(element.as_ref()),
+                            weights.focus_changes,
+                        ),
+                        element: element.clone(),
+                        track_id: None,
+                    };
+                    self.focused = Some(region.clone());
+                    self.last_typing_region = Some(event.timestamp);
+                    regions.push(region);
+                }
+                // A finished selection is about as clear a "look here" as there is.
+                EventType::TextSelection { bounds }
+                    if bounds.width > 0.0 && bounds.height > 0.0 =>
+                {
+                    regions.push(FocusRegion {
+                        timestamp: event.timestamp,
+                        bounds: *bounds,
+                        importance: weighted(1.0, weights.focus_changes),
+                        element: None,
+                        track_id: None,
+                    });
+                }
+                EventType::Scroll { delta_y } => {
+                    regions.extend(self.follow_scroll(event.timestamp, *delta_y));
+                }
+                EventType::VideoPlaying { bounds, playing } => {
+                    self.set_video(*bounds, *playing, false);
+                }
+                EventType::MouseClick { position, .. }
+                | EventType::MouseDown { position, .. }
+                | EventType::Touch {
+                    position,
+                    phase: PointerPhase::Down,
+                    ..
+                }
+                | EventType::Pen {
+                    position,
+                    phase: PointerPhase::Down,
+                    ..
+                } => self.click_videos(position, event.timestamp),
+                EventType::KeyPress { .. } | EventType::KeyDown { .. } => {
+                    let ts = event.timestamp.as_micros();
+                    let typing = self
+                        .last_key
+                        .replace(event.timestamp)
+                        .is_some_and(|last| ts.saturating_sub(last.as_micros()) <= TYPING_GAP_US);
+                    let due = self.last_typing_region.is_none_or(|last| {
+                        ts.saturating_sub(last.as_micros()) >= TYPING_REFRESH_US
+                    });
+                    if let Some(focused) = self.focused.as_ref().filter(|_| typing && due) {
+                        self.last_typing_region = Some(event.timestamp);
+                        // Typing into anything makes it worth staying on.
+                        regions.push(FocusRegion {
+                            timestamp: event.timestamp,
+                            importance: weighted(1.0, weights.typing),
+                            ..focused.clone()
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
         // Generate focus regions from cursor click positions.
         for point in cursor_track {
             if point.confidence >= 80 {
-                // High confidence cursor positions become focus regions.
+                // High confidence cursor positions become focus regions, except
+                // resting on a video that's just playing.
                 let region = self.cursor_to_focus_region(point);
-                regions.push(region);
+                if !self.on_idle_video(&region) {
+                    regions.push(region);
+                }
             }
         }
 
+        self.track(&mut regions);
         self.active_regions = regions.clone();
         regions
     }
@@ -61,11 +487,42 @@ impl FocusAnalyzer {
                 focus_size.min(1.0 - point.position.x + focus_size / 2.0),
                 focus_size.min(1.0 - point.position.y + focus_size / 2.0),
             ),
-            importance: point.confidence as f32 / 100.0,
+            importance: weighted(point.confidence as f32 / 100.0, self.weights.dwell),
+            element: None,
+            track_id: None,
         }
     }
 }
 
+/// Where a context menu opened at `position` will likely be: below and right of it,
+/// shifted to stay inside the frame as browsers do near the edges.
+fn context_menu_bounds(position: &NormalizedCoord) -> NormalizedRect {
+    let x = (position.x - CONTEXT_MENU_INSET).clamp(0.0, 1.0 - CONTEXT_MENU_WIDTH);
+    let y = (position.y - CONTEXT_MENU_INSET).clamp(0.0, 1.0 - CONTEXT_MENU_HEIGHT);
+    NormalizedRect::new(x, y, CONTEXT_MENU_WIDTH, CONTEXT_MENU_HEIGHT)
+}
+
+fn weighted(importance: f32, weight: f32) -> f32 {
+    (importance * weight).clamp(0.0, 1.0)
+}
+
+/// How much a focused element is worth zooming to: text entry first, then controls,
+/// then generic containers (below the camera's 0.8 threshold). Elements without
+/// metadata keep full importance.
+fn element_importance(element: Option<&ElementInfo>) -> f32 {
+    let Some(element) = element else {
+        return 1.0;
+    };
+    let role = element.role.as_deref().unwrap_or("");
+    match (element.tag.as_str(), role) {
+        ("input" | "textarea" | "select", _) | (_, "textbox" | "searchbox" | "combobox") => 1.0,
+        ("button" | "a" | "summary", _)
+        | (_, "button" | "link" | "checkbox" | "radio" | "menuitem" | "tab" | "switch") => 0.9,
+        ("div" | "span" | "section" | "body" | "main", _) => 0.5,
+        _ => 0.7,
+    }
+}
+
 impl Default for FocusAnalyzer {
     fn default() -> Self {
         Self::new()
@@ -84,6 +541,7 @@ mod tests {
                 timestamp: Timestamp::from_micros(1000),
                 event_type: EventType::FocusChange {
                     bounds: NormalizedRect::new(0.1, 0.1, 0.3, 0.1),
+                    element: None,
                 },
             }],
         };
@@ -93,6 +551,83 @@ mod tests {
         assert_eq!(regions[0].importance, 1.0);
     }
 
+    #[test]
+    fn right_clicks_frame_the_context_menu() {
+        let mut analyzer = FocusAnalyzer::new();
+        let click = |us: u64, button: u8| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::MouseClick {
+                position: NormalizedCoord::new(0.9, 0.3),
+                button,
+                element: None,
+            },
+        };
+        let signals = SignalBatch {
+            events: vec![click(1000, 0), click(2_000_000, 2)],
+        };
+
+        let regions = analyzer.analyze(&signals, &[]);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].timestamp, Timestamp::from_micros(2_000_000));
+        assert_eq!(regions[0].importance, 1.0);
+        // Shifted left to fit, like the menu itself.
+        assert_eq!(
+            regions[0].bounds,
+            NormalizedRect::new(0.75, 0.28, 0.25, 0.35)
+        );
+    }
+
+    #[test]
+    fn text_selection_creates_region() {
+        let mut analyzer = FocusAnalyzer::new();
+        let selection = |us: u64, bounds: NormalizedRect| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::TextSelection { bounds },
+        };
+        let signals = SignalBatch {
+            events: vec![
+                selection(1000, NormalizedRect::new(0.2, 0.4, 0.3, 0.05)),
+                // Cleared.
+                selection(2000, NormalizedRect::new(0.0, 0.0, 0.0, 0.0)),
+            ],
+        };
+
+        let regions = analyzer.analyze(&signals, &[]);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].importance, 1.0);
+        assert_eq!(regions[0].bounds, NormalizedRect::new(0.2, 0.4, 0.3, 0.05));
+    }
+
+    #[test]
+    fn weights_scale_importance() {
+        let mut analyzer = FocusAnalyzer::with_weights(ImportanceWeights {
+            focus_changes: 0.5,
+            dwell: 2.0,
+            ..Default::default()
+        });
+        let signals = SignalBatch {
+            events: vec![InputEvent {
+                timestamp: Timestamp::from_micros(1000),
+                event_type: EventType::FocusChange {
+                    bounds: NormalizedRect::new(0.1, 0.1, 0.3, 0.1),
+                    element: None,
+                },
+            }],
+        };
+        let cursor_track = vec![CursorTrackPoint {
+            timestamp: Timestamp::from_micros(2000),
+            position: NormalizedCoord::new(0.5, 0.5),
+            state: CursorState::Inferred,
+            confidence: 80,
+            reason: InferenceReason::UiChange,
+            smoothed: None,
+        }];
+
+        let regions = analyzer.analyze(&signals, &cursor_track);
+        let importance: Vec<f32> = regions.iter().map(|r| r.importance).collect();
+        assert_eq!(importance, vec![0.5, 1.0]);
+    }
+
     #[test]
     fn high_confidence_cursor_creates_region() {
         let mut analyzer = FocusAnalyzer::new();
@@ -102,9 +637,205 @@ mod tests {
             state: CursorState::Visible,
             confidence: 100,
             reason: InferenceReason::DirectInput,
+            smoothed: None,
         }];
 
         let regions = analyzer.analyze(&SignalBatch { events: vec![] }, &cursor_track);
         assert_eq!(regions.len(), 1);
     }
+
+    #[test]
+    fn element_type_weights_importance() {
+        let mut analyzer = FocusAnalyzer::new();
+        let focus = |us: u64, tag: &str, role: Option<&str>| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::FocusChange {
+                bounds: NormalizedRect::new(0.1, 0.1, 0.3, 0.1),
+                element: Some(ElementInfo {
+                    tag: tag.to_string(),
+                    role: role.map(str::to_string),
+                    name: Some("Email".to_string()),
+                    is_password: false,
+                    autocomplete: None,
+                }),
+            },
+        };
+        let regions = analyzer.analyze(
+            &SignalBatch {
+                events: vec![
+                    focus(0, "input", None),
+                    focus(1000, "div", Some("button")),
+                    focus(2000, "div", None),
+                ],
+            },
+            &[],
+        );
+        let importance: Vec<f32> = regions.iter().map(|r| r.importance).collect();
+        assert_eq!(importance, vec![1.0, 0.9, 0.5]);
+        assert_eq!(
+            regions[0].element.as_ref().and_then(|e| e.name.as_deref()),
+            Some("Email")
+        );
+    }
+
+    #[test]
+    fn sustained_typing_refreshes_focused_field() {
+        let mut analyzer = FocusAnalyzer::new();
+        let field = NormalizedRect::new(0.1, 0.1, 0.3, 0.1);
+        let key = |us: u64| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::KeyPress {
+                key: "a".to_string(),
+                modifiers: KeyModifiers::default(),
+            },
+        };
+        let mut events = vec![InputEvent {
+            timestamp: Timestamp::from_micros(0),
+            event_type: EventType::FocusChange {
+                bounds: field,
+                element: None,
+            },
+        }];
+        // Two seconds of typing at 5 keys per second, then one stray key much later.
+        events.extend((1..=10).map(|i| key(i * 200_000)));
+        events.push(key(10_000_000));
+
+        let regions = analyzer.analyze(&SignalBatch { events }, &[]);
+        let times: Vec<u64> = regions.iter().map(|r| r.timestamp.as_micros()).collect();
+        assert_eq!(times, vec![0, 600_000, 1_200_000, 1_800_000]);
+        assert!(regions.iter().all(|r| r.bounds == field));
+    }
+
+    #[test]
+    fn tracks_regions_across_batches() {
+        let mut analyzer = FocusAnalyzer::new();
+        let dialog = NormalizedRect::new(0.3, 0.3, 0.4, 0.3);
+        let focus = |us: u64, bounds: NormalizedRect| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::FocusChange {
+                bounds,
+                element: None,
+            },
+        };
+        let first = analyzer.analyze(
+            &SignalBatch {
+                events: vec![
+                    focus(0, dialog),
+                    focus(500_000, NormalizedRect::new(0.32, 0.3, 0.4, 0.3)),
+                    focus(600_000, NormalizedRect::new(0.0, 0.0, 0.1, 0.05)),
+                ],
+            },
+            &[],
+        );
+        let second = analyzer.analyze(
+            &SignalBatch {
+                events: vec![focus(1_200_000, dialog), focus(5_000_000, dialog)],
+            },
+            &[],
+        );
+
+        let ids: Vec<Option<u32>> = first.iter().chain(&second).map(|r| r.track_id).collect();
+        assert_eq!(ids, vec![Some(0), Some(0), Some(1), Some(0), Some(2)]);
+        let dialog_track = &analyzer.tracked_regions()[0];
+        assert_eq!(dialog_track.duration_us(), 1_200_000);
+        assert_eq!(dialog_track.samples, 3);
+    }
+
+    #[test]
+    fn playing_video_suppresses_dwell_until_clicked() {
+        let mut analyzer = FocusAnalyzer::new();
+        let video = NormalizedRect::new(0.2, 0.2, 0.5, 0.5);
+        let at = |us: u64, event_type: EventType| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type,
+        };
+        let dwell = |us: u64| CursorTrackPoint {
+            timestamp: Timestamp::from_micros(us),
+            position: NormalizedCoord::new(0.4, 0.4),
+            state: CursorState::Visible,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        };
+        let playing = SignalBatch {
+            events: vec![at(
+                0,
+                EventType::VideoPlaying {
+                    bounds: video,
+                    playing: true,
+                },
+            )],
+        };
+        assert!(analyzer.analyze(&playing, &[dwell(1_000_000)]).is_empty());
+
+        let click = SignalBatch {
+            events: vec![at(
+                2_000_000,
+                EventType::MouseClick {
+                    position: NormalizedCoord::new(0.4, 0.4),
+                    button: 0,
+                    element: None,
+                },
+            )],
+        };
+        assert_eq!(analyzer.analyze(&click, &[dwell(2_000_000)]).len(), 1);
+        // Long after the click, the video is just playing again.
+        assert!(analyzer
+            .analyze(&SignalBatch { events: vec![] }, &[dwell(9_000_000)])
+            .is_empty());
+    }
+
+    #[test]
+    fn long_scrolls_lean_toward_incoming_content() {
+        let mut analyzer = FocusAnalyzer::new();
+        let scroll = |us: u64, delta_y: f32| InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::Scroll { delta_y },
+        };
+        // A short flick, then two seconds of scrolling down.
+        let mut events = vec![scroll(0, 100.0), scroll(200_000, 100.0)];
+        events.extend((0..=10).map(|i| scroll(3_000_000 + i * 200_000, 100.0)));
+
+        let regions = analyzer.analyze(&SignalBatch { events }, &[]);
+        let times: Vec<u64> = regions.iter().map(|r| r.timestamp.as_micros()).collect();
+        assert_eq!(times, vec![4_000_000, 4_600_000]);
+        assert!(regions[0].bounds.center().y > 0.5);
+
+        analyzer.set_scroll_settings(ScrollSettings {
+            pan_sensitivity: 0.0,
+            ..Default::default()
+        });
+        let events = (0..=10)
+            .map(|i| scroll(9_000_000 + i * 200_000, -100.0))
+            .collect();
+        assert!(analyzer.analyze(&SignalBatch { events }, &[]).is_empty());
+    }
+
+    #[test]
+    fn drag_creates_region_at_its_start() {
+        let mut analyzer = FocusAnalyzer::new();
+        let drag = DragSegment {
+            start: Timestamp::from_micros(1000),
+            end: Timestamp::from_micros(5000),
+            button: 0,
+            start_position: NormalizedCoord::new(0.2, 0.2),
+            end_position: NormalizedCoord::new(0.6, 0.4),
+            bounds: NormalizedRect::new(0.2, 0.2, 0.4, 0.2),
+        };
+        let signals = SignalBatch {
+            events: vec![InputEvent {
+                timestamp: Timestamp::from_micros(3000),
+                event_type: EventType::MouseUp {
+                    position: NormalizedCoord::new(0.6, 0.4),
+                    button: 0,
+                },
+            }],
+        };
+
+        let regions = analyzer.analyze_with_gestures(&signals, &[], &[drag], &[], &[], &[]);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].bounds, drag.bounds);
+        // The drag began in an earlier batch.
+        assert_eq!(regions[0].timestamp.as_micros(), 3000);
+    }
 }
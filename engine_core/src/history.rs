@@ -0,0 +1,142 @@
+// Undo/redo: each edit is recorded with a label and the state it replaced, so any edit
+// undoes exactly however the engine normalized it (merged cuts, clamped viewports).
+// See steering.md: Rust Rules (Engine)
+
+/// Edits kept for undo; older ones are forgotten.
+pub const HISTORY_LIMIT: usize = 100;
+
+#[derive(Debug, Clone)]
+struct Entry<S> {
+    label: String,
+    state: S,
+}
+
+/// What `record` dropped: the redo stack, and the oldest edit if past the limit.
+#[derive(Debug, Clone)]
+struct Dropped<S> {
+    redo: Vec<Entry<S>>,
+    evicted: Option<Entry<S>>,
+}
+
+/// Undo and redo stacks of states of type `S`.
+#[derive(Debug, Clone)]
+pub struct EditHistory<S> {
+    undo: Vec<Entry<S>>,
+    redo: Vec<Entry<S>>,
+    limit: usize,
+    /// What the last `record` dropped, for `cancel` to put back.
+    dropped: Option<Dropped<S>>,
+}
+
+impl<S> Default for EditHistory<S> {
+    fn default() -> Self {
+        EditHistory::new(HISTORY_LIMIT)
+    }
+}
+
+impl<S> EditHistory<S> {
+    pub fn new(limit: usize) -> Self {
+        EditHistory {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit: limit.max(1),
+            dropped: None,
+        }
+    }
+
+    /// Record an edit about to be made: `before` is the state it replaces. Clears redo.
+    pub fn record(&mut self, label: &str, before: S) {
+        let redo = std::mem::take(&mut self.redo);
+        let evicted = (self.undo.len() == self.limit).then(|| self.undo.remove(0));
+        self.dropped = Some(Dropped { redo, evicted });
+        self.undo.push(Entry {
+            label: label.to_string(),
+            state: before,
+        });
+    }
+
+    /// Forget the last recorded edit, for edits that turned out to change nothing,
+    /// restoring the redo stack it cleared.
+    pub fn cancel(&mut self) {
+        let Some(dropped) = self.dropped.take() else {
+            return;
+        };
+        self.undo.pop();
+        self.undo.splice(0..0, dropped.evicted);
+        self.redo = dropped.redo;
+    }
+
+    /// Undo the last edit: returns its label and the state to restore, keeping
+    /// `current` for redo. `None` if there is nothing to undo.
+    pub fn undo(&mut self, current: S) -> Option<(String, S)> {
+        let entry = self.undo.pop()?;
+        self.dropped = None;
+        self.redo.push(Entry {
+            label: entry.label.clone(),
+            state: current,
+        });
+        Some((entry.label, entry.state))
+    }
+
+    /// Redo the last undone edit: returns its label and the state to restore, keeping
+    /// `current` for undo. `None` if there is nothing to redo.
+    pub fn redo(&mut self, current: S) -> Option<(String, S)> {
+        let entry = self.redo.pop()?;
+        self.dropped = None;
+        self.undo.push(Entry {
+            label: entry.label.clone(),
+            state: current,
+        });
+        Some((entry.label, entry.state))
+    }
+
+    /// Label of the edit `undo` would undo, e.g. "Add cut".
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo.last().map(|entry| entry.label.as_str())
+    }
+
+    /// Label of the edit `redo` would redo.
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|entry| entry.label.as_str())
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.dropped = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_walk_the_stacks() {
+        let mut history = EditHistory::new(2);
+        let mut state = 0;
+        for (label, next) in [("one", 1), ("two", 2), ("three", 3)] {
+            history.record(label, state);
+            state = next;
+        }
+
+        // The limit forgot "one".
+        assert_eq!(history.undo(state), Some(("three".to_string(), 2)));
+        assert_eq!(history.undo(2), Some(("two".to_string(), 1)));
+        assert_eq!(history.undo(1), None);
+        assert_eq!(history.redo_label(), Some("two"));
+        assert_eq!(history.redo(1), Some(("two".to_string(), 2)));
+
+        // An edit that changed nothing keeps what could be redone.
+        history.record("nothing", 2);
+        assert_eq!(history.redo_label(), None);
+        history.cancel();
+        assert_eq!(history.redo_label(), Some("three"));
+        assert_eq!(history.undo_label(), Some("two"));
+
+        // A new edit drops it.
+        history.record("four", 2);
+        assert_eq!(history.redo_label(), None);
+        assert_eq!(history.undo_label(), Some("four"));
+    }
+}
@@ -0,0 +1,409 @@
+// Edit interchange: the TimeRemapper's cuts, ramps, and trim (plus markers) as
+// OpenTimelineIO JSON, a CMX 3600 EDL, or FCPXML, so an edit can continue in
+// Resolve, Premiere, or Final Cut.
+// Clips follow `TimeRemapper::audio_segments`, which already merges runs of one speed.
+// See design.md: TimeRemapper (Rust)
+
+use std::fmt::Write as _;
+
+use serde_json::{json, Value};
+
+use crate::time_remap::{ExportMarker, TimeRemapper};
+use crate::timecode::Timecode;
+
+/// One clip of the edit in whole frames.
+struct FrameClip {
+    source_in: u64,
+    source_out: u64,
+    record_in: u64,
+    record_out: u64,
+    /// Average playback speed over the clip.
+    speed: f32,
+}
+
+/// The export as clips snapped to frames at `fps`. Eased ramps become clips at their
+/// average speed.
+fn frame_clips(remapper: &TimeRemapper, fps: f64) -> Vec<FrameClip> {
+    remapper
+        .audio_segments()
+        .iter()
+        .map(|segment| FrameClip {
+            source_in: segment.source_range.start.frame_at(fps),
+            source_out: segment.source_range.end.frame_at(fps),
+            record_in: segment.export_range.start.frame_at(fps),
+            record_out: segment.export_range.end.frame_at(fps),
+            speed: segment.speed,
+        })
+        .filter(|clip| clip.record_out > clip.record_in)
+        .collect()
+}
+
+/// OpenTimelineIO timeline of the export: one video track of clips referencing
+/// `media_url`, with speed changes as `LinearTimeWarp` effects and markers on the track.
+/// Each clip's `source_range` starts at its source frame and lasts its export duration,
+/// as OTIO applies the time warp on top.
+pub fn to_otio(remapper: &TimeRemapper, name: &str, media_url: &str, fps: f64) -> Value {
+    let time = |frames: u64| {
+        json!({
+            "OTIO_SCHEMA": "RationalTime.1",
+            "rate": fps,
+            "value": frames as f64
+        })
+    };
+    let range = |start: u64, duration: u64| {
+        json!({
+            "OTIO_SCHEMA": "TimeRange.1",
+            "start_time": time(start),
+            "duration": time(duration)
+        })
+    };
+
+    let clips: Vec<Value> = frame_clips(remapper, fps)
+        .iter()
+        .enumerate()
+        .map(|(index, clip)| {
+            let effects = if clip.speed != 1.0 {
+                vec![json!({
+                    "OTIO_SCHEMA": "LinearTimeWarp.1",
+                    "name": "",
+                    "effect_name": "LinearTimeWarp",
+                    "time_scalar": clip.speed,
+                    "metadata": {}
+                })]
+            } else {
+                Vec::new()
+            };
+            json!({
+                "OTIO_SCHEMA": "Clip.1",
+                "name": format!("{} {}", name, index + 1),
+                "source_range": range(clip.source_in, clip.record_out - clip.record_in),
+                "media_reference": {
+                    "OTIO_SCHEMA": "ExternalReference.1",
+                    "name": "",
+                    "target_url": media_url,
+                    "available_range": null,
+                    "metadata": {}
+                },
+                "effects": effects,
+                "markers": [],
+                "metadata": {}
+            })
+        })
+        .collect();
+
+    let markers: Vec<Value> = remapper
+        .export_markers()
+        .iter()
+        .map(|marker| {
+            json!({
+                "OTIO_SCHEMA": "Marker.2",
+                "name": marker.marker.label,
+                "color": "RED",
+                "marked_range": range(marker.export_time.frame_at(fps), 0),
+                "comment": "",
+                "metadata": { "screenmu": { "color": marker.marker.color } }
+            })
+        })
+        .collect();
+
+    json!({
+        "OTIO_SCHEMA": "Timeline.1",
+        "name": name,
+        "global_start_time": null,
+        "metadata": {},
+        "tracks": {
+            "OTIO_SCHEMA": "Stack.1",
+            "name": "tracks",
+            "source_range": null,
+            "effects": [],
+            "markers": [],
+            "metadata": {},
+            "children": [{
+                "OTIO_SCHEMA": "Track.1",
+                "name": "V1",
+                "kind": "Video",
+                "source_range": null,
+                "effects": [],
+                "markers": markers,
+                "metadata": {},
+                "children": clips
+            }]
+        }
+    })
+}
+
+/// CMX 3600 EDL of the export: one cut event per clip on reel `AX`, an `M2` motion
+/// line for each speed change, and markers as `* LOC:` comments under the event they
+/// fall in. Record timecode starts at 00:00:00:00.
+pub fn to_edl(remapper: &TimeRemapper, title: &str, fps: f64, drop_frame: bool) -> String {
+    let tc = |frame: u64| Timecode::from_frame(frame, fps, drop_frame);
+    let drop_frame = tc(0).drop_frame;
+    let markers = remapper.export_markers();
+    let clips = frame_clips(remapper, fps);
+
+    let mut edl = format!("TITLE: {}\n", title);
+    edl.push_str(if drop_frame {
+        "FCM: DROP FRAME\n"
+    } else {
+        "FCM: NON-DROP FRAME\n"
+    });
+    for (index, clip) in clips.iter().enumerate() {
+        let event = index + 1;
+        let _ = writeln!(
+            edl,
+            "\n{:03}  AX       V     C        {} {} {} {}",
+            event,
+            tc(clip.source_in),
+            tc(clip.source_out),
+            tc(clip.record_in),
+            tc(clip.record_out)
+        );
+        if clip.speed != 1.0 {
+            // Motion effects are given as the source frame rate that plays at `fps`.
+            let _ = writeln!(
+                edl,
+                "M2   AX       {:05.1}    {}",
+                clip.speed as f64 * fps,
+                tc(clip.source_in)
+            );
+        }
+        let _ = writeln!(edl, "* FROM CLIP NAME: {}", title);
+        let last = index + 1 == clips.len();
+        for marker in markers
+            .iter()
+            .filter(|marker| in_event(marker, clip, fps, last))
+        {
+            let _ = writeln!(
+                edl,
+                "* LOC: {} RED     {}",
+                tc(marker.export_time.frame_at(fps)),
+                marker.marker.label
+            );
+        }
+    }
+    edl
+}
+
+/// FCPXML 1.9 project of the export: one `asset-clip` of `media_url` per clip in the
+/// spine, so cuts become separate clip instances at their source offsets. Clips at
+/// other than 1x carry a linear `timeMap` from clip time to source time. Markers sit
+/// in the clip they fall in.
+pub fn to_fcpxml(
+    remapper: &TimeRemapper,
+    name: &str,
+    media_url: &str,
+    fps: f64,
+    width: u32,
+    height: u32,
+) -> String {
+    let (num, den) = frame_duration(fps);
+    let time = |frames: u64| {
+        if frames == 0 {
+            "0s".to_string()
+        } else {
+            format!("{}/{}s", frames * num, den)
+        }
+    };
+    let drop_frame = Timecode::from_frame(0, fps, true).drop_frame;
+    let markers = remapper.export_markers();
+    let clips = frame_clips(remapper, fps);
+    let name = xml_escape(name);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE fcpxml>\n");
+    xml.push_str("<fcpxml version=\"1.9\">\n  <resources>\n");
+    let _ = writeln!(
+        xml,
+        "    <format id=\"r1\" frameDuration=\"{}\" width=\"{}\" height=\"{}\"/>",
+        time(1),
+        width,
+        height
+    );
+    let _ = writeln!(
+        xml,
+        "    <asset id=\"r2\" name=\"{}\" start=\"0s\" duration=\"{}\" hasVideo=\"1\" \
+         hasAudio=\"1\" format=\"r1\">",
+        name,
+        time(remapper.out_point().frame_at(fps))
+    );
+    let _ = writeln!(
+        xml,
+        "      <media-rep kind=\"original-media\" src=\"{}\"/>\n    </asset>",
+        xml_escape(media_url)
+    );
+    xml.push_str("  </resources>\n  <library>\n");
+    let _ = writeln!(
+        xml,
+        "    <event name=\"{}\">\n      <project name=\"{}\">",
+        name, name
+    );
+    let _ = writeln!(
+        xml,
+        "        <sequence format=\"r1\" duration=\"{}\" tcStart=\"0s\" tcFormat=\"{}\">",
+        time(clips.last().map_or(0, |clip| clip.record_out)),
+        if drop_frame { "DF" } else { "NDF" }
+    );
+    xml.push_str("          <spine>\n");
+
+    for (index, clip) in clips.iter().enumerate() {
+        let duration = clip.record_out - clip.record_in;
+        let retimed = clip.speed != 1.0;
+        // Retimed clips count clip time from 0; the time map says which source it shows.
+        let start = if retimed { 0 } else { clip.source_in };
+        let _ = writeln!(
+            xml,
+            "            <asset-clip ref=\"r2\" name=\"{} {}\" offset=\"{}\" start=\"{}\" \
+             duration=\"{}\" format=\"r1\">",
+            name,
+            index + 1,
+            time(clip.record_in),
+            time(start),
+            time(duration)
+        );
+        if retimed {
+            let _ = writeln!(
+                xml,
+                "              <timeMap>\n                <timept time=\"0s\" value=\"{}\" \
+                 interp=\"linear\"/>\n                <timept time=\"{}\" value=\"{}\" \
+                 interp=\"linear\"/>\n              </timeMap>",
+                time(clip.source_in),
+                time(duration),
+                time(clip.source_out)
+            );
+        }
+        let last = index + 1 == clips.len();
+        for marker in markers
+            .iter()
+            .filter(|marker| in_event(marker, clip, fps, last))
+        {
+            let offset = marker.export_time.frame_at(fps) - clip.record_in;
+            let _ = writeln!(
+                xml,
+                "              <marker start=\"{}\" duration=\"{}\" value=\"{}\"/>",
+                time(start + offset),
+                time(1),
+                xml_escape(&marker.marker.label)
+            );
+        }
+        xml.push_str("            </asset-clip>\n");
+    }
+
+    xml.push_str(
+        "          </spine>\n        </sequence>\n      </project>\n    </event>\n  \
+         </library>\n</fcpxml>\n",
+    );
+    xml
+}
+
+/// One frame's duration as a fraction of a second: `1001/30000` at 29.97, `1/30` at 30.
+fn frame_duration(fps: f64) -> (u64, u64) {
+    let ntsc = fps * 1001.0 / 1000.0;
+    if (ntsc - ntsc.round()).abs() < 1e-3 && (fps - fps.round()).abs() > 1e-3 {
+        (1001, (ntsc.round() as u64) * 1000)
+    } else {
+        (1, (fps.round() as u64).max(1))
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Whether `marker` falls in `clip`'s record range (the last clip also takes markers at
+/// the very end of the export).
+fn in_event(marker: &ExportMarker, clip: &FrameClip, fps: f64, last: bool) -> bool {
+    let frame = marker.export_time.frame_at(fps);
+    frame >= clip.record_in && (frame < clip.record_out || last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_remap::{Marker, SpeedRamp, TimeRange};
+    use crate::types::Timestamp;
+
+    fn remapper() -> TimeRemapper {
+        let s = |secs: u64| Timestamp::from_micros(secs * 1_000_000);
+        TimeRemapper::new(
+            vec![TimeRange::new(s(2), s(4))],
+            vec![SpeedRamp::new(TimeRange::new(s(6), s(10)), 2.0)],
+            s(0),
+            s(10),
+        )
+        .with_markers(vec![
+            Marker::new(s(1), "Intro", ""),
+            Marker::new(s(8), "Deploy", "#ff0000"),
+        ])
+    }
+
+    #[test]
+    fn edl_lists_cut_events_with_motion() {
+        let edl = to_edl(&remapper(), "demo", 30.0, false);
+        let lines: Vec<&str> = edl.lines().collect();
+        assert_eq!(lines[0], "TITLE: demo");
+        assert_eq!(lines[1], "FCM: NON-DROP FRAME");
+        assert_eq!(
+            lines[3],
+            "001  AX       V     C        00:00:00:00 00:00:02:00 00:00:00:00 00:00:02:00"
+        );
+        assert_eq!(lines[5], "* LOC: 00:00:01:00 RED     Intro");
+        assert_eq!(
+            lines[7],
+            "002  AX       V     C        00:00:04:00 00:00:06:00 00:00:02:00 00:00:04:00"
+        );
+        assert_eq!(
+            lines[10],
+            "003  AX       V     C        00:00:06:00 00:00:10:00 00:00:04:00 00:00:06:00"
+        );
+        assert_eq!(lines[11], "M2   AX       060.0    00:00:06:00");
+        // 8s of source is 1s into the 2x clip.
+        assert_eq!(lines[13], "* LOC: 00:00:05:00 RED     Deploy");
+    }
+
+    #[test]
+    fn fcpxml_places_clip_instances_with_time_maps() {
+        let xml = to_fcpxml(
+            &remapper(),
+            "R&D demo",
+            "file:///demo.webm",
+            30.0,
+            1920,
+            1080,
+        );
+        assert!(xml.contains(
+            "<format id=\"r1\" frameDuration=\"1/30s\" width=\"1920\" height=\"1080\"/>"
+        ));
+        assert!(xml.contains("<event name=\"R&amp;D demo\">"));
+        assert!(xml.contains("<sequence format=\"r1\" duration=\"180/30s\""));
+        // The second clip resumes after the cut at 4s of source.
+        assert!(xml.contains(
+            "name=\"R&amp;D demo 2\" offset=\"60/30s\" start=\"120/30s\" duration=\"60/30s\""
+        ));
+        assert!(xml.contains("<timept time=\"60/30s\" value=\"300/30s\" interp=\"linear\"/>"));
+        assert!(xml.contains("<marker start=\"30/30s\" duration=\"1/30s\" value=\"Deploy\"/>"));
+        assert_eq!(frame_duration(30_000.0 / 1001.0), (1001, 30_000));
+    }
+
+    #[test]
+    fn otio_clips_carry_time_warps() {
+        let otio = to_otio(&remapper(), "demo", "file:///demo.webm", 30.0);
+        let track = &otio["tracks"]["children"][0];
+        let clips = track["children"].as_array().unwrap();
+        assert_eq!(clips.len(), 3);
+        assert_eq!(clips[1]["source_range"]["start_time"]["value"], 120.0);
+        assert_eq!(clips[2]["source_range"]["duration"]["value"], 60.0);
+        assert_eq!(clips[2]["effects"][0]["time_scalar"], 2.0);
+        assert!(clips[0]["effects"].as_array().unwrap().is_empty());
+        assert_eq!(
+            clips[0]["media_reference"]["target_url"],
+            "file:///demo.webm"
+        );
+        assert_eq!(
+            track["markers"][1]["marked_range"]["start_time"]["value"],
+            150.0
+        );
+    }
+}
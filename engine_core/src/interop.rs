@@ -0,0 +1,26 @@
+// Structured JsValue interop via serde-wasm-bindgen.
+// Alternative to the JSON-string surface: callers pass and receive plain JS objects.
+// See steering.md: Rust Rules (Engine)
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// Convert a Rust value into a plain JS object.
+/// Uses the JSON-compatible serializer so maps become objects and u64 become numbers,
+/// matching the shape callers already get from the JSON-string API.
+pub(crate) fn to_js_object<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+}
+
+/// Convert a plain JS object into a Rust value.
+/// `context` prefixes the error message, e.g. "Invalid config".
+pub(crate) fn from_js_object<T: DeserializeOwned>(
+    value: JsValue,
+    context: &str,
+) -> Result<T, JsValue> {
+    serde_wasm_bindgen::from_value(value)
+        .map_err(|e| JsValue::from_str(&format!("{}: {}", context, e)))
+}
@@ -2,23 +2,103 @@
 // See steering.md for architecture rules. All "magic" lives here; JS is plumbing.
 // LLM-assisted: initial scaffold generated with AI assistance per hackathon disclosure rules.
 
+mod activity;
+mod after_effects;
+mod binary;
+mod blur;
 mod camera;
+mod css;
 mod cursor;
 mod effects;
 mod error;
+mod ffmpeg;
 mod focus;
+mod history;
+mod interchange;
+mod interop;
+mod lottie;
+mod migrate;
+mod project;
+mod revision;
+mod ripple;
+mod saliency;
+mod sanitize;
+mod scenes;
+#[cfg(feature = "schema")]
+mod schema;
+mod scroll;
+mod sessions;
+mod subtitles;
+mod suggest;
+mod sync;
+mod synthetic_cursor;
+mod takes;
 mod time_remap;
+mod timecode;
+mod timeline;
 mod types;
+mod validation;
+mod watermark;
 
 use wasm_bindgen::prelude::*;
 
-pub use camera::CameraEngine;
-pub use cursor::CursorTracker;
+use history::EditHistory;
+use interop::{from_js_object, to_js_object};
+use revision::{changed_span, span};
+
+pub use activity::{ActivityAnalyzer, ActivitySettings, IdleSpan};
+pub use after_effects::{after_effects_export, AfterEffectsExport};
+pub use binary::{
+    decode_signal_buffer, encode_analysis_result, encode_signal_record, SIGNAL_RECORD_SIZE,
+};
+pub use blur::{sensitive_regions, track_blur_regions, AUTO_BLUR_ID_BASE};
+pub use camera::{CameraEngine, BAKED_MATRIX_STRIDE, BAKED_VIEWPORT_STRIDE};
+pub use css::camera_keyframes_css;
+pub use cursor::{derive_motion, simplify_path, CursorTracker};
 pub use effects::EffectGenerator;
 pub use error::EngineError;
+pub use ffmpeg::ffmpeg_filter_graph;
 pub use focus::FocusAnalyzer;
-pub use time_remap::{SpeedRamp, TimeRange, TimeRemapper, WasmTimeRemapper};
+pub use interchange::{to_edl, to_fcpxml, to_otio};
+pub use lottie::effects_to_lottie;
+pub use migrate::{
+    migrate_engine_config, migrate_remapper_config, AppliedMigration, MigrationReport,
+    ENGINE_CONFIG_VERSION, REMAPPER_CONFIG_VERSION,
+};
+pub use project::{Project, PROJECT_VERSION};
+pub use revision::{ChangeSet, RevisionLog, Track, TrackChange};
+pub use ripple::{RippleDelta, RippleSpan};
+pub use saliency::{GrayFrame, SaliencyAnalyzer};
+pub use sanitize::SignalSanitizer;
+pub use scenes::{detect_scene_changes, scene_markers, SceneChange, SceneReport, SceneSettings};
+#[cfg(feature = "schema")]
+pub use schema::json_schemas;
+pub use scroll::ScrollCompensator;
+pub use sessions::cluster_clicks;
+pub use subtitles::{chapters_vtt, typing_captions, CaptionFormat};
+pub use suggest::{
+    CondenseResult, CondenseSettings, CutReason, CutSuggestion, EditSuggester, FrameHash,
+    SuggestSettings,
+};
+pub use sync::{ClockSync, PauseCollapser};
+pub use synthetic_cursor::{
+    bake_synthetic_cursor, synthetic_cursor, CursorShape, SyntheticCursorFrame,
+    SyntheticCursorSettings, SYNTHETIC_CURSOR_STRIDE,
+};
+pub use takes::{merge_takes, Take, TakeBatch, TakeOverlap};
+pub use time_remap::{
+    AudioSegment, ExportMarker, FrameScheduleEntry, Marker, MarkerConfig, RemapSegment, SpeedRamp,
+    SpeedRampConfig, SpeedRange, TimeRange, TimeRangeConfig, TimeRemapper, TimeRemapperConfig,
+    WasmTimeRemapper,
+};
+pub use timecode::{timecode_to_timestamp, timestamp_to_timecode, Timecode};
+pub use timeline::{
+    ClipBoundary, ClipTime, TimelineClip, TimelineClipConfig, TimelineComposer, TimelineConfig,
+    WasmTimelineComposer,
+};
 pub use types::*;
+pub use validation::{validate_remapper_config, IssueKind, IssueSeverity, ValidationIssue};
+pub use watermark::place_watermark;
 
 /// Initialize panic hook for better error messages in browser console.
 #[wasm_bindgen(start)]
@@ -31,10 +111,43 @@ pub fn init() {
 /// Batch interface to minimize JS↔WASM crossings.
 #[wasm_bindgen]
 pub struct Engine {
+    /// Signal-to-media clock anchors. Kept across `reset` like the config.
+    clock_sync: ClockSync,
+    signal_sanitizer: SignalSanitizer,
+    pause_collapser: PauseCollapser,
+    scroll_compensator: ScrollCompensator,
     cursor_tracker: CursorTracker,
     focus_analyzer: FocusAnalyzer,
     camera_engine: CameraEngine,
     effect_generator: EffectGenerator,
+    /// User-drawn blur regions; cleared by `reset`.
+    blur_regions: Vec<BlurRegion>,
+    /// Signals arrived since the blur track was computed. Recomputing covers every
+    /// cached signal, so chunks only mark it and readers bring it up to date.
+    blur_stale: bool,
+    /// Undo/redo of edits to the config, manual keyframes, and blur regions; cleared
+    /// by `reset`.
+    history: EditHistory<EditState>,
+    /// What changed at each revision, for incremental re-renders. Kept across `reset`
+    /// so revisions only grow.
+    revisions: RevisionLog,
+    config: EngineConfig,
+    /// Signals ingested so far, kept so effects can be regenerated without re-feeding.
+    signals: SignalBatch,
+    /// Accumulated analysis across `process_signals` / `process_signals_chunk` calls.
+    analysis: AnalysisResult,
+    events_processed: u64,
+    processed_until: Timestamp,
+}
+
+/// What an engine edit can change, as kept for undo.
+#[derive(Debug, Clone, PartialEq)]
+struct EditState {
+    config: EngineConfig,
+    manual_keyframes: Vec<CameraKeyframe>,
+    scene_changes: Vec<Timestamp>,
+    blur_regions: Vec<BlurRegion>,
+    custom_effects: Vec<(String, Vec<CustomEffectSpec>)>,
 }
 
 #[wasm_bindgen]
@@ -44,38 +157,775 @@ impl Engine {
         let config: EngineConfig = serde_json::from_str(config_json)
             .map_err(|e| JsValue::from_str(&format!("Invalid config: {}", e)))?;
 
-        Ok(Engine {
-            cursor_tracker: CursorTracker::new(config.capture_mode),
-            focus_analyzer: FocusAnalyzer::new(),
-            camera_engine: CameraEngine::new(config.camera_settings),
-            effect_generator: EffectGenerator::new(config.effect_settings),
-        })
+        Ok(Engine::with_config(config))
+    }
+
+    /// Upgrade a saved config from an earlier version (see `migrate_engine_config`).
+    ///
+    /// # Arguments
+    /// * `config_json` - JSON EngineConfig of any supported version
+    ///
+    /// # Returns
+    /// JSON `{ config, report }`: the current-shape config and the MigrationReport
+    pub fn migrate_config(config_json: &str) -> Result<String, JsValue> {
+        let value: serde_json::Value = serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid config: {}", e)))?;
+        let (config, report) =
+            migrate_engine_config(value).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        serde_json::to_string(&serde_json::json!({ "config": config, "report": report }))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Create an engine from a plain JS config object (no JSON string round-trip).
+    pub fn from_object(
+        #[wasm_bindgen(unchecked_param_type = "EngineConfig")] config: JsValue,
+    ) -> Result<Engine, JsValue> {
+        let config: EngineConfig = from_js_object(config, "Invalid config")?;
+        Ok(Engine::with_config(config))
+    }
+
+    /// Save the whole editing session as a project document (see `Project`): config,
+    /// cached signals and analysis, manual keyframes, blur regions, custom effects, sync
+    /// anchors, and the edit.
+    ///
+    /// # Arguments
+    /// * `remapper_json` - JSON TimeRemapperConfig of the edit (`WasmTimeRemapper::to_json`),
+    ///   if there is one
+    ///
+    /// # Returns
+    /// Project JSON for `load`
+    pub fn save(&self, remapper_json: Option<String>) -> Result<String, JsValue> {
+        let remapper = remapper_json
+            .map(|json| serde_json::from_str::<TimeRemapperConfig>(&json))
+            .transpose()
+            .map_err(|e| JsValue::from_str(&format!("Invalid remapper config: {}", e)))?;
+
+        let mut analysis = self.analysis.clone();
+        if self.blur_stale {
+            analysis.blur_track = self.blur_track();
+        }
+        let project = Project {
+            version: PROJECT_VERSION,
+            config: self.config.clone(),
+            signals: self.signals.clone(),
+            analysis,
+            manual_keyframes: self.camera_engine.manual_keyframes().to_vec(),
+            scene_changes: self.camera_engine.scene_changes().to_vec(),
+            tracked_regions: self.focus_analyzer.tracked_regions().to_vec(),
+            blur_regions: self.blur_regions.clone(),
+            custom_effects: self.effect_generator.registered_custom().to_vec(),
+            sync_anchors: self.clock_sync.anchors().to_vec(),
+            remapper,
+            events_processed: self.events_processed,
+            processed_until: self.processed_until,
+        };
+        serde_json::to_string(&project)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Reopen a project saved by `save`: the analysis, hand edits, and tracked regions
+    /// come back as they were left. State carried between chunks (signal cleanup, pause
+    /// collapsing, scroll compensation, cursor smoothing, the focused element) is not
+    /// saved, so the loaded engine starts a new chunked session rather than continuing
+    /// the saved one. Older projects are migrated. The edit is the project's `remapper`
+    /// field, for `WasmTimeRemapper::from_object`.
+    pub fn load(project_json: &str) -> Result<Engine, JsValue> {
+        let project = Project::from_json(project_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid project: {}", e)))?;
+
+        let mut engine = Engine::with_config(project.config);
+        for (signal_ts, media_ts) in project.sync_anchors {
+            engine
+                .clock_sync
+                .add_anchor(signal_ts, media_ts)
+                .map_err(|e| JsValue::from_str(&format!("Invalid project: {}", e)))?;
+        }
+        engine
+            .camera_engine
+            .set_scene_changes(project.scene_changes);
+        engine
+            .focus_analyzer
+            .restore_tracks(project.tracked_regions);
+        engine.camera_engine.restore_keyframes(
+            project.analysis.camera_keyframes.clone(),
+            project.manual_keyframes,
+        );
+        for (name, specs) in project.custom_effects {
+            engine.effect_generator.register_custom(&name, specs);
+        }
+        engine.blur_regions = project.blur_regions;
+        engine.signals = project.signals;
+        engine.analysis = project.analysis;
+        engine.events_processed = project.events_processed;
+        engine.processed_until = project.processed_until;
+        Ok(engine)
     }
 
     /// Process a batch of input signals and return analysis results.
     /// This is the main entry point, designed as a batch call to reduce JS↔WASM overhead.
+    /// Each call analyzes a new recording: it starts with `reset`, so the user's edits to
+    /// the previous one (manual keyframes, scene changes, blur regions, custom effects)
+    /// and the undo history are discarded. Re-render edits with `regenerate_camera` /
+    /// `regenerate_effects` instead.
     pub fn process_signals(&mut self, signals_json: &str) -> Result<String, JsValue> {
         let signals: SignalBatch = serde_json::from_str(signals_json)
             .map_err(|e| JsValue::from_str(&format!("Invalid signals: {}", e)))?;
 
-        let cursor_track = self.cursor_tracker.process(&signals);
-        let focus_regions = self.focus_analyzer.analyze(&signals, &cursor_track);
-        let keyframes = self
+        let result = self.analyze(&signals);
+
+        serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Process a batch of input signals encoded as fixed-size binary records.
+    /// Skips JSON entirely on the input side; see `binary.rs` for the record layout.
+    /// JS passes a `Uint8Array` which is copied into WASM memory in one go.
+    pub fn process_signals_buffer(&mut self, buffer: &[u8]) -> Result<String, JsValue> {
+        let signals = decode_signal_buffer(buffer)
+            .map_err(|e| JsValue::from_str(&format!("Invalid signals: {}", e)))?;
+
+        let result = self.analyze(&signals);
+
+        serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Binary variant of `process_signals`: returns the `AnalysisResult` in the compact
+    /// layout documented on `encode_analysis_result` (a `Uint8Array` on the JS side).
+    pub fn process_signals_binary(&mut self, signals_json: &str) -> Result<Vec<u8>, JsValue> {
+        let signals: SignalBatch = serde_json::from_str(signals_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid signals: {}", e)))?;
+
+        let result = self.analyze(&signals);
+        Ok(encode_analysis_result(&result))
+    }
+
+    /// Object variant of `process_signals`: takes a `SignalBatch` object and
+    /// returns the `AnalysisResult` as a structured JS object.
+    #[wasm_bindgen(unchecked_return_type = "AnalysisResult")]
+    pub fn process_signals_object(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "SignalBatch")] signals: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let signals: SignalBatch = from_js_object(signals, "Invalid signals")?;
+        let result = self.analyze(&signals);
+        to_js_object(&result)
+    }
+
+    /// Merge several recording takes onto one timeline and analyze the result.
+    ///
+    /// # Arguments
+    /// * `takes_json` - JSON `TakeBatch`: `{ takes: [{ signals, offset_us, time_base_us }],
+    ///   overlap }` with takes in recording order
+    ///
+    /// # Returns
+    /// JSON `AnalysisResult`; events dropped where takes overlap are reported in
+    /// `warnings`
+    pub fn process_takes(&mut self, takes_json: &str) -> Result<String, JsValue> {
+        let takes: TakeBatch = serde_json::from_str(takes_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid takes: {}", e)))?;
+
+        let (signals, warnings) = merge_takes(&takes);
+        let mut result = self.analyze(&signals);
+        self.analysis.warnings.extend_from_slice(&warnings);
+        result.warnings.extend(warnings);
+
+        serde_json::to_string(&result)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Add focus regions from downscaled grayscale frames (screen/window capture only;
+    /// tab captures have better signals and ignore frames). Call `regenerate_camera`
+    /// afterwards to reframe.
+    ///
+    /// # Arguments
+    /// * `frames_json` - JSON array of `{ timestamp, width, height, pixels }`, in order
+    ///
+    /// # Returns
+    /// JSON array of the focus regions found
+    pub fn process_frames(&mut self, frames_json: &str) -> Result<String, JsValue> {
+        let frames: Vec<GrayFrame> = serde_json::from_str(frames_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid frames: {}", e)))?;
+        if self.config.capture_mode == CaptureMode::Tab {
+            return Ok("[]".to_string());
+        }
+
+        let regions = self
+            .focus_analyzer
+            .analyze_frames(frames)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        merge_by_timestamp(&mut self.analysis.focus_regions, &regions);
+        if let (Some(first), Some(last)) = (regions.first(), regions.last()) {
+            self.revisions
+                .bump(&[(Track::Focus, Some(span(first.timestamp, last.timestamp)))]);
+        }
+
+        serde_json::to_string(&regions)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Ingest a chunk of signals on top of previously processed chunks.
+    /// Cursor, focus, camera, and effect state carry over between calls, so a long
+    /// recording can be fed in slices without blocking the worker. Chunks must be in
+    /// timestamp order. Returns JSON `AnalysisChunk` with only the new portion.
+    pub fn process_signals_chunk(&mut self, signals_json: &str) -> Result<String, JsValue> {
+        let signals: SignalBatch = serde_json::from_str(signals_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid signals: {}", e)))?;
+
+        let chunk = self.analyze_chunk(&signals);
+
+        serde_json::to_string(&chunk)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Push a single input event (live preview during recording).
+    /// Runs the full `process_signals_chunk` pipeline on a one-event chunk (minus the
+    /// JSON delta), so each call pays its per-chunk cost: sanitizing, cursor tracking,
+    /// focus analysis, camera, and effects, plus inserting new focus regions into the
+    /// time-ordered cache. Read the resulting camera target with `current_viewport`.
+    pub fn push_event(&mut self, event_json: &str) -> Result<(), JsValue> {
+        let event: InputEvent = serde_json::from_str(event_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid event: {}", e)))?;
+
+        self.analyze_chunk(&SignalBatch {
+            events: vec![event],
+        });
+        Ok(())
+    }
+
+    /// Current camera target after the most recently pushed events.
+    /// Returns JSON with { center, zoom }.
+    pub fn current_viewport(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.camera_engine.current_viewport())
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Replace the engine configuration without discarding cached analysis.
+    /// Call `regenerate_camera` / `regenerate_effects` to apply the new settings to
+    /// the cached cursor track and focus regions. A changed capture mode or importance
+    /// weights apply to signals processed after this call. Privacy settings apply to the
+    /// blur track right away.
+    pub fn update_config(&mut self, config_json: &str) -> Result<(), JsValue> {
+        let config: EngineConfig = serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid config: {}", e)))?;
+
+        if config == self.config {
+            return Ok(());
+        }
+        self.begin_edit("Change settings");
+        self.apply_config(config);
+        Ok(())
+    }
+
+    /// Undo the last edit (manual keyframes, scene changes, blur regions, custom effects,
+    /// settings, or theme) and regenerate the camera and effects from the restored state.
+    ///
+    /// # Returns
+    /// Label of the undone edit (e.g. "Set keyframe"), or `undefined` if there was none
+    pub fn undo(&mut self) -> Result<Option<String>, JsValue> {
+        match self.history.undo(self.edit_state()) {
+            Some((label, state)) => self.restore_edit_state(state).map(|_| Some(label)),
+            None => Ok(None),
+        }
+    }
+
+    /// Redo the last undone edit, regenerating as `undo` does.
+    ///
+    /// # Returns
+    /// Label of the redone edit, or `undefined` if there was none
+    pub fn redo(&mut self) -> Result<Option<String>, JsValue> {
+        match self.history.redo(self.edit_state()) {
+            Some((label, state)) => self.restore_edit_state(state).map(|_| Some(label)),
+            None => Ok(None),
+        }
+    }
+
+    /// Label of the edit `undo` would undo (for "Undo Set keyframe" menus), if any.
+    pub fn undo_label(&self) -> Option<String> {
+        self.history.undo_label().map(str::to_string)
+    }
+
+    /// Label of the edit `redo` would redo, if any.
+    pub fn redo_label(&self) -> Option<String> {
+        self.history.redo_label().map(str::to_string)
+    }
+
+    /// Revision of the engine's state, bumped by every change to the cached tracks or
+    /// config. Compare against a saved value to debounce autosave.
+    pub fn revision(&self) -> u64 {
+        self.revisions.revision()
+    }
+
+    /// What changed after `revision`, so the UI re-renders only those parts.
+    ///
+    /// # Returns
+    /// JSON ChangeSet: `{ revision, changes: [{ track, ranges }] }`, where `ranges` are
+    /// the changed source-time ranges or `null` if the whole track may have changed.
+    /// Revisions too old to remember report every track as wholly changed.
+    pub fn changed_since(&self, revision: u64) -> Result<String, JsValue> {
+        serde_json::to_string(&self.revisions.changed_since(revision))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Regenerate camera keyframes from the cached cursor track and focus regions.
+    /// Returns JSON array of the new keyframes.
+    pub fn regenerate_camera(&mut self) -> Result<String, JsValue> {
+        let camera_track = self
+            .cursor_tracker
+            .camera_track(&self.analysis.cursor_track);
+        let keyframes = self.camera_engine.generate_keyframes_with_clicks(
+            &camera_track,
+            &self.analysis.focus_regions,
+            &click_times(&self.signals),
+        );
+        self.replace_keyframes(keyframes);
+
+        serde_json::to_string(&self.analysis.camera_keyframes)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Drop redundant camera keyframes (per `keyframe_epsilon` / `max_keyframes` in the
+    /// camera settings). Call once a chunked session finishes; regeneration does not
+    /// compact on its own. Returns JSON array of the remaining keyframes.
+    pub fn compact_camera(&mut self) -> Result<String, JsValue> {
+        self.camera_engine.compact_keyframes();
+        self.replace_keyframes(self.camera_engine.keyframes().to_vec());
+
+        serde_json::to_string(&self.analysis.camera_keyframes)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Pin the camera to a hand-edited viewport at a timestamp and regenerate the camera
+    /// around it. Manual keyframes survive later regeneration until cleared (or discarded
+    /// with the recording by `reset` / `process_signals`).
+    ///
+    /// # Arguments
+    /// * `timestamp_us` - Source timestamp in microseconds
+    /// * `viewport_json` - JSON Viewport (`{ center: { x, y }, zoom }`)
+    ///
+    /// # Returns
+    /// JSON array of the regenerated keyframes
+    pub fn set_manual_keyframe(
+        &mut self,
+        timestamp_us: u64,
+        viewport_json: &str,
+    ) -> Result<String, JsValue> {
+        let viewport: Viewport = serde_json::from_str(viewport_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid viewport: {}", e)))?;
+        let before = self.begin_edit("Set keyframe");
+        self.camera_engine
+            .set_manual_keyframe(Timestamp::from_micros(timestamp_us), viewport);
+        self.finish_edit(&before);
+        self.regenerate_camera()
+    }
+
+    /// Set a manual keyframe that locks the camera on the viewport for `hold_us` before
+    /// releasing it to the next keyframe, and regenerate the camera.
+    /// Returns JSON array of the regenerated keyframes.
+    pub fn set_manual_hold(
+        &mut self,
+        timestamp_us: u64,
+        viewport_json: &str,
+        hold_us: u64,
+    ) -> Result<String, JsValue> {
+        let viewport: Viewport = serde_json::from_str(viewport_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid viewport: {}", e)))?;
+        let before = self.begin_edit("Set hold");
+        self.camera_engine
+            .set_manual_hold(Timestamp::from_micros(timestamp_us), viewport, hold_us);
+        self.finish_edit(&before);
+        self.regenerate_camera()
+    }
+
+    /// Remove the manual keyframe at a timestamp (if any) and regenerate the camera.
+    /// Returns JSON array of the regenerated keyframes.
+    pub fn clear_manual_keyframe(&mut self, timestamp_us: u64) -> Result<String, JsValue> {
+        self.begin_edit("Clear keyframe");
+        if !self
             .camera_engine
-            .generate_keyframes(&cursor_track, &focus_regions);
-        let effects = self.effect_generator.generate(&signals, &cursor_track);
-
-        let result = AnalysisResult {
-            cursor_track,
-            focus_regions,
-            camera_keyframes: keyframes,
-            effect_tracks: effects,
+            .clear_manual_keyframe(Timestamp::from_micros(timestamp_us))
+        {
+            self.history.cancel();
+        }
+        self.regenerate_camera()
+    }
+
+    /// Register a sync anchor: signal time `signal_ts_us` (capture clock) is media time
+    /// `media_ts_us`. Signals processed afterwards are moved onto the media timeline by
+    /// piecewise-linear interpolation between anchors. Anchors survive `reset`.
+    pub fn add_sync_anchor(&mut self, signal_ts_us: u64, media_ts_us: u64) -> Result<(), JsValue> {
+        self.clock_sync
+            .add_anchor(
+                Timestamp::from_micros(signal_ts_us),
+                Timestamp::from_micros(media_ts_us),
+            )
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Remove all sync anchors; signal time is used as media time again.
+    pub fn clear_sync_anchors(&mut self) {
+        self.clock_sync.clear();
+    }
+
+    /// Regenerate effect tracks (and their audio cues) from the cached signals and
+    /// cursor track, plus registered custom effects, the progress bar, and the
+    /// watermark. The watermark
+    /// avoids content as the camera frames it, so call this after `regenerate_camera`.
+    /// Returns JSON of the new effect track.
+    pub fn regenerate_effects(&mut self) -> Result<String, JsValue> {
+        self.analysis.effect_tracks = self.effect_generator.generate_with_gestures(
+            &self.signals,
+            &self.analysis.cursor_track,
+            &self.analysis.drags,
+            &self.analysis.shakes,
+        );
+        self.analysis
+            .effect_tracks
+            .effects
+            .extend(self.effect_generator.custom_effects());
+        self.add_overlays();
+        self.analysis.audio_cues = self
+            .effect_generator
+            .audio_cues(&self.signals, &self.analysis.drags);
+        self.revisions
+            .bump(&[(Track::Effects, None), (Track::AudioCues, None)]);
+
+        serde_json::to_string(&self.analysis.effect_tracks)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Switch the effect theme and regenerate the effect track (as `regenerate_effects`);
+    /// the camera is left alone. Also updates the config's effect settings.
+    ///
+    /// # Arguments
+    /// * `theme` - `Standard`, `Light`, `Dark`, or `Brand`
+    /// * `brand_color` - Accent color for `Brand`; `None` keeps the current one
+    ///
+    /// # Returns
+    /// JSON of the new effect track
+    pub fn set_effect_theme(
+        &mut self,
+        theme: &str,
+        brand_color: Option<String>,
+    ) -> Result<String, JsValue> {
+        let theme: EffectTheme = serde_json::from_value(serde_json::Value::from(theme))
+            .map_err(|e| JsValue::from_str(&format!("Invalid theme: {}", e)))?;
+
+        let before = self.begin_edit("Change theme");
+        let settings = &mut self.config.effect_settings;
+        settings.theme = theme;
+        if let Some(color) = brand_color {
+            settings.brand_color = color;
+        }
+        if self.finish_edit(&before) {
+            self.effect_generator
+                .set_settings(self.config.effect_settings.clone());
+            self.revisions.bump(&[(Track::Config, None)]);
+        }
+        self.regenerate_effects()
+    }
+
+    /// Suggest speed ramps over idle spans (no clicks, focus changes, or scrolling and
+    /// little cursor motion) of the cached signals, for the user to accept or reject.
+    ///
+    /// # Arguments
+    /// * `settings_json` - JSON ActivitySettings; `{}` uses the defaults
+    ///
+    /// # Returns
+    /// JSON array of SpeedRampConfig, ready for a TimeRemapperConfig
+    pub fn suggest_speed_ramps(&self, settings_json: &str) -> Result<String, JsValue> {
+        let settings: ActivitySettings = serde_json::from_str(settings_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid activity settings: {}", e)))?;
+
+        let ramps = ActivityAnalyzer::new(settings)
+            .suggest_speed_ramps(&self.signals, &self.analysis.cursor_track);
+        serde_json::to_string(&ramps)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Blur user-drawn regions over the cached recording, moving them with scrolling and
+    /// window moves. Replaces earlier regions; `reset` (and so a new `process_signals`)
+    /// clears them. Automatic privacy blur (see `PrivacySettings`) is kept alongside.
+    ///
+    /// # Arguments
+    /// * `regions_json` - JSON array of BlurRegion (`id`, `bounds`, optional `start`,
+    ///   `end`, and `follow_scroll`)
+    ///
+    /// # Returns
+    /// JSON array of BlurSample, also kept as the result's `blur_track`
+    pub fn set_blur_regions(&mut self, regions_json: &str) -> Result<String, JsValue> {
+        let regions: Vec<BlurRegion> = serde_json::from_str(regions_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid blur regions: {}", e)))?;
+
+        if regions != self.blur_regions {
+            self.begin_edit("Edit blur regions");
+            self.blur_regions = regions;
+            self.revisions.bump(&[(Track::Blur, None)]);
+        }
+        self.refresh_blur_track();
+        serde_json::to_string(&self.analysis.blur_track)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Suggest cuts (dead air, static frames, loading spinners) over the cached signals.
+    ///
+    /// # Arguments
+    /// * `settings_json` - JSON SuggestSettings; `{}` uses the defaults
+    /// * `frame_timestamps_us` - Capture timestamps of hashed frames (may be empty)
+    /// * `frame_hashes` - 64-bit perceptual hash per frame, aligned with the timestamps
+    ///
+    /// # Returns
+    /// JSON array of CutSuggestion (`start_us`, `end_us`, `reason`, `confidence`)
+    pub fn suggest_cuts(
+        &self,
+        settings_json: &str,
+        frame_timestamps_us: Vec<u64>,
+        frame_hashes: Vec<u64>,
+    ) -> Result<String, JsValue> {
+        let settings: SuggestSettings = serde_json::from_str(settings_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid suggest settings: {}", e)))?;
+        let frames = frame_hashes_from(frame_timestamps_us, frame_hashes)?;
+
+        let cuts = EditSuggester::new(settings).suggest_cuts(
+            &self.signals,
+            &self.analysis.cursor_track,
+            &frames,
+        );
+        serde_json::to_string(&cuts)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Find scene changes in per-frame hashes (replacing earlier detections) and
+    /// regenerate the camera, which cuts back to full view at each one. The returned
+    /// chapter markers can go straight into a TimeRemapperConfig.
+    ///
+    /// # Arguments
+    /// * `settings_json` - JSON SceneSettings; `{}` uses the defaults
+    /// * `frame_timestamps_us` - Capture timestamps of hashed frames
+    /// * `frame_hashes` - 64-bit perceptual hash per frame, aligned with the timestamps
+    ///
+    /// # Returns
+    /// JSON SceneReport (`changes`, `markers`)
+    pub fn detect_scenes(
+        &mut self,
+        settings_json: &str,
+        frame_timestamps_us: Vec<u64>,
+        frame_hashes: Vec<u64>,
+    ) -> Result<String, JsValue> {
+        let settings: SceneSettings = serde_json::from_str(settings_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid scene settings: {}", e)))?;
+        let frames = frame_hashes_from(frame_timestamps_us, frame_hashes)?;
+
+        let changes = detect_scene_changes(&frames, &settings);
+        let before = self.begin_edit("Detect scenes");
+        self.camera_engine
+            .set_scene_changes(changes.iter().map(|change| change.timestamp).collect());
+        self.finish_edit(&before);
+        self.regenerate_camera()?;
+        let report = SceneReport {
+            markers: scene_markers(&changes),
+            changes,
         };
+        serde_json::to_string(&report)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Generate cuts and speed ramps that condense the cached recording to a target
+    /// duration while keeping clicks, focus changes, and scrolls at normal speed.
+    ///
+    /// # Arguments
+    /// * `settings_json` - JSON CondenseSettings; only `target_duration_us` is required
+    ///
+    /// # Returns
+    /// JSON CondenseResult with a ready-to-use TimeRemapperConfig and a summary of what
+    /// was dropped and accelerated
+    pub fn condense_to_duration(&self, settings_json: &str) -> Result<String, JsValue> {
+        let settings: CondenseSettings = serde_json::from_str(settings_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid condense settings: {}", e)))?;
 
+        let result = EditSuggester::new(SuggestSettings::default()).condense(
+            &self.signals,
+            &self.analysis.cursor_track,
+            &settings,
+        );
         serde_json::to_string(&result)
             .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
     }
 
+    /// Cached cursor track from the last analysis, as JSON.
+    pub fn get_cursor_track(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.analysis.cursor_track)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Cached cursor points with `start_us <= timestamp < end_us`, as JSON.
+    /// Binary-searched, so the timeline can fetch just what's visible.
+    pub fn get_cursor_points_in(&self, start_us: u64, end_us: u64) -> Result<String, JsValue> {
+        let points = time_slice(&self.analysis.cursor_track, start_us, end_us, |point| {
+            point.timestamp
+        });
+        serde_json::to_string(points)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Velocity and acceleration along the cached cursor track, as JSON
+    /// (see `derive_motion`).
+    pub fn get_cursor_motion(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&derive_motion(&self.analysis.cursor_track))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Cached cursor track reduced to a lightweight polyline, as JSON
+    /// (see `simplify_path`).
+    ///
+    /// # Arguments
+    /// * `tolerance` - Maximum deviation from the full path, in normalized units
+    pub fn get_simplified_cursor_path(&self, tolerance: f32) -> Result<String, JsValue> {
+        serde_json::to_string(&simplify_path(&self.analysis.cursor_track, tolerance))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Focus regions merged across time, with stable ids and start/end times, as JSON.
+    /// Each focus region's `track_id` points into this list.
+    pub fn get_tracked_regions(&self) -> Result<String, JsValue> {
+        serde_json::to_string(self.focus_analyzer.tracked_regions())
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Register extension-defined effects under `name`, replacing any earlier ones with
+    /// that name. They join the effect track from the next `regenerate_effects` and are
+    /// discarded by `reset` (and so by a new `process_signals`).
+    ///
+    /// # Arguments
+    /// * `name` - Effect name the renderer dispatches on, e.g. "confetti"
+    /// * `effects_json` - JSON array of CustomEffectSpec
+    ///   (`timestamp`, `duration_us`, `position`, optional `params_json` and `style`)
+    pub fn register_custom_effects(
+        &mut self,
+        name: &str,
+        effects_json: &str,
+    ) -> Result<(), JsValue> {
+        let specs: Vec<CustomEffectSpec> = serde_json::from_str(effects_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid custom effects: {}", e)))?;
+        let before = self.begin_edit("Add custom effects");
+        self.effect_generator.register_custom(name, specs);
+        if self.finish_edit(&before) {
+            self.revisions.bump(&[(Track::Effects, None)]);
+        }
+        Ok(())
+    }
+
+    /// Remove the custom effects registered under `name`. Takes effect on the next
+    /// `regenerate_effects`.
+    pub fn clear_custom_effects(&mut self, name: &str) {
+        let before = self.begin_edit("Remove custom effects");
+        self.effect_generator.clear_custom(Some(name));
+        if self.finish_edit(&before) {
+            self.revisions.bump(&[(Track::Effects, None)]);
+        }
+    }
+
+    /// Privacy blur over the recording so far, as JSON array of BlurSample. Brings the
+    /// track up to date with signals pushed since the last call.
+    pub fn get_blur_track(&mut self) -> Result<String, JsValue> {
+        if self.blur_stale {
+            self.refresh_blur_track();
+        }
+        serde_json::to_string(&self.analysis.blur_track)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Click sound cues from the last analysis, as JSON (see `regenerate_effects`).
+    pub fn get_audio_cues(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.analysis.audio_cues)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Drag gestures found by the last analysis, as JSON.
+    pub fn get_drags(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.analysis.drags)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Pinch gestures found by the last analysis, as JSON.
+    pub fn get_pinches(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.analysis.pinches)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Cursor shakes found by the last analysis, as JSON.
+    pub fn get_shakes(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.analysis.shakes)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Interaction sessions (clustered clicks) found by the last analysis, as JSON.
+    /// Sessions do not span `process_signals_chunk` calls.
+    pub fn get_interaction_sessions(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.analysis.sessions)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Problems fixed in the signals of the last analysis (out-of-order, duplicate, or
+    /// late events), as JSON.
+    pub fn get_warnings(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.analysis.warnings)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Cached focus regions from the last analysis, as JSON.
+    pub fn get_focus_regions(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.analysis.focus_regions)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Cached focus regions with `start_us <= timestamp < end_us`, as JSON.
+    /// Binary-searched, so the timeline can fetch just what's visible.
+    pub fn get_focus_regions_in(&self, start_us: u64, end_us: u64) -> Result<String, JsValue> {
+        let regions = time_slice(&self.analysis.focus_regions, start_us, end_us, |region| {
+            region.timestamp
+        });
+        serde_json::to_string(regions)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Cached camera keyframes from the last analysis or regeneration, as JSON.
+    pub fn get_keyframes(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.analysis.camera_keyframes)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Cached effect track from the last analysis or regeneration, as JSON.
+    pub fn get_effect_tracks(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.analysis.effect_tracks)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Discard accumulated analysis and start a new chunked session. The user's edits
+    /// (manual keyframes, scene changes, blur regions, custom effects) and the undo
+    /// history belong to the old recording and are discarded too; sync anchors and the
+    /// config are kept.
+    pub fn reset(&mut self) {
+        self.signal_sanitizer.reset();
+        self.pause_collapser.reset();
+        self.scroll_compensator.reset();
+        self.cursor_tracker.reset();
+        self.focus_analyzer.reset();
+        self.camera_engine.reset();
+        self.camera_engine.clear_manual_keyframes();
+        self.camera_engine.set_scene_changes(Vec::new());
+        self.effect_generator.clear_custom(None);
+        self.blur_regions.clear();
+        self.blur_stale = false;
+        self.history.clear();
+        self.signals.events.clear();
+        self.analysis = AnalysisResult::default();
+        self.events_processed = 0;
+        self.processed_until = Timestamp::default();
+        self.revisions.bump(&Track::ALL.map(|track| (track, None)));
+    }
+
     /// Apply camera transform to get viewport for a given timestamp.
     /// Returns JSON with { x, y, width, height, zoom } normalized 0-1.
     pub fn get_viewport_at(&self, timestamp_us: u64) -> Result<String, JsValue> {
@@ -85,6 +935,582 @@ impl Engine {
         serde_json::to_string(&viewport)
             .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
     }
+
+    /// Interpolated viewport plus every effect active at the timestamp (with progress),
+    /// so the renderer needs one WASM crossing per frame. Returns JSON `RenderState`.
+    pub fn get_render_state_at(&self, timestamp_us: u64) -> Result<String, JsValue> {
+        let ts = Timestamp::from_micros(timestamp_us);
+        let state = RenderState {
+            timestamp: ts,
+            viewport: self.camera_engine.get_viewport_at(ts),
+            active_effects: self.analysis.effect_tracks.active_at(ts),
+        };
+
+        serde_json::to_string(&state)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Animated state (progress, opacity, scale) of one effect at a timestamp.
+    ///
+    /// # Arguments
+    /// * `effect_id` - Index of the effect in the effect track
+    /// * `timestamp_us` - Query time in microseconds
+    ///
+    /// # Returns
+    /// JSON `EffectState`, or `null` while the effect isn't showing
+    pub fn get_effect_state_at(
+        &self,
+        effect_id: usize,
+        timestamp_us: u64,
+    ) -> Result<String, JsValue> {
+        let effect = self
+            .analysis
+            .effect_tracks
+            .effects
+            .get(effect_id)
+            .ok_or_else(|| JsValue::from_str(&format!("No effect with id {}", effect_id)))?;
+
+        serde_json::to_string(&effect.state_at(Timestamp::from_micros(timestamp_us)))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Bake the camera track for export: one `[center_x, center_y, zoom]` triple per
+    /// frame at `fps`, covering `duration_us` from the start of the recording.
+    /// Returned as a `Float32Array` so the export loop just indexes it.
+    pub fn bake_camera_track(&self, fps: f64, duration_us: u64) -> Result<Vec<f32>, JsValue> {
+        if fps <= 0.0 || !fps.is_finite() {
+            return Err(JsValue::from_str(&format!("Invalid fps: {}", fps)));
+        }
+
+        Ok(self
+            .camera_engine
+            .bake_track(fps, Timestamp::from_micros(0), duration_us))
+    }
+
+    /// Bake the synthetic cursor for export, for renderers that hide the captured
+    /// cursor and draw their own: `SYNTHETIC_CURSOR_STRIDE` floats per frame at `fps`
+    /// (`x, y, scale, shape, visible`), covering `duration_us` from the start of the
+    /// recording.
+    ///
+    /// # Arguments
+    /// * `settings_json` - JSON SyntheticCursorSettings; `{}` uses the defaults
+    /// * `fps` - Export frame rate
+    /// * `duration_us` - Length to bake in microseconds
+    pub fn bake_synthetic_cursor(
+        &self,
+        settings_json: &str,
+        fps: f64,
+        duration_us: u64,
+    ) -> Result<Vec<f32>, JsValue> {
+        let settings: SyntheticCursorSettings = serde_json::from_str(settings_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid cursor settings: {}", e)))?;
+        if fps <= 0.0 || !fps.is_finite() {
+            return Err(JsValue::from_str(&format!("Invalid fps: {}", fps)));
+        }
+
+        let frames = synthetic_cursor(
+            &self.analysis.cursor_track,
+            &self.signals,
+            &settings,
+            fps,
+            Timestamp::from_micros(0),
+            duration_us,
+        );
+        Ok(bake_synthetic_cursor(&frames))
+    }
+
+    /// FFmpeg `-filter_complex` graph that re-exports the original recording with the
+    /// camera and an edit applied (see `ffmpeg_filter_graph`), for headless exports.
+    ///
+    /// # Arguments
+    /// * `remapper_json` - JSON TimeRemapperConfig of the edit
+    /// * `fps` - Export frame rate
+    /// * `out_w` / `out_h` - Output size in pixels
+    ///
+    /// # Returns
+    /// Filter graph reading `[0:v]` and producing `[v]`
+    pub fn ffmpeg_filter_graph(
+        &self,
+        remapper_json: &str,
+        fps: f64,
+        out_w: u32,
+        out_h: u32,
+    ) -> Result<String, JsValue> {
+        let config: TimeRemapperConfig = serde_json::from_str(remapper_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid remapper config: {}", e)))?;
+        let remapper =
+            TimeRemapper::try_from_config(config).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        if fps <= 0.0 || !fps.is_finite() {
+            return Err(JsValue::from_str(&format!("Invalid fps: {}", fps)));
+        }
+
+        Ok(ffmpeg_filter_graph(
+            &self.camera_engine,
+            &remapper,
+            fps,
+            out_w,
+            out_h,
+        ))
+    }
+
+    /// Camera track as a CSS `@keyframes` transform animation for DOM previews (see
+    /// `camera_keyframes_css`).
+    ///
+    /// # Arguments
+    /// * `name` - Animation and class name
+    /// * `duration_us` - Animation length; shorter than the track means the track's end
+    ///
+    /// # Returns
+    /// CSS text with the `@keyframes` rule and a `.name` rule that plays it
+    pub fn export_camera_css(&self, name: &str, duration_us: u64) -> String {
+        camera_keyframes_css(
+            self.camera_engine.keyframes(),
+            name,
+            Timestamp::from_micros(duration_us),
+        )
+    }
+
+    /// Captions of what was typed into focused text fields, on the export timeline of
+    /// the given edit (see `typing_captions`). Sensitive fields are skipped as set in
+    /// the privacy settings.
+    ///
+    /// # Arguments
+    /// * `remapper_json` - JSON TimeRemapperConfig of the edit
+    /// * `format` - `"Srt"` or `"Vtt"`
+    ///
+    /// # Returns
+    /// Caption file text
+    pub fn export_typing_captions(
+        &self,
+        remapper_json: &str,
+        format: &str,
+    ) -> Result<String, JsValue> {
+        let config: TimeRemapperConfig = serde_json::from_str(remapper_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid remapper config: {}", e)))?;
+        let remapper =
+            TimeRemapper::try_from_config(config).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let format: CaptionFormat = serde_json::from_value(serde_json::Value::from(format))
+            .map_err(|e| JsValue::from_str(&format!("Invalid caption format: {}", e)))?;
+
+        Ok(typing_captions(
+            &self.signals,
+            &self.config.privacy,
+            &remapper,
+            format,
+        ))
+    }
+
+    /// Camera and click rings as After Effects keyframe data (see
+    /// `after_effects_export`), for pasting onto layers in a `width`x`height` comp.
+    ///
+    /// # Arguments
+    /// * `fps` - Comp frame rate
+    /// * `duration_us` - Recording time to cover from 0
+    /// * `width`, `height` - Comp size in pixels
+    ///
+    /// # Returns
+    /// JSON `{ camera, click_rings }` of clipboard-ready keyframe text
+    pub fn export_after_effects(
+        &self,
+        fps: f64,
+        duration_us: u64,
+        width: u32,
+        height: u32,
+    ) -> Result<String, JsValue> {
+        if fps <= 0.0 || !fps.is_finite() {
+            return Err(JsValue::from_str(&format!("Invalid fps: {}", fps)));
+        }
+
+        let export = after_effects_export(
+            &self.camera_engine,
+            &self.analysis.effect_tracks,
+            fps,
+            duration_us,
+            width,
+            height,
+        );
+        serde_json::to_string(&export)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Integer source-pixel crop for the viewport at a timestamp, for scaling a
+    /// `source_w`x`source_h` recording to `out_w`x`out_h`. Width, height, and offset
+    /// are even and the crop has the output's aspect.
+    /// Returns JSON `{ x, y, width, height }`.
+    pub fn get_crop_rect_at(
+        &self,
+        timestamp_us: u64,
+        source_w: u32,
+        source_h: u32,
+        out_w: u32,
+        out_h: u32,
+    ) -> Result<String, JsValue> {
+        let viewport = self
+            .camera_engine
+            .get_viewport_at(Timestamp::from_micros(timestamp_us));
+        let crop = viewport
+            .crop_rect(source_w, source_h, out_w, out_h)
+            .ok_or_else(|| {
+                JsValue::from_str(&format!(
+                    "Invalid crop dimensions: {}x{} to {}x{}",
+                    source_w, source_h, out_w, out_h
+                ))
+            })?;
+
+        serde_json::to_string(&crop)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Viewport at a timestamp as a column-major 3x3 matrix mapping output UVs to source
+    /// UVs (see `Viewport::uv_matrix`), for WebGL/WebGPU compositors.
+    pub fn get_viewport_matrix_at(&self, timestamp_us: u64) -> Vec<f32> {
+        let ts = Timestamp::from_micros(timestamp_us);
+        self.camera_engine.get_viewport_at(ts).uv_matrix().to_vec()
+    }
+
+    /// Matrix variant of `bake_camera_track`: 9 floats per frame (`BAKED_MATRIX_STRIDE`).
+    pub fn bake_camera_matrix_track(
+        &self,
+        fps: f64,
+        duration_us: u64,
+    ) -> Result<Vec<f32>, JsValue> {
+        if fps <= 0.0 || !fps.is_finite() {
+            return Err(JsValue::from_str(&format!("Invalid fps: {}", fps)));
+        }
+
+        Ok(self
+            .camera_engine
+            .bake_matrix_track(fps, Timestamp::from_micros(0), duration_us))
+    }
+
+    /// Object variant of `get_viewport_at`: returns the viewport as a JS object.
+    #[wasm_bindgen(unchecked_return_type = "Viewport")]
+    pub fn get_viewport_at_object(&self, timestamp_us: u64) -> Result<JsValue, JsValue> {
+        let ts = Timestamp::from_micros(timestamp_us);
+        to_js_object(&self.camera_engine.get_viewport_at(ts))
+    }
+}
+
+impl Engine {
+    /// Switch to `config`, updating each component's settings (see `update_config`).
+    fn apply_config(&mut self, config: EngineConfig) {
+        if config.capture_mode != self.config.capture_mode {
+            self.cursor_tracker =
+                CursorTracker::with_settings(config.capture_mode, config.cursor_settings.clone());
+        } else {
+            self.cursor_tracker
+                .set_settings(config.cursor_settings.clone());
+        }
+        self.scroll_compensator
+            .set_settings(config.scroll_settings.clone());
+        self.focus_analyzer
+            .set_weights(config.importance_weights.clone());
+        self.focus_analyzer
+            .set_scroll_settings(config.scroll_settings.clone());
+        self.camera_engine
+            .set_settings(config.camera_settings.clone());
+        self.effect_generator
+            .set_settings(config.effect_settings.clone());
+        self.config = config;
+        self.revisions
+            .bump(&[(Track::Config, None), (Track::Blur, None)]);
+        self.refresh_blur_track();
+    }
+
+    /// The state undo and redo move between.
+    fn edit_state(&self) -> EditState {
+        EditState {
+            config: self.config.clone(),
+            manual_keyframes: self.camera_engine.manual_keyframes().to_vec(),
+            scene_changes: self.camera_engine.scene_changes().to_vec(),
+            blur_regions: self.blur_regions.clone(),
+            custom_effects: self.effect_generator.registered_custom().to_vec(),
+        }
+    }
+
+    /// Record an edit about to be made, for undo. Returns the state before it.
+    fn begin_edit(&mut self, label: &str) -> EditState {
+        let before = self.edit_state();
+        self.history.record(label, before.clone());
+        before
+    }
+
+    /// Drop the edit begun with `begin_edit` if it left the state at `before`. Returns
+    /// whether it changed anything.
+    fn finish_edit(&mut self, before: &EditState) -> bool {
+        let changed = self.edit_state() != *before;
+        if !changed {
+            self.history.cancel();
+        }
+        changed
+    }
+
+    fn restore_edit_state(&mut self, state: EditState) -> Result<(), JsValue> {
+        self.camera_engine
+            .set_manual_keyframes(state.manual_keyframes);
+        self.camera_engine.set_scene_changes(state.scene_changes);
+        self.blur_regions = state.blur_regions;
+        self.effect_generator.clear_custom(None);
+        for (name, specs) in state.custom_effects {
+            self.effect_generator.register_custom(&name, specs);
+        }
+        self.apply_config(state.config);
+        self.regenerate_camera()?;
+        self.regenerate_effects()?;
+        Ok(())
+    }
+
+    fn with_config(config: EngineConfig) -> Engine {
+        let mut focus_analyzer = FocusAnalyzer::with_weights(config.importance_weights.clone());
+        focus_analyzer.set_scroll_settings(config.scroll_settings.clone());
+        Engine {
+            clock_sync: ClockSync::new(),
+            signal_sanitizer: SignalSanitizer::new(),
+            pause_collapser: PauseCollapser::new(),
+            scroll_compensator: ScrollCompensator::new(config.scroll_settings.clone()),
+            cursor_tracker: CursorTracker::with_settings(
+                config.capture_mode,
+                config.cursor_settings.clone(),
+            ),
+            focus_analyzer,
+            camera_engine: CameraEngine::new(config.camera_settings.clone()),
+            effect_generator: EffectGenerator::new(config.effect_settings.clone()),
+            blur_regions: Vec::new(),
+            blur_stale: false,
+            history: EditHistory::default(),
+            revisions: RevisionLog::new(),
+            config,
+            signals: SignalBatch { events: Vec::new() },
+            analysis: AnalysisResult::default(),
+            events_processed: 0,
+            processed_until: Timestamp::default(),
+        }
+    }
+
+    /// Recompute the blur track from the user's regions and the automatic ones, over all
+    /// cached signals (a region's end can depend on later focus changes).
+    fn refresh_blur_track(&mut self) {
+        self.analysis.blur_track = self.blur_track();
+        self.blur_stale = false;
+    }
+
+    fn blur_track(&self) -> Vec<BlurSample> {
+        let mut regions = self.blur_regions.clone();
+        regions.extend(sensitive_regions(&self.signals, &self.config.privacy));
+        track_blur_regions(&regions, &self.signals, &self.config.scroll_settings)
+    }
+
+    /// Replace the cached camera keyframes, recording the span where they differ.
+    fn replace_keyframes(&mut self, keyframes: Vec<CameraKeyframe>) {
+        let changed = changed_span(&self.analysis.camera_keyframes, &keyframes, |keyframe| {
+            span(keyframe.timestamp, keyframe.timestamp)
+        });
+        self.analysis.camera_keyframes = keyframes;
+        if changed.is_some() {
+            self.revisions.bump(&[(Track::Camera, changed)]);
+        }
+    }
+
+    /// Run the full analysis pipeline over a decoded signal batch.
+    /// Replaces any previously accumulated analysis.
+    fn analyze(&mut self, signals: &SignalBatch) -> AnalysisResult {
+        self.reset();
+        self.analyze_chunk(signals);
+        self.add_overlays();
+        if self.blur_stale {
+            self.refresh_blur_track();
+        }
+        self.analysis.clone()
+    }
+
+    /// Add the overlays that span the whole recording so far (progress bar, watermark).
+    /// Chunked sessions get them from `regenerate_effects`.
+    fn add_overlays(&mut self) {
+        let start = self
+            .signals
+            .events
+            .first()
+            .map_or(Timestamp::default(), |event| event.timestamp);
+        let effects = &mut self.analysis.effect_tracks.effects;
+        effects.extend(
+            self.effect_generator
+                .progress_bar(start, self.processed_until),
+        );
+        if let Some(settings) = &self.config.effect_settings.watermark {
+            effects.extend(place_watermark(
+                settings,
+                &self.camera_engine,
+                &self.analysis.focus_regions,
+                start,
+                self.processed_until,
+            ));
+        }
+    }
+
+    /// Run the analysis pipeline over one chunk and append it to the accumulated result.
+    fn analyze_chunk(&mut self, signals: &SignalBatch) -> AnalysisChunk {
+        let previous_keyframe = self
+            .analysis
+            .camera_keyframes
+            .last()
+            .map(|keyframe| keyframe.timestamp);
+        let corrected = self.clock_sync.correct(signals);
+        let (sanitized, mut warnings) = self.signal_sanitizer.sanitize(&corrected);
+        let (collapsed, pause_warnings) = self.pause_collapser.collapse(&sanitized);
+        warnings.extend(pause_warnings);
+        let compensated = self.scroll_compensator.compensate(&collapsed);
+        let signals = &*compensated;
+        let cursor_track = self.cursor_tracker.process(signals);
+        let drags = self.cursor_tracker.take_drags();
+        let pinches = self.cursor_tracker.take_pinches();
+        let shakes = self.cursor_tracker.take_shakes();
+        let sessions = cluster_clicks(&signals.events, &self.config.session_settings);
+        let focus_regions = self.focus_analyzer.analyze_with_gestures(
+            signals,
+            &cursor_track,
+            &drags,
+            &pinches,
+            &shakes,
+            &sessions,
+        );
+        let keyframes = self.camera_engine.extend_keyframes_with_clicks(
+            &self.cursor_tracker.camera_track(&cursor_track),
+            &focus_regions,
+            &click_times(signals),
+        );
+        let effects =
+            self.effect_generator
+                .generate_with_gestures(signals, &cursor_track, &drags, &shakes);
+        let audio_cues = self.effect_generator.audio_cues(signals, &drags);
+
+        self.analysis.cursor_track.extend_from_slice(&cursor_track);
+        // Kept in time order for range queries.
+        merge_by_timestamp(&mut self.analysis.focus_regions, &focus_regions);
+        self.analysis.camera_keyframes.extend_from_slice(&keyframes);
+        self.analysis.drags.extend_from_slice(&drags);
+        self.analysis.pinches.extend_from_slice(&pinches);
+        self.analysis.shakes.extend_from_slice(&shakes);
+        self.analysis.sessions.extend_from_slice(&sessions);
+        self.analysis.warnings.extend_from_slice(&warnings);
+        self.analysis.audio_cues.extend_from_slice(&audio_cues);
+        self.analysis
+            .effect_tracks
+            .effects
+            .extend_from_slice(&effects.effects);
+        self.signals.events.extend_from_slice(&signals.events);
+        self.events_processed += signals.events.len() as u64;
+        self.blur_stale |= self.config.privacy.auto_blur || !self.blur_regions.is_empty();
+
+        if let Some(latest) = signals.events.iter().map(|e| e.timestamp).max() {
+            self.processed_until = self.processed_until.max(latest);
+        }
+        let earliest = signals.events.iter().map(|e| e.timestamp).min();
+        if let Some(earliest) = earliest {
+            let chunk = Some(span(earliest, self.processed_until));
+            // New keyframes also change the ease out of the last old one.
+            let camera = span(
+                previous_keyframe.map_or(earliest, |ts| ts.min(earliest)),
+                self.processed_until,
+            );
+            let mut changes = vec![
+                (Track::Signals, chunk),
+                (Track::Cursor, chunk),
+                (Track::Focus, chunk),
+                (Track::Camera, Some(camera)),
+                (Track::Effects, chunk),
+                (Track::AudioCues, chunk),
+            ];
+            if self.blur_stale {
+                // A region's end can depend on later focus changes.
+                changes.push((Track::Blur, None));
+            }
+            self.revisions.bump(&changes);
+        }
+
+        AnalysisChunk {
+            delta: AnalysisResult {
+                cursor_track,
+                focus_regions,
+                camera_keyframes: keyframes,
+                effect_tracks: effects,
+                drags,
+                pinches,
+                shakes,
+                sessions,
+                warnings,
+                audio_cues,
+                blur_track: Vec::new(),
+            },
+            events_processed: self.events_processed,
+            processed_until: self.processed_until,
+        }
+    }
+}
+
+/// Pair frame timestamps with their hashes.
+fn frame_hashes_from(timestamps_us: Vec<u64>, hashes: Vec<u64>) -> Result<Vec<FrameHash>, JsValue> {
+    if timestamps_us.len() != hashes.len() {
+        return Err(JsValue::from_str(&format!(
+            "Frame hash count {} does not match timestamp count {}",
+            hashes.len(),
+            timestamps_us.len()
+        )));
+    }
+    Ok(timestamps_us
+        .into_iter()
+        .zip(hashes)
+        .map(|(timestamp_us, hash)| FrameHash {
+            timestamp: Timestamp::from_micros(timestamp_us),
+            hash,
+        })
+        .collect())
+}
+
+/// The items of a time-sorted slice with `start_us <= timestamp < end_us`.
+fn time_slice<T>(
+    items: &[T],
+    start_us: u64,
+    end_us: u64,
+    timestamp: impl Fn(&T) -> Timestamp,
+) -> &[T] {
+    let start = items.partition_point(|item| timestamp(item).as_micros() < start_us);
+    let end = items.partition_point(|item| timestamp(item).as_micros() < end_us);
+    &items[start..end.max(start)]
+}
+
+/// Merge `new` regions into time-sorted `regions`, keeping them sorted (earlier
+/// regions first on ties). Only the regions after the first new one are moved, so
+/// appending a chunk costs its own length.
+fn merge_by_timestamp(regions: &mut Vec<FocusRegion>, new: &[FocusRegion]) {
+    let mut new = new.to_vec();
+    new.sort_by_key(|region| region.timestamp);
+    let Some(first) = new.first() else {
+        return;
+    };
+    let at = regions.partition_point(|region| region.timestamp <= first.timestamp);
+    let mut tail = regions.split_off(at).into_iter().peekable();
+    let mut new = new.into_iter().peekable();
+    while let (Some(old), Some(next)) = (tail.peek(), new.peek()) {
+        if old.timestamp <= next.timestamp {
+            regions.extend(tail.next());
+        } else {
+            regions.extend(new.next());
+        }
+    }
+    regions.extend(tail);
+    regions.extend(new);
+}
+
+/// Sorted timestamps of the clicks in a batch (targets for camera look-ahead).
+fn click_times(signals: &SignalBatch) -> Vec<Timestamp> {
+    let mut clicks: Vec<Timestamp> = signals
+        .events
+        .iter()
+        .filter(|event| matches!(event.event_type, EventType::MouseClick { .. }))
+        .map(|event| event.timestamp)
+        .collect();
+    clicks.sort();
+    clicks
 }
 
 #[cfg(test)]
@@ -97,4 +1523,572 @@ mod tests {
         let engine = Engine::new(config);
         assert!(engine.is_ok());
     }
+
+    #[test]
+    fn chunks_accumulate_to_batch_result() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let events: Vec<String> = (0..6)
+            .map(|i| {
+                format!(
+                    r#"{{"timestamp":{},"event_type":{{"type":"MouseMove","position":{{"x":{},"y":0.5}}}}}}"#,
+                    i * 600_000,
+                    0.15 * i as f32
+                )
+            })
+            .collect();
+        let batch_json = format!(r#"{{"events":[{}]}}"#, events.join(","));
+        let first_json = format!(r#"{{"events":[{}]}}"#, events[..2].join(","));
+        let second_json = format!(r#"{{"events":[{}]}}"#, events[2..].join(","));
+
+        let mut batch_engine = Engine::new(config).unwrap();
+        let batch: AnalysisResult =
+            serde_json::from_str(&batch_engine.process_signals(&batch_json).unwrap()).unwrap();
+
+        let mut chunk_engine = Engine::new(config).unwrap();
+        let first: AnalysisChunk =
+            serde_json::from_str(&chunk_engine.process_signals_chunk(&first_json).unwrap())
+                .unwrap();
+        let second: AnalysisChunk =
+            serde_json::from_str(&chunk_engine.process_signals_chunk(&second_json).unwrap())
+                .unwrap();
+
+        assert_eq!(first.delta.cursor_track.len(), 2);
+        assert_eq!(second.events_processed, 6);
+        assert_eq!(second.processed_until.as_micros(), 3_000_000);
+        assert_eq!(
+            first.delta.camera_keyframes.len() + second.delta.camera_keyframes.len(),
+            batch.camera_keyframes.len()
+        );
+    }
+
+    #[test]
+    fn push_event_updates_current_viewport() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let mut engine = Engine::new(config).unwrap();
+
+        engine
+            .push_event(
+                r#"{"timestamp":0,"event_type":{"type":"MouseMove","position":{"x":0.5,"y":0.5}}}"#,
+            )
+            .unwrap();
+        engine
+            .push_event(r#"{"timestamp":600000,"event_type":{"type":"MouseClick","position":{"x":0.6,"y":0.5},"button":0}}"#)
+            .unwrap();
+
+        let viewport: Viewport = serde_json::from_str(&engine.current_viewport().unwrap()).unwrap();
+        assert!(viewport.zoom > 1.0);
+        assert!(viewport.center.x > 0.5);
+    }
+
+    #[test]
+    fn resampled_live_preview_follows_focus_keyframes() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{},
+            "cursor_settings":{"resample_hz":60}}"#;
+        let mut engine = Engine::new(config).unwrap();
+        // Resampled cursor points arrive a push late, behind the selection's keyframe.
+        for event in [
+            r#"{"timestamp":12190773,"event_type":{"type":"MouseDown","position":{"x":0.3,"y":0.3},"button":0}}"#,
+            r#"{"timestamp":12406315,"event_type":{"type":"TextSelection","bounds":{"x":0.6,"y":0.6,"width":0.2,"height":0.1}}}"#,
+            r#"{"timestamp":13704683,"event_type":{"type":"MouseMove","position":{"x":0.5,"y":0.5}}}"#,
+        ] {
+            engine.push_event(event).unwrap();
+        }
+        assert!(!engine.analysis.camera_keyframes.is_empty());
+
+        // With a low dwell weight the camera is still zoomed when the points trailing the
+        // selection are framed, so they run the held-focus check against it.
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{},
+            "cursor_settings":{"resample_hz":60},"importance_weights":{"dwell":0.5}}"#;
+        let mut engine = Engine::new(config).unwrap();
+        for event in [
+            r#"{"timestamp":2598281,"event_type":{"type":"MouseMove","position":{"x":0.4,"y":0.2}}}"#,
+            r#"{"timestamp":3763035,"event_type":{"type":"MouseClick","position":{"x":0.6,"y":0.9},"button":0}}"#,
+            r#"{"timestamp":4550208,"event_type":{"type":"MouseMove","position":{"x":0,"y":0}}}"#,
+            r#"{"timestamp":5005143,"event_type":{"type":"TextSelection","bounds":{"x":0.64,"y":0.08,"width":0.1,"height":0.1}}}"#,
+            r#"{"timestamp":5260538,"event_type":{"type":"MouseMove","position":{"x":0.2,"y":0.4}}}"#,
+        ] {
+            engine.push_event(event).unwrap();
+        }
+        let selection = Timestamp::from_micros(5005143);
+        assert!(engine
+            .analysis
+            .camera_keyframes
+            .iter()
+            .any(|kf| kf.timestamp < selection && kf.viewport.zoom > 1.0));
+    }
+
+    #[test]
+    fn focus_regions_merge_in_time_order() {
+        let region = |ts: u64, importance: f32| FocusRegion {
+            timestamp: Timestamp::from_micros(ts),
+            bounds: NormalizedRect::new(0.0, 0.0, 0.5, 0.5),
+            importance,
+            element: None,
+            track_id: None,
+        };
+        let mut regions = vec![region(1, 0.0), region(3, 0.0), region(5, 0.0)];
+        merge_by_timestamp(
+            &mut regions,
+            &[region(6, 1.0), region(3, 1.0), region(2, 1.0)],
+        );
+
+        let merged: Vec<(u64, f32)> = regions
+            .iter()
+            .map(|region| (region.timestamp.as_micros(), region.importance))
+            .collect();
+        assert_eq!(
+            merged,
+            vec![(1, 0.0), (2, 1.0), (3, 0.0), (3, 1.0), (5, 0.0), (6, 1.0)]
+        );
+    }
+
+    #[test]
+    fn update_config_regenerates_from_cache() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals = r#"{"events":[
+            {"timestamp":0,"event_type":{"type":"MouseMove","position":{"x":0.2,"y":0.2}}},
+            {"timestamp":600000,"event_type":{"type":"MouseClick","position":{"x":0.75,"y":0.5},"button":0}}
+        ]}"#;
+        let mut engine = Engine::new(config).unwrap();
+        engine.process_signals(signals).unwrap();
+
+        engine
+            .update_config(
+                r#"{"capture_mode":"Tab","camera_settings":{"zoom_strength":3.0},"effect_settings":{"click_rings":false,"cursor_highlight":false}}"#,
+            )
+            .unwrap();
+
+        let keyframes: Vec<CameraKeyframe> =
+            serde_json::from_str(&engine.regenerate_camera().unwrap()).unwrap();
+        assert!(keyframes.iter().any(|kf| kf.viewport.zoom == 3.0));
+
+        let effects: EffectTrack =
+            serde_json::from_str(&engine.regenerate_effects().unwrap()).unwrap();
+        assert!(effects.effects.is_empty());
+    }
+
+    #[test]
+    fn manual_keyframes_persist_until_cleared() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals = r#"{"events":[
+            {"timestamp":0,"event_type":{"type":"MouseMove","position":{"x":0.2,"y":0.2}}},
+            {"timestamp":2000000,"event_type":{"type":"MouseMove","position":{"x":0.7,"y":0.6}}}
+        ]}"#;
+        let mut engine = Engine::new(config).unwrap();
+        engine.process_signals(signals).unwrap();
+
+        let viewport = r#"{"center":{"x":0.4,"y":0.4},"zoom":2.0}"#;
+        engine.set_manual_keyframe(1_000_000, viewport).unwrap();
+        let keyframes: Vec<CameraKeyframe> =
+            serde_json::from_str(&engine.regenerate_camera().unwrap()).unwrap();
+        assert_eq!(keyframes.iter().filter(|kf| kf.is_manual).count(), 1);
+
+        let keyframes: Vec<CameraKeyframe> =
+            serde_json::from_str(&engine.clear_manual_keyframe(1_000_000).unwrap()).unwrap();
+        assert!(keyframes.iter().all(|kf| !kf.is_manual));
+    }
+
+    #[test]
+    fn saved_projects_reopen_unchanged() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals = r#"{"events":[
+            {"timestamp":0,"event_type":{"type":"MouseMove","position":{"x":0.2,"y":0.2}}},
+            {"timestamp":1500000,"event_type":{"type":"MouseClick","position":{"x":0.6,"y":0.5},"button":0}},
+            {"timestamp":3000000,"event_type":{"type":"MouseMove","position":{"x":0.7,"y":0.6}}}
+        ]}"#;
+        let mut engine = Engine::new(config).unwrap();
+        engine.add_sync_anchor(0, 40_000).unwrap();
+        engine.process_signals(signals).unwrap();
+        let viewport = r#"{"center":{"x":0.4,"y":0.4},"zoom":2.0}"#;
+        engine.set_manual_keyframe(1_000_000, viewport).unwrap();
+        let remapper = r#"{"in_point_us":0,"out_point_us":3000000,
+            "markers":[{"time_us":1500000,"label":"Click"}]}"#;
+
+        let saved = engine.save(Some(remapper.to_string())).unwrap();
+        let mut loaded = Engine::load(&saved).unwrap();
+        assert_eq!(loaded.save(Some(remapper.to_string())).unwrap(), saved);
+        assert_eq!(
+            loaded.get_viewport_at(1_200_000).unwrap(),
+            engine.get_viewport_at(1_200_000).unwrap()
+        );
+        // Manual keyframes survive regeneration after reopening.
+        let keyframes: Vec<CameraKeyframe> =
+            serde_json::from_str(&loaded.regenerate_camera().unwrap()).unwrap();
+        assert_eq!(keyframes.iter().filter(|kf| kf.is_manual).count(), 1);
+
+        assert_ne!(engine.get_tracked_regions().unwrap(), "[]");
+        assert_eq!(
+            loaded.get_tracked_regions().unwrap(),
+            engine.get_tracked_regions().unwrap()
+        );
+
+        let project: Project = serde_json::from_str(&saved).unwrap();
+        assert_eq!(project.remapper.unwrap().markers[0].label, "Click");
+
+        // Chunk state is not saved: a late event is only caught by the original engine.
+        let late = r#"{"events":[
+            {"timestamp":1000000,"event_type":{"type":"MouseMove","position":{"x":0.5,"y":0.5}}}
+        ]}"#;
+        let warnings = |engine: &mut Engine| {
+            let chunk: AnalysisChunk =
+                serde_json::from_str(&engine.process_signals_chunk(late).unwrap()).unwrap();
+            chunk.delta.warnings.len()
+        };
+        assert_eq!(warnings(&mut engine), 1);
+        assert_eq!(warnings(&mut loaded), 0);
+    }
+
+    #[test]
+    fn undo_restores_keyframes_and_settings() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals = r#"{"events":[
+            {"timestamp":0,"event_type":{"type":"MouseMove","position":{"x":0.2,"y":0.2}}},
+            {"timestamp":2000000,"event_type":{"type":"MouseMove","position":{"x":0.7,"y":0.6}}}
+        ]}"#;
+        let mut engine = Engine::new(config).unwrap();
+        engine.process_signals(signals).unwrap();
+        let generated = engine.get_keyframes().unwrap();
+
+        let viewport = r#"{"center":{"x":0.4,"y":0.4},"zoom":2.0}"#;
+        engine.set_manual_keyframe(1_000_000, viewport).unwrap();
+        engine.set_effect_theme("Dark", None).unwrap();
+        assert_eq!(engine.undo_label().as_deref(), Some("Change theme"));
+
+        assert_eq!(engine.undo().unwrap().as_deref(), Some("Change theme"));
+        assert_eq!(engine.config.effect_settings.theme, EffectTheme::Standard);
+        assert_eq!(engine.undo().unwrap().as_deref(), Some("Set keyframe"));
+        assert_eq!(engine.get_keyframes().unwrap(), generated);
+        assert_eq!(engine.undo().unwrap(), None);
+
+        assert_eq!(engine.redo().unwrap().as_deref(), Some("Set keyframe"));
+        assert_eq!(engine.camera_engine.manual_keyframes().len(), 1);
+        // Edits that change nothing leave redo alone.
+        engine.clear_manual_keyframe(5_000_000).unwrap();
+        engine.set_manual_keyframe(1_000_000, viewport).unwrap();
+        engine.set_effect_theme("Standard", None).unwrap();
+        engine.set_blur_regions("[]").unwrap();
+        engine.update_config(config).unwrap();
+        assert_eq!(engine.undo_label().as_deref(), Some("Set keyframe"));
+        assert_eq!(engine.redo_label().as_deref(), Some("Change theme"));
+    }
+
+    #[test]
+    fn new_recording_discards_edits() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals = r#"{"events":[
+            {"timestamp":0,"event_type":{"type":"MouseMove","position":{"x":0.2,"y":0.2}}},
+            {"timestamp":2000000,"event_type":{"type":"MouseMove","position":{"x":0.7,"y":0.6}}}
+        ]}"#;
+        let mut engine = Engine::new(config).unwrap();
+        engine.process_signals(signals).unwrap();
+        let viewport = r#"{"center":{"x":0.4,"y":0.4},"zoom":2.0}"#;
+        engine.set_manual_keyframe(1_000_000, viewport).unwrap();
+        engine
+            .set_blur_regions(r#"[{"id":1,"bounds":{"x":0.1,"y":0.1,"width":0.2,"height":0.2}}]"#)
+            .unwrap();
+        engine
+            .register_custom_effects(
+                "confetti",
+                r#"[{"timestamp":500000,"duration_us":1000,"position":{"x":0.5,"y":0.5}}]"#,
+            )
+            .unwrap();
+        engine.set_effect_theme("Dark", None).unwrap();
+
+        engine.process_signals(signals).unwrap();
+        assert!(engine.camera_engine.manual_keyframes().is_empty());
+        assert!(engine.blur_regions.is_empty());
+        assert!(engine.effect_generator.custom_effects().is_empty());
+        assert_eq!(engine.undo_label(), None);
+        // The config, theme included, is kept.
+        assert_eq!(engine.config.effect_settings.theme, EffectTheme::Dark);
+    }
+
+    #[test]
+    fn changes_are_reported_by_track_and_range() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals = r#"{"events":[
+            {"timestamp":0,"event_type":{"type":"MouseMove","position":{"x":0.2,"y":0.2}}},
+            {"timestamp":2000000,"event_type":{"type":"MouseMove","position":{"x":0.7,"y":0.6}}}
+        ]}"#;
+        let mut engine = Engine::new(config).unwrap();
+        engine.process_signals(signals).unwrap();
+        engine.regenerate_camera().unwrap();
+        let seen = engine.revision();
+        // Regenerating from the same signals, or re-applying the same edits, changes nothing.
+        engine.regenerate_camera().unwrap();
+        engine.update_config(config).unwrap();
+        engine.set_blur_regions("[]").unwrap();
+        assert_eq!(engine.revision(), seen);
+
+        let viewport = r#"{"center":{"x":0.4,"y":0.4},"zoom":2.0}"#;
+        engine.set_manual_keyframe(1_000_000, viewport).unwrap();
+        engine
+            .process_signals_chunk(
+                r#"{"events":[
+                    {"timestamp":3000000,"event_type":{"type":"MouseMove","position":{"x":0.5,"y":0.5}}}
+                ]}"#,
+            )
+            .unwrap();
+
+        let changes: ChangeSet =
+            serde_json::from_str(&engine.changed_since(seen).unwrap()).unwrap();
+        assert_eq!(changes.revision, engine.revision());
+        let ranges = |track| {
+            changes
+                .changes
+                .iter()
+                .find(|change| change.track == track)
+                .map(|change| change.ranges.clone())
+        };
+        let camera = ranges(Track::Camera).unwrap().unwrap();
+        assert!(camera[0].contains(Timestamp::from_micros(1_000_000)));
+        assert_eq!(
+            ranges(Track::Cursor),
+            Some(Some(vec![TimeRange::new(
+                Timestamp::from_micros(3_000_000),
+                Timestamp::from_micros(3_000_000)
+            )]))
+        );
+        assert_eq!(ranges(Track::Config), None);
+    }
+
+    #[test]
+    fn accessors_return_cached_tracks() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals = r#"{"events":[
+            {"timestamp":0,"event_type":{"type":"MouseMove","position":{"x":0.2,"y":0.2}}},
+            {"timestamp":600000,"event_type":{"type":"MouseClick","position":{"x":0.75,"y":0.5},"button":0}}
+        ]}"#;
+        let mut engine = Engine::new(config).unwrap();
+        let result: AnalysisResult =
+            serde_json::from_str(&engine.process_signals(signals).unwrap()).unwrap();
+
+        let cursor_track: Vec<CursorTrackPoint> =
+            serde_json::from_str(&engine.get_cursor_track().unwrap()).unwrap();
+        let focus_regions: Vec<FocusRegion> =
+            serde_json::from_str(&engine.get_focus_regions().unwrap()).unwrap();
+        let keyframes: Vec<CameraKeyframe> =
+            serde_json::from_str(&engine.get_keyframes().unwrap()).unwrap();
+        let effects: EffectTrack =
+            serde_json::from_str(&engine.get_effect_tracks().unwrap()).unwrap();
+
+        assert_eq!(cursor_track.len(), result.cursor_track.len());
+        assert_eq!(focus_regions.len(), result.focus_regions.len());
+        assert_eq!(keyframes.len(), result.camera_keyframes.len());
+        assert_eq!(effects.effects.len(), result.effect_tracks.effects.len());
+
+        let early: Vec<CursorTrackPoint> =
+            serde_json::from_str(&engine.get_cursor_points_in(0, 600_000).unwrap()).unwrap();
+        assert!(!early.is_empty() && early.iter().all(|p| p.timestamp.as_micros() < 600_000));
+        let late: Vec<FocusRegion> =
+            serde_json::from_str(&engine.get_focus_regions_in(600_000, 700_000).unwrap()).unwrap();
+        assert!(!late.is_empty() && late.iter().all(|r| r.timestamp.as_micros() == 600_000));
+        assert_eq!(engine.get_focus_regions_in(700_000, 0).unwrap(), "[]");
+    }
+
+    #[test]
+    fn suggests_speed_ramps_over_idle_spans() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals = r#"{"events":[
+            {"timestamp":0,"event_type":{"type":"MouseClick","position":{"x":0.5,"y":0.5},"button":0}},
+            {"timestamp":10000000,"event_type":{"type":"MouseClick","position":{"x":0.5,"y":0.5},"button":0}}
+        ]}"#;
+        let mut engine = Engine::new(config).unwrap();
+        engine.process_signals(signals).unwrap();
+
+        let ramps: Vec<SpeedRampConfig> =
+            serde_json::from_str(&engine.suggest_speed_ramps("{}").unwrap()).unwrap();
+        assert_eq!(ramps.len(), 1);
+        assert_eq!((ramps[0].start_us, ramps[0].end_us), (500_000, 9_500_000));
+    }
+
+    #[test]
+    fn suggests_cuts_from_frame_hashes() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let engine = Engine::new(config).unwrap();
+
+        let timestamps: Vec<u64> = (0..6).map(|s| s * 1_000_000).collect();
+        let cuts: Vec<CutSuggestion> =
+            serde_json::from_str(&engine.suggest_cuts("{}", timestamps, vec![42; 6]).unwrap())
+                .unwrap();
+        assert_eq!(cuts.len(), 1);
+        assert_eq!(cuts[0].reason, CutReason::StaticFrames);
+    }
+
+    #[test]
+    fn scene_changes_reset_the_camera() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals = r#"{"events":[
+            {"timestamp":0,"event_type":{"type":"MouseMove","position":{"x":0.2,"y":0.2}}},
+            {"timestamp":1000000,"event_type":{"type":"MouseClick","position":{"x":0.2,"y":0.2},"button":0}},
+            {"timestamp":4000000,"event_type":{"type":"MouseMove","position":{"x":0.25,"y":0.2}}}
+        ]}"#;
+        let mut engine = Engine::new(config).unwrap();
+        engine.process_signals(signals).unwrap();
+
+        let seen = engine.revision();
+        let timestamps: Vec<u64> = (0..6).map(|s| s * 1_000_000).collect();
+        let hashes = vec![0, 0, 0, u64::MAX, u64::MAX, u64::MAX];
+        let report: SceneReport =
+            serde_json::from_str(&engine.detect_scenes("{}", timestamps, hashes).unwrap()).unwrap();
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(report.markers[0].time_us, 3_000_000);
+        let changes: ChangeSet =
+            serde_json::from_str(&engine.changed_since(seen).unwrap()).unwrap();
+        assert_eq!(changes.changes[0].track, Track::Camera);
+
+        let keyframes: Vec<CameraKeyframe> =
+            serde_json::from_str(&engine.get_keyframes().unwrap()).unwrap();
+        let cut = keyframes
+            .iter()
+            .find(|kf| kf.reason == KeyframeReason::SceneChange)
+            .expect("Scene change should reset the camera");
+        assert_eq!(cut.timestamp.as_micros(), 3_000_000);
+        assert_eq!(cut.viewport, Viewport::default());
+
+        assert_eq!(engine.undo().unwrap().as_deref(), Some("Detect scenes"));
+        assert!(engine.camera_engine.scene_changes().is_empty());
+    }
+
+    #[test]
+    fn blurs_focused_password_fields() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals = r#"{"events":[
+            {"timestamp":1000000,"event_type":{"type":"FocusChange",
+                "bounds":{"x":0.2,"y":0.4,"width":0.3,"height":0.05},
+                "element":{"tag":"input","is_password":true}}},
+            {"timestamp":4000000,"event_type":{"type":"FocusChange",
+                "bounds":{"x":0.2,"y":0.5,"width":0.3,"height":0.05},
+                "element":{"tag":"input","autocomplete":"billing cc-number"}}},
+            {"timestamp":6000000,"event_type":{"type":"FocusChange",
+                "bounds":{"x":0.2,"y":0.6,"width":0.3,"height":0.05},
+                "element":{"tag":"input","autocomplete":"email"}}}
+        ]}"#;
+        let mut engine = Engine::new(config).unwrap();
+        let result: AnalysisResult =
+            serde_json::from_str(&engine.process_signals(signals).unwrap()).unwrap();
+
+        let spans: Vec<(u64, usize)> = result
+            .blur_track
+            .iter()
+            .map(|sample| (sample.timestamp.as_micros(), sample.rects.len()))
+            .collect();
+        // Each field is blurred from 0.5s before focus to 0.5s after it moves on.
+        assert_eq!(
+            spans,
+            vec![(500_000, 1), (3_500_000, 2), (4_500_000, 1), (6_500_000, 0)]
+        );
+        assert_eq!(result.blur_track[0].rects[0].id, AUTO_BLUR_ID_BASE);
+
+        // Chunks leave the blur track to be brought up to date when read.
+        let mut chunked = Engine::new(config).unwrap();
+        chunked.process_signals_chunk(signals).unwrap();
+        assert!(chunked.analysis.blur_track.is_empty());
+        let blur_track: Vec<BlurSample> =
+            serde_json::from_str(&chunked.get_blur_track().unwrap()).unwrap();
+        assert_eq!(blur_track.len(), result.blur_track.len());
+
+        let user: Vec<BlurSample> = serde_json::from_str(
+            &engine
+                .set_blur_regions(r#"[{"id":1,"bounds":{"x":0,"y":0,"width":0.2,"height":0.1}}]"#)
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(user[0].timestamp.as_micros(), 0);
+        assert_eq!(user[1].rects.len(), 2);
+    }
+
+    #[test]
+    fn custom_effects_join_the_track() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals = r#"{"events":[
+            {"timestamp":1000000,"event_type":{"type":"MouseClick","position":{"x":0.5,"y":0.5},"button":0}}
+        ]}"#;
+        let mut engine = Engine::new(config).unwrap();
+        engine.process_signals(signals).unwrap();
+        engine
+            .register_custom_effects(
+                "confetti",
+                r#"[{"timestamp":2000000,"duration_us":800000,"position":{"x":0.5,"y":0.3},
+                    "params_json":"{\"pieces\":40}"}]"#,
+            )
+            .unwrap();
+
+        let track: EffectTrack =
+            serde_json::from_str(&engine.regenerate_effects().unwrap()).unwrap();
+        let custom: Vec<&Effect> = track
+            .effects
+            .iter()
+            .filter(|effect| matches!(effect.effect_type, EffectType::Custom { .. }))
+            .collect();
+        assert_eq!(custom.len(), 1);
+        assert!(matches!(
+            &custom[0].effect_type,
+            EffectType::Custom { name, params_json }
+                if name == "confetti" && params_json == r#"{"pieces":40}"#
+        ));
+
+        let seen = engine.revision();
+        engine.clear_custom_effects("confetti");
+        let changes: ChangeSet =
+            serde_json::from_str(&engine.changed_since(seen).unwrap()).unwrap();
+        assert_eq!(changes.changes[0].track, Track::Effects);
+        let track: EffectTrack =
+            serde_json::from_str(&engine.regenerate_effects().unwrap()).unwrap();
+        assert!(track
+            .effects
+            .iter()
+            .all(|effect| !matches!(effect.effect_type, EffectType::Custom { .. })));
+
+        // Undo brings them back.
+        assert_eq!(
+            engine.undo().unwrap().as_deref(),
+            Some("Remove custom effects")
+        );
+        assert_eq!(engine.effect_generator.custom_effects().len(), 1);
+        assert_eq!(engine.undo_label().as_deref(), Some("Add custom effects"));
+    }
+
+    #[test]
+    fn condenses_cached_recording() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals = r#"{"events":[
+            {"timestamp":0,"event_type":{"type":"MouseClick","position":{"x":0.5,"y":0.5},"button":0}},
+            {"timestamp":30000000,"event_type":{"type":"MouseClick","position":{"x":0.5,"y":0.5},"button":0}}
+        ]}"#;
+        let mut engine = Engine::new(config).unwrap();
+        engine.process_signals(signals).unwrap();
+
+        let result: CondenseResult = serde_json::from_str(
+            &engine
+                .condense_to_duration(r#"{"target_duration_us":10000000}"#)
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(result.target_reached);
+        let remapper = TimeRemapper::from_config(result.config);
+        assert_eq!(remapper.export_duration().as_micros(), 10_000_000);
+    }
+
+    #[test]
+    fn buffer_and_json_paths_agree() {
+        let config = r#"{"capture_mode":"Tab","camera_settings":{},"effect_settings":{}}"#;
+        let signals_json = r#"{"events":[
+            {"timestamp":0,"event_type":{"type":"MouseMove","position":{"x":0.2,"y":0.2}}},
+            {"timestamp":600000,"event_type":{"type":"MouseClick","position":{"x":0.75,"y":0.5},"button":0}}
+        ]}"#;
+        let signals: SignalBatch = serde_json::from_str(signals_json).unwrap();
+        let buffer: Vec<u8> = signals
+            .events
+            .iter()
+            .flat_map(encode_signal_record)
+            .collect();
+
+        let mut json_engine = Engine::new(config).unwrap();
+        let mut buffer_engine = Engine::new(config).unwrap();
+
+        let from_json = json_engine.process_signals(signals_json).unwrap();
+        let from_buffer = buffer_engine.process_signals_buffer(&buffer).unwrap();
+        assert_eq!(from_json, from_buffer);
+    }
 }
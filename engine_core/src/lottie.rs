@@ -0,0 +1,298 @@
+// Lottie export of effect tracks, so click rings, highlights, and spotlights can be
+// composited by standard tooling or burned in by a separate pipeline.
+// Effects are expected on the export timeline (see `TimeRemapper::project_effects`).
+// See design.md: TimeRemapper (Rust)
+
+use serde_json::{json, Value};
+
+use crate::types::{Effect, EffectTrack, EffectType, Timestamp};
+
+/// Lottie schema version written to the file.
+const LOTTIE_VERSION: &str = "5.7.0";
+
+/// A Lottie animation of `track` over `duration` at `fps`, sized `width`x`height`
+/// pixels. Click rings, cursor highlights, and spotlights become one shape layer each;
+/// other effect types are left out. Effect coordinates are those of the recording, so
+/// composite the animation over the recording before the camera transform. Radii and
+/// stroke widths are fractions of the width. Envelopes become opacity and scale
+/// keyframes.
+pub fn effects_to_lottie(
+    track: &EffectTrack,
+    width: u32,
+    height: u32,
+    fps: f64,
+    duration: Timestamp,
+) -> Value {
+    let frame_of = |us: u64| us as f64 * fps / 1_000_000.0;
+    let layers: Vec<Value> = track
+        .effects
+        .iter()
+        .filter(|effect| effect.duration_us > 0)
+        .filter_map(|effect| {
+            let (name, shape) = effect_shape(effect, width as f32, height as f32)?;
+            Some((name, shape, effect))
+        })
+        .enumerate()
+        .map(|(index, (name, shape, effect))| {
+            let start = effect.timestamp.as_micros();
+            let (opacity, scale) = envelope_keyframes(effect, &frame_of);
+            json!({
+                "ddd": 0,
+                "ind": index + 1,
+                "ty": 4,
+                "nm": name,
+                "sr": 1,
+                "ks": {
+                    "o": opacity,
+                    "r": { "a": 0, "k": 0 },
+                    "p": {
+                        "a": 0,
+                        "k": [
+                            effect.position.x * width as f32,
+                            effect.position.y * height as f32,
+                            0
+                        ]
+                    },
+                    "a": { "a": 0, "k": [0, 0, 0] },
+                    "s": scale
+                },
+                "ao": 0,
+                "shapes": [shape],
+                "ip": frame_of(start),
+                "op": frame_of(start + effect.duration_us),
+                "st": 0,
+                "bm": 0
+            })
+        })
+        .collect();
+
+    json!({
+        "v": LOTTIE_VERSION,
+        "fr": fps,
+        "ip": 0,
+        "op": frame_of(duration.as_micros()),
+        "w": width,
+        "h": height,
+        "nm": "ScreenMu effects",
+        "ddd": 0,
+        "assets": [],
+        "layers": layers
+    })
+}
+
+/// Layer name and shape group for the effect types Lottie export supports.
+fn effect_shape(effect: &Effect, width: f32, height: f32) -> Option<(&'static str, Value)> {
+    let style = &effect.style;
+    let diameter = 2.0 * style.radius * width;
+    let color = parse_color(&style.color);
+    let (name, diameter, paint) = match effect.effect_type {
+        EffectType::ClickRing | EffectType::CursorHighlight => {
+            let name = if matches!(effect.effect_type, EffectType::ClickRing) {
+                "Click ring"
+            } else {
+                "Cursor highlight"
+            };
+            let paint = if style.stroke_width > 0.0 {
+                json!({
+                    "ty": "st",
+                    "c": { "a": 0, "k": color },
+                    "o": { "a": 0, "k": 100 },
+                    "w": { "a": 0, "k": style.stroke_width * width },
+                    "lc": 2,
+                    "lj": 2
+                })
+            } else {
+                json!({
+                    "ty": "fl",
+                    "c": { "a": 0, "k": color },
+                    "o": { "a": 0, "k": 100 },
+                    "r": 1
+                })
+            };
+            (name, diameter, paint)
+        }
+        // A stroke wide enough to cover the frame outside the spotlight circle.
+        EffectType::Spotlight => {
+            let cover = 2.0 * width.hypot(height);
+            let paint = json!({
+                "ty": "st",
+                "c": { "a": 0, "k": color },
+                "o": { "a": 0, "k": 100 },
+                "w": { "a": 0, "k": cover },
+                "lc": 1,
+                "lj": 1
+            });
+            ("Spotlight", diameter + cover, paint)
+        }
+        _ => return None,
+    };
+
+    Some((
+        name,
+        json!({
+            "ty": "gr",
+            "nm": name,
+            "it": [
+                {
+                    "ty": "el",
+                    "p": { "a": 0, "k": [0, 0] },
+                    "s": { "a": 0, "k": [diameter, diameter] }
+                },
+                paint,
+                {
+                    "ty": "tr",
+                    "p": { "a": 0, "k": [0, 0] },
+                    "a": { "a": 0, "k": [0, 0] },
+                    "s": { "a": 0, "k": [100, 100] },
+                    "r": { "a": 0, "k": 0 },
+                    "o": { "a": 0, "k": 100 }
+                }
+            ]
+        }),
+    ))
+}
+
+/// Opacity (0-100) and scale (percent) properties sampled from the effect's state at
+/// each envelope key, repeated every period for periodic envelopes.
+fn envelope_keyframes(effect: &Effect, frame_of: &impl Fn(u64) -> f64) -> (Value, Value) {
+    let start = effect.timestamp.as_micros();
+    let duration = effect.duration_us;
+    let envelope = &effect.envelope;
+
+    let mut offsets: Vec<u64> = vec![0, duration - 1];
+    let keys = envelope.opacity.iter().chain(&envelope.scale);
+    match envelope.period_us {
+        Some(period) if period > 0 => {
+            for cycle in (0..duration).step_by(period as usize) {
+                offsets.extend(
+                    keys.clone()
+                        .map(|key| cycle + (key.at.clamp(0.0, 1.0) * period as f32) as u64),
+                );
+            }
+        }
+        _ => offsets.extend(keys.map(|key| (key.at.clamp(0.0, 1.0) * duration as f32) as u64)),
+    }
+    offsets.retain(|&offset| offset < duration);
+    offsets.sort_unstable();
+    offsets.dedup();
+
+    let samples: Vec<(f64, f32, f32)> = offsets
+        .into_iter()
+        .filter_map(|offset| {
+            let state = effect.state_at(Timestamp::from_micros(start + offset))?;
+            Some((frame_of(start + offset), state.opacity, state.scale))
+        })
+        .collect();
+    let animated = |values: Vec<(f64, Value)>| {
+        if values.windows(2).all(|pair| pair[0].1 == pair[1].1) {
+            let value = values.first().map(|(_, value)| value.clone());
+            return json!({ "a": 0, "k": value.unwrap_or(Value::Null) });
+        }
+        let keyframes: Vec<Value> = values
+            .into_iter()
+            .map(|(frame, value)| {
+                json!({
+                    "t": frame,
+                    "s": value,
+                    "i": { "x": [1], "y": [1] },
+                    "o": { "x": [0], "y": [0] }
+                })
+            })
+            .collect();
+        json!({ "a": 1, "k": keyframes })
+    };
+
+    let opacity = animated(
+        samples
+            .iter()
+            .map(|&(frame, opacity, _)| (frame, json!([opacity * 100.0])))
+            .collect(),
+    );
+    let scale = animated(
+        samples
+            .iter()
+            .map(|&(frame, _, scale)| (frame, json!([scale * 100.0, scale * 100.0, 100])))
+            .collect(),
+    );
+    (opacity, scale)
+}
+
+/// `#rrggbb` (or `#rgb`) as Lottie's 0-1 RGBA; anything else is white.
+fn parse_color(color: &str) -> [f32; 4] {
+    let hex = color.trim().trim_start_matches('#');
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok().map(f32::from);
+    let rgb = match hex.len() {
+        6 => (
+            channel(&hex[0..2]),
+            channel(&hex[2..4]),
+            channel(&hex[4..6]),
+        ),
+        3 => (
+            channel(&hex[0..1]).map(|v| v * 17.0),
+            channel(&hex[1..2]).map(|v| v * 17.0),
+            channel(&hex[2..3]).map(|v| v * 17.0),
+        ),
+        _ => (None, None, None),
+    };
+    match rgb {
+        (Some(r), Some(g), Some(b)) => [r / 255.0, g / 255.0, b / 255.0, 1.0],
+        _ => [1.0; 4],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::NormalizedCoord;
+
+    fn effect(us: u64, effect_type: EffectType) -> Effect {
+        Effect {
+            timestamp: Timestamp::from_micros(us),
+            duration_us: 500_000,
+            position: NormalizedCoord::new(0.5, 0.25),
+            style: effect_type.default_style(),
+            envelope: effect_type.default_envelope(),
+            effect_type,
+        }
+    }
+
+    #[test]
+    fn exports_rings_as_animated_shape_layers() {
+        let track = EffectTrack {
+            effects: vec![
+                effect(1_000_000, EffectType::ClickRing),
+                effect(
+                    1_000_000,
+                    EffectType::Keystrokes {
+                        text: "⌘S".to_string(),
+                    },
+                ),
+                effect(2_000_000, EffectType::Spotlight),
+            ],
+        };
+
+        let lottie = effects_to_lottie(&track, 1920, 1080, 30.0, Timestamp::from_micros(4_000_000));
+        assert_eq!(lottie["op"], 120.0);
+        let layers = lottie["layers"].as_array().unwrap();
+        assert_eq!(layers.len(), 2);
+
+        let ring = &layers[0];
+        assert_eq!(ring["nm"], "Click ring");
+        assert_eq!(ring["ip"], 30.0);
+        assert_eq!(ring["op"], 45.0);
+        assert_eq!(ring["ks"]["p"]["k"], json!([960.0, 270.0, 0]));
+        // Grows from 30% and fades out.
+        let scale = ring["ks"]["s"]["k"].as_array().unwrap();
+        assert_eq!(scale[0]["t"], 30.0);
+        assert!((scale[0]["s"][0].as_f64().unwrap() - 30.0).abs() < 1e-3);
+        let opacity = ring["ks"]["o"]["k"].as_array().unwrap();
+        assert!(opacity.last().unwrap()["s"][0].as_f64().unwrap() < 1.0);
+        assert_eq!(ring["shapes"][0]["it"][1]["ty"], "st");
+
+        assert_eq!(layers[1]["nm"], "Spotlight");
+        assert_eq!(
+            layers[1]["shapes"][0]["it"][1]["c"]["k"],
+            json!([0.0, 0.0, 0.0, 1.0])
+        );
+    }
+}
@@ -0,0 +1,200 @@
+// Config migrations: saved `EngineConfig` and `TimeRemapperConfig` JSON from earlier
+// extension versions is upgraded step by step to the current shape before parsing, so
+// old projects open instead of failing on a field that moved.
+// See steering.md: Rust Rules (Engine)
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::EngineError;
+use crate::time_remap::TimeRemapperConfig;
+use crate::types::EngineConfig;
+
+/// Current `EngineConfig::version`.
+pub const ENGINE_CONFIG_VERSION: u32 = 1;
+/// Current `TimeRemapperConfig::version`.
+pub const REMAPPER_CONFIG_VERSION: u32 = 1;
+
+/// What it took to bring a saved config up to date.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationReport {
+    /// Version the config was saved with; 0 for configs from before versioning.
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Steps run, oldest first.
+    pub applied: Vec<AppliedMigration>,
+}
+
+/// One migration step and what it changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppliedMigration {
+    /// Version the step upgrades to.
+    pub to_version: u32,
+    pub description: String,
+    /// Each change made, e.g. "dropped null `markers`". Empty if nothing needed fixing.
+    pub changes: Vec<String>,
+}
+
+/// Upgrade step from `to_version - 1`.
+struct Migration {
+    to_version: u32,
+    description: &'static str,
+    apply: fn(&mut Map<String, Value>) -> Vec<String>,
+}
+
+const ENGINE_CONFIG_MIGRATIONS: &[Migration] = &[Migration {
+    to_version: 1,
+    description: "Add config versioning",
+    apply: |config| {
+        let mut changes = drop_nulls(
+            config,
+            &[
+                "camera_settings",
+                "effect_settings",
+                "cursor_settings",
+                "scroll_settings",
+                "session_settings",
+                "importance_weights",
+                "privacy",
+            ],
+        );
+        // Capture modes are variant names; accept any case.
+        if let Some(Value::String(mode)) = config.get_mut("capture_mode") {
+            let known = ["Tab", "Screen", "Window"]
+                .into_iter()
+                .find(|known| known.eq_ignore_ascii_case(mode) && *known != mode.as_str());
+            if let Some(known) = known {
+                changes.push(format!("renamed capture mode `{}` to `{}`", mode, known));
+                *mode = known.to_string();
+            }
+        }
+        changes
+    },
+}];
+
+const REMAPPER_CONFIG_MIGRATIONS: &[Migration] = &[Migration {
+    to_version: 1,
+    description: "Add config versioning",
+    apply: |config| {
+        drop_nulls(
+            config,
+            &["cuts", "speed_ramps", "markers", "protected_ranges"],
+        )
+    },
+}];
+
+/// Parse a saved `EngineConfig` of any supported version, upgrading it first.
+pub fn migrate_engine_config(value: Value) -> Result<(EngineConfig, MigrationReport), EngineError> {
+    let (value, report) = migrate(value, ENGINE_CONFIG_VERSION, ENGINE_CONFIG_MIGRATIONS)?;
+    Ok((serde_json::from_value(value)?, report))
+}
+
+/// Parse a saved `TimeRemapperConfig` of any supported version, upgrading it first.
+pub fn migrate_remapper_config(
+    value: Value,
+) -> Result<(TimeRemapperConfig, MigrationReport), EngineError> {
+    let (value, report) = migrate(value, REMAPPER_CONFIG_VERSION, REMAPPER_CONFIG_MIGRATIONS)?;
+    Ok((serde_json::from_value(value)?, report))
+}
+
+/// Run the steps after the config's `version` (0 when missing) and stamp the current
+/// version. Configs from a newer engine are rejected rather than guessed at.
+fn migrate(
+    mut value: Value,
+    current: u32,
+    migrations: &[Migration],
+) -> Result<(Value, MigrationReport), EngineError> {
+    let config = value
+        .as_object_mut()
+        .ok_or_else(|| EngineError::InvalidConfig("config must be an object".to_string()))?;
+    let from_version = match config.get("version") {
+        None | Some(Value::Null) => 0,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| EngineError::InvalidConfig(format!("invalid version {}", version)))?,
+    };
+    if from_version > current {
+        return Err(EngineError::InvalidConfig(format!(
+            "config version {} is newer than this engine supports ({})",
+            from_version, current
+        )));
+    }
+
+    let applied = migrations
+        .iter()
+        .filter(|migration| migration.to_version > from_version)
+        .map(|migration| AppliedMigration {
+            to_version: migration.to_version,
+            description: migration.description.to_string(),
+            changes: (migration.apply)(config),
+        })
+        .collect();
+    config.insert("version".to_string(), Value::from(current));
+
+    Ok((
+        value,
+        MigrationReport {
+            from_version,
+            to_version: current,
+            applied,
+        },
+    ))
+}
+
+/// Remove `keys` set to null, so their serde defaults apply.
+fn drop_nulls(config: &mut Map<String, Value>, keys: &[&str]) -> Vec<String> {
+    let mut changes = Vec::new();
+    for key in keys {
+        if config.get(*key).is_some_and(Value::is_null) {
+            config.remove(*key);
+            changes.push(format!("dropped null `{}`", key));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CaptureMode;
+    use serde_json::json;
+
+    #[test]
+    fn unversioned_configs_are_upgraded() {
+        let (config, report) = migrate_engine_config(json!({
+            "capture_mode": "tab",
+            "camera_settings": null,
+        }))
+        .unwrap();
+        assert_eq!(config.capture_mode, CaptureMode::Tab);
+        assert_eq!(config.version, ENGINE_CONFIG_VERSION);
+        assert_eq!(report.from_version, 0);
+        assert_eq!(
+            report.applied[0].changes,
+            vec![
+                "dropped null `camera_settings`".to_string(),
+                "renamed capture mode `tab` to `Tab`".to_string(),
+            ]
+        );
+
+        let (config, report) = migrate_remapper_config(json!({
+            "version": 1,
+            "in_point_us": 0,
+            "out_point_us": 1000,
+        }))
+        .unwrap();
+        assert_eq!(config.out_point_us, 1000);
+        assert!(report.applied.is_empty());
+    }
+
+    #[test]
+    fn newer_configs_are_rejected() {
+        let result = migrate_remapper_config(json!({
+            "version": REMAPPER_CONFIG_VERSION + 1,
+            "in_point_us": 0,
+            "out_point_us": 1000,
+        }));
+        assert!(matches!(result, Err(EngineError::InvalidConfig(_))));
+    }
+}
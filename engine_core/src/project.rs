@@ -0,0 +1,106 @@
+// Project documents: everything the editor needs to reopen a recording exactly as it was
+// left (config, cached signals and analysis, hand edits, and the edit decision list)
+// as one JSON document.
+// See steering.md: Rust Rules (Engine)
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::EngineError;
+use crate::migrate::{migrate_engine_config, migrate_remapper_config};
+use crate::time_remap::TimeRemapperConfig;
+use crate::types::*;
+
+/// Current `Project::version`.
+pub const PROJECT_VERSION: u32 = 1;
+
+/// A saved editing session. The engine parts are restored by `Engine::load`; the edit
+/// (cuts, ramps, markers) is `remapper`, for `WasmTimeRemapper::from_object`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Project {
+    pub version: u32,
+    pub config: EngineConfig,
+    /// Signals ingested so far, as the analysis saw them (synced and cleaned up).
+    pub signals: SignalBatch,
+    /// Analysis as last shown, including compacted or regenerated tracks.
+    pub analysis: AnalysisResult,
+    #[serde(default)]
+    pub manual_keyframes: Vec<CameraKeyframe>,
+    #[serde(default)]
+    pub scene_changes: Vec<Timestamp>,
+    /// Focus regions merged across time; the analysis' regions refer to them by
+    /// `track_id`.
+    #[serde(default)]
+    pub tracked_regions: Vec<TrackedRegion>,
+    /// User-drawn blur regions (automatic ones are rebuilt from the signals).
+    #[serde(default)]
+    pub blur_regions: Vec<BlurRegion>,
+    /// Registered custom effect instances, by effect name.
+    #[serde(default)]
+    pub custom_effects: Vec<(String, Vec<CustomEffectSpec>)>,
+    /// Signal-to-media clock anchors, as `(signal, media)` pairs.
+    #[serde(default)]
+    pub sync_anchors: Vec<(Timestamp, Timestamp)>,
+    #[serde(default)]
+    pub remapper: Option<TimeRemapperConfig>,
+    #[serde(default)]
+    pub events_processed: u64,
+    #[serde(default)]
+    pub processed_until: Timestamp,
+}
+
+impl Project {
+    /// Parse a saved project, upgrading its engine and remapper configs from earlier
+    /// versions (see `migrate_engine_config`). Projects from a newer engine are rejected.
+    pub fn from_json(json: &str) -> Result<Project, EngineError> {
+        let mut value: Value = serde_json::from_str(json)?;
+        let project = value
+            .as_object_mut()
+            .ok_or_else(|| EngineError::InvalidConfig("project must be an object".to_string()))?;
+        let version = project.get("version").and_then(Value::as_u64).unwrap_or(0);
+        if version > u64::from(PROJECT_VERSION) {
+            return Err(EngineError::InvalidConfig(format!(
+                "project version {} is newer than this engine supports ({})",
+                version, PROJECT_VERSION
+            )));
+        }
+
+        let (config, _) = migrate_engine_config(project.remove("config").unwrap_or(Value::Null))?;
+        let remapper = match project.remove("remapper") {
+            None | Some(Value::Null) => None,
+            Some(remapper) => Some(migrate_remapper_config(remapper)?.0),
+        };
+        project.insert("version".to_string(), Value::from(PROJECT_VERSION));
+        project.insert("config".to_string(), serde_json::to_value(&config)?);
+        project.insert("remapper".to_string(), serde_json::to_value(&remapper)?);
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn older_configs_inside_projects_are_migrated() {
+        let json = r#"{
+            "version": 1,
+            "config": { "capture_mode": "tab" },
+            "signals": { "events": [] },
+            "analysis": {
+                "cursor_track": [], "focus_regions": [], "camera_keyframes": [],
+                "effect_tracks": { "effects": [] }
+            },
+            "remapper": { "in_point_us": 0, "out_point_us": 5000000,
+                          "markers": [{ "time_us": 1000, "label": "Intro" }] }
+        }"#;
+
+        let project = Project::from_json(json).unwrap();
+        assert_eq!(project.config.capture_mode, CaptureMode::Tab);
+        let remapper = project.remapper.unwrap();
+        assert_eq!(remapper.markers[0].label, "Intro");
+
+        let newer = json.replacen("\"version\": 1", "\"version\": 2", 1);
+        assert!(Project::from_json(&newer).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+// Revisions: a counter bumped on every change to the engine's state, with a log of which
+// tracks changed over which time ranges, so the UI can re-render just those parts and
+// debounce autosave without diffing the tracks itself.
+// See steering.md: Rust Rules (Engine)
+
+use serde::{Deserialize, Serialize};
+
+use crate::time_remap::TimeRange;
+use crate::types::Timestamp;
+
+/// Changes kept for `changed_since`; asking about older revisions reports everything.
+const REVISION_LOG_LIMIT: usize = 1024;
+
+/// A part of the engine's state the UI draws or saves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Track {
+    Signals,
+    Cursor,
+    Focus,
+    Camera,
+    Effects,
+    AudioCues,
+    Blur,
+    Config,
+}
+
+impl Track {
+    pub const ALL: [Track; 8] = [
+        Track::Signals,
+        Track::Cursor,
+        Track::Focus,
+        Track::Camera,
+        Track::Effects,
+        Track::AudioCues,
+        Track::Blur,
+        Track::Config,
+    ];
+}
+
+/// Where one track changed since a revision.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackChange {
+    pub track: Track,
+    /// Changed source-time ranges, sorted and merged; `None` if the whole track may have
+    /// changed.
+    pub ranges: Option<Vec<TimeRange>>,
+}
+
+/// Everything that changed after a revision, up to the current one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeSet {
+    pub revision: u64,
+    pub changes: Vec<TrackChange>,
+}
+
+#[derive(Debug, Clone)]
+struct Change {
+    revision: u64,
+    track: Track,
+    range: Option<TimeRange>,
+}
+
+/// Monotonic revision counter with a bounded log of changes.
+#[derive(Debug, Clone, Default)]
+pub struct RevisionLog {
+    revision: u64,
+    changes: Vec<Change>,
+}
+
+impl RevisionLog {
+    pub fn new() -> Self {
+        RevisionLog::default()
+    }
+
+    /// Current revision; 0 until the first change.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Start a new revision in which `changes` happened (`None` range: the whole track).
+    /// Returns the new revision.
+    pub fn bump(&mut self, changes: &[(Track, Option<TimeRange>)]) -> u64 {
+        self.revision += 1;
+        for &(track, range) in changes {
+            self.changes.push(Change {
+                revision: self.revision,
+                track,
+                range,
+            });
+        }
+        // Forget whole revisions, so every logged revision is complete.
+        if self.changes.len() > REVISION_LOG_LIMIT {
+            let cut = self.changes[self.changes.len() - REVISION_LOG_LIMIT].revision;
+            let keep = self
+                .changes
+                .partition_point(|change| change.revision <= cut);
+            self.changes.drain(..keep);
+        }
+        self.revision
+    }
+
+    /// Tracks changed after `revision`, in `Track::ALL` order. A revision older than the
+    /// log reaches reports every track as wholly changed.
+    pub fn changed_since(&self, revision: u64) -> ChangeSet {
+        let oldest = self
+            .changes
+            .first()
+            .map_or(self.revision, |c| c.revision - 1);
+        let changes = if revision < oldest {
+            Track::ALL
+                .iter()
+                .map(|&track| TrackChange {
+                    track,
+                    ranges: None,
+                })
+                .collect()
+        } else {
+            Track::ALL
+                .iter()
+                .filter_map(|&track| {
+                    let mut ranges = Some(Vec::new());
+                    let mut changed = false;
+                    for change in &self.changes {
+                        if change.revision <= revision || change.track != track {
+                            continue;
+                        }
+                        changed = true;
+                        match (change.range, ranges.as_mut()) {
+                            (Some(range), Some(ranges)) => ranges.push(range),
+                            _ => ranges = None,
+                        }
+                    }
+                    changed.then(|| TrackChange {
+                        track,
+                        ranges: ranges.map(merge_ranges),
+                    })
+                })
+                .collect()
+        };
+        ChangeSet {
+            revision: self.revision,
+            changes,
+        }
+    }
+}
+
+/// Span from the first to the last timestamp at which two sorted lists differ, or `None`
+/// if they are equal. The span includes the unchanged neighbours of the differing items,
+/// since the curve between them moves too. `extent` gives each item's time range.
+pub(crate) fn changed_span<T: PartialEq>(
+    before: &[T],
+    after: &[T],
+    extent: impl Fn(&T) -> TimeRange,
+) -> Option<TimeRange> {
+    let common_prefix = before.iter().zip(after).take_while(|(a, b)| a == b).count();
+    if common_prefix == before.len() && common_prefix == after.len() {
+        return None;
+    }
+    let common_suffix = before[common_prefix..]
+        .iter()
+        .rev()
+        .zip(after[common_prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let changed = before[common_prefix..before.len() - common_suffix]
+        .iter()
+        .chain(&after[common_prefix..after.len() - common_suffix])
+        .map(&extent);
+    // Items between neighbours move the curve from the neighbour before them.
+    let neighbours = common_prefix
+        .checked_sub(1)
+        .and_then(|i| after.get(i))
+        .into_iter()
+        .chain(after.get(after.len() - common_suffix))
+        .map(&extent);
+    changed
+        .chain(neighbours)
+        .reduce(|a, b| TimeRange::new(a.start.min(b.start), a.end.max(b.end)))
+}
+
+fn merge_ranges(mut ranges: Vec<TimeRange>) -> Vec<TimeRange> {
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<TimeRange> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Range from `start` to `end`, empty rather than inverted if `end` is earlier.
+pub(crate) fn span(start: Timestamp, end: Timestamp) -> TimeRange {
+    TimeRange::new(start, end.max(start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: u64, end: u64) -> TimeRange {
+        TimeRange::new(Timestamp::from_micros(start), Timestamp::from_micros(end))
+    }
+
+    #[test]
+    fn changes_merge_per_track_since_a_revision() {
+        let mut log = RevisionLog::new();
+        log.bump(&[(Track::Cursor, Some(range(0, 10)))]);
+        let seen = log.bump(&[(Track::Camera, Some(range(0, 10)))]);
+        log.bump(&[
+            (Track::Camera, Some(range(20, 30))),
+            (Track::Cursor, Some(range(25, 40))),
+        ]);
+        log.bump(&[(Track::Camera, Some(range(5, 22))), (Track::Config, None)]);
+
+        let changes = log.changed_since(seen);
+        assert_eq!(changes.revision, 4);
+        assert_eq!(
+            changes.changes,
+            vec![
+                TrackChange {
+                    track: Track::Cursor,
+                    ranges: Some(vec![range(25, 40)]),
+                },
+                TrackChange {
+                    track: Track::Camera,
+                    ranges: Some(vec![range(5, 30)]),
+                },
+                TrackChange {
+                    track: Track::Config,
+                    ranges: None,
+                },
+            ]
+        );
+        assert!(log.changed_since(4).changes.is_empty());
+    }
+
+    #[test]
+    fn forgotten_revisions_report_everything() {
+        let mut log = RevisionLog::new();
+        let seen = log.bump(&[(Track::Cursor, Some(range(0, 10)))]);
+        // Each revision logs 3 changes, so the limit falls inside one of them.
+        for _ in 0..=REVISION_LOG_LIMIT / 3 {
+            log.bump(&[
+                (Track::Cursor, None),
+                (Track::Focus, None),
+                (Track::Blur, None),
+            ]);
+        }
+        let changes = log.changed_since(seen);
+        assert_eq!(changes.changes.len(), Track::ALL.len());
+        assert!(changes.changes.iter().all(|change| change.ranges.is_none()));
+        assert_eq!(log.changed_since(log.revision() - 1).changes.len(), 3);
+    }
+
+    #[test]
+    fn changed_span_covers_the_differing_items() {
+        let extent = |&t: &u64| range(t, t);
+        assert_eq!(changed_span(&[1, 2, 3], &[1, 2, 3], extent), None);
+        // Replacing 5 with 6 changes the curve from 3 to 8.
+        assert_eq!(
+            changed_span(&[1, 3, 5, 8], &[1, 3, 6, 8], extent),
+            Some(range(3, 8))
+        );
+        assert_eq!(changed_span(&[1, 3], &[1, 3, 9], extent), Some(range(3, 9)));
+    }
+}
@@ -0,0 +1,380 @@
+// Ripple edits: re-projecting timed artifacts when cuts, ramps, or trim change.
+// Camera keyframes, effects, and markers are analysed in source time; the editor shows
+// them in export time. This keeps that projection math out of JS.
+// See design.md: TimeRemapper (Rust)
+
+use serde::{Deserialize, Serialize};
+
+use crate::time_remap::{TimeRange, TimeRemapper};
+use crate::types::{CameraKeyframe, Effect, EffectTrack, EffectType, Timestamp};
+
+/// How one piece of source time sits on the export timeline before and after an edit.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RippleSpan {
+    pub source: TimeRange,
+    /// Export range before the edit; `None` if the piece was cut or trimmed.
+    pub before: Option<TimeRange>,
+    /// Export range after the edit; `None` if the piece is now cut or trimmed.
+    pub after: Option<TimeRange>,
+}
+
+/// The effect of one edit on the export timeline.
+#[derive(Debug, Clone)]
+pub struct RippleDelta {
+    before: TimeRemapper,
+    after: TimeRemapper,
+    spans: Vec<RippleSpan>,
+}
+
+impl RippleDelta {
+    /// Compare two states of the same recording's remapper.
+    pub fn between(before: &TimeRemapper, after: &TimeRemapper) -> Self {
+        // Every segment edge of either table; between two edges nothing changes speed
+        // or crosses a cut in either state.
+        let mut edges: Vec<Timestamp> = before
+            .segments()
+            .iter()
+            .chain(after.segments())
+            .flat_map(|segment| [segment.source.start, segment.source.end])
+            .collect();
+        edges.sort();
+        edges.dedup();
+
+        let mut spans: Vec<RippleSpan> = Vec::new();
+        for pair in edges.windows(2) {
+            let source = TimeRange::new(pair[0], pair[1]);
+            let span = RippleSpan {
+                source,
+                before: before.export_range_of(source),
+                after: after.export_range_of(source),
+            };
+            if span.before.is_none() && span.after.is_none() {
+                continue;
+            }
+
+            if let Some(last) = spans.last_mut() {
+                if last.source.end == source.start
+                    && continues(last.before, span.before)
+                    && continues(last.after, span.after)
+                {
+                    last.source.end = source.end;
+                    last.before = join(last.before, span.before);
+                    last.after = join(last.after, span.after);
+                    continue;
+                }
+            }
+            spans.push(span);
+        }
+
+        RippleDelta {
+            before: before.clone(),
+            after: after.clone(),
+            spans,
+        }
+    }
+
+    /// Source pieces in source order, merged wherever both timelines just continue.
+    pub fn spans(&self) -> &[RippleSpan] {
+        &self.spans
+    }
+
+    /// Whether the edit left every export position unchanged.
+    pub fn is_noop(&self) -> bool {
+        self.spans.iter().all(|span| span.before == span.after)
+    }
+
+    /// Where content shown at `export_time` before the edit is shown after it.
+    /// Returns `None` if that content was removed by the edit.
+    pub fn map_export_time(&self, export_time: Timestamp) -> Option<Timestamp> {
+        if export_time >= self.before.export_duration() {
+            return None;
+        }
+        self.after
+            .to_export_time(self.before.to_source_time(export_time))
+    }
+
+    /// Move keyframes placed on the pre-edit export timeline to the post-edit one.
+    /// Keyframes on removed content are dropped.
+    pub fn ripple_keyframes(&self, keyframes: &[CameraKeyframe]) -> Vec<CameraKeyframe> {
+        retime_keyframes(keyframes, |ts| self.map_export_time(ts))
+    }
+
+    /// Move effects placed on the pre-edit export timeline to the post-edit one.
+    /// Effects whose start was removed are dropped; durations are kept. Progress bars
+    /// are stretched over the new export.
+    pub fn ripple_effects(&self, track: &EffectTrack) -> EffectTrack {
+        let mut rippled = retime_effects(track, |ts| self.map_export_time(ts));
+        span_progress_bars(&mut rippled, track, self.after.export_duration());
+        rippled
+    }
+}
+
+impl TimeRemapper {
+    /// Project source-time keyframes (as produced by analysis) onto the export timeline.
+    /// Keyframes on cut or trimmed content are dropped.
+    pub fn project_keyframes(&self, keyframes: &[CameraKeyframe]) -> Vec<CameraKeyframe> {
+        retime_keyframes(keyframes, |ts| self.to_export_time(ts))
+    }
+
+    /// Project source-time effects onto the export timeline.
+    /// Effects starting on cut or trimmed content are dropped. Durations are kept so
+    /// effects stay readable inside sped-up sections, except for drag ribbons, which
+    /// follow the cursor and so are retimed point by point, and progress bars, which
+    /// always span the whole export.
+    pub fn project_effects(&self, track: &EffectTrack) -> EffectTrack {
+        let mut projected = retime_effects(track, |ts| self.to_export_time(ts));
+        span_progress_bars(&mut projected, track, self.export_duration());
+        projected
+    }
+}
+
+fn retime_keyframes(
+    keyframes: &[CameraKeyframe],
+    map: impl Fn(Timestamp) -> Option<Timestamp>,
+) -> Vec<CameraKeyframe> {
+    keyframes
+        .iter()
+        .filter_map(|keyframe| {
+            map(keyframe.timestamp).map(|timestamp| CameraKeyframe {
+                timestamp,
+                ..keyframe.clone()
+            })
+        })
+        .collect()
+}
+
+fn retime_effects(
+    track: &EffectTrack,
+    map: impl Fn(Timestamp) -> Option<Timestamp>,
+) -> EffectTrack {
+    let mut effects: Vec<_> = track
+        .effects
+        .iter()
+        .filter_map(|effect| {
+            map(effect.timestamp).map(|timestamp| {
+                let mut effect = effect.clone();
+                retime_ribbon(&mut effect, timestamp, &map);
+                effect.timestamp = timestamp;
+                effect
+            })
+        })
+        .collect();
+    effects.sort_by_key(|effect| effect.timestamp);
+    EffectTrack { effects }
+}
+
+/// Replace the retimed progress bars in `retimed` with ones covering the export from
+/// 0 to `duration` (the source bar's start may have been trimmed away).
+fn span_progress_bars(retimed: &mut EffectTrack, source: &EffectTrack, duration: Timestamp) {
+    let is_bar = |effect: &Effect| matches!(effect.effect_type, EffectType::ProgressBar { .. });
+    retimed.effects.retain(|effect| !is_bar(effect));
+    let bars: Vec<Effect> = source
+        .effects
+        .iter()
+        .filter(|effect| is_bar(effect))
+        .map(|effect| Effect {
+            timestamp: Timestamp::from_micros(0),
+            duration_us: duration.as_micros(),
+            ..effect.clone()
+        })
+        .collect();
+    retimed.effects.splice(0..0, bars);
+}
+
+/// Move a drag ribbon's points along with the content under them, dropping points on
+/// removed content. The ribbon ends at its last remaining point.
+fn retime_ribbon(
+    effect: &mut Effect,
+    timestamp: Timestamp,
+    map: &impl Fn(Timestamp) -> Option<Timestamp>,
+) {
+    let EffectType::DragRibbon { points } = &mut effect.effect_type else {
+        return;
+    };
+    let start = effect.timestamp.as_micros();
+    points.retain_mut(
+        |point| match map(Timestamp::from_micros(start + point.offset_us)) {
+            Some(at) => {
+                point.offset_us = at.as_micros().saturating_sub(timestamp.as_micros());
+                true
+            }
+            None => false,
+        },
+    );
+    effect.duration_us = points.last().map_or(0, |point| point.offset_us);
+}
+
+/// Whether `next` picks up exactly where `prev` ended (or both are absent).
+fn continues(prev: Option<TimeRange>, next: Option<TimeRange>) -> bool {
+    match (prev, next) {
+        (None, None) => true,
+        (Some(prev), Some(next)) => prev.end == next.start,
+        _ => false,
+    }
+}
+
+fn join(prev: Option<TimeRange>, next: Option<TimeRange>) -> Option<TimeRange> {
+    match (prev, next) {
+        (Some(prev), Some(next)) => Some(TimeRange::new(prev.start, next.end)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_remap::SpeedRamp;
+    use crate::types::{
+        EasingType, EffectEnvelope, EffectStyle, FrameEdge, KeyframeReason, NormalizedCoord,
+        RibbonPoint, Viewport,
+    };
+
+    fn range(start_us: u64, end_us: u64) -> TimeRange {
+        TimeRange::new(
+            Timestamp::from_micros(start_us),
+            Timestamp::from_micros(end_us),
+        )
+    }
+
+    fn keyframe(us: u64) -> CameraKeyframe {
+        CameraKeyframe {
+            timestamp: Timestamp::from_micros(us),
+            viewport: Viewport::default(),
+            easing: EasingType::Linear,
+            is_manual: false,
+            reason: KeyframeReason::Cursor,
+            hold_us: 0,
+        }
+    }
+
+    #[test]
+    fn adding_a_cut_shifts_later_content() {
+        let before = TimeRemapper::identity(
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        );
+        let mut after = before.clone();
+        after.add_cut(range(2_000_000, 4_000_000));
+
+        let delta = RippleDelta::between(&before, &after);
+        assert_eq!(
+            delta.spans(),
+            &[
+                RippleSpan {
+                    source: range(0, 2_000_000),
+                    before: Some(range(0, 2_000_000)),
+                    after: Some(range(0, 2_000_000)),
+                },
+                RippleSpan {
+                    source: range(2_000_000, 4_000_000),
+                    before: Some(range(2_000_000, 4_000_000)),
+                    after: None,
+                },
+                RippleSpan {
+                    source: range(4_000_000, 10_000_000),
+                    before: Some(range(4_000_000, 10_000_000)),
+                    after: Some(range(2_000_000, 8_000_000)),
+                },
+            ]
+        );
+
+        let rippled = delta.ripple_keyframes(&[
+            keyframe(1_000_000),
+            keyframe(3_000_000),
+            keyframe(5_000_000),
+        ]);
+        let times: Vec<u64> = rippled.iter().map(|k| k.timestamp.as_micros()).collect();
+        assert_eq!(times, vec![1_000_000, 3_000_000]);
+        assert!(!delta.is_noop());
+        assert!(RippleDelta::between(&after, &after).is_noop());
+    }
+
+    #[test]
+    fn projects_source_tracks_through_ramps() {
+        let remapper = TimeRemapper::new(
+            vec![range(1_000_000, 2_000_000)],
+            vec![SpeedRamp::new(range(4_000_000, 8_000_000), 2.0)],
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        );
+        let projected = remapper.project_keyframes(&[
+            keyframe(500_000),
+            keyframe(1_500_000),
+            keyframe(6_000_000),
+            keyframe(9_000_000),
+        ]);
+        let times: Vec<u64> = projected.iter().map(|k| k.timestamp.as_micros()).collect();
+        // 6s: 1s before the cut, 2s at 1x after it, then 2s of source at 2x.
+        assert_eq!(times, vec![500_000, 4_000_000, 6_000_000]);
+    }
+
+    #[test]
+    fn progress_bars_span_the_export() {
+        let mut remapper = TimeRemapper::identity(
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        );
+        remapper.set_trim(
+            Timestamp::from_micros(1_000_000),
+            Timestamp::from_micros(9_000_000),
+        );
+        remapper.add_cut(range(4_000_000, 6_000_000));
+        let track = EffectTrack {
+            effects: vec![Effect {
+                timestamp: Timestamp::from_micros(0),
+                duration_us: 10_000_000,
+                effect_type: EffectType::ProgressBar {
+                    edge: FrameEdge::Bottom,
+                    thickness: 0.01,
+                },
+                position: NormalizedCoord::new(0.5, 0.995),
+                style: EffectStyle::default(),
+                envelope: EffectEnvelope::default(),
+            }],
+        };
+
+        let projected = remapper.project_effects(&track);
+        assert_eq!(projected.effects.len(), 1);
+        let bar = &projected.effects[0];
+        assert_eq!(bar.timestamp.as_micros(), 0);
+        assert_eq!(bar.duration_us, 6_000_000);
+        let state = bar.state_at(Timestamp::from_micros(3_000_000)).unwrap();
+        assert!((state.progress - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn drag_ribbons_follow_ramps() {
+        let remapper = TimeRemapper::new(
+            vec![],
+            vec![SpeedRamp::new(range(4_000_000, 8_000_000), 2.0)],
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        );
+        let point = |offset_us: u64| RibbonPoint {
+            offset_us,
+            position: NormalizedCoord::center(),
+        };
+        let track = EffectTrack {
+            effects: vec![Effect {
+                timestamp: Timestamp::from_micros(3_000_000),
+                duration_us: 3_000_000,
+                effect_type: EffectType::DragRibbon {
+                    points: vec![point(0), point(1_000_000), point(3_000_000)],
+                },
+                position: NormalizedCoord::center(),
+                style: EffectStyle::default(),
+                envelope: EffectEnvelope::default(),
+            }],
+        };
+
+        let projected = remapper.project_effects(&track);
+        let effect = &projected.effects[0];
+        // 1s at 1x up to the ramp, then 2s of source at 2x.
+        assert_eq!(effect.duration_us, 2_000_000);
+        let EffectType::DragRibbon { points } = &effect.effect_type else {
+            panic!("Expected a drag ribbon");
+        };
+        let offsets: Vec<u64> = points.iter().map(|p| p.offset_us).collect();
+        assert_eq!(offsets, vec![0, 1_000_000, 2_000_000]);
+    }
+}
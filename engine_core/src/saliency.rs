@@ -0,0 +1,227 @@
+// Saliency from small grayscale frames (Tier B).
+// Screen/window captures have no telemetry; motion between frames and local contrast stand in
+// for the focus signals a content script would send.
+// See steering.md: Auto-Zoom Strategy Rules
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::EngineError;
+use crate::types::*;
+
+/// A downscaled grayscale frame (e.g. 64x36), one byte per pixel, row-major.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrayFrame {
+    pub timestamp: Timestamp,
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+/// Pixels below this saliency (0-255 scale) never make a region.
+const MIN_SALIENCY: f32 = 24.0;
+/// Local contrast counts this much relative to frame-to-frame motion.
+const CONTRAST_WEIGHT: f32 = 0.5;
+/// Regions driven by contrast alone are capped below the camera's 0.8 threshold, so
+/// a static screen doesn't make the camera wander.
+const CONTRAST_ONLY_IMPORTANCE: f32 = 0.7;
+/// Per-frame smoothing of each pixel's "keeps moving" level.
+const PERSISTENCE_RATE: f32 = 0.2;
+/// Pixels above this level (about five moving frames in a row) are treated as playing
+/// video: their motion is ignored.
+const PERSISTENT_LEVEL: f32 = 0.6;
+
+/// Finds the most salient area of each frame, comparing against the previous frame.
+#[derive(Default)]
+pub struct SaliencyAnalyzer {
+    previous: Option<GrayFrame>,
+    /// Per pixel, how consistently it has been moving (0.0-1.0).
+    persistence: Vec<f32>,
+    /// Bounds of the pixels that keep moving, as of the last frame.
+    persistent_motion: Option<NormalizedRect>,
+}
+
+impl SaliencyAnalyzer {
+    pub fn new() -> Self {
+        SaliencyAnalyzer::default()
+    }
+
+    /// Forget the previous frame.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.persistence.clear();
+        self.persistent_motion = None;
+    }
+
+    /// Area that has kept moving for the last several frames (typically a playing
+    /// video). Motion there doesn't count towards saliency.
+    pub fn persistent_motion(&self) -> Option<NormalizedRect> {
+        self.persistent_motion
+    }
+
+    /// Focus region around the pixels at least half as salient as the frame's peak.
+    /// Small areas of change score highest; a frame that changes everywhere (a scroll
+    /// or scene change) scores low. Returns `None` for frames with nothing salient.
+    pub fn analyze_frame(&mut self, frame: GrayFrame) -> Result<Option<FocusRegion>, EngineError> {
+        let (w, h) = (frame.width as usize, frame.height as usize);
+        if w == 0 || h == 0 || frame.pixels.len() != w * h {
+            return Err(EngineError::FrameAnalysis {
+                frame_index: 0,
+                message: format!(
+                    "{} pixels for a {}x{} frame",
+                    frame.pixels.len(),
+                    frame.width,
+                    frame.height
+                ),
+            });
+        }
+        // A resolution change restarts motion detection.
+        let previous = self
+            .previous
+            .take()
+            .filter(|p| p.width == frame.width && p.height == frame.height);
+        if previous.is_none() {
+            self.persistence = vec![0.0; w * h];
+        }
+
+        let at = |x: usize, y: usize| frame.pixels[y * w + x] as f32;
+        let mut saliency = vec![0.0f32; w * h];
+        let mut has_motion = false;
+        for y in 0..h {
+            for x in 0..w {
+                let neighbours = [
+                    at(x.saturating_sub(1), y),
+                    at((x + 1).min(w - 1), y),
+                    at(x, y.saturating_sub(1)),
+                    at(x, (y + 1).min(h - 1)),
+                ];
+                let contrast = (at(x, y) - neighbours.iter().sum::<f32>() / 4.0).abs();
+                let mut motion = previous
+                    .as_ref()
+                    .map_or(0.0, |p| (at(x, y) - p.pixels[y * w + x] as f32).abs());
+                let level = &mut self.persistence[y * w + x];
+                let moving = if motion >= MIN_SALIENCY { 1.0 } else { 0.0 };
+                *level += (moving - *level) * PERSISTENCE_RATE;
+                if *level >= PERSISTENT_LEVEL {
+                    motion = 0.0;
+                }
+                has_motion |= motion >= MIN_SALIENCY;
+                saliency[y * w + x] = motion + CONTRAST_WEIGHT * contrast;
+            }
+        }
+
+        let timestamp = frame.timestamp;
+        self.previous = Some(frame);
+        self.persistent_motion = bounding_box(w, h, |i| self.persistence[i] >= PERSISTENT_LEVEL);
+
+        let peak = saliency.iter().copied().fold(0.0, f32::max);
+        if peak < MIN_SALIENCY {
+            return Ok(None);
+        }
+        let Some(bounds) = bounding_box(w, h, |i| saliency[i] >= peak * 0.5) else {
+            return Ok(None);
+        };
+        let mut importance = (1.0 - bounds.width * bounds.height).clamp(0.0, 1.0);
+        if !has_motion {
+            importance = importance.min(CONTRAST_ONLY_IMPORTANCE);
+        }
+        Ok(Some(FocusRegion {
+            timestamp,
+            bounds,
+            importance,
+            element: None,
+            track_id: None,
+        }))
+    }
+}
+
+/// Normalized bounds of the pixels matching `hit`, if any.
+fn bounding_box(w: usize, h: usize, hit: impl Fn(usize) -> bool) -> Option<NormalizedRect> {
+    let (mut x0, mut y0, mut x1, mut y1) = (w, h, 0, 0);
+    for i in (0..w * h).filter(|&i| hit(i)) {
+        let (x, y) = (i % w, i / w);
+        x0 = x0.min(x);
+        y0 = y0.min(y);
+        x1 = x1.max(x + 1);
+        y1 = y1.max(y + 1);
+    }
+    (x1 > x0).then(|| {
+        NormalizedRect::new(
+            x0 as f32 / w as f32,
+            y0 as f32 / h as f32,
+            (x1 - x0) as f32 / w as f32,
+            (y1 - y0) as f32 / h as f32,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(us: u64, lit: &[(usize, usize)]) -> GrayFrame {
+        let mut pixels = vec![100u8; 16 * 8];
+        for &(x, y) in lit {
+            pixels[y * 16 + x] = 220;
+        }
+        GrayFrame {
+            timestamp: Timestamp::from_micros(us),
+            width: 16,
+            height: 8,
+            pixels,
+        }
+    }
+
+    #[test]
+    fn motion_makes_an_important_region() {
+        let mut analyzer = SaliencyAnalyzer::new();
+        // A flat first frame has nothing salient.
+        assert!(analyzer.analyze_frame(frame(0, &[])).unwrap().is_none());
+
+        // A small block lights up in the top-right quarter.
+        let region = analyzer
+            .analyze_frame(frame(33_000, &[(12, 1), (13, 1), (12, 2), (13, 2)]))
+            .unwrap()
+            .expect("Should find the changed block");
+        assert!(region.importance >= 0.8);
+        let center = region.bounds.center();
+        assert!(center.x > 0.7 && center.y < 0.4);
+
+        // Unchanged since: contrast alone stays below the camera's threshold.
+        let region = analyzer
+            .analyze_frame(frame(66_000, &[(12, 1), (13, 1), (12, 2), (13, 2)]))
+            .unwrap()
+            .expect("Block is still high-contrast");
+        assert!(region.importance < 0.8);
+
+        assert!(analyzer
+            .analyze_frame(GrayFrame {
+                pixels: vec![0; 3],
+                ..frame(0, &[])
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn ignores_persistent_motion() {
+        let mut analyzer = SaliencyAnalyzer::new();
+        // A "video" in the left quarter flickers every frame.
+        let video: Vec<(usize, usize)> = (0..4).flat_map(|x| (2..6).map(move |y| (x, y))).collect();
+        for i in 0..10u64 {
+            let lit = if i % 2 == 0 { &video[..] } else { &[] };
+            analyzer.analyze_frame(frame(i * 33_000, lit)).unwrap();
+        }
+        let area = analyzer
+            .persistent_motion()
+            .expect("Video should be detected");
+        assert_eq!(area, NormalizedRect::new(0.0, 0.25, 0.25, 0.5));
+
+        // A real change elsewhere wins over the still-flickering video.
+        let mut lit = video.clone();
+        lit.push((14, 1));
+        let region = analyzer
+            .analyze_frame(frame(330_000, &lit))
+            .unwrap()
+            .expect("Should find the change");
+        assert!(region.bounds.center().x > 0.8);
+    }
+}
@@ -0,0 +1,181 @@
+// Signal sanitization: ordering and de-duplication before analysis.
+// Events from several content-script frames can arrive out of order or twice; every later stage
+// assumes a clean, time-ordered stream, so this fixes what it can and reports what it fixed.
+// See steering.md: Rust Rules (Engine)
+
+use std::borrow::Cow;
+
+use crate::types::*;
+
+/// Sorts and de-duplicates incoming batches, carrying the last timestamp across chunks.
+#[derive(Default)]
+pub struct SignalSanitizer {
+    /// Events at the latest timestamp processed so far (for duplicates split across
+    /// chunks).
+    boundary: Vec<InputEvent>,
+}
+
+impl SignalSanitizer {
+    pub fn new() -> Self {
+        SignalSanitizer::default()
+    }
+
+    /// Forget earlier chunks.
+    pub fn reset(&mut self) {
+        self.boundary.clear();
+    }
+
+    /// Sort a batch by timestamp (keeping arrival order for ties), drop duplicates and
+    /// events older than earlier chunks, and report each kind of fix applied.
+    pub fn sanitize<'a>(
+        &mut self,
+        signals: &'a SignalBatch,
+    ) -> (Cow<'a, SignalBatch>, Vec<SignalWarning>) {
+        let last = self.boundary.first().map(|event| event.timestamp);
+        let mut out_of_order = 0;
+        let mut latest = last;
+        for event in &signals.events {
+            if latest.is_some_and(|latest| event.timestamp < latest) {
+                out_of_order += 1;
+            } else {
+                latest = Some(event.timestamp);
+            }
+        }
+
+        let mut events = signals.events.clone();
+        events.sort_by_key(|event| event.timestamp);
+        let before = events.len();
+        events.retain(|event| last.is_none_or(|last| event.timestamp >= last));
+        let late = before - events.len();
+
+        let mut kept: Vec<InputEvent> = Vec::with_capacity(events.len());
+        let mut duplicates = 0;
+        for event in events {
+            // Only events sharing a timestamp can be identical; they sit together.
+            let group_start = kept.partition_point(|e| e.timestamp < event.timestamp);
+            let seen = kept[group_start..].contains(&event)
+                || (Some(event.timestamp) == last && self.boundary.contains(&event));
+            if seen {
+                duplicates += 1;
+            } else {
+                kept.push(event);
+            }
+        }
+
+        if let Some(newest) = kept.last().map(|event| event.timestamp) {
+            if Some(newest) != last {
+                self.boundary.clear();
+            }
+            let group_start = kept.partition_point(|e| e.timestamp < newest);
+            self.boundary.extend_from_slice(&kept[group_start..]);
+        }
+
+        // Late events were counted as out of order too; report them once.
+        let out_of_order = out_of_order - late;
+        let mut warnings = Vec::new();
+        let mut warn = |kind, count: usize, message: String| {
+            if count > 0 {
+                warnings.push(SignalWarning {
+                    kind,
+                    count: count as u32,
+                    message,
+                });
+            }
+        };
+        warn(
+            SignalWarningKind::OutOfOrder,
+            out_of_order,
+            format!(
+                "{} events arrived out of order and were sorted",
+                out_of_order
+            ),
+        );
+        warn(
+            SignalWarningKind::Duplicate,
+            duplicates,
+            format!("{} duplicate events were dropped", duplicates),
+        );
+        warn(
+            SignalWarningKind::Late,
+            late,
+            format!(
+                "{} events were older than an earlier chunk and were dropped",
+                late
+            ),
+        );
+
+        if warnings.is_empty() {
+            (Cow::Borrowed(signals), warnings)
+        } else {
+            (Cow::Owned(SignalBatch { events: kept }), warnings)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mv(us: u64, x: f32) -> InputEvent {
+        InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::MouseMove {
+                position: NormalizedCoord::new(x, 0.5),
+            },
+        }
+    }
+
+    #[test]
+    fn sorts_dedups_and_reports() {
+        let mut sanitizer = SignalSanitizer::new();
+        let first = SignalBatch {
+            events: vec![
+                mv(0, 0.1),
+                mv(2000, 0.3),
+                mv(1000, 0.2),
+                mv(2000, 0.3),
+                mv(2000, 0.4),
+            ],
+        };
+        let (batch, warnings) = sanitizer.sanitize(&first);
+        let times: Vec<u64> = batch
+            .events
+            .iter()
+            .map(|e| e.timestamp.as_micros())
+            .collect();
+        assert_eq!(times, vec![0, 1000, 2000, 2000]);
+        let counts: Vec<(SignalWarningKind, u32)> =
+            warnings.iter().map(|w| (w.kind, w.count)).collect();
+        assert_eq!(
+            counts,
+            vec![
+                (SignalWarningKind::OutOfOrder, 1),
+                (SignalWarningKind::Duplicate, 1)
+            ]
+        );
+
+        // Next chunk: a repeat of the last event and one from the past.
+        let second = SignalBatch {
+            events: vec![mv(2000, 0.4), mv(500, 0.9), mv(3000, 0.5)],
+        };
+        let (batch, warnings) = sanitizer.sanitize(&second);
+        assert_eq!(batch.events.len(), 1);
+        let counts: Vec<(SignalWarningKind, u32)> =
+            warnings.iter().map(|w| (w.kind, w.count)).collect();
+        assert_eq!(
+            counts,
+            vec![
+                (SignalWarningKind::Duplicate, 1),
+                (SignalWarningKind::Late, 1)
+            ]
+        );
+
+        // Clean batches pass through untouched.
+        let clean = SignalBatch {
+            events: vec![mv(4000, 0.5), mv(5000, 0.6)],
+        };
+        let (batch, warnings) = sanitizer.sanitize(&clean);
+        assert!(matches!(batch, Cow::Borrowed(_)));
+        assert!(warnings.is_empty());
+    }
+}
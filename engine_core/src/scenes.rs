@@ -0,0 +1,145 @@
+// Scene (shot) change detection from per-frame perceptual hashes.
+// A page navigation or app switch changes most of the frame at once; each boundary
+// becomes a chapter marker, a camera reset to full view, and (for slow transitions)
+// a cut suggestion.
+// See steering.md: Auto-Zoom Strategy Rules
+
+use serde::{Deserialize, Serialize};
+
+use crate::suggest::{hamming, FrameHash};
+use crate::time_remap::MarkerConfig;
+use crate::types::Timestamp;
+
+/// Thresholds for scene change detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneSettings {
+    /// Min Hamming distance between consecutive frame hashes for a scene change.
+    #[serde(default = "default_min_distance")]
+    pub min_distance: u32,
+    /// Changes closer than this to the previous one extend the same transition,
+    /// in microseconds.
+    #[serde(default = "default_min_scene")]
+    pub min_scene_us: u64,
+    /// Shortest transition worth suggesting as a cut, in microseconds.
+    #[serde(default = "default_min_transition")]
+    pub min_transition_us: u64,
+}
+
+fn default_min_distance() -> u32 {
+    20
+}
+fn default_min_scene() -> u64 {
+    1_000_000
+}
+fn default_min_transition() -> u64 {
+    500_000
+}
+
+impl Default for SceneSettings {
+    fn default() -> Self {
+        SceneSettings {
+            min_distance: default_min_distance(),
+            min_scene_us: default_min_scene(),
+            min_transition_us: default_min_transition(),
+        }
+    }
+}
+
+/// A scene boundary. Transitions that take several frames (loading flashes, fades)
+/// start at `transition_start` and settle at `timestamp`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SceneChange {
+    /// First frame of the new scene.
+    pub timestamp: Timestamp,
+    /// First frame that differed from the old scene.
+    pub transition_start: Timestamp,
+    /// Largest frame-to-frame Hamming distance in the transition.
+    pub distance: u32,
+}
+
+impl SceneChange {
+    pub fn transition_us(&self) -> u64 {
+        self.timestamp.as_micros() - self.transition_start.as_micros()
+    }
+}
+
+/// Result of scene detection: the boundaries plus chapter markers for them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneReport {
+    pub changes: Vec<SceneChange>,
+    pub markers: Vec<MarkerConfig>,
+}
+
+/// Scene boundaries in time order. Frames may be given in any order.
+pub fn detect_scene_changes(frames: &[FrameHash], settings: &SceneSettings) -> Vec<SceneChange> {
+    let mut frames = frames.to_vec();
+    frames.sort_by_key(|f| f.timestamp);
+
+    let mut changes: Vec<SceneChange> = Vec::new();
+    for pair in frames.windows(2) {
+        let distance = hamming(pair[0].hash, pair[1].hash);
+        if distance < settings.min_distance {
+            continue;
+        }
+        let timestamp = pair[1].timestamp;
+        match changes.last_mut() {
+            Some(last)
+                if timestamp.as_micros() - last.timestamp.as_micros() < settings.min_scene_us =>
+            {
+                last.timestamp = timestamp;
+                last.distance = last.distance.max(distance);
+            }
+            _ => changes.push(SceneChange {
+                timestamp,
+                transition_start: timestamp,
+                distance,
+            }),
+        }
+    }
+    changes
+}
+
+/// Chapter markers for the scenes after the first ("Scene 2", "Scene 3", ...), ready
+/// for `TimeRemapperConfig::markers`.
+pub fn scene_markers(changes: &[SceneChange]) -> Vec<MarkerConfig> {
+    changes
+        .iter()
+        .enumerate()
+        .map(|(i, change)| MarkerConfig {
+            time_us: change.timestamp.as_micros(),
+            label: format!("Scene {}", i + 2),
+            color: String::new(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_multi_frame_transitions() {
+        // 30 fps; a hard cut at frame 10, then a 10-frame loading flash from frame 60.
+        let frames: Vec<FrameHash> = (0..120u64)
+            .map(|i| FrameHash {
+                timestamp: Timestamp::from_micros(i * 33_333),
+                hash: match i {
+                    0..=9 => 0,
+                    10..=59 => u64::MAX,
+                    60..=69 => (i % 2) * 0xFFFF_FFFF,
+                    _ => 0xFFFF_0000_FFFF_0000,
+                },
+            })
+            .collect();
+
+        let changes = detect_scene_changes(&frames, &SceneSettings::default());
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].timestamp.as_micros(), 10 * 33_333);
+        assert_eq!(changes[0].transition_us(), 0);
+        assert_eq!(changes[1].transition_start.as_micros(), 60 * 33_333);
+        assert_eq!(changes[1].timestamp.as_micros(), 70 * 33_333);
+
+        let markers = scene_markers(&changes);
+        assert_eq!(markers[1].label, "Scene 3");
+    }
+}
@@ -0,0 +1,39 @@
+// JSON Schemas of the messages crossing the engine boundary, generated from the serde
+// types so the extension and external tools validate against the structs themselves.
+// Built with the `schema` feature; `cargo run --features schema --bin schemas` prints them.
+// See steering.md: Rust Rules (Engine)
+
+use schemars::schema_for;
+use serde_json::{json, Value};
+
+use crate::time_remap::TimeRemapperConfig;
+use crate::types::{AnalysisResult, EngineConfig, SignalBatch};
+
+/// JSON Schema (draft-07) of each config and result type, keyed by type name.
+pub fn json_schemas() -> Value {
+    json!({
+        "EngineConfig": schema_for!(EngineConfig),
+        "SignalBatch": schema_for!(SignalBatch),
+        "TimeRemapperConfig": schema_for!(TimeRemapperConfig),
+        "AnalysisResult": schema_for!(AnalysisResult),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schemas_describe_the_serde_shapes() {
+        let schemas = json_schemas();
+        let config = &schemas["EngineConfig"];
+        assert_eq!(config["required"], json!(["capture_mode"]));
+        assert!(config["properties"]["privacy"].is_object());
+
+        let remapper = &schemas["TimeRemapperConfig"];
+        let required = remapper["required"].as_array().unwrap();
+        assert!(required.contains(&json!("in_point_us")));
+        assert!(!required.contains(&json!("cuts")));
+        assert!(schemas["AnalysisResult"]["definitions"]["CameraKeyframe"].is_object());
+    }
+}
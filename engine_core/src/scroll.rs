@@ -0,0 +1,156 @@
+// Scroll-offset compensation between page and viewport coordinates.
+// Content scripts may report page-relative positions while the capture shows the viewport (or the
+// reverse); cumulative scroll deltas line them up before any analysis runs.
+// See steering.md: Rust Rules (Engine)
+
+use std::borrow::Cow;
+
+use crate::types::*;
+
+/// Tracks the scroll offset across batches and moves event positions into the
+/// capture's coordinate space.
+pub struct ScrollCompensator {
+    settings: ScrollSettings,
+    /// Cumulative vertical scroll, in normalized viewport heights.
+    offset_y: f32,
+}
+
+impl ScrollCompensator {
+    pub fn new(settings: ScrollSettings) -> Self {
+        ScrollCompensator {
+            settings,
+            offset_y: 0.0,
+        }
+    }
+
+    /// Replace scroll settings. Applies to signals processed after this call.
+    pub fn set_settings(&mut self, settings: ScrollSettings) {
+        self.settings = settings;
+    }
+
+    /// Forget the scroll offset so the next batch starts at the top of the page.
+    pub fn reset(&mut self) {
+        self.offset_y = 0.0;
+    }
+
+    /// Current scroll offset, in normalized viewport heights.
+    pub fn offset_y(&self) -> f32 {
+        self.offset_y
+    }
+
+    /// Shift the positions and bounds in a batch by the scroll offset at each event.
+    /// Frame-detected cursors are already in capture space and are left alone.
+    pub fn compensate<'a>(&mut self, signals: &'a SignalBatch) -> Cow<'a, SignalBatch> {
+        let sign = match self.settings.compensation {
+            ScrollCompensation::None => return Cow::Borrowed(signals),
+            ScrollCompensation::PageToViewport => -1.0,
+            ScrollCompensation::ViewportToPage => 1.0,
+        };
+        let scale = if self.settings.delta_scale > 0.0 {
+            self.settings.delta_scale
+        } else {
+            1.0
+        };
+
+        let mut batch = signals.clone();
+        for event in &mut batch.events {
+            let shift = sign * self.offset_y;
+            match &mut event.event_type {
+                EventType::Scroll { delta_y } => {
+                    // The page can't scroll above its top.
+                    self.offset_y = (self.offset_y + *delta_y / scale).max(0.0);
+                }
+                EventType::MouseMove { position }
+                | EventType::MouseClick { position, .. }
+                | EventType::MouseDown { position, .. }
+                | EventType::MouseUp { position, .. }
+                | EventType::Touch { position, .. }
+                | EventType::Pen { position, .. } => position.y += shift,
+                EventType::FocusChange { bounds, .. }
+                | EventType::TextSelection { bounds }
+                | EventType::VideoPlaying { bounds, .. } => bounds.y += shift,
+                EventType::FrameCaptured { .. }
+                | EventType::CursorDetected { .. }
+                | EventType::Pause
+                | EventType::Resume
+                | EventType::WindowMoved { .. }
+                | EventType::KeyPress { .. }
+                | EventType::KeyDown { .. }
+                | EventType::KeyUp { .. } => {}
+            }
+        }
+        Cow::Owned(batch)
+    }
+}
+
+impl Default for ScrollCompensator {
+    fn default() -> Self {
+        Self::new(ScrollSettings::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(us: u64, event_type: EventType) -> InputEvent {
+        InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type,
+        }
+    }
+
+    #[test]
+    fn page_positions_follow_the_scroll() {
+        let mut compensator = ScrollCompensator::new(ScrollSettings {
+            compensation: ScrollCompensation::PageToViewport,
+            delta_scale: 800.0,
+            ..Default::default()
+        });
+        let signals = SignalBatch {
+            events: vec![
+                event(0, EventType::Scroll { delta_y: 400.0 }),
+                event(
+                    1000,
+                    EventType::MouseClick {
+                        position: NormalizedCoord::new(0.5, 0.9),
+                        button: 0,
+                        element: None,
+                    },
+                ),
+                event(
+                    2000,
+                    EventType::FocusChange {
+                        bounds: NormalizedRect::new(0.1, 1.2, 0.3, 0.1),
+                        element: None,
+                    },
+                ),
+            ],
+        };
+
+        let compensated = compensator.compensate(&signals);
+        assert!(matches!(
+            compensated.events[1].event_type,
+            EventType::MouseClick { position, .. } if (position.y - 0.4).abs() < 1e-6
+        ));
+        assert!(matches!(
+            compensated.events[2].event_type,
+            EventType::FocusChange { bounds, .. } if (bounds.y - 0.7).abs() < 1e-6
+        ));
+
+        // The offset carries into the next batch, and never goes above the page top.
+        compensator.compensate(&SignalBatch {
+            events: vec![event(3000, EventType::Scroll { delta_y: -2000.0 })],
+        });
+        assert_eq!(compensator.offset_y(), 0.0);
+    }
+
+    #[test]
+    fn no_compensation_borrows() {
+        let mut compensator = ScrollCompensator::default();
+        let signals = SignalBatch {
+            events: vec![event(0, EventType::Scroll { delta_y: 1.0 })],
+        };
+        assert!(matches!(compensator.compensate(&signals), Cow::Borrowed(_)));
+    }
+}
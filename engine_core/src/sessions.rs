@@ -0,0 +1,93 @@
+// Interaction sessions: runs of clicks close together in time and space.
+// A session is one piece of work on one part of the screen (filling a form, stepping through a
+// menu), so the camera can hold a single framing for it and edits can respect its boundaries.
+// See steering.md: Auto-Zoom Strategy Rules
+
+use crate::types::*;
+
+/// Group the clicks in `events` into sessions. A click joins the current session if it
+/// comes within `max_click_gap_us` of the session's last click and keeps the session
+/// inside `max_span`; otherwise it starts a new one. Sessions come out in time order.
+pub fn cluster_clicks(
+    events: &[InputEvent],
+    settings: &SessionSettings,
+) -> Vec<InteractionSession> {
+    let mut clicks: Vec<(Timestamp, NormalizedCoord)> = events
+        .iter()
+        .filter_map(|event| match &event.event_type {
+            EventType::MouseClick { position, .. } => Some((event.timestamp, *position)),
+            _ => None,
+        })
+        .collect();
+    clicks.sort_by_key(|(ts, _)| *ts);
+
+    let mut sessions: Vec<InteractionSession> = Vec::new();
+    for (timestamp, position) in clicks {
+        let point = NormalizedRect::new(position.x, position.y, 0.0, 0.0);
+        if let Some(session) = sessions.last_mut() {
+            let gap = timestamp.as_micros() - session.end.as_micros();
+            let bounds = session.bounds.union(&point);
+            if gap <= settings.max_click_gap_us
+                && bounds.width.max(bounds.height) <= settings.max_span
+            {
+                session.end = timestamp;
+                session.bounds = bounds;
+                session.clicks += 1;
+                continue;
+            }
+        }
+        sessions.push(InteractionSession {
+            start: timestamp,
+            end: timestamp,
+            bounds: point,
+            clicks: 1,
+        });
+    }
+    sessions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(us: u64, x: f32, y: f32) -> InputEvent {
+        InputEvent {
+            timestamp: Timestamp::from_micros(us),
+            event_type: EventType::MouseClick {
+                position: NormalizedCoord::new(x, y),
+                button: 0,
+                element: None,
+            },
+        }
+    }
+
+    #[test]
+    fn clusters_nearby_clicks() {
+        let events = vec![
+            // A form: three clicks down one column.
+            click(0, 0.2, 0.2),
+            click(1_000_000, 0.2, 0.3),
+            click(2_500_000, 0.25, 0.4),
+            // Straight after, far across the screen.
+            click(3_000_000, 0.8, 0.8),
+            // Back near the form, but much later.
+            click(10_000_000, 0.2, 0.4),
+        ];
+
+        let sessions = cluster_clicks(&events, &SessionSettings::default());
+        let summary: Vec<(u64, u64, u32)> = sessions
+            .iter()
+            .map(|s| (s.start.as_micros(), s.end.as_micros(), s.clicks))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, 2_500_000, 3),
+                (3_000_000, 3_000_000, 1),
+                (10_000_000, 10_000_000, 1)
+            ]
+        );
+        let bounds = sessions[0].bounds;
+        assert!((bounds.width - 0.05).abs() < 1e-6 && (bounds.height - 0.2).abs() < 1e-6);
+    }
+}
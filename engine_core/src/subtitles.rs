@@ -0,0 +1,324 @@
+// Text tracks on the export timeline: WebVTT chapters from markers, so uploads and
+// players pick up chapters without re-entering them, and captions of what was typed.
+// See design.md: TimeRemapper (Rust)
+
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+
+use crate::blur::is_sensitive;
+use crate::time_remap::{TimeRange, TimeRemapper};
+use crate::types::{ElementInfo, EventType, PrivacySettings, SignalBatch, Timestamp};
+
+/// A pause longer than this between keys starts a new caption (microseconds).
+const TYPING_GAP_US: u64 = 1_500_000;
+/// How long a caption stays up after its last key (microseconds).
+const TYPING_HOLD_US: u64 = 1_500_000;
+/// Typed text longer than this is cut off with an ellipsis.
+const CAPTION_MAX_CHARS: usize = 40;
+
+/// Caption file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaptionFormat {
+    Srt,
+    Vtt,
+}
+
+/// WebVTT chapter track of the export: each marker (on the export timeline, so after
+/// cuts and ramps) starts a chapter that runs to the next marker or the end. Markers
+/// landing on the same export time keep the last one.
+pub fn chapters_vtt(remapper: &TimeRemapper) -> String {
+    let end = remapper.export_duration();
+    let markers = remapper.export_markers();
+
+    let mut vtt = String::from("WEBVTT\n");
+    let mut cue = 0;
+    for (i, marker) in markers.iter().enumerate() {
+        let next = markers.get(i + 1).map_or(end, |next| next.export_time);
+        if next <= marker.export_time {
+            continue;
+        }
+        cue += 1;
+        let _ = write!(
+            vtt,
+            "\n{}\n{} --> {}\n{}\n",
+            cue,
+            vtt_timestamp(marker.export_time),
+            vtt_timestamp(next),
+            cue_text(&marker.marker.label)
+        );
+    }
+    vtt
+}
+
+/// Captions of text typed into focused text fields (`typing: npm install`), on the
+/// export timeline. Each run of typing is one cue, ending at Enter, Tab, a focus change,
+/// or a pause; Backspace takes back the last character and shortcuts are left out.
+/// Fields the privacy settings treat as sensitive are never captioned, and runs whose
+/// start is cut are dropped.
+pub fn typing_captions(
+    signals: &SignalBatch,
+    privacy: &PrivacySettings,
+    remapper: &TimeRemapper,
+    format: CaptionFormat,
+) -> String {
+    let mut cues = typing_cues(signals, privacy);
+    // A caption gives way to the next one.
+    for i in 1..cues.len() {
+        let next_start = cues[i].0.start;
+        cues[i - 1].0.end = cues[i - 1].0.end.min(next_start);
+    }
+
+    let mut captions = match format {
+        CaptionFormat::Srt => String::new(),
+        CaptionFormat::Vtt => String::from("WEBVTT\n"),
+    };
+    let mut cue = 0;
+    for (range, text) in cues {
+        let Some(range) = remapper.export_range_of(range) else {
+            continue;
+        };
+        if range.duration() == 0 {
+            continue;
+        }
+        cue += 1;
+        let text = format!("typing: {}", cue_text(&text));
+        let _ = match format {
+            CaptionFormat::Srt => write!(
+                captions,
+                "{}{}\n{} --> {}\n{}\n",
+                if cue > 1 { "\n" } else { "" },
+                cue,
+                srt_timestamp(range.start),
+                srt_timestamp(range.end),
+                text
+            ),
+            CaptionFormat::Vtt => write!(
+                captions,
+                "\n{}\n{} --> {}\n{}\n",
+                cue,
+                vtt_timestamp(range.start),
+                vtt_timestamp(range.end),
+                text
+            ),
+        };
+    }
+    captions
+}
+
+/// Runs of typing in source time, with their text.
+fn typing_cues(signals: &SignalBatch, privacy: &PrivacySettings) -> Vec<(TimeRange, String)> {
+    let is_text_field = |element: &ElementInfo| {
+        matches!(element.tag.as_str(), "input" | "textarea")
+            || matches!(
+                element.role.as_deref(),
+                Some("textbox" | "searchbox" | "combobox")
+            )
+    };
+
+    let mut cues = Vec::new();
+    // Start, last key, and text of the run being typed.
+    let mut run: Option<(Timestamp, Timestamp, String)> = None;
+    let mut flush = |run: &mut Option<(Timestamp, Timestamp, String)>| {
+        if let Some((start, last, text)) = run.take() {
+            if !text.trim().is_empty() {
+                let end = Timestamp::from_micros(last.as_micros() + TYPING_HOLD_US);
+                cues.push((TimeRange::new(start, end), truncate(text.trim())));
+            }
+        }
+    };
+    let mut captioned = false;
+    // Key of a `KeyDown` still waiting for its `KeyPress`.
+    let mut pending_press: Option<&str> = None;
+
+    for event in &signals.events {
+        let (key, modifiers) = match &event.event_type {
+            EventType::FocusChange { element, .. } => {
+                flush(&mut run);
+                captioned = element.as_ref().is_some_and(|element| {
+                    is_text_field(element) && !is_sensitive(element, privacy)
+                });
+                continue;
+            }
+            EventType::KeyDown { key, modifiers } => {
+                pending_press = Some(key);
+                (key, modifiers)
+            }
+            EventType::KeyPress { key, modifiers } => {
+                if pending_press.take() == Some(key.as_str()) {
+                    continue;
+                }
+                (key, modifiers)
+            }
+            _ => continue,
+        };
+        if !captioned || modifiers.ctrl || modifiers.alt || modifiers.meta {
+            continue;
+        }
+        let ts = event.timestamp;
+        if run
+            .as_ref()
+            .is_some_and(|(_, last, _)| ts.as_micros() - last.as_micros() > TYPING_GAP_US)
+        {
+            flush(&mut run);
+        }
+
+        match key.as_str() {
+            "Enter" | "Tab" => flush(&mut run),
+            "Backspace" => {
+                if let Some((_, last, text)) = run.as_mut() {
+                    text.pop();
+                    *last = ts;
+                }
+            }
+            key if key.chars().count() == 1 => {
+                let (_, last, text) = run.get_or_insert_with(|| (ts, ts, String::new()));
+                text.push_str(key);
+                *last = ts;
+            }
+            _ => {}
+        }
+    }
+    flush(&mut run);
+    cues
+}
+
+/// At most `CAPTION_MAX_CHARS` characters, ending in an ellipsis when cut.
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= CAPTION_MAX_CHARS {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(CAPTION_MAX_CHARS).collect();
+    format!("{} …", kept.trim_end())
+}
+
+/// `HH:MM:SS,mmm`, as SRT cue timings are written.
+fn srt_timestamp(timestamp: Timestamp) -> String {
+    vtt_timestamp(timestamp).replace('.', ",")
+}
+
+/// `HH:MM:SS.mmm`, as WebVTT cue timings are written.
+pub(crate) fn vtt_timestamp(timestamp: Timestamp) -> String {
+    let ms = timestamp.as_micros() / 1000;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+/// Cue payload on one line, with the characters WebVTT reserves escaped.
+fn cue_text(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_remap::Marker;
+    use crate::types::{InputEvent, KeyModifiers, NormalizedRect};
+
+    #[test]
+    fn chapters_follow_the_export_timeline() {
+        let s = |secs: u64| Timestamp::from_micros(secs * 1_000_000);
+        let remapper = TimeRemapper::new(vec![TimeRange::new(s(10), s(70))], vec![], s(0), s(4000))
+            .with_markers(vec![
+                Marker::new(s(0), "Intro", ""),
+                Marker::new(s(20), "Cut away", ""),
+                Marker::new(s(70), "Setup & <config>", ""),
+                Marker::new(s(3700), "Deploy", ""),
+            ]);
+
+        let vtt = chapters_vtt(&remapper);
+        assert_eq!(
+            vtt,
+            "WEBVTT\n\
+             \n1\n00:00:00.000 --> 00:00:10.000\nIntro\n\
+             \n2\n00:00:10.000 --> 01:00:40.000\nSetup &amp; &lt;config&gt;\n\
+             \n3\n01:00:40.000 --> 01:05:40.000\nDeploy\n"
+        );
+    }
+
+    #[test]
+    fn typing_becomes_captions_outside_sensitive_fields() {
+        let ms = |ms: u64| Timestamp::from_micros(ms * 1000);
+        let focus = |at: u64, tag: &str, is_password: bool| InputEvent {
+            timestamp: ms(at),
+            event_type: EventType::FocusChange {
+                bounds: NormalizedRect::default(),
+                element: Some(ElementInfo {
+                    tag: tag.to_string(),
+                    role: None,
+                    name: None,
+                    is_password,
+                    autocomplete: None,
+                }),
+            },
+        };
+        let key = |at: u64, key: &str, ctrl: bool| InputEvent {
+            timestamp: ms(at),
+            event_type: EventType::KeyDown {
+                key: key.to_string(),
+                modifiers: KeyModifiers {
+                    ctrl,
+                    ..Default::default()
+                },
+            },
+        };
+
+        let mut events = vec![focus(500, "input", false)];
+        let typed = [
+            "n",
+            "p",
+            "m",
+            " ",
+            "i",
+            "n",
+            "s",
+            "t",
+            "a",
+            "x",
+            "Backspace",
+            "l",
+            "l",
+        ];
+        for (i, k) in typed.iter().enumerate() {
+            events.push(key(1000 + i as u64 * 100, k, false));
+        }
+        events.push(key(2300, "Enter", false));
+        events.push(focus(4000, "input", true));
+        events.push(key(4100, "p", false));
+        events.push(focus(9000, "textarea", false));
+        events.extend([
+            key(10_000, "h", false),
+            key(10_050, "a", true),
+            key(10_100, "i", false),
+        ]);
+        let signals = SignalBatch { events };
+
+        // The cut removes 5-8s, so the second run lands 3s earlier.
+        let remapper = TimeRemapper::new(
+            vec![TimeRange::new(ms(5000), ms(8000))],
+            vec![],
+            ms(0),
+            ms(60_000),
+        );
+        let privacy = PrivacySettings::default();
+        assert_eq!(
+            typing_captions(&signals, &privacy, &remapper, CaptionFormat::Srt),
+            "1\n00:00:01,000 --> 00:00:03,700\ntyping: npm install\n\
+             \n2\n00:00:07,000 --> 00:00:08,600\ntyping: hi\n"
+        );
+        assert!(
+            typing_captions(&signals, &privacy, &remapper, CaptionFormat::Vtt)
+                .starts_with("WEBVTT\n\n1\n00:00:01.000 --> 00:00:03.700\ntyping: npm install\n")
+        );
+    }
+}
@@ -0,0 +1,643 @@
+// Edit suggestions: cut ranges the user can accept with one click.
+// Dead air comes from input activity; static frames, loading spinners, and scene
+// transitions come from per-frame perceptual hashes computed in JS.
+// See steering.md: Auto-Zoom Strategy Rules
+
+use serde::{Deserialize, Serialize};
+
+use crate::activity::{is_discrete_activity, ActivityAnalyzer, ActivitySettings};
+use crate::migrate::REMAPPER_CONFIG_VERSION;
+use crate::scenes::{detect_scene_changes, SceneSettings};
+use crate::time_remap::{
+    SpeedRampConfig, TimeRangeConfig, TimeRemapper, TimeRemapperConfig, DEFAULT_MAX_SPEED,
+    DEFAULT_MIN_SPEED,
+};
+use crate::types::*;
+
+/// Why a cut was suggested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CutReason {
+    /// No input and a still cursor.
+    DeadAir,
+    /// Consecutive frames are (near-)identical.
+    StaticFrames,
+    /// Frames change only slightly around a fixed image, e.g. a spinner on a loading page.
+    LoadingSpinner,
+    /// Frames churn for a while between two scenes, e.g. a page navigating.
+    SceneTransition,
+}
+
+/// A suggested cut. Serializes with `start_us`/`end_us` at the top level so it can be
+/// passed wherever a TimeRangeConfig is expected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CutSuggestion {
+    #[serde(flatten)]
+    pub range: TimeRangeConfig,
+    pub reason: CutReason,
+    /// 0.0-1.0; longer spans and spans without any input score higher.
+    pub confidence: f32,
+}
+
+/// Perceptual hash of one captured frame (64-bit dHash/pHash).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameHash {
+    pub timestamp: Timestamp,
+    pub hash: u64,
+}
+
+/// Thresholds for cut suggestions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestSettings {
+    /// Shortest dead-air span worth cutting, in microseconds.
+    #[serde(default = "default_min_dead_air")]
+    pub min_dead_air_us: u64,
+    /// Shortest static or spinner run worth cutting, in microseconds.
+    #[serde(default = "default_min_static")]
+    pub min_static_us: u64,
+    /// Time kept on each side of a suggested cut, in microseconds.
+    #[serde(default = "default_padding")]
+    pub padding_us: u64,
+    /// Max Hamming distance between frames considered identical.
+    #[serde(default = "default_static_distance")]
+    pub static_max_distance: u32,
+    /// Max Hamming distance from the run's first frame for a spinner-like run.
+    #[serde(default = "default_spinner_distance")]
+    pub spinner_max_distance: u32,
+    /// Cursor speed below which the cursor counts as still (see ActivitySettings).
+    #[serde(default = "default_motion_threshold")]
+    pub motion_threshold: f32,
+    /// Scene change detection (for transition cuts).
+    #[serde(default)]
+    pub scenes: SceneSettings,
+}
+
+fn default_min_dead_air() -> u64 {
+    5_000_000
+}
+fn default_min_static() -> u64 {
+    2_000_000
+}
+fn default_padding() -> u64 {
+    250_000
+}
+fn default_static_distance() -> u32 {
+    2
+}
+fn default_spinner_distance() -> u32 {
+    10
+}
+fn default_motion_threshold() -> f32 {
+    0.05
+}
+
+impl Default for SuggestSettings {
+    fn default() -> Self {
+        SuggestSettings {
+            min_dead_air_us: default_min_dead_air(),
+            min_static_us: default_min_static(),
+            padding_us: default_padding(),
+            static_max_distance: default_static_distance(),
+            spinner_max_distance: default_spinner_distance(),
+            motion_threshold: default_motion_threshold(),
+            scenes: SceneSettings::default(),
+        }
+    }
+}
+
+/// Settings for condensing a recording to a target duration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CondenseSettings {
+    /// Desired export duration in microseconds.
+    pub target_duration_us: u64,
+    /// Source range to condense; defaults to the span of the recorded signals.
+    #[serde(default)]
+    pub in_point_us: Option<u64>,
+    #[serde(default)]
+    pub out_point_us: Option<u64>,
+    /// Time kept at 1x around each click, focus change, or scroll, in microseconds.
+    #[serde(default = "default_keep_around")]
+    pub keep_around_us: u64,
+    /// Fastest speed applied to the remaining footage.
+    #[serde(default = "default_max_speed")]
+    pub max_speed: f32,
+    /// Suggested cuts below this confidence are not used.
+    #[serde(default = "default_min_cut_confidence")]
+    pub min_cut_confidence: f32,
+}
+
+fn default_keep_around() -> u64 {
+    1_000_000
+}
+fn default_max_speed() -> f32 {
+    4.0
+}
+fn default_min_cut_confidence() -> f32 {
+    0.5
+}
+
+/// Result of `EditSuggester::condense`: a ready-to-use config plus what was done.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CondenseResult {
+    pub config: TimeRemapperConfig,
+    pub source_duration_us: u64,
+    /// Export duration of `config`.
+    pub result_duration_us: u64,
+    /// False if the important moments alone are longer than the target.
+    pub target_reached: bool,
+    /// Source time removed by cuts.
+    pub dropped_us: u64,
+    /// Source time played faster than 1x.
+    pub accelerated_us: u64,
+    /// Speed applied to accelerated footage (1.0 if none).
+    pub speed: f32,
+}
+
+/// Produces cut suggestions from signals, the cursor track, and optional frame hashes.
+pub struct EditSuggester {
+    settings: SuggestSettings,
+}
+
+impl EditSuggester {
+    pub fn new(settings: SuggestSettings) -> Self {
+        EditSuggester { settings }
+    }
+
+    /// All suggestions, sorted by start. Suggestions of different reasons may overlap;
+    /// the UI decides which to show.
+    pub fn suggest_cuts(
+        &self,
+        signals: &SignalBatch,
+        cursor_track: &[CursorTrackPoint],
+        frame_hashes: &[FrameHash],
+    ) -> Vec<CutSuggestion> {
+        let mut suggestions = self.dead_air(signals, cursor_track);
+        suggestions.extend(self.frame_runs(signals, frame_hashes));
+        suggestions.extend(self.scene_transitions(signals, frame_hashes));
+        suggestions.sort_by_key(|s| (s.range.start_us, s.range.end_us));
+        suggestions
+    }
+
+    /// Generate cuts and speed ramps that bring the recording down to
+    /// `settings.target_duration_us` while keeping clicks, focus changes, and scrolls at 1x.
+    /// The suggester's own settings drive the cut suggestions used in the first pass.
+    ///
+    /// Passes, in order, stopping as soon as the target is met:
+    /// 1. Cut suggested dead air / static footage, most confident first.
+    /// 2. Speed up the remaining unimportant footage uniformly, up to `max_speed`.
+    /// 3. Cut the middle of the longest remaining unimportant stretches.
+    pub fn condense(
+        &self,
+        signals: &SignalBatch,
+        cursor_track: &[CursorTrackPoint],
+        settings: &CondenseSettings,
+    ) -> CondenseResult {
+        let times = signals
+            .events
+            .iter()
+            .map(|event| event.timestamp.as_micros())
+            .chain(cursor_track.iter().map(|point| point.timestamp.as_micros()));
+        let in_point = settings
+            .in_point_us
+            .unwrap_or_else(|| times.clone().min().unwrap_or(0));
+        let out_point = settings
+            .out_point_us
+            .unwrap_or_else(|| times.max().unwrap_or(0))
+            .max(in_point);
+        let source_duration = out_point - in_point;
+        let target = settings.target_duration_us;
+
+        // Important moments (with context) stay at 1x.
+        let protected = merge_spans(
+            signals
+                .events
+                .iter()
+                .filter(|event| is_discrete_activity(&event.event_type))
+                .map(|event| {
+                    let t = event.timestamp.as_micros();
+                    (
+                        t.saturating_sub(settings.keep_around_us).max(in_point),
+                        (t + settings.keep_around_us).min(out_point),
+                    )
+                })
+                .collect(),
+        );
+        let mut free = vec![(in_point, out_point)];
+        for &span in &protected {
+            free = subtract_span(&free, span);
+        }
+
+        let mut cuts: Vec<(u64, u64)> = Vec::new();
+        let mut need = source_duration.saturating_sub(target);
+
+        // Pass 1: suggested cuts, clipped to unimportant footage.
+        if need > 0 {
+            let mut suggestions: Vec<CutSuggestion> = self
+                .suggest_cuts(signals, cursor_track, &[])
+                .into_iter()
+                .filter(|s| s.confidence >= settings.min_cut_confidence)
+                .collect();
+            suggestions.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+            for suggestion in suggestions {
+                for (start, end) in
+                    intersect_spans(&free, (suggestion.range.start_us, suggestion.range.end_us))
+                {
+                    if need == 0 {
+                        break;
+                    }
+                    let cut = middle_of((start, end), need);
+                    need -= cut.1 - cut.0;
+                    free = subtract_span(&free, cut);
+                    cuts.push(cut);
+                }
+            }
+        }
+
+        // Pass 2: uniform speed-up of what's left.
+        let mut speed = 1.0f64;
+        let free_len: u64 = free.iter().map(|(start, end)| end - start).sum();
+        if need > 0 && free_len > 0 {
+            let max_speed = settings.max_speed.max(1.0) as f64;
+            if (need as f64) < free_len as f64 * (1.0 - 1.0 / max_speed) {
+                speed = free_len as f64 / (free_len - need) as f64;
+                need = 0;
+            } else {
+                speed = max_speed;
+                let saved = (free_len as f64 * (1.0 - 1.0 / max_speed)) as u64;
+                // Pass 3: each source microsecond cut from sped-up footage saves 1/speed.
+                let mut source_to_cut = ((need - saved.min(need)) as f64 * max_speed).ceil() as u64;
+                let mut by_length = free.clone();
+                by_length.sort_by_key(|&(start, end)| std::cmp::Reverse(end - start));
+                for span in by_length {
+                    if source_to_cut == 0 {
+                        break;
+                    }
+                    let cut = middle_of(span, source_to_cut);
+                    source_to_cut -= cut.1 - cut.0;
+                    free = subtract_span(&free, cut);
+                    cuts.push(cut);
+                }
+                need = 0;
+            }
+        }
+
+        cuts.sort_unstable();
+        let speed_ramps: Vec<SpeedRampConfig> = if speed > 1.0 {
+            free.iter()
+                .map(|&(start, end)| SpeedRampConfig {
+                    start_us: start,
+                    end_us: end,
+                    speed: speed as f32,
+                    ramp_in_us: 0,
+                    ramp_out_us: 0,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let accelerated_us = speed_ramps.iter().map(|r| r.end_us - r.start_us).sum();
+
+        let config = TimeRemapperConfig {
+            version: REMAPPER_CONFIG_VERSION,
+            cuts: cuts
+                .iter()
+                .map(|&(start_us, end_us)| TimeRangeConfig { start_us, end_us })
+                .collect(),
+            speed_ramps,
+            in_point_us: in_point,
+            out_point_us: out_point,
+            min_speed: DEFAULT_MIN_SPEED,
+            // Let `max_speed` above the default limit through.
+            max_speed: DEFAULT_MAX_SPEED.max(speed as f32),
+            markers: Vec::new(),
+            protected_ranges: protected
+                .iter()
+                .map(|&(start_us, end_us)| TimeRangeConfig { start_us, end_us })
+                .collect(),
+        };
+        let result_duration_us = TimeRemapper::from_config(config.clone())
+            .export_duration()
+            .as_micros();
+
+        CondenseResult {
+            source_duration_us: source_duration,
+            result_duration_us,
+            // Allow for per-segment rounding in the remapper.
+            target_reached: need == 0
+                && result_duration_us <= target + config.speed_ramps.len() as u64,
+            dropped_us: cuts.iter().map(|(start, end)| end - start).sum(),
+            accelerated_us,
+            speed: speed as f32,
+            config,
+        }
+    }
+
+    fn dead_air(
+        &self,
+        signals: &SignalBatch,
+        cursor_track: &[CursorTrackPoint],
+    ) -> Vec<CutSuggestion> {
+        let analyzer = ActivityAnalyzer::new(ActivitySettings {
+            min_idle_us: self.settings.min_dead_air_us,
+            motion_threshold: self.settings.motion_threshold,
+            padding_us: self.settings.padding_us,
+            ..ActivitySettings::default()
+        });
+
+        analyzer
+            .find_idle_spans(signals, cursor_track)
+            .into_iter()
+            .map(|span| CutSuggestion {
+                range: TimeRangeConfig {
+                    start_us: span.start.as_micros(),
+                    end_us: span.end.as_micros(),
+                },
+                reason: CutReason::DeadAir,
+                confidence: length_score(span.duration_us(), self.settings.min_dead_air_us),
+            })
+            .collect()
+    }
+
+    /// Runs of frames that stay close to the run's first frame. A run that never
+    /// changes beyond `static_max_distance` is static; otherwise it's spinner-like.
+    fn frame_runs(&self, signals: &SignalBatch, frame_hashes: &[FrameHash]) -> Vec<CutSuggestion> {
+        let mut frames = frame_hashes.to_vec();
+        frames.sort_by_key(|f| f.timestamp);
+
+        let mut suggestions = Vec::new();
+        let mut start = 0;
+        while start < frames.len() {
+            let anchor = frames[start].hash;
+            let mut end = start + 1;
+            let mut max_step = 0;
+            while end < frames.len()
+                && hamming(frames[end].hash, anchor) <= self.settings.spinner_max_distance
+            {
+                max_step = max_step.max(hamming(frames[end].hash, frames[end - 1].hash));
+                end += 1;
+            }
+
+            // The run lasts until the first frame that breaks it (or the last frame).
+            let run_start = frames[start].timestamp.as_micros() + self.settings.padding_us;
+            let run_end = frames[end.min(frames.len() - 1)]
+                .timestamp
+                .as_micros()
+                .saturating_sub(self.settings.padding_us);
+
+            if run_end > run_start && run_end - run_start >= self.settings.min_static_us {
+                let (reason, weight) = if max_step <= self.settings.static_max_distance {
+                    (CutReason::StaticFrames, 1.0)
+                } else {
+                    (CutReason::LoadingSpinner, 0.9)
+                };
+                let mut confidence =
+                    weight * length_score(run_end - run_start, self.settings.min_static_us);
+                if has_input_between(signals, run_start, run_end) {
+                    // The screen didn't change, but the user was doing something.
+                    confidence *= 0.5;
+                }
+                suggestions.push(CutSuggestion {
+                    range: TimeRangeConfig {
+                        start_us: run_start,
+                        end_us: run_end,
+                    },
+                    reason,
+                    confidence,
+                });
+            }
+            start = end;
+        }
+        suggestions
+    }
+
+    /// The churn between two scenes, when it lasts at least `min_transition_us`.
+    fn scene_transitions(
+        &self,
+        signals: &SignalBatch,
+        frame_hashes: &[FrameHash],
+    ) -> Vec<CutSuggestion> {
+        let scenes = &self.settings.scenes;
+        detect_scene_changes(frame_hashes, scenes)
+            .into_iter()
+            .filter(|change| change.transition_us() >= scenes.min_transition_us)
+            .map(|change| {
+                let (start, end) = (
+                    change.transition_start.as_micros(),
+                    change.timestamp.as_micros(),
+                );
+                let mut confidence = 0.8 * length_score(end - start, scenes.min_transition_us);
+                if has_input_between(signals, start, end) {
+                    confidence *= 0.5;
+                }
+                CutSuggestion {
+                    range: TimeRangeConfig {
+                        start_us: start,
+                        end_us: end,
+                    },
+                    reason: CutReason::SceneTransition,
+                    confidence,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Sort and merge overlapping spans; empty spans are dropped.
+fn merge_spans(mut spans: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    spans.retain(|&(start, end)| end > start);
+    spans.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// `spans` with `hole` removed.
+fn subtract_span(spans: &[(u64, u64)], hole: (u64, u64)) -> Vec<(u64, u64)> {
+    let mut result = Vec::with_capacity(spans.len() + 1);
+    for &(start, end) in spans {
+        if end <= hole.0 || start >= hole.1 {
+            result.push((start, end));
+            continue;
+        }
+        if start < hole.0 {
+            result.push((start, hole.0));
+        }
+        if end > hole.1 {
+            result.push((hole.1, end));
+        }
+    }
+    result
+}
+
+/// Parts of `spans` inside `range`.
+fn intersect_spans(spans: &[(u64, u64)], range: (u64, u64)) -> Vec<(u64, u64)> {
+    spans
+        .iter()
+        .map(|&(start, end)| (start.max(range.0), end.min(range.1)))
+        .filter(|&(start, end)| end > start)
+        .collect()
+}
+
+/// A centered piece of `span` at most `length` long, so context is kept on both sides.
+fn middle_of(span: (u64, u64), length: u64) -> (u64, u64) {
+    let length = length.min(span.1 - span.0);
+    let start = span.0 + (span.1 - span.0 - length) / 2;
+    (start, start + length)
+}
+
+/// 0.5 at the minimum length, approaching 1.0 as the span grows.
+fn length_score(length_us: u64, min_us: u64) -> f32 {
+    let length = length_us as f32;
+    (length / (length + min_us.max(1) as f32)).clamp(0.0, 1.0)
+}
+
+pub(crate) fn hamming(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+fn has_input_between(signals: &SignalBatch, start_us: u64, end_us: u64) -> bool {
+    signals.events.iter().any(|event| {
+        let t = event.timestamp.as_micros();
+        t >= start_us && t < end_us && is_discrete_activity(&event.event_type)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(hashes: &[u64]) -> Vec<FrameHash> {
+        hashes
+            .iter()
+            .enumerate()
+            .map(|(i, &hash)| FrameHash {
+                timestamp: Timestamp::from_micros(i as u64 * 1_000_000),
+                hash,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn classifies_static_and_spinner_runs() {
+        // 0-4s identical, 4-8s small cycling changes, 8s new content.
+        let hashes = [
+            0,
+            0,
+            0,
+            0,
+            0xFFFF_FFFF,
+            0xFFFF_FFF0,
+            0xFFFF_FF0F,
+            0xFFFF_FFF0,
+            u64::MAX,
+            u64::MAX,
+        ];
+        let suggester = EditSuggester::new(SuggestSettings::default());
+        let cuts = suggester.suggest_cuts(&SignalBatch { events: vec![] }, &[], &frames(&hashes));
+
+        let reasons: Vec<CutReason> = cuts.iter().map(|c| c.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![CutReason::StaticFrames, CutReason::LoadingSpinner]
+        );
+        assert_eq!(
+            (cuts[0].range.start_us, cuts[0].range.end_us),
+            (250_000, 3_750_000)
+        );
+        assert!(cuts[0].confidence > cuts[1].confidence);
+    }
+
+    fn clicks_at(seconds: &[u64]) -> SignalBatch {
+        SignalBatch {
+            events: seconds
+                .iter()
+                .map(|&s| InputEvent {
+                    timestamp: Timestamp::from_micros(s * 1_000_000),
+                    event_type: EventType::MouseClick {
+                        position: NormalizedCoord::center(),
+                        button: 0,
+                        element: None,
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    fn condense_settings(target_s: u64) -> CondenseSettings {
+        serde_json::from_str(&format!(
+            r#"{{ "target_duration_us": {} }}"#,
+            target_s * 1_000_000
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn condense_cuts_dead_air_first() {
+        // 0-60s with clicks at 0, 1, 40, 60: 1-40 is dead air.
+        let signals = clicks_at(&[0, 1, 40, 60]);
+        let suggester = EditSuggester::new(SuggestSettings::default());
+        let result = suggester.condense(&signals, &[], &condense_settings(40));
+
+        assert!(result.target_reached);
+        assert_eq!(result.result_duration_us, 40_000_000);
+        assert_eq!(result.speed, 1.0);
+        assert_eq!(result.dropped_us, 20_000_000);
+        // The cut stays clear of the protected second around each click.
+        let cut = &result.config.cuts[0];
+        assert!(cut.start_us >= 2_000_000 && cut.end_us <= 39_000_000);
+    }
+
+    #[test]
+    fn condense_speeds_up_then_cuts_when_needed() {
+        // Clicks every 2s leave no dead air; 20s -> 15s needs a speed-up.
+        let signals = clicks_at(&[0, 4, 8, 12, 16, 20]);
+        let suggester = EditSuggester::new(SuggestSettings::default());
+        let result = suggester.condense(&signals, &[], &condense_settings(15));
+        assert!(result.target_reached);
+        assert!(result.speed > 1.0 && result.speed <= 4.0);
+        assert!(result.result_duration_us.abs_diff(15_000_000) <= 10);
+
+        // 20s -> 8s: 10s is protected around the clicks, so even with everything else
+        // cut the target can't be met.
+        let result = suggester.condense(&signals, &[], &condense_settings(8));
+        assert!(!result.target_reached);
+        assert!(result.result_duration_us < 15_000_000);
+    }
+
+    #[test]
+    fn input_during_static_run_lowers_confidence() {
+        let hashes = [7; 6];
+        let click = InputEvent {
+            timestamp: Timestamp::from_micros(2_000_000),
+            event_type: EventType::MouseClick {
+                position: NormalizedCoord::center(),
+                button: 0,
+                element: None,
+            },
+        };
+        let suggester = EditSuggester::new(SuggestSettings::default());
+        let quiet = suggester.suggest_cuts(&SignalBatch { events: vec![] }, &[], &frames(&hashes));
+        let busy = suggester.suggest_cuts(
+            &SignalBatch {
+                events: vec![click],
+            },
+            &[],
+            &frames(&hashes),
+        );
+
+        assert_eq!(quiet.len(), 1);
+        let busy_static = busy
+            .iter()
+            .find(|c| c.reason == CutReason::StaticFrames)
+            .unwrap();
+        assert!(busy_static.confidence < quiet[0].confidence);
+
+        let json = serde_json::to_string(&quiet[0]).unwrap();
+        let range: TimeRangeConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(range, quiet[0].range);
+    }
+}
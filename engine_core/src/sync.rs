@@ -0,0 +1,233 @@
+// Clock drift correction and pause collapsing between the signal clock and the media timeline.
+// Signals are stamped with performance.now() while video frames follow the encoder's clock; over
+// long recordings the two drift apart, and a paused recording leaves a gap only in the signals.
+// See steering.md: Rust Rules (Engine)
+
+use std::borrow::Cow;
+
+use crate::error::EngineError;
+use crate::types::*;
+
+/// Piecewise-linear mapping from signal time to media time through sync anchors.
+/// With no anchors signal time is used as is; with one, a constant offset applies.
+#[derive(Debug, Clone, Default)]
+pub struct ClockSync {
+    /// `(signal_ts, media_ts)` pairs in increasing order of both.
+    anchors: Vec<(Timestamp, Timestamp)>,
+}
+
+impl ClockSync {
+    pub fn new() -> Self {
+        ClockSync::default()
+    }
+
+    /// Register that signal time `signal_ts` corresponds to media time `media_ts`.
+    /// An anchor at the same signal time is replaced. Anchors that would make media
+    /// time run backwards are rejected.
+    pub fn add_anchor(
+        &mut self,
+        signal_ts: Timestamp,
+        media_ts: Timestamp,
+    ) -> Result<(), EngineError> {
+        let index = self.anchors.partition_point(|(s, _)| *s < signal_ts);
+        let replaces = self
+            .anchors
+            .get(index)
+            .is_some_and(|(s, _)| *s == signal_ts);
+        let before = index.checked_sub(1).map(|i| self.anchors[i].1);
+        let after = self.anchors.get(index + usize::from(replaces)).map(|a| a.1);
+        if before.is_some_and(|m| m > media_ts) || after.is_some_and(|m| m < media_ts) {
+            return Err(EngineError::InvalidConfig(format!(
+                "Sync anchor {} -> {} would make media time run backwards",
+                signal_ts.as_micros(),
+                media_ts.as_micros()
+            )));
+        }
+        if replaces {
+            self.anchors[index] = (signal_ts, media_ts);
+        } else {
+            self.anchors.insert(index, (signal_ts, media_ts));
+        }
+        Ok(())
+    }
+
+    /// Remove all anchors.
+    pub fn clear(&mut self) {
+        self.anchors.clear();
+    }
+
+    pub fn anchors(&self) -> &[(Timestamp, Timestamp)] {
+        &self.anchors
+    }
+
+    /// Media time of a signal timestamp. Outside the anchors the nearest segment's
+    /// rate is extended.
+    pub fn to_media_time(&self, signal_ts: Timestamp) -> Timestamp {
+        let ((s0, m0), (s1, m1)) = match self.anchors[..] {
+            [] => return signal_ts,
+            [(s, m)] => {
+                let media =
+                    signal_ts.as_micros() as i64 + (m.as_micros() as i64 - s.as_micros() as i64);
+                return Timestamp::from_micros(media.max(0) as u64);
+            }
+            _ => {
+                let i = self
+                    .anchors
+                    .partition_point(|(s, _)| *s <= signal_ts)
+                    .clamp(1, self.anchors.len() - 1);
+                (self.anchors[i - 1], self.anchors[i])
+            }
+        };
+        let (s0, s1) = (s0.as_micros() as f64, s1.as_micros() as f64);
+        let (m0, m1) = (m0.as_micros() as f64, m1.as_micros() as f64);
+        let media = m0 + (signal_ts.as_micros() as f64 - s0) * (m1 - m0) / (s1 - s0);
+        Timestamp::from_micros(media.round().max(0.0) as u64)
+    }
+
+    /// Move every event in a batch onto the media timeline.
+    pub fn correct<'a>(&self, signals: &'a SignalBatch) -> Cow<'a, SignalBatch> {
+        if self.anchors.is_empty() {
+            return Cow::Borrowed(signals);
+        }
+        let events = signals
+            .events
+            .iter()
+            .map(|event| InputEvent {
+                timestamp: self.to_media_time(event.timestamp),
+                ..event.clone()
+            })
+            .collect();
+        Cow::Owned(SignalBatch { events })
+    }
+}
+
+/// Removes paused time from the signal stream, carrying the pause state across
+/// chunks. Expects time-ordered batches (see `SignalSanitizer`).
+#[derive(Debug, Clone, Default)]
+pub struct PauseCollapser {
+    /// When the current pause began, while paused.
+    paused_since: Option<Timestamp>,
+    /// Total paused time so far, subtracted from later events.
+    paused_us: u64,
+}
+
+impl PauseCollapser {
+    pub fn new() -> Self {
+        PauseCollapser::default()
+    }
+
+    /// Forget earlier pauses.
+    pub fn reset(&mut self) {
+        self.paused_since = None;
+        self.paused_us = 0;
+    }
+
+    /// Shift events back by the time paused before them. `Pause`/`Resume` markers are
+    /// consumed; events between them are dropped and reported.
+    pub fn collapse<'a>(
+        &mut self,
+        signals: &'a SignalBatch,
+    ) -> (Cow<'a, SignalBatch>, Vec<SignalWarning>) {
+        let has_pauses = signals
+            .events
+            .iter()
+            .any(|event| matches!(event.event_type, EventType::Pause | EventType::Resume));
+        if self.paused_us == 0 && self.paused_since.is_none() && !has_pauses {
+            return (Cow::Borrowed(signals), Vec::new());
+        }
+
+        let mut events = Vec::with_capacity(signals.events.len());
+        let mut dropped = 0;
+        for event in &signals.events {
+            match event.event_type {
+                EventType::Pause => {
+                    self.paused_since.get_or_insert(event.timestamp);
+                }
+                EventType::Resume => {
+                    if let Some(since) = self.paused_since.take() {
+                        self.paused_us += event.timestamp.as_micros() - since.as_micros();
+                    }
+                }
+                _ if self.paused_since.is_some() => dropped += 1,
+                _ => events.push(InputEvent {
+                    timestamp: Timestamp::from_micros(
+                        event.timestamp.as_micros().saturating_sub(self.paused_us),
+                    ),
+                    ..event.clone()
+                }),
+            }
+        }
+
+        let mut warnings = Vec::new();
+        if dropped > 0 {
+            warnings.push(SignalWarning {
+                kind: SignalWarningKind::DuringPause,
+                count: dropped,
+                message: format!("{} events recorded while paused were dropped", dropped),
+            });
+        }
+        (Cow::Owned(SignalBatch { events }), warnings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(us: u64) -> Timestamp {
+        Timestamp::from_micros(us)
+    }
+
+    #[test]
+    fn interpolates_between_anchors() {
+        let mut sync = ClockSync::new();
+        assert_eq!(sync.to_media_time(ts(5_000)), ts(5_000));
+
+        // Signals start 100ms early, then run 1% fast.
+        sync.add_anchor(ts(0), ts(100_000)).unwrap();
+        assert_eq!(sync.to_media_time(ts(5_000)), ts(105_000));
+        sync.add_anchor(ts(10_000_000), ts(10_000_000)).unwrap();
+
+        assert_eq!(sync.to_media_time(ts(5_000_000)), ts(5_050_000));
+        // Beyond the last anchor the drift keeps going.
+        assert_eq!(sync.to_media_time(ts(20_000_000)), ts(19_900_000));
+
+        assert!(sync.add_anchor(ts(5_000_000), ts(20_000_000)).is_err());
+        assert_eq!(sync.anchors().len(), 2);
+    }
+
+    #[test]
+    fn pauses_are_collapsed_across_chunks() {
+        let event = |us: u64, event_type: EventType| InputEvent {
+            timestamp: ts(us),
+            event_type,
+        };
+        let mv = |us: u64| {
+            event(
+                us,
+                EventType::MouseMove {
+                    position: NormalizedCoord::center(),
+                },
+            )
+        };
+        let mut collapser = PauseCollapser::new();
+        let first = SignalBatch {
+            events: vec![mv(0), event(1_000_000, EventType::Pause), mv(1_500_000)],
+        };
+        let (batch, warnings) = collapser.collapse(&first);
+        assert_eq!(batch.events.len(), 1);
+        assert_eq!(warnings[0].kind, SignalWarningKind::DuringPause);
+
+        let second = SignalBatch {
+            events: vec![event(5_000_000, EventType::Resume), mv(6_000_000)],
+        };
+        let (batch, warnings) = collapser.collapse(&second);
+        let times: Vec<u64> = batch
+            .events
+            .iter()
+            .map(|e| e.timestamp.as_micros())
+            .collect();
+        assert_eq!(times, vec![2_000_000]);
+        assert!(warnings.is_empty());
+    }
+}
@@ -0,0 +1,305 @@
+// Synthetic cursor: the track a renderer follows when it hides the captured cursor and
+// draws its own. Positions are smoothed and resampled at the export frame rate; the
+// shape comes from what the cursor was over, as far as element metadata tells.
+// See steering.md: Rust Rules (Engine)
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::*;
+
+/// Floats per frame in a baked synthetic cursor track:
+/// `x, y, scale, shape (CursorShape declaration order), visible (0 or 1)`.
+pub const SYNTHETIC_CURSOR_STRIDE: usize = 5;
+
+/// The shape is taken from a clicked element from this long before the click...
+const SHAPE_LEAD_US: u64 = 300_000;
+/// ...until this long after it...
+const SHAPE_HOLD_US: u64 = 300_000;
+/// ...while the cursor stays this close (normalized) to the click.
+const SHAPE_RADIUS: f32 = 0.03;
+/// Length of the press "squeeze" on each click.
+const CLICK_PULSE_US: u64 = 200_000;
+
+/// Tuning for the synthetic cursor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticCursorSettings {
+    /// Width of the centered smoothing window, in microseconds. 0 disables smoothing.
+    #[serde(default = "default_smoothing")]
+    pub smoothing_us: u64,
+    /// Cursor size relative to the system cursor.
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    /// Scale factor at the bottom of a click's squeeze (1.0 for none).
+    #[serde(default = "default_click_scale")]
+    pub click_scale: f32,
+}
+
+fn default_smoothing() -> u64 {
+    120_000
+}
+fn default_scale() -> f32 {
+    1.5
+}
+fn default_click_scale() -> f32 {
+    0.8
+}
+
+impl Default for SyntheticCursorSettings {
+    fn default() -> Self {
+        SyntheticCursorSettings {
+            smoothing_us: default_smoothing(),
+            scale: default_scale(),
+            click_scale: default_click_scale(),
+        }
+    }
+}
+
+/// Which cursor image to draw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CursorShape {
+    #[default]
+    Pointer,
+    /// Over editable text.
+    IBeam,
+    /// Over links and buttons.
+    Hand,
+}
+
+impl CursorShape {
+    /// Shape a browser would show over `element`.
+    pub fn for_element(element: &ElementInfo) -> CursorShape {
+        match element.role.as_deref() {
+            Some("textbox" | "searchbox" | "combobox") => return CursorShape::IBeam,
+            Some(
+                "button" | "link" | "tab" | "menuitem" | "checkbox" | "radio" | "switch" | "option",
+            ) => return CursorShape::Hand,
+            _ => {}
+        }
+        match element.tag.as_str() {
+            "textarea" => CursorShape::IBeam,
+            "input" if element.is_password || element.role.is_none() => CursorShape::IBeam,
+            "a" | "button" | "select" | "summary" | "label" => CursorShape::Hand,
+            _ => CursorShape::Pointer,
+        }
+    }
+}
+
+/// One output frame of the synthetic cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SyntheticCursorFrame {
+    pub timestamp: Timestamp,
+    pub position: NormalizedCoord,
+    pub shape: CursorShape,
+    pub scale: f32,
+    /// False before the cursor is first seen and while the tracker reports it hidden.
+    pub visible: bool,
+}
+
+/// Synthetic cursor for every output frame at `fps`, covering `duration_us` from
+/// `start` (frame `i` samples `start + i / fps`, as in `CameraEngine::bake_track`).
+/// Positions follow the tracker's smoothed track, then a centered moving average, so
+/// the cursor doesn't lag behind clicks.
+pub fn synthetic_cursor(
+    track: &[CursorTrackPoint],
+    signals: &SignalBatch,
+    settings: &SyntheticCursorSettings,
+    fps: f64,
+    start: Timestamp,
+    duration_us: u64,
+) -> Vec<SyntheticCursorFrame> {
+    if fps <= 0.0 || !fps.is_finite() {
+        return Vec::new();
+    }
+    let points: Vec<&CursorTrackPoint> = track
+        .iter()
+        .filter(|point| point.reason != InferenceReason::Outlier)
+        .collect();
+    let frame_count = (duration_us as f64 * fps / 1_000_000.0).ceil() as u64;
+
+    // Sample the track at each frame.
+    let mut frames = Vec::with_capacity(frame_count as usize);
+    let mut next = 0;
+    for frame in 0..frame_count {
+        let offset_us = (frame as f64 * 1_000_000.0 / fps).round() as u64;
+        let timestamp = Timestamp::from_micros(start.as_micros() + offset_us);
+        while next < points.len() && points[next].timestamp <= timestamp {
+            next += 1;
+        }
+        let (position, visible) = match (next.checked_sub(1), points.get(next)) {
+            (None, _) => (
+                points
+                    .first()
+                    .map(|point| position_of(point))
+                    .unwrap_or(NormalizedCoord::new(0.5, 0.5)),
+                false,
+            ),
+            (Some(before), None) => (position_of(points[before]), is_shown(points[before])),
+            (Some(before), Some(after)) => {
+                let (a, b) = (points[before], after);
+                let span = b.timestamp.as_micros() - a.timestamp.as_micros();
+                let t = (timestamp.as_micros() - a.timestamp.as_micros()) as f32 / span as f32;
+                let (pa, pb) = (position_of(a), position_of(b));
+                (
+                    NormalizedCoord::new(pa.x + (pb.x - pa.x) * t, pa.y + (pb.y - pa.y) * t),
+                    is_shown(a),
+                )
+            }
+        };
+        frames.push(SyntheticCursorFrame {
+            timestamp,
+            position,
+            shape: CursorShape::Pointer,
+            scale: settings.scale,
+            visible,
+        });
+    }
+
+    // Centered moving average.
+    let radius = (settings.smoothing_us as f64 * fps / 2_000_000.0).round() as usize;
+    if radius > 0 {
+        let raw: Vec<NormalizedCoord> = frames.iter().map(|frame| frame.position).collect();
+        for (i, frame) in frames.iter_mut().enumerate() {
+            let window = &raw[i.saturating_sub(radius)..(i + radius + 1).min(raw.len())];
+            let n = window.len() as f32;
+            frame.position = NormalizedCoord::new(
+                window.iter().map(|p| p.x).sum::<f32>() / n,
+                window.iter().map(|p| p.y).sum::<f32>() / n,
+            );
+        }
+    }
+
+    // Shapes and click squeezes.
+    for event in &signals.events {
+        let EventType::MouseClick {
+            position, element, ..
+        } = &event.event_type
+        else {
+            continue;
+        };
+        let click_us = event.timestamp.as_micros();
+        let shape = element
+            .as_ref()
+            .map(CursorShape::for_element)
+            .unwrap_or_default();
+        for frame in frames.iter_mut() {
+            let frame_us = frame.timestamp.as_micros();
+            if frame_us + SHAPE_LEAD_US < click_us {
+                continue;
+            }
+            if frame_us > click_us + SHAPE_HOLD_US.max(CLICK_PULSE_US) {
+                break;
+            }
+            let dx = frame.position.x - position.x;
+            let dy = frame.position.y - position.y;
+            if shape != CursorShape::Pointer
+                && frame_us < click_us + SHAPE_HOLD_US
+                && dx.hypot(dy) <= SHAPE_RADIUS
+            {
+                frame.shape = shape;
+            }
+            if (click_us..click_us + CLICK_PULSE_US).contains(&frame_us) {
+                let progress = (frame_us - click_us) as f32 / CLICK_PULSE_US as f32;
+                let squeeze =
+                    (1.0 - settings.click_scale) * (progress * std::f32::consts::PI).sin();
+                frame.scale = settings.scale * (1.0 - squeeze);
+            }
+        }
+    }
+
+    frames
+}
+
+/// Flatten frames into `SYNTHETIC_CURSOR_STRIDE` floats each.
+pub fn bake_synthetic_cursor(frames: &[SyntheticCursorFrame]) -> Vec<f32> {
+    frames
+        .iter()
+        .flat_map(|frame| {
+            [
+                frame.position.x,
+                frame.position.y,
+                frame.scale,
+                frame.shape as u8 as f32,
+                if frame.visible { 1.0 } else { 0.0 },
+            ]
+        })
+        .collect()
+}
+
+fn position_of(point: &CursorTrackPoint) -> NormalizedCoord {
+    point.smoothed.unwrap_or(point.position)
+}
+
+fn is_shown(point: &CursorTrackPoint) -> bool {
+    point.state != CursorState::Hidden
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(us: u64, x: f32, state: CursorState) -> CursorTrackPoint {
+        CursorTrackPoint {
+            timestamp: Timestamp::from_micros(us),
+            position: NormalizedCoord::new(x, 0.5),
+            state,
+            confidence: 100,
+            reason: InferenceReason::DirectInput,
+            smoothed: None,
+        }
+    }
+
+    #[test]
+    fn resamples_at_export_rate_with_shapes_and_squeeze() {
+        let track = vec![
+            point(100_000, 0.2, CursorState::Visible),
+            point(500_000, 0.6, CursorState::Visible),
+            point(900_000, 0.6, CursorState::Hidden),
+        ];
+        let signals = SignalBatch {
+            events: vec![InputEvent {
+                timestamp: Timestamp::from_micros(600_000),
+                event_type: EventType::MouseClick {
+                    position: NormalizedCoord::new(0.6, 0.5),
+                    button: 0,
+                    element: Some(ElementInfo {
+                        tag: "a".to_string(),
+                        role: None,
+                        name: Some("Docs".to_string()),
+                        is_password: false,
+                        autocomplete: None,
+                    }),
+                },
+            }],
+        };
+        let settings = SyntheticCursorSettings {
+            smoothing_us: 0,
+            ..Default::default()
+        };
+
+        let frames = synthetic_cursor(
+            &track,
+            &signals,
+            &settings,
+            10.0,
+            Timestamp::from_micros(0),
+            1_000_000,
+        );
+        assert_eq!(frames.len(), 10);
+        // Not seen yet, then interpolated at 10 fps.
+        assert!(!frames[0].visible);
+        assert!(frames[1].visible);
+        assert!((frames[3].position.x - 0.4).abs() < 1e-5);
+        // Hand while over the link, squeezed right after the click.
+        assert_eq!(frames[4].shape, CursorShape::Pointer);
+        assert_eq!(frames[5].shape, CursorShape::Hand);
+        assert_eq!(frames[6].shape, CursorShape::Hand);
+        assert!((frames[6].scale - 1.5).abs() < 1e-5);
+        assert!((frames[7].scale - 1.5 * 0.8).abs() < 1e-5);
+        assert_eq!(frames[9].shape, CursorShape::Pointer);
+        assert!(!frames[9].visible);
+
+        let baked = bake_synthetic_cursor(&frames);
+        assert_eq!(baked.len(), 10 * SYNTHETIC_CURSOR_STRIDE);
+        assert_eq!(baked[6 * SYNTHETIC_CURSOR_STRIDE + 3], 2.0);
+    }
+}
@@ -0,0 +1,153 @@
+// Multi-take merging: several recording segments onto one timeline.
+// "Resume recording" produces a new take with its own clock; the takes are laid out on the
+// project timeline and merged into a single signal stream before analysis.
+// See steering.md: Rust Rules (Engine)
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::*;
+
+/// One recording segment's signals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Take {
+    pub signals: SignalBatch,
+    /// Where the take starts on the merged timeline (microseconds).
+    pub offset_us: u64,
+    /// Take-local timestamp of the take's start; subtracted before `offset_us` is added.
+    #[serde(default)]
+    pub time_base_us: u64,
+}
+
+/// Which take keeps its events where takes overlap on the merged timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TakeOverlap {
+    /// A later take replaces what earlier takes recorded over its span (re-recording).
+    #[default]
+    LaterWins,
+    /// Earlier takes are kept; a later take's events over them are dropped.
+    EarlierWins,
+    /// Events from all takes are kept and interleaved.
+    Interleave,
+}
+
+/// Takes to merge, in recording order, with the overlap policy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TakeBatch {
+    pub takes: Vec<Take>,
+    #[serde(default)]
+    pub overlap: TakeOverlap,
+}
+
+impl Take {
+    /// Span of the take's events on the merged timeline, if it has any.
+    fn span(&self) -> Option<(u64, u64)> {
+        let times = self.signals.events.iter().map(|e| self.place(e.timestamp));
+        let start = times.clone().min()?;
+        Some((start.min(self.offset_us), times.max()?))
+    }
+
+    fn place(&self, timestamp: Timestamp) -> u64 {
+        timestamp.as_micros().saturating_sub(self.time_base_us) + self.offset_us
+    }
+}
+
+/// Merge takes into one time-ordered batch. Events dropped to resolve overlaps are
+/// reported as a `TakeOverlap` warning.
+pub fn merge_takes(batch: &TakeBatch) -> (SignalBatch, Vec<SignalWarning>) {
+    let mut events: Vec<InputEvent> = Vec::new();
+    let mut spans: Vec<(u64, u64)> = Vec::new();
+    let mut dropped = 0;
+
+    for take in &batch.takes {
+        let Some(span) = take.span() else {
+            continue;
+        };
+        let inside = |us: u64, (start, end): (u64, u64)| us >= start && us <= end;
+        match batch.overlap {
+            TakeOverlap::LaterWins => {
+                let before = events.len();
+                events.retain(|e| !inside(e.timestamp.as_micros(), span));
+                dropped += before - events.len();
+            }
+            TakeOverlap::EarlierWins | TakeOverlap::Interleave => {}
+        }
+        for event in &take.signals.events {
+            let us = take.place(event.timestamp);
+            if batch.overlap == TakeOverlap::EarlierWins
+                && spans.iter().any(|&earlier| inside(us, earlier))
+            {
+                dropped += 1;
+                continue;
+            }
+            events.push(InputEvent {
+                timestamp: Timestamp::from_micros(us),
+                ..event.clone()
+            });
+        }
+        spans.push(span);
+    }
+    events.sort_by_key(|e| e.timestamp);
+
+    let mut warnings = Vec::new();
+    if dropped > 0 {
+        warnings.push(SignalWarning {
+            kind: SignalWarningKind::TakeOverlap,
+            count: dropped as u32,
+            message: format!("{} events from overlapping takes were dropped", dropped),
+        });
+    }
+    (SignalBatch { events }, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn take(offset_us: u64, time_base_us: u64, local: &[u64]) -> Take {
+        let events = local
+            .iter()
+            .map(|&us| InputEvent {
+                timestamp: Timestamp::from_micros(us),
+                event_type: EventType::MouseMove {
+                    position: NormalizedCoord::new(offset_us as f32 / 1e7, 0.5),
+                },
+            })
+            .collect();
+        Take {
+            signals: SignalBatch { events },
+            offset_us,
+            time_base_us,
+        }
+    }
+
+    #[test]
+    fn merges_takes_and_resolves_overlap() {
+        // Take 1 covers 0-4s; take 2 (own clock starting at 50s) is placed at 3s.
+        let takes = vec![
+            take(0, 0, &[0, 2_000_000, 3_500_000, 4_000_000]),
+            take(3_000_000, 50_000_000, &[50_000_000, 52_000_000]),
+        ];
+        let times = |overlap| {
+            let (batch, warnings) = merge_takes(&TakeBatch {
+                takes: takes.clone(),
+                overlap,
+            });
+            let times: Vec<u64> = batch
+                .events
+                .iter()
+                .map(|e| e.timestamp.as_micros())
+                .collect();
+            (times, warnings.first().map(|w| w.count))
+        };
+
+        assert_eq!(
+            times(TakeOverlap::LaterWins),
+            (vec![0, 2_000_000, 3_000_000, 5_000_000], Some(2))
+        );
+        assert_eq!(
+            times(TakeOverlap::EarlierWins),
+            (vec![0, 2_000_000, 3_500_000, 4_000_000, 5_000_000], Some(1))
+        );
+        assert_eq!(times(TakeOverlap::Interleave).0.len(), 6);
+    }
+}
@@ -3,9 +3,19 @@
 // See design.md: TimeRemapper (Rust)
 
 use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
 use wasm_bindgen::prelude::*;
 
-use crate::types::Timestamp;
+use crate::error::EngineError;
+use crate::history::EditHistory;
+use crate::interchange::{to_edl, to_fcpxml, to_otio};
+use crate::interop::{from_js_object, to_js_object};
+use crate::lottie::effects_to_lottie;
+use crate::migrate::{migrate_remapper_config, REMAPPER_CONFIG_VERSION};
+use crate::ripple::RippleDelta;
+use crate::subtitles::chapters_vtt;
+use crate::types::{CameraKeyframe, EffectTrack, Timestamp};
+use crate::validation::{has_errors, validate_remapper_config, IssueSeverity};
 
 /// A time range in microseconds.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -30,36 +40,388 @@ impl TimeRange {
     }
 }
 
+/// Default speed limits, used when a config doesn't set its own.
+pub const DEFAULT_MIN_SPEED: f32 = 0.25;
+pub const DEFAULT_MAX_SPEED: f32 = 4.0;
+
+/// Allowed playback speeds. Ramps outside the range are clamped to it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpeedRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl Default for SpeedRange {
+    fn default() -> Self {
+        SpeedRange {
+            min: DEFAULT_MIN_SPEED,
+            max: DEFAULT_MAX_SPEED,
+        }
+    }
+}
+
+impl SpeedRange {
+    /// Create a range; a non-positive or non-finite minimum falls back to the default,
+    /// and the maximum is raised to the minimum if needed.
+    pub fn new(min: f32, max: f32) -> Self {
+        let min = if min.is_finite() && min > 0.0 {
+            min
+        } else {
+            DEFAULT_MIN_SPEED
+        };
+        let max = if max.is_finite() { max.max(min) } else { min };
+        SpeedRange { min, max }
+    }
+
+    pub fn clamp(&self, speed: f32) -> f32 {
+        speed.clamp(self.min, self.max)
+    }
+
+    pub fn contains(&self, speed: f32) -> bool {
+        (self.min..=self.max).contains(&speed)
+    }
+}
+
 /// A speed ramp segment with a time range and speed multiplier.
+///
+/// Optional `ramp_in_us` / `ramp_out_us` windows make the transition gradual: speed
+/// changes linearly (over source time) from 1.0 to `speed` across the first
+/// `ramp_in_us`, and back to 1.0 across the last `ramp_out_us`.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct SpeedRamp {
     pub range: TimeRange,
-    pub speed: f32, // 0.25 to 4.0
+    pub speed: f32, // within the remapper's SpeedRange (0.25 to 4.0 by default)
+    /// Duration of the ease-in window at the start of the range (microseconds).
+    #[serde(default)]
+    pub ramp_in_us: u64,
+    /// Duration of the ease-out window at the end of the range (microseconds).
+    #[serde(default)]
+    pub ramp_out_us: u64,
 }
 
 impl SpeedRamp {
     pub fn new(range: TimeRange, speed: f32) -> Self {
-        // Clamp speed to valid range
-        let speed = speed.clamp(0.25, 4.0);
-        SpeedRamp { range, speed }
+        Self::with_easing(range, speed, 0, 0)
+    }
+
+    /// Create a gradual speed ramp. The ease windows are scaled down proportionally
+    /// if together they exceed the range duration. Speed is clamped to the default range.
+    pub fn with_easing(range: TimeRange, speed: f32, ramp_in_us: u64, ramp_out_us: u64) -> Self {
+        Self::with_limits(range, speed, ramp_in_us, ramp_out_us, SpeedRange::default())
+    }
+
+    /// Like `with_easing`, clamping speed to `limits` instead of the default range.
+    pub fn with_limits(
+        range: TimeRange,
+        speed: f32,
+        ramp_in_us: u64,
+        ramp_out_us: u64,
+        limits: SpeedRange,
+    ) -> Self {
+        Self::unclamped(range, limits.clamp(speed), ramp_in_us, ramp_out_us)
+    }
+
+    /// Build a ramp without touching its speed (used when reshaping existing ramps).
+    fn unclamped(range: TimeRange, speed: f32, ramp_in_us: u64, ramp_out_us: u64) -> Self {
+        let duration = range.duration();
+        let total = ramp_in_us.saturating_add(ramp_out_us);
+        let (ramp_in_us, ramp_out_us) = if total > duration {
+            let ramp_in = (ramp_in_us as f64 * duration as f64 / total as f64).round() as u64;
+            (ramp_in, duration - ramp_in)
+        } else {
+            (ramp_in_us, ramp_out_us)
+        };
+
+        SpeedRamp {
+            range,
+            speed,
+            ramp_in_us,
+            ramp_out_us,
+        }
+    }
+
+    /// Whether this ramp has ease-in or ease-out windows.
+    pub fn is_gradual(&self) -> bool {
+        self.ramp_in_us > 0 || self.ramp_out_us > 0
+    }
+
+    /// End of the ease-in window (start of the plateau).
+    fn plateau_start(&self) -> u64 {
+        self.range.start.as_micros() + self.ramp_in_us
+    }
+
+    /// Start of the ease-out window (end of the plateau).
+    fn plateau_end(&self) -> u64 {
+        self.range.end.as_micros() - self.ramp_out_us
+    }
+
+    /// Instantaneous speed at a source time, clamped to the ramp's range.
+    /// Evaluated at the range end this is the left-hand limit, which is what
+    /// segment integration needs.
+    pub fn speed_profile_at(&self, source_time: u64) -> f32 {
+        let t = source_time.clamp(self.range.start.as_micros(), self.range.end.as_micros());
+        if t < self.plateau_start() {
+            let progress = (t - self.range.start.as_micros()) as f32 / self.ramp_in_us as f32;
+            1.0 + (self.speed - 1.0) * progress
+        } else if t > self.plateau_end() {
+            let progress = (t - self.plateau_end()) as f32 / self.ramp_out_us as f32;
+            self.speed + (1.0 - self.speed) * progress
+        } else {
+            self.speed
+        }
+    }
+
+    /// The parts of this ramp outside `hole`. Remnants keep the ease window on their
+    /// untouched side; a ramp that doesn't overlap `hole` is returned unchanged.
+    pub fn split_around(&self, hole: TimeRange) -> Vec<SpeedRamp> {
+        if self.range.end <= hole.start || self.range.start >= hole.end {
+            return vec![*self];
+        }
+        let mut remnants = Vec::with_capacity(2);
+        if self.range.start < hole.start {
+            remnants.push(SpeedRamp::unclamped(
+                TimeRange::new(self.range.start, hole.start),
+                self.speed,
+                self.ramp_in_us,
+                0,
+            ));
+        }
+        if self.range.end > hole.end {
+            remnants.push(SpeedRamp::unclamped(
+                TimeRange::new(hole.end, self.range.end),
+                self.speed,
+                0,
+                self.ramp_out_us,
+            ));
+        }
+        remnants
     }
 
     /// Calculate the export duration for this speed ramp segment.
-    /// Export duration = source duration / speed
+    /// Export duration = source duration / speed (integrated across ease windows)
     pub fn export_duration(&self) -> u64 {
-        let source_duration = self.range.duration() as f64;
-        (source_duration / self.speed as f64).round() as u64
+        if !self.is_gradual() {
+            let source_duration = self.range.duration() as f64;
+            return (source_duration / self.speed as f64).round() as u64;
+        }
+
+        let speed = self.speed as f64;
+        let plateau = (self.plateau_end() - self.plateau_start()) as f64;
+        let total = export_time_for(self.ramp_in_us as f64, 1.0, speed)
+            + plateau / speed
+            + export_time_for(self.ramp_out_us as f64, speed, 1.0);
+        total.round() as u64
+    }
+}
+
+/// Export time needed to play `source_len` microseconds while speed changes
+/// linearly (over source time) from `v0` to `v1`.
+///
+/// With v(x) = v0 + k·x, export time is ∫ dx / v(x) = ln(v1 / v0) / k.
+fn export_time_for(source_len: f64, v0: f64, v1: f64) -> f64 {
+    if v0 == v1 {
+        source_len / v0
+    } else {
+        source_len * (v1 / v0).ln() / (v1 - v0)
+    }
+}
+
+/// Inverse of `export_time_for`: source offset reached after `export_offset`
+/// microseconds of export time.
+fn source_offset_for(export_offset: f64, source_len: f64, v0: f64, v1: f64) -> f64 {
+    if v0 == v1 {
+        export_offset * v0
+    } else {
+        let k = (v1 - v0) / source_len;
+        v0 * ((k * export_offset).exp() - 1.0) / k
+    }
+}
+
+/// A contiguous piece of source time that survives into the export. Speed is constant
+/// across the segment, or changes linearly from `speed` to `end_speed` inside a ramp's
+/// ease window. The segment table is precomputed so queries are a binary search instead
+/// of a walk.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RemapSegment {
+    /// Source range covered by this segment (never inside a cut).
+    pub source: TimeRange,
+    /// Export timestamp at which this segment starts.
+    pub export_start: Timestamp,
+    /// Export duration of this segment in microseconds (source duration / speed, rounded).
+    pub export_duration: u64,
+    /// Playback speed at the start of the segment.
+    pub speed: f32,
+    /// Playback speed at the end of the segment (equal to `speed` unless easing).
+    pub end_speed: f32,
+}
+
+impl RemapSegment {
+    /// Export timestamp at which this segment ends (exclusive).
+    pub fn export_end(&self) -> Timestamp {
+        Timestamp::from_micros(self.export_start.as_micros() + self.export_duration)
+    }
+
+    /// Source offset (from `source.start`) reached `export_offset` microseconds into
+    /// the segment.
+    pub fn source_offset_at(&self, export_offset: u64) -> u64 {
+        source_offset_for(
+            export_offset as f64,
+            self.source.duration() as f64,
+            self.speed as f64,
+            self.end_speed as f64,
+        )
+        .round() as u64
+    }
+
+    /// Export offset (from `export_start`) reached `source_offset` microseconds into
+    /// the segment's source range. Inverse of `source_offset_at`.
+    pub fn export_offset_at(&self, source_offset: u64) -> u64 {
+        let len = self.source.duration() as f64;
+        let offset = (source_offset as f64).min(len);
+        let v0 = self.speed as f64;
+        let v1 = self.end_speed as f64;
+        let v = v0 + (v1 - v0) * offset / len;
+        (export_time_for(offset, v0, v).round() as u64).min(self.export_duration)
+    }
+}
+
+/// How one stretch of exported audio is produced from the source.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AudioSegment {
+    /// Range on the export timeline.
+    pub export_range: TimeRange,
+    /// Range of source audio played over `export_range`.
+    pub source_range: TimeRange,
+    /// Average speed (source duration / export duration).
+    pub speed: f32,
+    /// Instantaneous speed at the start and end; differ only inside ease windows.
+    pub start_speed: f32,
+    pub end_speed: f32,
+    /// True when the chunk must be time-stretched with pitch preserved; false when it
+    /// plays at 1x and can be copied (or plainly resampled) as-is.
+    pub preserve_pitch: bool,
+}
+
+/// One output frame of the export and the source content it shows.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FrameScheduleEntry {
+    /// Export frame index.
+    pub frame: u64,
+    /// Presentation time of the frame on the export timeline.
+    pub export_time: Timestamp,
+    /// Source timestamp shown in the frame.
+    pub source_time: Timestamp,
+    /// Playback speed at `source_time`.
+    pub speed: f32,
+}
+
+/// A labelled point in source time (chapter, note, bookmark).
+/// Markers live in source time so they stay attached to the content as cuts and
+/// ramps are edited; `TimeRemapper::export_markers` maps them to the export timeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Marker {
+    pub timestamp: Timestamp,
+    pub label: String,
+    /// CSS color string, e.g. "#ff8800". Empty means the UI default.
+    #[serde(default)]
+    pub color: String,
+}
+
+impl Marker {
+    pub fn new(timestamp: Timestamp, label: impl Into<String>, color: impl Into<String>) -> Self {
+        Marker {
+            timestamp,
+            label: label.into(),
+            color: color.into(),
+        }
+    }
+}
+
+/// A marker placed on the export timeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportMarker {
+    /// Index into `TimeRemapper::markers()`.
+    pub index: usize,
+    pub marker: Marker,
+    /// Position on the export timeline.
+    pub export_time: Timestamp,
+    /// The marker's source time was cut; `export_time` is where the cut was removed.
+    pub in_cut: bool,
+}
+
+/// Sort ranges, drop empty ones, and merge overlapping ones (adjacent ranges stay separate).
+fn merge_overlapping(mut ranges: Vec<TimeRange>) -> Vec<TimeRange> {
+    ranges.retain(|range| range.duration() > 0);
+    ranges.sort_by_key(|r| r.start);
+
+    let mut merged: Vec<TimeRange> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        if let Some(last) = merged.last_mut() {
+            if range.start < last.end {
+                last.end = last.end.max(range.end);
+                continue;
+            }
+        }
+        merged.push(range);
     }
+    merged
 }
 
 /// Time remapper that handles cuts and speed ramps.
 /// Maps export timestamps to source timestamps for the export pipeline.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "TimeRemapperParts")]
 pub struct TimeRemapper {
     cuts: Vec<TimeRange>,
     speed_ramps: Vec<SpeedRamp>,
     in_point: Timestamp,
     out_point: Timestamp,
+    /// Allowed ramp speeds.
+    speed_range: SpeedRange,
+    /// Markers in source-time order. They don't affect the mapping.
+    markers: Vec<Marker>,
+    /// Ranges that always play at 1x (e.g. narration); ramps are split around them.
+    protected: Vec<TimeRange>,
+    /// Decodable (GOP keyframe) timestamps of the source video, sorted.
+    video_keyframes: Vec<Timestamp>,
+    /// Derived from the fields above; rebuilt on construction and deserialization.
+    #[serde(skip)]
+    segments: Vec<RemapSegment>,
+    /// `speed_ramps` with protected ranges cut out; what the segment table is built from.
+    #[serde(skip)]
+    effective_ramps: Vec<SpeedRamp>,
+}
+
+/// Serialized fields of a TimeRemapper, used to rebuild the segment table on deserialize.
+#[derive(Deserialize)]
+struct TimeRemapperParts {
+    cuts: Vec<TimeRange>,
+    speed_ramps: Vec<SpeedRamp>,
+    in_point: Timestamp,
+    out_point: Timestamp,
+    #[serde(default)]
+    speed_range: SpeedRange,
+    #[serde(default)]
+    markers: Vec<Marker>,
+    #[serde(default)]
+    protected: Vec<TimeRange>,
+    #[serde(default)]
+    video_keyframes: Vec<Timestamp>,
+}
+
+impl From<TimeRemapperParts> for TimeRemapper {
+    fn from(parts: TimeRemapperParts) -> Self {
+        TimeRemapper::new(
+            parts.cuts,
+            parts.speed_ramps,
+            parts.in_point,
+            parts.out_point,
+        )
+        .with_speed_range(parts.speed_range)
+        .with_markers(parts.markers)
+        .with_protected_ranges(parts.protected)
+        .with_video_keyframes(parts.video_keyframes)
+    }
 }
 
 impl TimeRemapper {
@@ -75,22 +437,63 @@ impl TimeRemapper {
             speed_ramps,
             in_point,
             out_point,
+            speed_range: SpeedRange::default(),
+            markers: Vec::new(),
+            protected: Vec::new(),
+            video_keyframes: Vec::new(),
+            segments: Vec::new(),
+            effective_ramps: Vec::new(),
         };
         // Sort cuts by start time for efficient processing
         remapper.cuts.sort_by_key(|c| c.start);
         // Sort speed ramps by start time
-        remapper.speed_ramps.sort_by(|a, b| a.range.start.cmp(&b.range.start));
+        remapper.speed_ramps.sort_by_key(|r| r.range.start);
+        remapper.rebuild_segments();
         remapper
     }
 
+    /// Set the allowed speed range, clamping existing ramps into it.
+    pub fn with_speed_range(mut self, speed_range: SpeedRange) -> Self {
+        self.apply_speed_range(speed_range);
+        self
+    }
+
+    fn apply_speed_range(&mut self, speed_range: SpeedRange) {
+        self.speed_range = speed_range;
+        for ramp in &mut self.speed_ramps {
+            ramp.speed = speed_range.clamp(ramp.speed);
+        }
+        self.rebuild_segments();
+    }
+
+    /// Get the allowed speed range.
+    pub fn speed_range(&self) -> SpeedRange {
+        self.speed_range
+    }
+
+    /// Attach markers, replacing any existing ones.
+    pub fn with_markers(mut self, markers: Vec<Marker>) -> Self {
+        self.markers = markers;
+        self.markers.sort_by_key(|m| m.timestamp);
+        self
+    }
+
+    /// Attach protected (always 1x) ranges, replacing any existing ones.
+    pub fn with_protected_ranges(mut self, ranges: Vec<TimeRange>) -> Self {
+        self.protected = merge_overlapping(ranges);
+        self.rebuild_segments();
+        self
+    }
+
+    /// Attach the source video's keyframe timestamps (see `set_video_keyframes`).
+    pub fn with_video_keyframes(mut self, keyframes: Vec<Timestamp>) -> Self {
+        self.set_video_keyframes(keyframes);
+        self
+    }
+
     /// Create a TimeRemapper with no cuts or speed ramps.
     pub fn identity(in_point: Timestamp, out_point: Timestamp) -> Self {
-        TimeRemapper {
-            cuts: Vec::new(),
-            speed_ramps: Vec::new(),
-            in_point,
-            out_point,
-        }
+        TimeRemapper::new(Vec::new(), Vec::new(), in_point, out_point)
     }
 
     /// Check if a source timestamp is within a cut region.
@@ -100,13 +503,18 @@ impl TimeRemapper {
 
     /// Get playback speed at a source timestamp.
     /// Returns 1.0 if no speed ramp is active at this timestamp.
+    /// Inside a ramp's ease window this is the instantaneous (interpolated) speed.
     pub fn speed_at(&self, source_time: Timestamp) -> f32 {
-        for ramp in &self.speed_ramps {
-            if ramp.range.contains(source_time) {
-                return ramp.speed;
-            }
-        }
-        1.0
+        self.ramp_at(source_time)
+            .map(|ramp| ramp.speed_profile_at(source_time.as_micros()))
+            .unwrap_or(1.0)
+    }
+
+    /// The first effective speed ramp containing a source timestamp.
+    fn ramp_at(&self, source_time: Timestamp) -> Option<&SpeedRamp> {
+        self.effective_ramps
+            .iter()
+            .find(|ramp| ramp.range.contains(source_time))
     }
 
     /// Calculate total export duration after cuts and speed changes.
@@ -115,15 +523,29 @@ impl TimeRemapper {
     /// 2. Cut segments (removed from duration)
     /// 3. Speed ramps (duration adjusted by speed factor)
     pub fn export_duration(&self) -> Timestamp {
+        self.segments
+            .last()
+            .map(|segment| segment.export_end())
+            .unwrap_or_else(|| Timestamp::from_micros(0))
+    }
+
+    /// Get the precomputed segment table, ordered by source (and export) time.
+    pub fn segments(&self) -> &[RemapSegment] {
+        &self.segments
+    }
+
+    /// Rebuild the segment table by walking source time from in_point to out_point,
+    /// skipping cuts and splitting at every speed ramp boundary.
+    fn rebuild_segments(&mut self) {
+        self.segments.clear();
+        self.rebuild_effective_ramps();
+
         let trimmed_duration = self.out_point.as_micros().saturating_sub(self.in_point.as_micros());
-        
         if trimmed_duration == 0 {
-            return Timestamp::from_micros(0);
+            return;
         }
 
-        // Calculate export duration by iterating through source time
-        // and accounting for cuts and speed ramps
-        let mut export_duration = 0u64;
+        let mut export_time = 0u64;
         let mut source_time = self.in_point.as_micros();
 
         while source_time < self.out_point.as_micros() {
@@ -140,21 +562,49 @@ impl TimeRemapper {
             let next_boundary = self.find_next_boundary(source_time);
             let segment_source_duration = next_boundary - source_time;
 
-            // Get speed at this source time
-            let speed = self.speed_at(ts);
-
-            // Export duration for this segment = source duration / speed
-            let segment_export_duration = (segment_source_duration as f64 / speed as f64).round() as u64;
-            export_duration += segment_export_duration;
-
+            // Export duration for this segment = source duration / speed,
+            // integrated when the speed is easing across the segment
+            let (speed, end_speed) = match self.ramp_at(ts) {
+                Some(ramp) => (
+                    ramp.speed_profile_at(source_time),
+                    ramp.speed_profile_at(next_boundary),
+                ),
+                None => (1.0, 1.0),
+            };
+            let segment_export_duration = export_time_for(
+                segment_source_duration as f64,
+                speed as f64,
+                end_speed as f64,
+            )
+            .round() as u64;
+
+            self.segments.push(RemapSegment {
+                source: TimeRange::new(ts, Timestamp::from_micros(next_boundary)),
+                export_start: Timestamp::from_micros(export_time),
+                export_duration: segment_export_duration,
+                speed,
+                end_speed,
+            });
+
+            export_time += segment_export_duration;
             source_time = next_boundary;
         }
+    }
 
-        Timestamp::from_micros(export_duration)
+    /// Split the authored speed ramps around every protected range.
+    fn rebuild_effective_ramps(&mut self) {
+        let mut ramps = self.speed_ramps.clone();
+        for &hole in &self.protected {
+            ramps = ramps
+                .iter()
+                .flat_map(|ramp| ramp.split_around(hole))
+                .collect();
+        }
+        self.effective_ramps = ramps;
     }
 
     /// Find the next boundary point from a given source time.
-    /// Boundaries are: cut starts, speed ramp starts/ends, or out_point.
+    /// Boundaries are: cut starts, speed ramp starts/ends, ease window edges, or out_point.
     fn find_next_boundary(&self, source_time: u64) -> u64 {
         let mut next = self.out_point.as_micros();
 
@@ -167,16 +617,24 @@ impl TimeRemapper {
         }
 
         // Check speed ramp boundaries
-        for ramp in &self.speed_ramps {
+        for ramp in &self.effective_ramps {
             let ramp_start = ramp.range.start.as_micros();
             let ramp_end = ramp.range.end.as_micros();
-            
+
             if ramp_start > source_time && ramp_start < next {
                 next = ramp_start;
             }
             if ramp_end > source_time && ramp_end < next {
                 next = ramp_end;
             }
+
+            if ramp.is_gradual() {
+                for edge in [ramp.plateau_start(), ramp.plateau_end()] {
+                    if edge > source_time && edge < next {
+                        next = edge;
+                    }
+                }
+            }
         }
 
         next
@@ -191,49 +649,210 @@ impl TimeRemapper {
     /// 1. Trim points (export starts at in_point)
     /// 2. Cut segments (skipped in export)
     /// 3. Speed ramps (time scaled by speed factor)
+    ///
+    /// Runs in O(log n) over the precomputed segment table.
     pub fn to_source_time(&self, export_time: Timestamp) -> Timestamp {
         let export_time_us = export_time.as_micros();
-        
-        // Start at in_point and skip any cuts at the beginning
-        let mut source_time = self.skip_cuts_forward(self.in_point).as_micros();
-        
+
         if export_time_us == 0 {
-            return Timestamp::from_micros(source_time);
+            // Start at in_point and skip any cuts at the beginning
+            return self.skip_cuts_forward(self.in_point);
         }
 
-        let mut remaining_export_time = export_time_us;
+        // First segment that reaches the target export time.
+        let index = self
+            .segments
+            .partition_point(|segment| segment.export_end().as_micros() < export_time_us);
+
+        let source_time = match self.segments.get(index) {
+            // Past the end of the export.
+            None => self.out_point.as_micros(),
+            // Exactly at a segment end: land on the start of the next segment.
+            Some(segment) if segment.export_end().as_micros() == export_time_us => self
+                .segments
+                .get(index + 1)
+                .map(|next| next.source.start.as_micros())
+                .unwrap_or(self.out_point.as_micros()),
+            // Inside the segment: source_offset = export_offset * speed
+            // (inverted through the speed integral when easing)
+            Some(segment) => {
+                let offset = export_time_us - segment.export_start.as_micros();
+                segment.source.start.as_micros() + segment.source_offset_at(offset)
+            }
+        };
 
-        while remaining_export_time > 0 && source_time < self.out_point.as_micros() {
-            let ts = Timestamp::from_micros(source_time);
+        // Ensure we don't exceed out_point
+        Timestamp::from_micros(source_time.min(self.out_point.as_micros()))
+    }
 
-            // Find the next boundary (cut start, speed ramp boundary, or out_point)
-            let next_boundary = self.find_next_boundary(source_time);
-            let segment_source_duration = next_boundary - source_time;
+    /// Number of frames in the export at `fps`.
+    ///
+    /// Rounding policy: frame `n` is presented at `Timestamp::from_frame(n, fps)`
+    /// (rounded to the nearest microsecond from the frame index), and a frame is part
+    /// of the export iff its presentation time is strictly before `export_duration()`.
+    pub fn export_frame_count(&self, fps: f64) -> u64 {
+        if fps <= 0.0 || !fps.is_finite() {
+            return 0;
+        }
+        let duration = self.export_duration();
+        let mut count = (duration.as_micros() as f64 * fps / 1_000_000.0).ceil() as u64;
+        while count > 0 && Timestamp::from_frame(count - 1, fps) >= duration {
+            count -= 1;
+        }
+        while Timestamp::from_frame(count, fps) < duration {
+            count += 1;
+        }
+        count
+    }
 
-            // Get speed at this source time
-            let speed = self.speed_at(ts);
+    /// Source timestamp shown at export frame `frame` (see `export_frame_count` for
+    /// the rounding policy).
+    pub fn source_time_for_export_frame(&self, frame: u64, fps: f64) -> Timestamp {
+        self.to_source_time(Timestamp::from_frame(frame, fps))
+    }
 
-            // Calculate export duration for this segment
-            let segment_export_duration = (segment_source_duration as f64 / speed as f64).round() as u64;
+    /// Source frame index (at the same `fps`) shown at export frame `frame`: the last
+    /// source frame whose presentation time is at or before the mapped source time.
+    pub fn source_frame_for_export_frame(&self, frame: u64, fps: f64) -> u64 {
+        self.source_time_for_export_frame(frame, fps).frame_at(fps)
+    }
 
-            if remaining_export_time < segment_export_duration {
-                // The target is within this segment
-                // source_offset = export_offset * speed
-                let source_offset = (remaining_export_time as f64 * speed as f64).round() as u64;
-                source_time += source_offset;
-                remaining_export_time = 0;
-            } else {
-                // Move past this segment
-                remaining_export_time -= segment_export_duration;
-                source_time = next_boundary;
-                
-                // Skip any cuts at the new position
-                source_time = self.skip_cuts_forward(Timestamp::from_micros(source_time)).as_micros();
+    /// Every export frame at `fps`, in order, with the source time it shows and the
+    /// speed there. Uses the same rounding as `export_frame_count` and
+    /// `source_time_for_export_frame`, so the exporter can drive decoding from it directly.
+    pub fn frame_schedule(&self, fps: f64) -> Vec<FrameScheduleEntry> {
+        (0..self.export_frame_count(fps))
+            .map(|frame| {
+                let source_time = self.source_time_for_export_frame(frame, fps);
+                FrameScheduleEntry {
+                    frame,
+                    export_time: Timestamp::from_frame(frame, fps),
+                    source_time,
+                    speed: self.speed_at(source_time),
+                }
+            })
+            .collect()
+    }
+
+    /// Map a source timestamp to export time.
+    /// Returns `None` if the timestamp is cut or outside the trim range.
+    pub fn to_export_time(&self, source_time: Timestamp) -> Option<Timestamp> {
+        if self.is_cut(source_time) {
+            return None;
+        }
+        self.export_position(source_time)
+    }
+
+    /// Export range covered by a source range, or `None` if its start is cut or trimmed.
+    /// The end maps to its left limit, so a range ending at a cut ends at the cut point.
+    pub fn export_range_of(&self, source: TimeRange) -> Option<TimeRange> {
+        let start = self.to_export_time(source.start)?;
+        let end = if source.end >= self.out_point {
+            self.export_duration()
+        } else {
+            self.export_position(source.end)?
+        };
+        Some(TimeRange::new(start, end.max(start)))
+    }
+
+    /// Export position of a source timestamp inside the trim range [in_point, out_point).
+    /// Cut timestamps map to the export point where the cut was removed.
+    fn export_position(&self, source_time: Timestamp) -> Option<Timestamp> {
+        if source_time < self.in_point || source_time >= self.out_point {
+            return None;
+        }
+
+        // First segment that ends after the source time.
+        let index = self
+            .segments
+            .partition_point(|segment| segment.source.end <= source_time);
+
+        let export_time = match self.segments.get(index) {
+            None => self.export_duration().as_micros(),
+            Some(segment) if source_time < segment.source.start => segment.export_start.as_micros(),
+            Some(segment) => {
+                let offset = source_time.as_micros() - segment.source.start.as_micros();
+                segment.export_start.as_micros() + segment.export_offset_at(offset)
+            }
+        };
+
+        Some(Timestamp::from_micros(export_time))
+    }
+
+    /// Get the markers, in source-time order.
+    pub fn markers(&self) -> &[Marker] {
+        &self.markers
+    }
+
+    /// Add a marker, keeping source-time order (after existing markers at the same time).
+    /// Returns its index.
+    pub fn add_marker(&mut self, marker: Marker) -> usize {
+        let index = self
+            .markers
+            .partition_point(|m| m.timestamp <= marker.timestamp);
+        self.markers.insert(index, marker);
+        index
+    }
+
+    /// Remove the marker at `index`.
+    pub fn remove_marker(&mut self, index: usize) -> Option<Marker> {
+        (index < self.markers.len()).then(|| self.markers.remove(index))
+    }
+
+    /// Markers on the export timeline, in export-time order.
+    /// Markers outside the trim range are dropped; markers inside a cut are kept at
+    /// the cut point (flagged `in_cut`) so chapters aren't silently lost.
+    pub fn export_markers(&self) -> Vec<ExportMarker> {
+        self.markers
+            .iter()
+            .enumerate()
+            .filter_map(|(index, marker)| {
+                self.export_position(marker.timestamp)
+                    .map(|export_time| ExportMarker {
+                        index,
+                        marker: marker.clone(),
+                        export_time,
+                        in_cut: self.is_cut(marker.timestamp),
+                    })
+            })
+            .collect()
+    }
+
+    /// Audio chunks for the export, in export order. Contiguous segments with the same
+    /// constant speed are merged so the audio pipeline sees as few chunks as possible.
+    pub fn audio_segments(&self) -> Vec<AudioSegment> {
+        let mut chunks: Vec<AudioSegment> = Vec::with_capacity(self.segments.len());
+        for segment in &self.segments {
+            if segment.export_duration == 0 {
+                continue;
             }
+            let constant = segment.speed == segment.end_speed;
+            if let Some(last) = chunks.last_mut() {
+                if constant
+                    && last.start_speed == last.end_speed
+                    && last.end_speed == segment.speed
+                    && last.source_range.end == segment.source.start
+                {
+                    last.export_range.end = segment.export_end();
+                    last.source_range.end = segment.source.end;
+                    continue;
+                }
+            }
+            chunks.push(AudioSegment {
+                export_range: TimeRange::new(segment.export_start, segment.export_end()),
+                source_range: segment.source,
+                speed: segment.speed,
+                start_speed: segment.speed,
+                end_speed: segment.end_speed,
+                preserve_pitch: !(constant && segment.speed == 1.0),
+            });
         }
 
-        // Ensure we don't exceed out_point
-        Timestamp::from_micros(source_time.min(self.out_point.as_micros()))
+        for chunk in &mut chunks {
+            chunk.speed = (chunk.source_range.duration() as f64
+                / chunk.export_range.duration() as f64) as f32;
+        }
+        chunks
     }
 
     /// Skip forward past any cut regions starting from the given timestamp.
@@ -252,6 +871,185 @@ impl TimeRemapper {
         Timestamp::from_micros(current.min(self.out_point.as_micros()))
     }
 
+    // -------------------------------------------------------------------------
+    // Edit operations. Each edit re-normalizes and rebuilds the segment table.
+    // -------------------------------------------------------------------------
+
+    /// Add a cut. Overlapping cuts are merged and the result is clamped to the trim.
+    pub fn add_cut(&mut self, range: TimeRange) {
+        self.cuts.push(range);
+        self.normalize();
+    }
+
+    /// Remove the cut at `index` (in start-time order). Returns the removed cut.
+    pub fn remove_cut(&mut self, index: usize) -> Option<TimeRange> {
+        if index >= self.cuts.len() {
+            return None;
+        }
+        let removed = self.cuts.remove(index);
+        self.normalize();
+        Some(removed)
+    }
+
+    /// Split the cut at `index` into two adjacent cuts at `at`.
+    /// Adjacent cuts are not merged, so the halves can be edited independently.
+    /// Returns `false` if the index is invalid or `at` is not strictly inside the cut.
+    pub fn split_cut(&mut self, index: usize, at: Timestamp) -> bool {
+        let Some(cut) = self.cuts.get(index).copied() else {
+            return false;
+        };
+        if at <= cut.start || at >= cut.end {
+            return false;
+        }
+        self.cuts[index] = TimeRange::new(cut.start, at);
+        self.cuts.insert(index + 1, TimeRange::new(at, cut.end));
+        self.normalize();
+        true
+    }
+
+    /// Set the speed over a range, replacing whatever ramps covered that range.
+    /// Existing ramps that partially overlap are trimmed (or split) around it.
+    pub fn set_speed_ramp(&mut self, ramp: SpeedRamp) {
+        let mut ramps: Vec<SpeedRamp> = self
+            .speed_ramps
+            .iter()
+            .flat_map(|existing| existing.split_around(ramp.range))
+            .collect();
+        ramps.push(ramp);
+        self.speed_ramps = ramps;
+        self.normalize();
+    }
+
+    /// Change the allowed speed range. Existing ramps are clamped into it.
+    pub fn set_speed_range(&mut self, speed_range: SpeedRange) {
+        self.apply_speed_range(speed_range);
+    }
+
+    /// Remove the speed ramp at `index` (in start-time order). Returns the removed ramp.
+    pub fn remove_speed_ramp(&mut self, index: usize) -> Option<SpeedRamp> {
+        if index >= self.speed_ramps.len() {
+            return None;
+        }
+        let removed = self.speed_ramps.remove(index);
+        self.normalize();
+        Some(removed)
+    }
+
+    /// Protect a range so it always plays at 1x. Overlapping protected ranges merge;
+    /// authored ramps are kept and only split in the effective ramp list.
+    pub fn add_protected_range(&mut self, range: TimeRange) {
+        self.protected.push(range);
+        self.normalize();
+    }
+
+    /// Remove the protected range at `index` (in start-time order).
+    pub fn remove_protected_range(&mut self, index: usize) -> Option<TimeRange> {
+        if index >= self.protected.len() {
+            return None;
+        }
+        let removed = self.protected.remove(index);
+        self.rebuild_segments();
+        Some(removed)
+    }
+
+    /// Set the source video's keyframe (GOP start) timestamps, as reported by the demuxer.
+    /// They are media metadata, not part of the edit, so `to_config` doesn't include them.
+    pub fn set_video_keyframes(&mut self, mut keyframes: Vec<Timestamp>) {
+        keyframes.sort();
+        keyframes.dedup();
+        self.video_keyframes = keyframes;
+    }
+
+    /// Get the source video's keyframe timestamps.
+    pub fn video_keyframes(&self) -> &[Timestamp] {
+        &self.video_keyframes
+    }
+
+    /// Move the end of each cut (where playback resumes, so where the decoder seeks) to
+    /// the nearest video keyframe within `tolerance_us`. Cuts with no keyframe in range,
+    /// or whose end would move to or before their start, are left alone.
+    /// Returns how many cuts moved.
+    pub fn snap_cuts_to_keyframes(&mut self, tolerance_us: u64) -> usize {
+        let mut snapped = 0;
+        for i in 0..self.cuts.len() {
+            let cut = self.cuts[i];
+            let Some(keyframe) = self.nearest_video_keyframe(cut.end) else {
+                continue;
+            };
+            if keyframe == cut.end
+                || keyframe <= cut.start
+                || keyframe.as_micros().abs_diff(cut.end.as_micros()) > tolerance_us
+            {
+                continue;
+            }
+            self.cuts[i].end = keyframe;
+            snapped += 1;
+        }
+        if snapped > 0 {
+            self.normalize();
+        }
+        snapped
+    }
+
+    /// The video keyframe closest to `ts` (the earlier one on a tie).
+    fn nearest_video_keyframe(&self, ts: Timestamp) -> Option<Timestamp> {
+        let index = self.video_keyframes.partition_point(|&k| k < ts);
+        let after = self.video_keyframes.get(index).copied();
+        let before = index.checked_sub(1).map(|i| self.video_keyframes[i]);
+        match (before, after) {
+            (Some(b), Some(a)) => {
+                if ts.as_micros() - b.as_micros() <= a.as_micros() - ts.as_micros() {
+                    Some(b)
+                } else {
+                    Some(a)
+                }
+            }
+            (b, a) => b.or(a),
+        }
+    }
+
+    /// Set the trim points. Cuts and ramps are clamped to the new range.
+    pub fn set_trim(&mut self, in_point: Timestamp, out_point: Timestamp) {
+        self.in_point = in_point;
+        self.out_point = out_point.max(in_point);
+        self.normalize();
+    }
+
+    /// Sort, clamp to the trim range, drop empty ranges, and merge overlapping cuts
+    /// and protected ranges. Ramp speeds are clamped to the speed range. Ramps never
+    /// overlap after `set_speed_ramp`, so they are only sorted and clamped.
+    fn normalize(&mut self) {
+        let clamp = |range: TimeRange| {
+            TimeRange::new(
+                range.start.max(self.in_point),
+                range.end.min(self.out_point),
+            )
+        };
+
+        self.cuts = merge_overlapping(self.cuts.iter().map(|&cut| clamp(cut)).collect());
+        self.protected =
+            merge_overlapping(self.protected.iter().map(|&range| clamp(range)).collect());
+
+        let mut ramps: Vec<SpeedRamp> = self
+            .speed_ramps
+            .iter()
+            .map(|ramp| {
+                SpeedRamp::with_limits(
+                    clamp(ramp.range),
+                    ramp.speed,
+                    ramp.ramp_in_us,
+                    ramp.ramp_out_us,
+                    self.speed_range,
+                )
+            })
+            .filter(|ramp| ramp.range.duration() > 0)
+            .collect();
+        ramps.sort_by_key(|r| r.range.start);
+        self.speed_ramps = ramps;
+
+        self.rebuild_segments();
+    }
+
     /// Get the in point.
     pub fn in_point(&self) -> Timestamp {
         self.in_point
@@ -267,21 +1065,157 @@ impl TimeRemapper {
         &self.cuts
     }
 
-    /// Get the speed ramps.
+    /// Get the speed ramps as authored.
     pub fn speed_ramps(&self) -> &[SpeedRamp] {
         &self.speed_ramps
     }
-}
 
-// =============================================================================
-// WASM Bindings
-// =============================================================================
+    /// Get the protected (always 1x) ranges.
+    pub fn protected_ranges(&self) -> &[TimeRange] {
+        &self.protected
+    }
 
-/// Configuration for creating a TimeRemapper from JavaScript.
-/// This is the JSON structure expected by `WasmTimeRemapper::new()`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TimeRemapperConfig {
-    /// Cut segments to remove from the export.
+    /// Get the speed ramps actually applied: authored ramps split around protected ranges.
+    pub fn effective_speed_ramps(&self) -> &[SpeedRamp] {
+        &self.effective_ramps
+    }
+
+    /// Build a TimeRemapper from its JSON-friendly configuration.
+    pub fn from_config(config: TimeRemapperConfig) -> Self {
+        let markers: Vec<Marker> = config
+            .markers
+            .into_iter()
+            .map(|m| Marker::new(Timestamp::from_micros(m.time_us), m.label, m.color))
+            .collect();
+
+        let cuts: Vec<TimeRange> = config
+            .cuts
+            .into_iter()
+            .map(|c| {
+                TimeRange::new(
+                    Timestamp::from_micros(c.start_us),
+                    Timestamp::from_micros(c.end_us),
+                )
+            })
+            .collect();
+
+        let speed_range = SpeedRange::new(config.min_speed, config.max_speed);
+        let speed_ramps: Vec<SpeedRamp> = config
+            .speed_ramps
+            .into_iter()
+            .map(|r| {
+                SpeedRamp::with_limits(
+                    TimeRange::new(
+                        Timestamp::from_micros(r.start_us),
+                        Timestamp::from_micros(r.end_us),
+                    ),
+                    r.speed,
+                    r.ramp_in_us,
+                    r.ramp_out_us,
+                    speed_range,
+                )
+            })
+            .collect();
+
+        let protected: Vec<TimeRange> = config
+            .protected_ranges
+            .into_iter()
+            .map(|r| {
+                TimeRange::new(
+                    Timestamp::from_micros(r.start_us),
+                    Timestamp::from_micros(r.end_us),
+                )
+            })
+            .collect();
+
+        TimeRemapper::new(
+            cuts,
+            speed_ramps,
+            Timestamp::from_micros(config.in_point_us),
+            Timestamp::from_micros(config.out_point_us),
+        )
+        .with_speed_range(speed_range)
+        .with_markers(markers)
+        .with_protected_ranges(protected)
+    }
+
+    /// Build a TimeRemapper, rejecting configs with error-severity validation issues
+    /// (overlaps, empty ranges, inverted trim) instead of silently tolerating them.
+    pub fn try_from_config(config: TimeRemapperConfig) -> Result<Self, EngineError> {
+        let issues = validate_remapper_config(&config);
+        if has_errors(&issues) {
+            let messages: Vec<&str> = issues
+                .iter()
+                .filter(|issue| issue.severity == IssueSeverity::Error)
+                .map(|issue| issue.message.as_str())
+                .collect();
+            return Err(EngineError::InvalidConfig(messages.join("; ")));
+        }
+        Ok(Self::from_config(config))
+    }
+
+    /// Convert back to the JSON-friendly configuration.
+    pub fn to_config(&self) -> TimeRemapperConfig {
+        TimeRemapperConfig {
+            version: REMAPPER_CONFIG_VERSION,
+            cuts: self
+                .cuts
+                .iter()
+                .map(|c| TimeRangeConfig {
+                    start_us: c.start.as_micros(),
+                    end_us: c.end.as_micros(),
+                })
+                .collect(),
+            speed_ramps: self
+                .speed_ramps
+                .iter()
+                .map(|r| SpeedRampConfig {
+                    start_us: r.range.start.as_micros(),
+                    end_us: r.range.end.as_micros(),
+                    speed: r.speed,
+                    ramp_in_us: r.ramp_in_us,
+                    ramp_out_us: r.ramp_out_us,
+                })
+                .collect(),
+            in_point_us: self.in_point.as_micros(),
+            out_point_us: self.out_point.as_micros(),
+            min_speed: self.speed_range.min,
+            max_speed: self.speed_range.max,
+            markers: self
+                .markers
+                .iter()
+                .map(|m| MarkerConfig {
+                    time_us: m.timestamp.as_micros(),
+                    label: m.label.clone(),
+                    color: m.color.clone(),
+                })
+                .collect(),
+            protected_ranges: self
+                .protected
+                .iter()
+                .map(|r| TimeRangeConfig {
+                    start_us: r.start.as_micros(),
+                    end_us: r.end.as_micros(),
+                })
+                .collect(),
+        }
+    }
+}
+
+// =============================================================================
+// WASM Bindings
+// =============================================================================
+
+/// Configuration for creating a TimeRemapper from JavaScript.
+/// This is the JSON structure expected by `WasmTimeRemapper::new()`.
+#[derive(Debug, Clone, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TimeRemapperConfig {
+    /// Shape version (see `migrate_remapper_config`). Parsing directly assumes the
+    /// current one.
+    #[serde(default = "default_remapper_config_version")]
+    pub version: u32,
+    /// Cut segments to remove from the export.
     #[serde(default)]
     pub cuts: Vec<TimeRangeConfig>,
     /// Speed ramp segments.
@@ -291,10 +1225,47 @@ pub struct TimeRemapperConfig {
     pub in_point_us: u64,
     /// Out point (end of export) in microseconds.
     pub out_point_us: u64,
+    /// Slowest allowed ramp speed (default 0.25).
+    #[serde(default = "default_min_speed")]
+    pub min_speed: f32,
+    /// Fastest allowed ramp speed (default 4.0); raise for timelapse-style 8x/16x ramps.
+    #[serde(default = "default_max_speed")]
+    pub max_speed: f32,
+    /// Markers in source time.
+    #[serde(default)]
+    pub markers: Vec<MarkerConfig>,
+    /// Ranges that always play at 1x; overlapping speed ramps are split around them.
+    #[serde(default)]
+    pub protected_ranges: Vec<TimeRangeConfig>,
+}
+
+/// JSON-friendly marker configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MarkerConfig {
+    /// Source time in microseconds.
+    pub time_us: u64,
+    pub label: String,
+    /// CSS color string; empty for the UI default.
+    #[serde(default)]
+    pub color: String,
+}
+
+fn default_remapper_config_version() -> u32 {
+    REMAPPER_CONFIG_VERSION
+}
+
+fn default_min_speed() -> f32 {
+    DEFAULT_MIN_SPEED
+}
+
+fn default_max_speed() -> f32 {
+    DEFAULT_MAX_SPEED
 }
 
 /// JSON-friendly time range configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TimeRangeConfig {
     /// Start time in microseconds.
     pub start_us: u64,
@@ -303,14 +1274,21 @@ pub struct TimeRangeConfig {
 }
 
 /// JSON-friendly speed ramp configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SpeedRampConfig {
     /// Start time in microseconds.
     pub start_us: u64,
     /// End time in microseconds.
     pub end_us: u64,
-    /// Speed multiplier (0.25 to 4.0).
+    /// Speed multiplier, clamped to the config's `min_speed`..`max_speed`.
     pub speed: f32,
+    /// Optional ease-in duration in microseconds (speed eases from 1.0 to `speed`).
+    #[serde(default)]
+    pub ramp_in_us: u64,
+    /// Optional ease-out duration in microseconds (speed eases from `speed` back to 1.0).
+    #[serde(default)]
+    pub ramp_out_us: u64,
 }
 
 /// WASM-exposed TimeRemapper for JavaScript interop.
@@ -332,6 +1310,11 @@ pub struct SpeedRampConfig {
 #[wasm_bindgen]
 pub struct WasmTimeRemapper {
     inner: TimeRemapper,
+    /// State before the most recent timing edit, for ripple queries.
+    previous: Option<TimeRemapper>,
+    history: EditHistory<TimeRemapper>,
+    /// Bumped by every edit, undo, and redo.
+    revision: u64,
 }
 
 #[wasm_bindgen]
@@ -348,39 +1331,87 @@ impl WasmTimeRemapper {
         let config: TimeRemapperConfig = serde_json::from_str(config_json)
             .map_err(|e| JsValue::from_str(&format!("Invalid TimeRemapper config: {}", e)))?;
 
-        let cuts: Vec<TimeRange> = config
-            .cuts
-            .into_iter()
-            .map(|c| {
-                TimeRange::new(
-                    Timestamp::from_micros(c.start_us),
-                    Timestamp::from_micros(c.end_us),
-                )
-            })
-            .collect();
+        Ok(WasmTimeRemapper {
+            inner: TimeRemapper::from_config(config),
+            previous: None,
+            history: EditHistory::default(),
+            revision: 0,
+        })
+    }
 
-        let speed_ramps: Vec<SpeedRamp> = config
-            .speed_ramps
-            .into_iter()
-            .map(|r| {
-                SpeedRamp::new(
-                    TimeRange::new(
-                        Timestamp::from_micros(r.start_us),
-                        Timestamp::from_micros(r.end_us),
-                    ),
-                    r.speed,
-                )
-            })
-            .collect();
+    /// Create a new TimeRemapper, rejecting configs that fail validation
+    /// (overlapping cuts or ramps, empty ranges, out point before in point).
+    ///
+    /// # Arguments
+    /// * `config_json` - JSON string with TimeRemapperConfig structure
+    ///
+    /// # Returns
+    /// A new WasmTimeRemapper instance or an error listing the validation failures.
+    #[wasm_bindgen]
+    pub fn new_strict(config_json: &str) -> Result<WasmTimeRemapper, JsValue> {
+        let config: TimeRemapperConfig = serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid TimeRemapper config: {}", e)))?;
 
-        let inner = TimeRemapper::new(
-            cuts,
-            speed_ramps,
-            Timestamp::from_micros(config.in_point_us),
-            Timestamp::from_micros(config.out_point_us),
-        );
+        let inner =
+            TimeRemapper::try_from_config(config).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(WasmTimeRemapper {
+            inner,
+            previous: None,
+            history: EditHistory::default(),
+            revision: 0,
+        })
+    }
+
+    /// Upgrade a saved config from an earlier version (see `migrate_remapper_config`).
+    ///
+    /// # Arguments
+    /// * `config_json` - JSON TimeRemapperConfig of any supported version
+    ///
+    /// # Returns
+    /// JSON `{ config, report }`: the current-shape config and the MigrationReport
+    #[wasm_bindgen]
+    pub fn migrate_config(config_json: &str) -> Result<String, JsValue> {
+        let value: serde_json::Value = serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid TimeRemapper config: {}", e)))?;
+        let (config, report) =
+            migrate_remapper_config(value).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        serde_json::to_string(&serde_json::json!({ "config": config, "report": report }))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Validate a TimeRemapper config without constructing it.
+    ///
+    /// # Arguments
+    /// * `config_json` - JSON string with TimeRemapperConfig structure
+    ///
+    /// # Returns
+    /// JSON array of validation issues (empty if the config is clean)
+    #[wasm_bindgen]
+    pub fn validate_config(config_json: &str) -> Result<String, JsValue> {
+        let config: TimeRemapperConfig = serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid TimeRemapper config: {}", e)))?;
+
+        serde_json::to_string(&validate_remapper_config(&config))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
 
-        Ok(WasmTimeRemapper { inner })
+    /// Create a new TimeRemapper from a plain JS config object.
+    ///
+    /// # Arguments
+    /// * `config` - Object with TimeRemapperConfig structure
+    #[wasm_bindgen]
+    pub fn from_object(
+        #[wasm_bindgen(unchecked_param_type = "TimeRemapperConfig")] config: JsValue,
+    ) -> Result<WasmTimeRemapper, JsValue> {
+        let config: TimeRemapperConfig = from_js_object(config, "Invalid TimeRemapper config")?;
+
+        Ok(WasmTimeRemapper {
+            inner: TimeRemapper::from_config(config),
+            previous: None,
+            history: EditHistory::default(),
+            revision: 0,
+        })
     }
 
     /// Create an identity TimeRemapper with no cuts or speed ramps.
@@ -395,6 +1426,9 @@ impl WasmTimeRemapper {
                 Timestamp::from_micros(in_point_us),
                 Timestamp::from_micros(out_point_us),
             ),
+            previous: None,
+            history: EditHistory::default(),
+            revision: 0,
         }
     }
 
@@ -424,6 +1458,53 @@ impl WasmTimeRemapper {
         self.inner.export_duration().as_micros()
     }
 
+    /// Number of frames in the export at `fps`.
+    ///
+    /// Frame `n` is presented at `round(n * 1e6 / fps)` microseconds (computed from the
+    /// index, never accumulated), and is included iff that is before `export_duration()`.
+    ///
+    /// # Arguments
+    /// * `fps` - Export frame rate (e.g. 30, 60, 29.97002997)
+    #[wasm_bindgen]
+    pub fn export_frame_count(&self, fps: f64) -> u64 {
+        self.inner.export_frame_count(fps)
+    }
+
+    /// Map an export frame index to the source frame index at the same fps.
+    ///
+    /// # Arguments
+    /// * `frame` - Export frame index
+    /// * `fps` - Frame rate for both export and source frame numbering
+    ///
+    /// # Returns
+    /// The last source frame presented at or before the mapped source time
+    #[wasm_bindgen]
+    pub fn source_frame_for_export_frame(&self, frame: u64, fps: f64) -> u64 {
+        self.inner.source_frame_for_export_frame(frame, fps)
+    }
+
+    /// Map an export frame index to a source timestamp.
+    ///
+    /// # Returns
+    /// Source timestamp in microseconds
+    #[wasm_bindgen]
+    pub fn source_time_for_export_frame(&self, frame: u64, fps: f64) -> u64 {
+        self.inner
+            .source_time_for_export_frame(frame, fps)
+            .as_micros()
+    }
+
+    /// Get the full frame schedule for an export at `fps`.
+    ///
+    /// # Returns
+    /// JSON array of FrameScheduleEntry (frame, export_time, source_time, speed), one per
+    /// output frame
+    #[wasm_bindgen]
+    pub fn get_frame_schedule(&self, fps: f64) -> Result<String, JsValue> {
+        serde_json::to_string(&self.inner.frame_schedule(fps))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
     /// Check if a source timestamp is within a cut region.
     ///
     /// # Arguments
@@ -472,32 +1553,490 @@ impl WasmTimeRemapper {
     /// JSON string with the current configuration
     #[wasm_bindgen]
     pub fn to_json(&self) -> Result<String, JsValue> {
-        let config = TimeRemapperConfig {
-            cuts: self
-                .inner
-                .cuts()
-                .iter()
-                .map(|c| TimeRangeConfig {
-                    start_us: c.start.as_micros(),
-                    end_us: c.end.as_micros(),
-                })
-                .collect(),
-            speed_ramps: self
-                .inner
-                .speed_ramps()
-                .iter()
-                .map(|r| SpeedRampConfig {
-                    start_us: r.range.start.as_micros(),
-                    end_us: r.range.end.as_micros(),
-                    speed: r.speed,
-                })
+        let config = self.inner.to_config();
+
+        serde_json::to_string(&config)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Add a cut and re-normalize (overlapping cuts merge, clamped to the trim).
+    #[wasm_bindgen]
+    pub fn add_cut(&mut self, start_us: u64, end_us: u64) {
+        self.begin_edit("Add cut");
+        self.inner.add_cut(TimeRange::new(
+            Timestamp::from_micros(start_us),
+            Timestamp::from_micros(end_us),
+        ));
+        self.finish_edit_if_changed();
+    }
+
+    /// Remove the cut at `index` (in start-time order, as listed by `to_json`).
+    ///
+    /// # Returns
+    /// `true` if a cut was removed
+    #[wasm_bindgen]
+    pub fn remove_cut(&mut self, index: usize) -> bool {
+        self.begin_edit("Remove cut");
+        let removed = self.inner.remove_cut(index).is_some();
+        self.finish_edit(removed)
+    }
+
+    /// Split the cut at `index` into two adjacent cuts at `at_us`.
+    ///
+    /// # Returns
+    /// `true` if `at_us` was strictly inside the cut and the split happened
+    #[wasm_bindgen]
+    pub fn split_cut(&mut self, index: usize, at_us: u64) -> bool {
+        self.begin_edit("Split cut");
+        let split = self.inner.split_cut(index, Timestamp::from_micros(at_us));
+        self.finish_edit(split)
+    }
+
+    /// Set the speed over a range, replacing overlapping portions of existing ramps.
+    #[wasm_bindgen]
+    pub fn set_speed_ramp(&mut self, start_us: u64, end_us: u64, speed: f32) {
+        self.begin_edit("Set speed");
+        self.inner.set_speed_ramp(SpeedRamp::with_limits(
+            TimeRange::new(
+                Timestamp::from_micros(start_us),
+                Timestamp::from_micros(end_us),
+            ),
+            speed,
+            0,
+            0,
+            self.inner.speed_range(),
+        ));
+        self.finish_edit_if_changed();
+    }
+
+    /// Set a gradual speed ramp whose speed eases in from 1.0 over `ramp_in_us` and
+    /// back out over `ramp_out_us`, replacing overlapping portions of existing ramps.
+    #[wasm_bindgen]
+    pub fn set_gradual_speed_ramp(
+        &mut self,
+        start_us: u64,
+        end_us: u64,
+        speed: f32,
+        ramp_in_us: u64,
+        ramp_out_us: u64,
+    ) {
+        self.begin_edit("Set speed ramp");
+        self.inner.set_speed_ramp(SpeedRamp::with_limits(
+            TimeRange::new(
+                Timestamp::from_micros(start_us),
+                Timestamp::from_micros(end_us),
+            ),
+            speed,
+            ramp_in_us,
+            ramp_out_us,
+            self.inner.speed_range(),
+        ));
+        self.finish_edit_if_changed();
+    }
+
+    /// Change the allowed speed range (e.g. up to 16x for timelapse); existing ramps are
+    /// clamped into it.
+    #[wasm_bindgen]
+    pub fn set_speed_range(&mut self, min_speed: f32, max_speed: f32) {
+        self.begin_edit("Change speed range");
+        self.inner
+            .set_speed_range(SpeedRange::new(min_speed, max_speed));
+        self.finish_edit_if_changed();
+    }
+
+    /// Remove the speed ramp at `index` (in start-time order, as listed by `to_json`).
+    ///
+    /// # Returns
+    /// `true` if a ramp was removed
+    #[wasm_bindgen]
+    pub fn remove_speed_ramp(&mut self, index: usize) -> bool {
+        self.begin_edit("Remove speed ramp");
+        let removed = self.inner.remove_speed_ramp(index).is_some();
+        self.finish_edit(removed)
+    }
+
+    /// Get the audio chunks for the export, telling the audio pipeline which
+    /// chunks need pitch-preserving time-stretch and which play at 1x.
+    ///
+    /// # Returns
+    /// JSON array of AudioSegment (export_range, source_range, speed, preserve_pitch)
+    #[wasm_bindgen]
+    pub fn get_audio_segments(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.inner.audio_segments())
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Set the source video's keyframe (GOP start) timestamps in microseconds.
+    #[wasm_bindgen]
+    pub fn set_video_keyframes(&mut self, keyframes_us: Vec<u64>) {
+        self.inner.set_video_keyframes(
+            keyframes_us
+                .into_iter()
+                .map(Timestamp::from_micros)
                 .collect(),
-            in_point_us: self.inner.in_point().as_micros(),
-            out_point_us: self.inner.out_point().as_micros(),
+        );
+    }
+
+    /// Snap each cut's end (where playback resumes) to the nearest video keyframe
+    /// within `tolerance_us`, so export seeks land on decodable frames.
+    ///
+    /// # Returns
+    /// Number of cuts that moved
+    #[wasm_bindgen]
+    pub fn snap_cuts_to_keyframes(&mut self, tolerance_us: u64) -> usize {
+        self.begin_edit("Snap cuts to keyframes");
+        let moved = self.inner.snap_cuts_to_keyframes(tolerance_us);
+        self.finish_edit(moved > 0);
+        moved
+    }
+
+    /// Protect a range so it always plays at 1x (e.g. where narration occurs).
+    #[wasm_bindgen]
+    pub fn add_protected_range(&mut self, start_us: u64, end_us: u64) {
+        self.begin_edit("Protect range");
+        self.inner.add_protected_range(TimeRange::new(
+            Timestamp::from_micros(start_us),
+            Timestamp::from_micros(end_us),
+        ));
+        self.finish_edit_if_changed();
+    }
+
+    /// Remove the protected range at `index` (in start-time order, as listed by `to_json`).
+    ///
+    /// # Returns
+    /// `true` if a range was removed
+    #[wasm_bindgen]
+    pub fn remove_protected_range(&mut self, index: usize) -> bool {
+        self.begin_edit("Remove protected range");
+        let removed = self.inner.remove_protected_range(index).is_some();
+        self.finish_edit(removed)
+    }
+
+    /// Set the trim points; cuts and ramps are clamped to the new range.
+    #[wasm_bindgen]
+    pub fn set_trim(&mut self, in_point_us: u64, out_point_us: u64) {
+        self.begin_edit("Trim");
+        self.inner.set_trim(
+            Timestamp::from_micros(in_point_us),
+            Timestamp::from_micros(out_point_us),
+        );
+        self.finish_edit_if_changed();
+    }
+
+    /// Map a source timestamp to export time.
+    ///
+    /// # Returns
+    /// Export timestamp in microseconds, or `undefined` if the source time is cut or trimmed
+    #[wasm_bindgen]
+    pub fn to_export_time(&self, source_time_us: u64) -> Option<u64> {
+        self.inner
+            .to_export_time(Timestamp::from_micros(source_time_us))
+            .map(|ts| ts.as_micros())
+    }
+
+    /// Add a marker at a source time.
+    ///
+    /// # Returns
+    /// Index of the new marker in source-time order
+    #[wasm_bindgen]
+    pub fn add_marker(&mut self, time_us: u64, label: &str, color: &str) -> usize {
+        self.history.record("Add marker", self.inner.clone());
+        self.revision += 1;
+        self.inner
+            .add_marker(Marker::new(Timestamp::from_micros(time_us), label, color))
+    }
+
+    /// Remove the marker at `index` (in source-time order, as listed by `to_json`).
+    ///
+    /// # Returns
+    /// `true` if a marker was removed
+    #[wasm_bindgen]
+    pub fn remove_marker(&mut self, index: usize) -> bool {
+        self.history.record("Remove marker", self.inner.clone());
+        let removed = self.inner.remove_marker(index).is_some();
+        if removed {
+            self.revision += 1;
+        } else {
+            self.history.cancel();
+        }
+        removed
+    }
+
+    /// Undo the last edit (cuts, ramps, trim, protected ranges, or markers). Ripple
+    /// queries then describe the undo.
+    ///
+    /// # Returns
+    /// Label of the undone edit (e.g. "Add cut"), or `undefined` if there was none
+    #[wasm_bindgen]
+    pub fn undo(&mut self) -> Option<String> {
+        let (label, state) = self.history.undo(self.inner.clone())?;
+        self.previous = Some(std::mem::replace(&mut self.inner, state));
+        self.revision += 1;
+        Some(label)
+    }
+
+    /// Redo the last undone edit.
+    ///
+    /// # Returns
+    /// Label of the redone edit, or `undefined` if there was none
+    #[wasm_bindgen]
+    pub fn redo(&mut self) -> Option<String> {
+        let (label, state) = self.history.redo(self.inner.clone())?;
+        self.previous = Some(std::mem::replace(&mut self.inner, state));
+        self.revision += 1;
+        Some(label)
+    }
+
+    /// Label of the edit `undo` would undo (for "Undo Add cut" menus), if any.
+    #[wasm_bindgen]
+    pub fn undo_label(&self) -> Option<String> {
+        self.history.undo_label().map(str::to_string)
+    }
+
+    /// Label of the edit `redo` would redo, if any.
+    #[wasm_bindgen]
+    pub fn redo_label(&self) -> Option<String> {
+        self.history.redo_label().map(str::to_string)
+    }
+
+    /// Revision of the edit, bumped by every change (including undo and redo). Where
+    /// the latest timing edit moved the export timeline is `get_last_ripple`.
+    #[wasm_bindgen]
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Get markers placed on the export timeline, in export-time order.
+    ///
+    /// # Returns
+    /// JSON array of ExportMarker
+    #[wasm_bindgen]
+    pub fn get_export_markers(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.inner.export_markers())
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// WebVTT chapter track from the markers, on the export timeline.
+    ///
+    /// # Returns
+    /// WebVTT text with one cue per chapter
+    #[wasm_bindgen]
+    pub fn export_chapters_vtt(&self) -> String {
+        chapters_vtt(&self.inner)
+    }
+
+    /// Get the configuration as a plain JS object.
+    ///
+    /// # Returns
+    /// Object with the current TimeRemapperConfig
+    #[wasm_bindgen(unchecked_return_type = "TimeRemapperConfig")]
+    pub fn to_object(&self) -> Result<JsValue, JsValue> {
+        to_js_object(&self.inner.to_config())
+    }
+
+    /// Get how the most recent timing edit (cut, ramp, protection, or trim change)
+    /// moved content on the export timeline.
+    ///
+    /// # Returns
+    /// JSON array of RippleSpan (source, before, after); empty if nothing was edited yet
+    #[wasm_bindgen]
+    pub fn get_last_ripple(&self) -> Result<String, JsValue> {
+        let spans = self
+            .last_ripple()
+            .map(|delta| delta.spans().to_vec())
+            .unwrap_or_default();
+        serde_json::to_string(&spans)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Map a pre-edit export timestamp through the most recent timing edit.
+    ///
+    /// # Returns
+    /// Post-edit export timestamp in microseconds, or `undefined` if that content was removed
+    #[wasm_bindgen]
+    pub fn ripple_export_time(&self, export_time_us: u64) -> Option<u64> {
+        match self.last_ripple() {
+            Some(delta) => delta
+                .map_export_time(Timestamp::from_micros(export_time_us))
+                .map(|ts| ts.as_micros()),
+            None => Some(export_time_us),
+        }
+    }
+
+    /// Move export-time camera keyframes through the most recent timing edit.
+    ///
+    /// # Arguments
+    /// * `keyframes_json` - JSON array of CameraKeyframe on the pre-edit export timeline
+    #[wasm_bindgen]
+    pub fn ripple_keyframes(&self, keyframes_json: &str) -> Result<String, JsValue> {
+        let keyframes: Vec<CameraKeyframe> = serde_json::from_str(keyframes_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid keyframes: {}", e)))?;
+        let rippled = match self.last_ripple() {
+            Some(delta) => delta.ripple_keyframes(&keyframes),
+            None => keyframes,
         };
+        serde_json::to_string(&rippled)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Move an export-time effect track through the most recent timing edit.
+    ///
+    /// # Arguments
+    /// * `effects_json` - JSON EffectTrack on the pre-edit export timeline
+    #[wasm_bindgen]
+    pub fn ripple_effects(&self, effects_json: &str) -> Result<String, JsValue> {
+        let track: EffectTrack = serde_json::from_str(effects_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid effect track: {}", e)))?;
+        let rippled = match self.last_ripple() {
+            Some(delta) => delta.ripple_effects(&track),
+            None => track,
+        };
+        serde_json::to_string(&rippled)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Project source-time camera keyframes (from analysis) onto the export timeline.
+    ///
+    /// # Arguments
+    /// * `keyframes_json` - JSON array of CameraKeyframe in source time
+    #[wasm_bindgen]
+    pub fn project_keyframes(&self, keyframes_json: &str) -> Result<String, JsValue> {
+        let keyframes: Vec<CameraKeyframe> = serde_json::from_str(keyframes_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid keyframes: {}", e)))?;
+        serde_json::to_string(&self.inner.project_keyframes(&keyframes))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Project a source-time effect track (from analysis) onto the export timeline.
+    ///
+    /// # Arguments
+    /// * `effects_json` - JSON EffectTrack in source time
+    #[wasm_bindgen]
+    pub fn project_effects(&self, effects_json: &str) -> Result<String, JsValue> {
+        let track: EffectTrack = serde_json::from_str(effects_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid effect track: {}", e)))?;
+        serde_json::to_string(&self.inner.project_effects(&track))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Export the edit (cuts, ramps, trim, markers) as an OpenTimelineIO timeline.
+    ///
+    /// # Arguments
+    /// * `name` - Timeline name
+    /// * `media_url` - URL or path of the recording the clips reference
+    /// * `fps` - Timeline frame rate
+    ///
+    /// # Returns
+    /// OTIO JSON
+    #[wasm_bindgen]
+    pub fn export_otio(&self, name: &str, media_url: &str, fps: f64) -> Result<String, JsValue> {
+        if fps <= 0.0 || !fps.is_finite() {
+            return Err(JsValue::from_str(&format!("Invalid fps: {}", fps)));
+        }
+        serde_json::to_string(&to_otio(&self.inner, name, media_url, fps))
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Export the edit (cuts, ramps, trim, markers) as a CMX 3600 EDL.
+    ///
+    /// # Arguments
+    /// * `title` - EDL title, also used as the clip name
+    /// * `fps` - Timecode frame rate
+    /// * `drop_frame` - Drop-frame timecode (only at 29.97/59.94)
+    #[wasm_bindgen]
+    pub fn export_edl(&self, title: &str, fps: f64, drop_frame: bool) -> Result<String, JsValue> {
+        if fps <= 0.0 || !fps.is_finite() {
+            return Err(JsValue::from_str(&format!("Invalid fps: {}", fps)));
+        }
+        Ok(to_edl(&self.inner, title, fps, drop_frame))
+    }
+
+    /// Export the edit (cuts, ramps, trim, markers) as an FCPXML project for Final Cut.
+    ///
+    /// # Arguments
+    /// * `name` - Event, project, and clip name
+    /// * `media_url` - URL or path of the recording the clips reference
+    /// * `fps` - Sequence frame rate
+    /// * `width` / `height` - Recording size in pixels
+    #[wasm_bindgen]
+    pub fn export_fcpxml(
+        &self,
+        name: &str,
+        media_url: &str,
+        fps: f64,
+        width: u32,
+        height: u32,
+    ) -> Result<String, JsValue> {
+        if fps <= 0.0 || !fps.is_finite() {
+            return Err(JsValue::from_str(&format!("Invalid fps: {}", fps)));
+        }
+        Ok(to_fcpxml(&self.inner, name, media_url, fps, width, height))
+    }
+
+    /// Export a source-time effect track as a Lottie animation on the export timeline
+    /// (click rings, cursor highlights, and spotlights; see `effects_to_lottie`).
+    ///
+    /// # Arguments
+    /// * `effects_json` - JSON EffectTrack in source time
+    /// * `width` / `height` - Recording size in pixels
+    /// * `fps` - Animation frame rate
+    ///
+    /// # Returns
+    /// Lottie JSON covering the whole export
+    #[wasm_bindgen]
+    pub fn export_lottie(
+        &self,
+        effects_json: &str,
+        width: u32,
+        height: u32,
+        fps: f64,
+    ) -> Result<String, JsValue> {
+        let track: EffectTrack = serde_json::from_str(effects_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid effect track: {}", e)))?;
+        if fps <= 0.0 || !fps.is_finite() {
+            return Err(JsValue::from_str(&format!("Invalid fps: {}", fps)));
+        }
+
+        let lottie = effects_to_lottie(
+            &self.inner.project_effects(&track),
+            width,
+            height,
+            fps,
+            self.inner.export_duration(),
+        );
+        serde_json::to_string(&lottie)
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+}
+
+impl WasmTimeRemapper {
+    /// Remember the current state so the next edit can be rippled and undone.
+    fn begin_edit(&mut self, label: &str) {
+        self.previous = Some(self.inner.clone());
+        self.history.record(label, self.inner.clone());
+    }
+
+    /// Bump the revision for an edit that changed something, or drop its history entry
+    /// if it didn't. Returns `changed`.
+    fn finish_edit(&mut self, changed: bool) -> bool {
+        if changed {
+            self.revision += 1;
+        } else {
+            self.history.cancel();
+        }
+        changed
+    }
 
-        serde_json::to_string(&config)
-            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    /// Finish an edit, dropping it if it left the remapper as it was.
+    fn finish_edit_if_changed(&mut self) {
+        let changed = self.previous.as_ref() != Some(&self.inner);
+        self.finish_edit(changed);
+    }
+
+    fn last_ripple(&self) -> Option<RippleDelta> {
+        self.previous
+            .as_ref()
+            .map(|previous| RippleDelta::between(previous, &self.inner))
     }
 }
 
@@ -519,6 +2058,7 @@ mod tests {
     /// This property ensures that the time remapper maintains temporal ordering
     /// regardless of cuts and speed ramps. If export time advances, source time
     /// must not go backwards.
+    #[allow(clippy::unnecessary_sort_by, clippy::manual_abs_diff)]
     mod property_tests {
         use super::*;
 
@@ -1096,6 +2636,77 @@ mod tests {
                     diff
                 );
             }
+
+            /// Property: Segment Table Equivalence
+            /// The binary-searched segment table SHALL map every export timestamp to the
+            /// same source timestamp as a boundary-by-boundary walk.
+            #[test]
+            fn segment_table_matches_linear_walk(
+                remapper in time_remapper_strategy(),
+                export_times in sorted_export_times_strategy(20, 10_000_000u64)
+            ) {
+                for t in export_times {
+                    let expected = linear_walk_to_source_time(&remapper, t);
+                    let actual = remapper.to_source_time(Timestamp::from_micros(t)).as_micros();
+                    prop_assert_eq!(expected, actual, "export_time={}", t);
+                }
+            }
+
+            /// Property: Gradual Ramp Continuity
+            /// With ease-in/ease-out windows, increasing export timestamps SHALL still map
+            /// to non-decreasing source timestamps.
+            #[test]
+            fn gradual_ramps_keep_mapping_monotonic(
+                ramps in speed_ramps_strategy(10_000_000u64, 3),
+                ease in (0u64..2_000_000, 0u64..2_000_000),
+                export_times in sorted_export_times_strategy(20, 10_000_000u64)
+            ) {
+                let ramps = ramps
+                    .into_iter()
+                    .map(|r| SpeedRamp::with_easing(r.range, r.speed, ease.0, ease.1))
+                    .collect();
+                let remapper = TimeRemapper::new(
+                    vec![],
+                    ramps,
+                    Timestamp::from_micros(0),
+                    Timestamp::from_micros(10_000_000),
+                );
+
+                let source_times: Vec<u64> = export_times
+                    .iter()
+                    .map(|&t| remapper.to_source_time(Timestamp::from_micros(t)).as_micros())
+                    .collect();
+                for i in 1..source_times.len() {
+                    prop_assert!(source_times[i] >= source_times[i - 1]);
+                }
+            }
+        }
+
+        /// Reference implementation: walk boundaries from in_point until the
+        /// remaining export time is consumed.
+        fn linear_walk_to_source_time(remapper: &TimeRemapper, export_time_us: u64) -> u64 {
+            let out_point = remapper.out_point().as_micros();
+            let mut source_time = remapper.skip_cuts_forward(remapper.in_point()).as_micros();
+            let mut remaining = export_time_us;
+
+            while remaining > 0 && source_time < out_point {
+                let next_boundary = remapper.find_next_boundary(source_time);
+                let speed = remapper.speed_at(Timestamp::from_micros(source_time));
+                let segment_export =
+                    ((next_boundary - source_time) as f64 / speed as f64).round() as u64;
+
+                if remaining < segment_export {
+                    source_time += (remaining as f64 * speed as f64).round() as u64;
+                    remaining = 0;
+                } else {
+                    remaining -= segment_export;
+                    source_time = remapper
+                        .skip_cuts_forward(Timestamp::from_micros(next_boundary))
+                        .as_micros();
+                }
+            }
+
+            source_time.min(out_point)
         }
     }
 
@@ -1641,6 +3252,574 @@ mod tests {
         assert_eq!(output.out_point_us, 10_000_000);
     }
 
+    #[test]
+    fn test_add_cut_merges_overlaps_and_clamps() {
+        let mut remapper = TimeRemapper::identity(
+            Timestamp::from_micros(1_000_000),
+            Timestamp::from_micros(10_000_000),
+        );
+
+        remapper.add_cut(TimeRange::new(
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(2_000_000),
+        ));
+        remapper.add_cut(TimeRange::new(
+            Timestamp::from_micros(4_000_000),
+            Timestamp::from_micros(6_000_000),
+        ));
+        remapper.add_cut(TimeRange::new(
+            Timestamp::from_micros(5_000_000),
+            Timestamp::from_micros(7_000_000),
+        ));
+
+        assert_eq!(remapper.cuts().len(), 2);
+        assert_eq!(remapper.cuts()[0].start.as_micros(), 1_000_000);
+        assert_eq!(remapper.cuts()[1].end.as_micros(), 7_000_000);
+        // 9s trimmed - 1s - 3s of cuts
+        assert_eq!(remapper.export_duration().as_micros(), 5_000_000);
+    }
+
+    #[test]
+    fn test_split_and_remove_cut() {
+        let mut remapper = TimeRemapper::identity(
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        );
+        remapper.add_cut(TimeRange::new(
+            Timestamp::from_micros(2_000_000),
+            Timestamp::from_micros(6_000_000),
+        ));
+
+        assert!(!remapper.split_cut(0, Timestamp::from_micros(2_000_000)));
+        assert!(remapper.split_cut(0, Timestamp::from_micros(4_000_000)));
+        assert_eq!(remapper.cuts().len(), 2);
+
+        assert!(remapper.remove_cut(1).is_some());
+        assert!(remapper.remove_cut(5).is_none());
+        assert_eq!(remapper.export_duration().as_micros(), 8_000_000);
+        assert_eq!(
+            remapper
+                .to_source_time(Timestamp::from_micros(2_000_000))
+                .as_micros(),
+            4_000_000
+        );
+    }
+
+    #[test]
+    fn test_set_speed_ramp_replaces_overlap() {
+        let mut remapper = TimeRemapper::identity(
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        );
+        remapper.set_speed_ramp(SpeedRamp::new(
+            TimeRange::new(Timestamp::from_micros(0), Timestamp::from_micros(8_000_000)),
+            2.0,
+        ));
+        remapper.set_speed_ramp(SpeedRamp::new(
+            TimeRange::new(
+                Timestamp::from_micros(2_000_000),
+                Timestamp::from_micros(4_000_000),
+            ),
+            4.0,
+        ));
+
+        let ramps = remapper.speed_ramps();
+        assert_eq!(ramps.len(), 3);
+        assert_eq!(remapper.speed_at(Timestamp::from_micros(1_000_000)), 2.0);
+        assert_eq!(remapper.speed_at(Timestamp::from_micros(3_000_000)), 4.0);
+        assert_eq!(remapper.speed_at(Timestamp::from_micros(5_000_000)), 2.0);
+
+        assert!(remapper.remove_speed_ramp(1).is_some());
+        assert_eq!(remapper.speed_at(Timestamp::from_micros(3_000_000)), 1.0);
+    }
+
+    #[test]
+    fn test_set_trim_clamps_edits() {
+        let mut remapper = TimeRemapper::identity(
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        );
+        remapper.add_cut(TimeRange::new(
+            Timestamp::from_micros(1_000_000),
+            Timestamp::from_micros(3_000_000),
+        ));
+        remapper.set_speed_ramp(SpeedRamp::new(
+            TimeRange::new(
+                Timestamp::from_micros(7_000_000),
+                Timestamp::from_micros(9_000_000),
+            ),
+            2.0,
+        ));
+
+        remapper.set_trim(
+            Timestamp::from_micros(2_000_000),
+            Timestamp::from_micros(8_000_000),
+        );
+
+        assert_eq!(remapper.cuts()[0].start.as_micros(), 2_000_000);
+        assert_eq!(remapper.speed_ramps()[0].range.end.as_micros(), 8_000_000);
+        // 1s cut remains, 1s at 2x -> 0.5s, 4s at 1x
+        assert_eq!(remapper.export_duration().as_micros(), 4_500_000);
+    }
+
+    #[test]
+    fn test_wasm_time_remapper_edits() {
+        let mut remapper = WasmTimeRemapper::identity(0, 10_000_000);
+        remapper.add_cut(2_000_000, 4_000_000);
+        remapper.set_speed_ramp(6_000_000, 8_000_000, 2.0);
+        assert_eq!(remapper.export_duration(), 7_000_000);
+
+        assert!(remapper.remove_cut(0));
+        assert!(remapper.remove_speed_ramp(0));
+        remapper.set_trim(1_000_000, 5_000_000);
+        assert_eq!(remapper.export_duration(), 4_000_000);
+    }
+
+    #[test]
+    fn test_wasm_undo_redo() {
+        let mut remapper = WasmTimeRemapper::identity(0, 10_000_000);
+        remapper.add_cut(2_000_000, 4_000_000);
+        remapper.add_cut(3_000_000, 5_000_000);
+        // Removing nothing is not an edit.
+        assert!(!remapper.remove_cut(5));
+        assert_eq!(remapper.undo_label().as_deref(), Some("Add cut"));
+        assert_eq!(remapper.revision(), 2);
+
+        // The second cut merged into the first; undo restores the single cut exactly.
+        assert_eq!(remapper.undo().as_deref(), Some("Add cut"));
+        assert_eq!(remapper.export_duration(), 8_000_000);
+        // Ripple queries map through the undo: 6s after the merged cut is 7s again.
+        assert_eq!(remapper.ripple_export_time(6_000_000), Some(7_000_000));
+        assert_eq!(remapper.undo().as_deref(), Some("Add cut"));
+        assert_eq!(remapper.undo(), None);
+        assert_eq!(remapper.export_duration(), 10_000_000);
+
+        assert_eq!(remapper.redo().as_deref(), Some("Add cut"));
+        remapper.add_marker(1_000_000, "Intro", "");
+        assert_eq!(remapper.redo(), None);
+        assert_eq!(remapper.undo().as_deref(), Some("Add marker"));
+        assert_eq!(remapper.export_duration(), 8_000_000);
+    }
+
+    #[test]
+    fn test_wasm_no_op_edits_keep_history() {
+        let mut remapper = WasmTimeRemapper::identity(0, 10_000_000);
+        remapper.add_cut(2_000_000, 4_000_000);
+        remapper.add_protected_range(5_000_000, 6_000_000);
+        assert_eq!(remapper.undo().as_deref(), Some("Protect range"));
+        let revision = remapper.revision();
+
+        // None of these change anything, so the undone edit can still be redone.
+        remapper.add_cut(2_500_000, 3_500_000);
+        remapper.set_trim(0, 10_000_000);
+        remapper.set_speed_range(0.25, 4.0);
+        assert_eq!(remapper.revision(), revision);
+        assert_eq!(remapper.undo_label().as_deref(), Some("Add cut"));
+        assert_eq!(remapper.redo().as_deref(), Some("Protect range"));
+        remapper.add_protected_range(5_000_000, 6_000_000);
+        assert_eq!(remapper.undo_label().as_deref(), Some("Protect range"));
+    }
+
+    #[test]
+    fn test_gradual_ramp_integrates_speed() {
+        // 4s ramp at 2x with 1s ease-in and 1s ease-out, inside a 10s recording.
+        let ramp = SpeedRamp::with_easing(
+            TimeRange::new(Timestamp::from_micros(0), Timestamp::from_micros(4_000_000)),
+            2.0,
+            1_000_000,
+            1_000_000,
+        );
+        let ease = 1_000_000.0 * 2f64.ln(); // ∫ dx / (1 + x) over one second
+        let expected_ramp = (2.0 * ease + 1_000_000.0).round() as u64;
+        assert_eq!(ramp.export_duration(), expected_ramp);
+
+        let remapper = TimeRemapper::new(
+            vec![],
+            vec![ramp],
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        );
+        let total = remapper.export_duration().as_micros();
+        assert!(total.abs_diff(expected_ramp + 6_000_000) <= 2);
+
+        // Halfway through the ease-in, speed is 1.5x.
+        assert!((remapper.speed_at(Timestamp::from_micros(500_000)) - 1.5).abs() < 0.001);
+
+        // The end of the ease-in window in export time maps back to 1s of source.
+        let source = remapper
+            .to_source_time(Timestamp::from_micros(ease.round() as u64))
+            .as_micros();
+        assert!(source.abs_diff(1_000_000) <= 2);
+    }
+
+    #[test]
+    fn test_gradual_ramp_clamps_ease_windows() {
+        let ramp = SpeedRamp::with_easing(
+            TimeRange::new(Timestamp::from_micros(0), Timestamp::from_micros(1_000_000)),
+            3.0,
+            1_500_000,
+            500_000,
+        );
+        assert_eq!(ramp.ramp_in_us + ramp.ramp_out_us, 1_000_000);
+        assert_eq!(ramp.ramp_in_us, 750_000);
+    }
+
+    #[test]
+    fn test_gradual_ramp_round_trips_config() {
+        let config_json = r#"{
+            "speed_ramps": [
+                { "start_us": 0, "end_us": 4000000, "speed": 2.0, "ramp_in_us": 500000 }
+            ],
+            "in_point_us": 0,
+            "out_point_us": 5000000
+        }"#;
+
+        let remapper = WasmTimeRemapper::new(config_json).expect("Should parse valid config");
+        let output: TimeRemapperConfig =
+            serde_json::from_str(&remapper.to_json().unwrap()).unwrap();
+        assert_eq!(output.speed_ramps[0].ramp_in_us, 500_000);
+        assert_eq!(output.speed_ramps[0].ramp_out_us, 0);
+    }
+
+    #[test]
+    fn test_try_from_config_rejects_overlapping_cuts() {
+        let config: TimeRemapperConfig = serde_json::from_str(
+            r#"{
+            "cuts": [
+                { "start_us": 1000000, "end_us": 3000000 },
+                { "start_us": 2000000, "end_us": 4000000 }
+            ],
+            "in_point_us": 0,
+            "out_point_us": 10000000
+        }"#,
+        )
+        .unwrap();
+
+        let result = TimeRemapper::try_from_config(config.clone());
+        assert!(matches!(result, Err(EngineError::InvalidConfig(msg)) if msg.contains("overlap")));
+
+        let config_json = serde_json::to_string(&config).unwrap();
+        let issues_json = WasmTimeRemapper::validate_config(&config_json).unwrap();
+        let issues: Vec<crate::validation::ValidationIssue> =
+            serde_json::from_str(&issues_json).unwrap();
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[test]
+    fn test_export_frame_count_and_mapping() {
+        // 10s with a 2s cut -> 8s export; at 30fps that's exactly 240 frames.
+        let remapper = TimeRemapper::new(
+            vec![TimeRange::new(
+                Timestamp::from_micros(2_000_000),
+                Timestamp::from_micros(4_000_000),
+            )],
+            vec![],
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        );
+
+        assert_eq!(remapper.export_frame_count(30.0), 240);
+        assert_eq!(remapper.export_frame_count(0.0), 0);
+        // A partial last frame still counts: 8s at 29.97fps = 239.76 frames.
+        assert_eq!(remapper.export_frame_count(30_000.0 / 1001.0), 240);
+
+        // Export frame 59 is before the cut, frame 60 lands at source 4s (frame 120).
+        assert_eq!(remapper.source_frame_for_export_frame(59, 30.0), 59);
+        assert_eq!(remapper.source_frame_for_export_frame(60, 30.0), 120);
+    }
+
+    #[test]
+    fn test_frame_schedule_matches_frame_methods() {
+        // Cut 1-2s, 2x ramp 3-5s -> 1s + 1s + 1s + 1s = 4s export.
+        let remapper = TimeRemapper::new(
+            vec![TimeRange::new(
+                Timestamp::from_micros(1_000_000),
+                Timestamp::from_micros(2_000_000),
+            )],
+            vec![SpeedRamp::new(
+                TimeRange::new(
+                    Timestamp::from_micros(3_000_000),
+                    Timestamp::from_micros(5_000_000),
+                ),
+                2.0,
+            )],
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(6_000_000),
+        );
+
+        let fps = 30_000.0 / 1001.0;
+        let schedule = remapper.frame_schedule(fps);
+        assert_eq!(schedule.len() as u64, remapper.export_frame_count(fps));
+        for entry in &schedule {
+            assert_eq!(
+                entry.source_time,
+                remapper.source_time_for_export_frame(entry.frame, fps)
+            );
+            assert!(entry.export_time < remapper.export_duration());
+        }
+
+        let at_30 = remapper.frame_schedule(30.0);
+        assert_eq!(at_30.len(), 120);
+        // Frame 30 resumes after the cut; frame 60 is the start of the ramp.
+        assert_eq!(at_30[30].source_time.as_micros(), 2_000_000);
+        assert_eq!(at_30[60].source_time.as_micros(), 3_000_000);
+        assert_eq!(at_30[60].speed, 2.0);
+        assert_eq!(at_30[90].speed, 1.0);
+    }
+
+    #[test]
+    fn test_wasm_time_remapper_frames() {
+        let remapper = WasmTimeRemapper::identity(0, 1_000_000);
+        assert_eq!(remapper.export_frame_count(60.0), 60);
+        assert_eq!(remapper.source_time_for_export_frame(30, 60.0), 500_000);
+        assert_eq!(remapper.source_frame_for_export_frame(30, 60.0), 30);
+    }
+
+    #[test]
+    fn test_export_markers_follow_cuts_and_ramps() {
+        // Cut 2-4s, 2x ramp 6-8s.
+        let mut remapper = TimeRemapper::new(
+            vec![TimeRange::new(
+                Timestamp::from_micros(2_000_000),
+                Timestamp::from_micros(4_000_000),
+            )],
+            vec![SpeedRamp::new(
+                TimeRange::new(
+                    Timestamp::from_micros(6_000_000),
+                    Timestamp::from_micros(8_000_000),
+                ),
+                2.0,
+            )],
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        );
+        remapper.add_marker(Marker::new(Timestamp::from_micros(9_000_000), "Outro", ""));
+        remapper.add_marker(Marker::new(
+            Timestamp::from_micros(1_000_000),
+            "Intro",
+            "#f00",
+        ));
+        remapper.add_marker(Marker::new(Timestamp::from_micros(3_000_000), "Cut", ""));
+        remapper.add_marker(Marker::new(Timestamp::from_micros(7_000_000), "Fast", ""));
+        remapper.add_marker(Marker::new(Timestamp::from_micros(12_000_000), "Gone", ""));
+
+        let exported = remapper.export_markers();
+        let times: Vec<u64> = exported.iter().map(|m| m.export_time.as_micros()).collect();
+        // Intro 1s; Cut snaps to 2s; Fast = 2 + 2 + 0.5; Outro = 2 + 2 + 1 + 1.
+        assert_eq!(times, vec![1_000_000, 2_000_000, 4_500_000, 6_000_000]);
+        assert!(exported[1].in_cut);
+        assert_eq!(exported[0].marker.color, "#f00");
+
+        assert_eq!(
+            remapper.to_export_time(Timestamp::from_micros(3_000_000)),
+            None
+        );
+        for t in [0, 1_500_000, 4_000_000, 6_500_000, 7_999_999, 9_000_000] {
+            let export = remapper.to_export_time(Timestamp::from_micros(t)).unwrap();
+            let back = remapper.to_source_time(export).as_micros();
+            assert!(back.abs_diff(t) <= 2, "{} -> {:?} -> {}", t, export, back);
+        }
+    }
+
+    #[test]
+    fn test_markers_round_trip_through_config() {
+        let json = r#"{
+            "in_point_us": 0,
+            "out_point_us": 10000000,
+            "markers": [
+                { "time_us": 5000000, "label": "B" },
+                { "time_us": 1000000, "label": "A", "color": "green" }
+            ]
+        }"#;
+        let config: TimeRemapperConfig = serde_json::from_str(json).unwrap();
+        let remapper = TimeRemapper::from_config(config);
+        assert_eq!(remapper.markers()[0].label, "A");
+
+        let config = remapper.to_config();
+        assert_eq!(config.markers.len(), 2);
+        assert_eq!(config.markers[0].color, "green");
+
+        let restored: TimeRemapper =
+            serde_json::from_str(&serde_json::to_string(&remapper).unwrap()).unwrap();
+        assert_eq!(restored.markers(), remapper.markers());
+    }
+
+    #[test]
+    fn test_protected_range_forces_normal_speed() {
+        // 2x over 0-10s with 4-6s protected: 4s/2 + 2s + 4s/2 = 6s.
+        let mut remapper = TimeRemapper::new(
+            vec![],
+            vec![SpeedRamp::new(
+                TimeRange::new(
+                    Timestamp::from_micros(0),
+                    Timestamp::from_micros(10_000_000),
+                ),
+                2.0,
+            )],
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        );
+        remapper.add_protected_range(TimeRange::new(
+            Timestamp::from_micros(4_000_000),
+            Timestamp::from_micros(6_000_000),
+        ));
+
+        assert_eq!(remapper.export_duration().as_micros(), 6_000_000);
+        assert_eq!(remapper.speed_at(Timestamp::from_micros(5_000_000)), 1.0);
+        assert_eq!(remapper.speed_at(Timestamp::from_micros(7_000_000)), 2.0);
+        assert_eq!(remapper.speed_ramps().len(), 1);
+        assert_eq!(remapper.effective_speed_ramps().len(), 2);
+        assert_eq!(
+            remapper.to_source_time(Timestamp::from_micros(3_000_000)),
+            Timestamp::from_micros(5_000_000)
+        );
+
+        // Removing the protection restores the authored ramp.
+        assert!(remapper.remove_protected_range(0).is_some());
+        assert_eq!(remapper.export_duration().as_micros(), 5_000_000);
+    }
+
+    #[test]
+    fn test_protected_ranges_round_trip_through_config() {
+        let json = r#"{
+            "in_point_us": 0,
+            "out_point_us": 10000000,
+            "speed_ramps": [{ "start_us": 0, "end_us": 10000000, "speed": 2.0 }],
+            "protected_ranges": [{ "start_us": 1000000, "end_us": 3000000 }]
+        }"#;
+        let remapper = WasmTimeRemapper::new(json).unwrap();
+        assert_eq!(remapper.export_duration(), 6_000_000);
+
+        let restored = WasmTimeRemapper::new(&remapper.to_json().unwrap()).unwrap();
+        assert_eq!(restored.export_duration(), 6_000_000);
+    }
+
+    #[test]
+    fn test_audio_segments_merge_and_flag_stretching() {
+        // 0-2 at 1x, cut 2-3, 3-5 at 1x, 5-7 at 2x, 7-10 at 1x.
+        let remapper = TimeRemapper::new(
+            vec![TimeRange::new(
+                Timestamp::from_micros(2_000_000),
+                Timestamp::from_micros(3_000_000),
+            )],
+            vec![SpeedRamp::new(
+                TimeRange::new(
+                    Timestamp::from_micros(5_000_000),
+                    Timestamp::from_micros(7_000_000),
+                ),
+                2.0,
+            )],
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        );
+
+        let chunks = remapper.audio_segments();
+        let summary: Vec<(u64, u64, u64, bool)> = chunks
+            .iter()
+            .map(|c| {
+                (
+                    c.source_range.start.as_micros(),
+                    c.source_range.end.as_micros(),
+                    c.export_range.start.as_micros(),
+                    c.preserve_pitch,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, 2_000_000, 0, false),
+                (3_000_000, 5_000_000, 2_000_000, false),
+                (5_000_000, 7_000_000, 4_000_000, true),
+                (7_000_000, 10_000_000, 5_000_000, false),
+            ]
+        );
+        assert_eq!(chunks[2].speed, 2.0);
+        assert_eq!(
+            chunks.last().unwrap().export_range.end,
+            remapper.export_duration()
+        );
+    }
+
+    #[test]
+    fn test_wasm_ripple_after_edit() {
+        let mut remapper = WasmTimeRemapper::identity(0, 10_000_000);
+        assert_eq!(remapper.get_last_ripple().unwrap(), "[]");
+        assert_eq!(remapper.ripple_export_time(5_000_000), Some(5_000_000));
+
+        remapper.add_cut(2_000_000, 4_000_000);
+        assert_eq!(remapper.ripple_export_time(5_000_000), Some(3_000_000));
+        assert_eq!(remapper.ripple_export_time(3_000_000), None);
+
+        let effects = r#"{ "effects": [
+            { "timestamp": 6000000, "duration_us": 500000, "effect_type": "ClickRing",
+              "position": { "x": 0.5, "y": 0.5 } }
+        ] }"#;
+        let rippled: EffectTrack =
+            serde_json::from_str(&remapper.ripple_effects(effects).unwrap()).unwrap();
+        assert_eq!(rippled.effects[0].timestamp.as_micros(), 4_000_000);
+    }
+
+    #[test]
+    fn test_snap_cuts_to_keyframes() {
+        let mut remapper = TimeRemapper::new(
+            vec![
+                TimeRange::new(
+                    Timestamp::from_micros(1_000_000),
+                    Timestamp::from_micros(2_100_000),
+                ),
+                TimeRange::new(
+                    Timestamp::from_micros(5_000_000),
+                    Timestamp::from_micros(5_500_000),
+                ),
+            ],
+            vec![],
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(10_000_000),
+        )
+        .with_video_keyframes(vec![
+            Timestamp::from_micros(4_000_000),
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(2_000_000),
+        ]);
+
+        // First cut snaps back to 2.0s; the second has no keyframe within 200ms.
+        assert_eq!(remapper.snap_cuts_to_keyframes(200_000), 1);
+        assert_eq!(remapper.cuts()[0].end.as_micros(), 2_000_000);
+        assert_eq!(remapper.cuts()[1].end.as_micros(), 5_500_000);
+        assert_eq!(remapper.export_duration().as_micros(), 8_500_000);
+
+        // A keyframe at or before the cut start never empties the cut.
+        assert_eq!(remapper.snap_cuts_to_keyframes(2_000_000), 0);
+    }
+
+    #[test]
+    fn test_configurable_speed_range_allows_timelapse() {
+        let json = r#"{
+            "in_point_us": 0,
+            "out_point_us": 20000000,
+            "max_speed": 16.0,
+            "speed_ramps": [{ "start_us": 0, "end_us": 16000000, "speed": 16.0 }]
+        }"#;
+        let mut remapper = WasmTimeRemapper::new(json).unwrap();
+        // 16s at 16x = 1s, plus 4s at 1x.
+        assert_eq!(remapper.export_duration(), 5_000_000);
+
+        remapper.set_speed_ramp(16_000_000, 20_000_000, 8.0);
+        assert_eq!(remapper.export_duration(), 1_500_000);
+
+        // Narrowing the range clamps existing ramps; the default range still applies
+        // when none is configured.
+        remapper.set_speed_range(0.25, 4.0);
+        assert_eq!(remapper.export_duration(), 5_000_000);
+        let default_range = WasmTimeRemapper::new(
+            r#"{ "in_point_us": 0, "out_point_us": 8000000,
+                 "speed_ramps": [{ "start_us": 0, "end_us": 8000000, "speed": 8.0 }] }"#,
+        )
+        .unwrap();
+        assert_eq!(default_range.export_duration(), 2_000_000);
+    }
+
     // Note: test_wasm_time_remapper_invalid_json is only testable in wasm32 target
     // because JsValue::from_str is not available on non-wasm32 targets.
     // The error handling is tested implicitly through the WASM build.
@@ -0,0 +1,240 @@
+// SMPTE timecode conversion (drop-frame and non-drop-frame).
+// Uses the same frame rounding as TimeRemapper's frame methods, so timecodes shown in the UI,
+// written to markers/EDLs, and used by the exporter agree.
+// See design.md: TimeRemapper (Rust)
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::error::EngineError;
+use crate::types::Timestamp;
+
+/// An SMPTE timecode. Drop-frame timecodes display with `;` before the frame field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timecode {
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub frames: u32,
+    pub drop_frame: bool,
+}
+
+impl Timecode {
+    /// Timecode of frame `frame` at `fps`. Drop-frame is only honoured for the NTSC
+    /// rates (29.97, 59.94); other rates fall back to non-drop.
+    pub fn from_frame(frame: u64, fps: f64, drop_frame: bool) -> Self {
+        let nominal = nominal_rate(fps);
+        let drop_frame = drop_frame && supports_drop_frame(fps);
+
+        let mut frame = frame;
+        if drop_frame {
+            // Re-insert the frame numbers skipped at the start of each minute
+            // (except every tenth minute).
+            let drop = drop_per_minute(nominal);
+            let per_ten_minutes = nominal * 600 - drop * 9;
+            let per_minute = nominal * 60 - drop;
+            let tens = frame / per_ten_minutes;
+            let rest = frame % per_ten_minutes;
+            frame += drop * 9 * tens;
+            if rest > drop {
+                frame += drop * ((rest - drop) / per_minute);
+            }
+        }
+
+        let total_seconds = frame / nominal;
+        Timecode {
+            hours: (total_seconds / 3600) as u32,
+            minutes: ((total_seconds / 60) % 60) as u32,
+            seconds: (total_seconds % 60) as u32,
+            frames: (frame % nominal) as u32,
+            drop_frame,
+        }
+    }
+
+    /// Timecode of the frame shown at `timestamp` (see `Timestamp::frame_at`).
+    pub fn from_timestamp(timestamp: Timestamp, fps: f64, drop_frame: bool) -> Self {
+        Timecode::from_frame(timestamp.frame_at(fps), fps, drop_frame)
+    }
+
+    /// Frame index this timecode labels at `fps`.
+    pub fn to_frame(&self, fps: f64) -> Result<u64, EngineError> {
+        let nominal = nominal_rate(fps);
+        if self.minutes >= 60 || self.seconds >= 60 || self.frames as u64 >= nominal {
+            return Err(EngineError::InvalidTimecode(format!(
+                "{} is out of range at {} fps",
+                self, fps
+            )));
+        }
+
+        let total_minutes = self.hours as u64 * 60 + self.minutes as u64;
+        let mut frame = (total_minutes * 60 + self.seconds as u64) * nominal + self.frames as u64;
+
+        if self.drop_frame {
+            if !supports_drop_frame(fps) {
+                return Err(EngineError::InvalidTimecode(format!(
+                    "Drop-frame timecode is not defined at {} fps",
+                    fps
+                )));
+            }
+            let drop = drop_per_minute(nominal);
+            if self.seconds == 0 && !self.minutes.is_multiple_of(10) && (self.frames as u64) < drop
+            {
+                return Err(EngineError::InvalidTimecode(format!(
+                    "{} is a dropped frame number",
+                    self
+                )));
+            }
+            frame -= drop * (total_minutes - total_minutes / 10);
+        }
+        Ok(frame)
+    }
+
+    /// Presentation time of the frame this timecode labels.
+    pub fn to_timestamp(&self, fps: f64) -> Result<Timestamp, EngineError> {
+        Ok(Timestamp::from_frame(self.to_frame(fps)?, fps))
+    }
+}
+
+impl fmt::Display for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if self.drop_frame { ';' } else { ':' };
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours, self.minutes, self.seconds, separator, self.frames
+        )
+    }
+}
+
+impl FromStr for Timecode {
+    type Err = EngineError;
+
+    /// Parse `HH:MM:SS:FF`, or `HH:MM:SS;FF` / `HH;MM;SS;FF` / `HH:MM:SS.FF` for drop-frame.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || EngineError::InvalidTimecode(format!("Cannot parse '{}'", s));
+        let drop_frame = s.contains(';') || s.contains('.');
+        let fields: Vec<u32> = s
+            .split([':', ';', '.'])
+            .map(|field| field.trim().parse::<u32>().map_err(|_| invalid()))
+            .collect::<Result<_, _>>()?;
+        let [hours, minutes, seconds, frames] = fields[..] else {
+            return Err(invalid());
+        };
+        Ok(Timecode {
+            hours,
+            minutes,
+            seconds,
+            frames,
+            drop_frame,
+        })
+    }
+}
+
+/// Integer frame rate used for timecode labels (30 for 29.97, 60 for 59.94).
+fn nominal_rate(fps: f64) -> u64 {
+    (fps.round() as u64).max(1)
+}
+
+fn supports_drop_frame(fps: f64) -> bool {
+    let nominal = nominal_rate(fps);
+    (nominal == 30 || nominal == 60) && (fps - nominal as f64).abs() > 1e-6
+}
+
+/// Frame numbers skipped per minute: 2 at 29.97, 4 at 59.94.
+fn drop_per_minute(nominal: u64) -> u64 {
+    nominal / 15
+}
+
+// =============================================================================
+// WASM Bindings
+// =============================================================================
+
+/// Format a timestamp as SMPTE timecode.
+///
+/// # Arguments
+/// * `timestamp_us` - Timestamp in microseconds
+/// * `fps` - Frame rate (e.g. 30, 29.97002997)
+/// * `drop_frame` - Use drop-frame labels (only at 29.97/59.94)
+#[wasm_bindgen]
+pub fn timestamp_to_timecode(timestamp_us: u64, fps: f64, drop_frame: bool) -> String {
+    Timecode::from_timestamp(Timestamp::from_micros(timestamp_us), fps, drop_frame).to_string()
+}
+
+/// Parse SMPTE timecode into the presentation timestamp of its frame.
+/// Drop-frame is inferred from a `;` (or `.`) separator.
+///
+/// # Returns
+/// Timestamp in microseconds, or an error for malformed or dropped timecodes
+#[wasm_bindgen]
+pub fn timecode_to_timestamp(timecode: &str, fps: f64) -> Result<u64, JsValue> {
+    timecode
+        .parse::<Timecode>()
+        .and_then(|tc| tc.to_timestamp(fps))
+        .map(|ts| ts.as_micros())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NTSC: f64 = 30_000.0 / 1001.0;
+
+    #[test]
+    fn drop_frame_skips_labels_each_minute() {
+        assert_eq!(
+            Timecode::from_frame(1799, NTSC, true).to_string(),
+            "00:00:59;29"
+        );
+        assert_eq!(
+            Timecode::from_frame(1800, NTSC, true).to_string(),
+            "00:01:00;02"
+        );
+        assert_eq!(
+            Timecode::from_frame(17982, NTSC, true).to_string(),
+            "00:10:00;00"
+        );
+        // One hour of 29.97 is exactly 01:00:00;00 in drop-frame.
+        assert_eq!(
+            Timecode::from_frame(107_892, NTSC, true).to_string(),
+            "01:00:00;00"
+        );
+        assert_eq!(
+            Timecode::from_frame(107_892, NTSC, false).to_string(),
+            "00:59:56:12"
+        );
+
+        for frame in [0, 1, 1799, 1800, 17_981, 17_982, 107_891, 1_000_000] {
+            let tc = Timecode::from_frame(frame, NTSC, true);
+            assert_eq!(tc.to_frame(NTSC).unwrap(), frame);
+        }
+        assert!("00:01:00;01"
+            .parse::<Timecode>()
+            .unwrap()
+            .to_frame(NTSC)
+            .is_err());
+    }
+
+    #[test]
+    fn round_trips_through_timestamps() {
+        let tc: Timecode = "00:00:10:15".parse().unwrap();
+        let ts = tc.to_timestamp(30.0).unwrap();
+        assert_eq!(ts.as_micros(), 10_500_000);
+        assert_eq!(Timecode::from_timestamp(ts, 30.0, false), tc);
+
+        // Drop-frame isn't defined at integer rates.
+        assert!(!Timecode::from_frame(1800, 30.0, true).drop_frame);
+        assert_eq!(
+            timestamp_to_timecode(10_500_000, 30.0, false),
+            "00:00:10:15"
+        );
+        assert_eq!(
+            timecode_to_timestamp("00:00:01:00", 25.0).unwrap(),
+            1_000_000
+        );
+        assert!("10:00".parse::<Timecode>().is_err());
+    }
+}
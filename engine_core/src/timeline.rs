@@ -0,0 +1,461 @@
+// Multi-clip timeline composition.
+// Stitches several takes, each with its own TimeRemapper, into one export timeline.
+// See design.md: TimeRemapper (Rust)
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::time_remap::{TimeRemapper, TimeRemapperConfig};
+use crate::types::Timestamp;
+
+/// One clip on the timeline: a take plus the cuts/ramps/trim applied to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineClip {
+    pub clip_id: String,
+    pub remapper: TimeRemapper,
+}
+
+/// Where a clip sits on the global export timeline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClipBoundary {
+    pub clip_id: String,
+    /// Position of the clip in timeline order.
+    pub index: usize,
+    pub export_start: Timestamp,
+    /// Exclusive.
+    pub export_end: Timestamp,
+}
+
+/// A global export time resolved to a position inside one clip.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClipTime {
+    pub clip_id: String,
+    pub index: usize,
+    /// Export time relative to the start of the clip.
+    pub clip_export_time: Timestamp,
+    /// Source time within the clip's recording.
+    pub source_time: Timestamp,
+}
+
+/// Ordered list of clips played back to back.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "TimelineParts")]
+pub struct TimelineComposer {
+    clips: Vec<TimelineClip>,
+    /// Global export end of each clip; rebuilt after every edit.
+    #[serde(skip)]
+    ends: Vec<u64>,
+}
+
+#[derive(Deserialize)]
+struct TimelineParts {
+    clips: Vec<TimelineClip>,
+}
+
+impl From<TimelineParts> for TimelineComposer {
+    fn from(parts: TimelineParts) -> Self {
+        TimelineComposer::new(parts.clips)
+    }
+}
+
+impl TimelineComposer {
+    /// Create a timeline from clips in playback order.
+    pub fn new(clips: Vec<TimelineClip>) -> Self {
+        let mut composer = TimelineComposer {
+            clips,
+            ends: Vec::new(),
+        };
+        composer.rebuild_ends();
+        composer
+    }
+
+    fn rebuild_ends(&mut self) {
+        let mut end = 0u64;
+        self.ends = self
+            .clips
+            .iter()
+            .map(|clip| {
+                end += clip.remapper.export_duration().as_micros();
+                end
+            })
+            .collect();
+    }
+
+    /// Global export start of the clip at `index`.
+    fn start_of(&self, index: usize) -> u64 {
+        index.checked_sub(1).map(|i| self.ends[i]).unwrap_or(0)
+    }
+
+    /// Get the clips in playback order.
+    pub fn clips(&self) -> &[TimelineClip] {
+        &self.clips
+    }
+
+    /// Append a clip to the end of the timeline.
+    pub fn add_clip(&mut self, clip_id: impl Into<String>, remapper: TimeRemapper) {
+        self.insert_clip(self.clips.len(), clip_id, remapper);
+    }
+
+    /// Insert a clip at `index` (clamped to the end of the timeline).
+    pub fn insert_clip(
+        &mut self,
+        index: usize,
+        clip_id: impl Into<String>,
+        remapper: TimeRemapper,
+    ) {
+        let index = index.min(self.clips.len());
+        self.clips.insert(
+            index,
+            TimelineClip {
+                clip_id: clip_id.into(),
+                remapper,
+            },
+        );
+        self.rebuild_ends();
+    }
+
+    /// Remove the clip at `index`.
+    pub fn remove_clip(&mut self, index: usize) -> Option<TimelineClip> {
+        if index >= self.clips.len() {
+            return None;
+        }
+        let clip = self.clips.remove(index);
+        self.rebuild_ends();
+        Some(clip)
+    }
+
+    /// Move the clip at `from` so it ends up at position `to`.
+    pub fn move_clip(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.clips.len() || to >= self.clips.len() {
+            return false;
+        }
+        let clip = self.clips.remove(from);
+        self.clips.insert(to, clip);
+        self.rebuild_ends();
+        true
+    }
+
+    /// Replace the remapper of the clip at `index` (e.g. after editing its cuts).
+    pub fn set_clip_remapper(&mut self, index: usize, remapper: TimeRemapper) -> bool {
+        match self.clips.get_mut(index) {
+            Some(clip) => {
+                clip.remapper = remapper;
+                self.rebuild_ends();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Total export duration of all clips.
+    pub fn total_duration(&self) -> Timestamp {
+        Timestamp::from_micros(self.ends.last().copied().unwrap_or(0))
+    }
+
+    /// Global export range of every clip, in playback order.
+    pub fn boundaries(&self) -> Vec<ClipBoundary> {
+        self.clips
+            .iter()
+            .enumerate()
+            .map(|(index, clip)| ClipBoundary {
+                clip_id: clip.clip_id.clone(),
+                index,
+                export_start: Timestamp::from_micros(self.start_of(index)),
+                export_end: Timestamp::from_micros(self.ends[index]),
+            })
+            .collect()
+    }
+
+    /// Resolve a global export time to a clip and its source time.
+    /// A time exactly on a boundary belongs to the following clip; empty clips are never
+    /// returned. Returns `None` at or past `total_duration()`.
+    pub fn to_clip_time(&self, export_time: Timestamp) -> Option<ClipTime> {
+        let t = export_time.as_micros();
+        // First clip that ends after t.
+        let index = self.ends.partition_point(|&end| end <= t);
+        let clip = self.clips.get(index)?;
+        let clip_export_time = Timestamp::from_micros(t - self.start_of(index));
+
+        Some(ClipTime {
+            clip_id: clip.clip_id.clone(),
+            index,
+            clip_export_time,
+            source_time: clip.remapper.to_source_time(clip_export_time),
+        })
+    }
+
+    /// Build a timeline from its JSON-friendly configuration.
+    pub fn from_config(config: TimelineConfig) -> Self {
+        TimelineComposer::new(
+            config
+                .clips
+                .into_iter()
+                .map(|clip| TimelineClip {
+                    clip_id: clip.clip_id,
+                    remapper: TimeRemapper::from_config(clip.remapper),
+                })
+                .collect(),
+        )
+    }
+
+    /// Convert back to the JSON-friendly configuration.
+    pub fn to_config(&self) -> TimelineConfig {
+        TimelineConfig {
+            clips: self
+                .clips
+                .iter()
+                .map(|clip| TimelineClipConfig {
+                    clip_id: clip.clip_id.clone(),
+                    remapper: clip.remapper.to_config(),
+                })
+                .collect(),
+        }
+    }
+}
+
+// =============================================================================
+// WASM Bindings
+// =============================================================================
+
+/// Configuration for creating a TimelineComposer from JavaScript.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimelineConfig {
+    /// Clips in playback order.
+    #[serde(default)]
+    pub clips: Vec<TimelineClipConfig>,
+}
+
+/// JSON-friendly clip configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineClipConfig {
+    pub clip_id: String,
+    pub remapper: TimeRemapperConfig,
+}
+
+/// WASM-exposed TimelineComposer for JavaScript interop.
+///
+/// # Example JSON Config
+/// ```json
+/// {
+///   "clips": [
+///     { "clip_id": "take-1", "remapper": { "in_point_us": 0, "out_point_us": 5000000 } },
+///     { "clip_id": "take-2", "remapper": { "in_point_us": 1000000, "out_point_us": 8000000 } }
+///   ]
+/// }
+/// ```
+#[wasm_bindgen]
+pub struct WasmTimelineComposer {
+    inner: TimelineComposer,
+}
+
+#[wasm_bindgen]
+impl WasmTimelineComposer {
+    /// Create a timeline from JSON configuration.
+    ///
+    /// # Arguments
+    /// * `config_json` - JSON string with TimelineConfig structure
+    #[wasm_bindgen(constructor)]
+    pub fn new(config_json: &str) -> Result<WasmTimelineComposer, JsValue> {
+        let config: TimelineConfig = serde_json::from_str(config_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid timeline config: {}", e)))?;
+
+        Ok(WasmTimelineComposer {
+            inner: TimelineComposer::from_config(config),
+        })
+    }
+
+    /// Create an empty timeline.
+    #[wasm_bindgen]
+    pub fn empty() -> WasmTimelineComposer {
+        WasmTimelineComposer {
+            inner: TimelineComposer::default(),
+        }
+    }
+
+    /// Append a clip.
+    ///
+    /// # Arguments
+    /// * `clip_id` - Caller-defined identifier for the take
+    /// * `remapper_json` - JSON string with TimeRemapperConfig structure
+    #[wasm_bindgen]
+    pub fn add_clip(&mut self, clip_id: &str, remapper_json: &str) -> Result<(), JsValue> {
+        let config: TimeRemapperConfig = serde_json::from_str(remapper_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid TimeRemapper config: {}", e)))?;
+        self.inner
+            .add_clip(clip_id, TimeRemapper::from_config(config));
+        Ok(())
+    }
+
+    /// Replace the cuts/ramps/trim of the clip at `index`.
+    ///
+    /// # Returns
+    /// `true` if the clip exists
+    #[wasm_bindgen]
+    pub fn set_clip_remapper(
+        &mut self,
+        index: usize,
+        remapper_json: &str,
+    ) -> Result<bool, JsValue> {
+        let config: TimeRemapperConfig = serde_json::from_str(remapper_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid TimeRemapper config: {}", e)))?;
+        Ok(self
+            .inner
+            .set_clip_remapper(index, TimeRemapper::from_config(config)))
+    }
+
+    /// Remove the clip at `index`.
+    ///
+    /// # Returns
+    /// `true` if a clip was removed
+    #[wasm_bindgen]
+    pub fn remove_clip(&mut self, index: usize) -> bool {
+        self.inner.remove_clip(index).is_some()
+    }
+
+    /// Move the clip at `from` to position `to`.
+    ///
+    /// # Returns
+    /// `true` if both indices were valid
+    #[wasm_bindgen]
+    pub fn move_clip(&mut self, from: usize, to: usize) -> bool {
+        self.inner.move_clip(from, to)
+    }
+
+    /// Number of clips on the timeline.
+    #[wasm_bindgen]
+    pub fn clip_count(&self) -> usize {
+        self.inner.clips().len()
+    }
+
+    /// Total export duration in microseconds.
+    #[wasm_bindgen]
+    pub fn total_duration(&self) -> u64 {
+        self.inner.total_duration().as_micros()
+    }
+
+    /// Get the global export range of every clip.
+    ///
+    /// # Returns
+    /// JSON array of ClipBoundary
+    #[wasm_bindgen]
+    pub fn get_boundaries(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.inner.boundaries())
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Resolve a global export time to a clip and source time.
+    ///
+    /// # Returns
+    /// JSON ClipTime, or `null` past the end of the timeline
+    #[wasm_bindgen]
+    pub fn to_clip_time(&self, export_time_us: u64) -> Result<String, JsValue> {
+        serde_json::to_string(
+            &self
+                .inner
+                .to_clip_time(Timestamp::from_micros(export_time_us)),
+        )
+        .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+
+    /// Get the configuration as JSON.
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.inner.to_config())
+            .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_remap::TimeRange;
+
+    fn clip(in_us: u64, out_us: u64) -> TimeRemapper {
+        TimeRemapper::identity(
+            Timestamp::from_micros(in_us),
+            Timestamp::from_micros(out_us),
+        )
+    }
+
+    #[test]
+    fn maps_global_time_across_clips() {
+        let mut composer = TimelineComposer::default();
+        composer.add_clip("a", clip(0, 3_000_000));
+        composer.add_clip("empty", clip(0, 0));
+        let mut cut = clip(1_000_000, 6_000_000);
+        cut.add_cut(TimeRange::new(
+            Timestamp::from_micros(2_000_000),
+            Timestamp::from_micros(3_000_000),
+        ));
+        composer.add_clip("b", cut);
+
+        assert_eq!(composer.total_duration().as_micros(), 7_000_000);
+
+        let at = composer
+            .to_clip_time(Timestamp::from_micros(1_000_000))
+            .unwrap();
+        assert_eq!(
+            (at.clip_id.as_str(), at.source_time.as_micros()),
+            ("a", 1_000_000)
+        );
+
+        // Boundary belongs to the next non-empty clip.
+        let at = composer
+            .to_clip_time(Timestamp::from_micros(3_000_000))
+            .unwrap();
+        assert_eq!((at.clip_id.as_str(), at.index), ("b", 2));
+        assert_eq!(at.source_time.as_micros(), 1_000_000);
+
+        // 1.5s into b: 1s before the cut, then 0.5s after it.
+        let at = composer
+            .to_clip_time(Timestamp::from_micros(4_500_000))
+            .unwrap();
+        assert_eq!(at.source_time.as_micros(), 3_500_000);
+
+        assert!(composer
+            .to_clip_time(Timestamp::from_micros(7_000_000))
+            .is_none());
+    }
+
+    #[test]
+    fn edits_update_boundaries() {
+        let mut composer = TimelineComposer::new(vec![
+            TimelineClip {
+                clip_id: "a".into(),
+                remapper: clip(0, 1_000_000),
+            },
+            TimelineClip {
+                clip_id: "b".into(),
+                remapper: clip(0, 2_000_000),
+            },
+        ]);
+        assert!(composer.move_clip(1, 0));
+
+        let boundaries = composer.boundaries();
+        assert_eq!(boundaries[0].clip_id, "b");
+        assert_eq!(boundaries[1].export_start.as_micros(), 2_000_000);
+
+        assert!(composer.remove_clip(0).is_some());
+        assert_eq!(composer.total_duration().as_micros(), 1_000_000);
+
+        let json = serde_json::to_string(&composer).unwrap();
+        let restored: TimelineComposer = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.boundaries(), composer.boundaries());
+    }
+
+    #[test]
+    fn wasm_timeline_from_json() {
+        let json = r#"{ "clips": [
+            { "clip_id": "a", "remapper": { "in_point_us": 0, "out_point_us": 2000000 } }
+        ] }"#;
+        let mut composer = WasmTimelineComposer::new(json).unwrap();
+        composer
+            .add_clip("b", r#"{ "in_point_us": 0, "out_point_us": 1000000 }"#)
+            .unwrap();
+
+        assert_eq!(composer.clip_count(), 2);
+        assert_eq!(composer.total_duration(), 3_000_000);
+        assert!(composer.to_clip_time(2_500_000).unwrap().contains("\"b\""));
+        assert_eq!(composer.to_clip_time(3_000_000).unwrap(), "null");
+    }
+}
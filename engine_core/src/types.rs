@@ -2,9 +2,15 @@
 // See steering.md: Rust Rules (Engine)
 
 use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+
+use crate::migrate::ENGINE_CONFIG_VERSION;
 
 /// Timestamp in microseconds. Newtype for type safety.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default, Tsify,
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Timestamp(u64);
 
 impl Timestamp {
@@ -23,10 +29,34 @@ impl Timestamp {
     pub fn as_secs(&self) -> f64 {
         self.0 as f64 / 1_000_000.0
     }
+
+    /// Presentation time of frame `frame` at `fps`.
+    /// Always computed from the frame index (`round(frame * 1e6 / fps)`), never by
+    /// accumulating per-frame durations, so long exports don't drift.
+    pub fn from_frame(frame: u64, fps: f64) -> Self {
+        Timestamp((frame as f64 * 1_000_000.0 / fps).round() as u64)
+    }
+
+    /// Index of the frame being shown at this time at `fps`: the last frame whose
+    /// presentation time (per `from_frame`) is at or before this timestamp.
+    pub fn frame_at(&self, fps: f64) -> u64 {
+        let mut frame = (self.0 as f64 * fps / 1_000_000.0).floor() as u64;
+        // Correct float error at exact frame boundaries against `from_frame`.
+        while Timestamp::from_frame(frame + 1, fps) <= *self {
+            frame += 1;
+        }
+        while frame > 0 && Timestamp::from_frame(frame, fps) > *self {
+            frame -= 1;
+        }
+        frame
+    }
 }
 
 /// Frame index. Newtype for type safety.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default, Tsify,
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FrameIndex(u32);
 
 impl FrameIndex {
@@ -53,7 +83,8 @@ impl PixelCoord {
 }
 
 /// Normalized coordinate (0.0 to 1.0, resolution-independent).
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct NormalizedCoord {
     pub x: f32,
     pub y: f32,
@@ -73,7 +104,8 @@ impl NormalizedCoord {
 }
 
 /// Capture mode determines signal quality tier.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum CaptureMode {
     /// Tier A: Browser tab with content script signals (best quality).
     Tab,
@@ -84,7 +116,8 @@ pub enum CaptureMode {
 }
 
 /// Cursor visibility/inference state.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum CursorState {
     /// Cursor position is known from real input (Tab Mode).
     Visible,
@@ -95,7 +128,8 @@ pub enum CursorState {
 }
 
 /// Reason for an inference decision (for debugging, analysis).
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum InferenceReason {
     /// Direct input from Tab Mode content script.
     DirectInput,
@@ -107,20 +141,337 @@ pub enum InferenceReason {
     UiChange,
     /// Fallback to saliency-based focus.
     SaliencyFallback,
+    /// Reported position implies an implausibly fast jump (e.g. a bogus 0,0 or an
+    /// iframe coordinate mix-up); kept for reference at low confidence.
+    Outlier,
 }
 
 /// Engine configuration passed from JS.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EngineConfig {
+    /// Shape version (see `migrate_engine_config`). Parsing directly assumes the
+    /// current one.
+    #[serde(default = "default_engine_config_version")]
+    pub version: u32,
     pub capture_mode: CaptureMode,
     #[serde(default)]
     pub camera_settings: CameraSettings,
     #[serde(default)]
     pub effect_settings: EffectSettings,
+    #[serde(default)]
+    pub cursor_settings: CursorSettings,
+    #[serde(default)]
+    pub scroll_settings: ScrollSettings,
+    #[serde(default)]
+    pub session_settings: SessionSettings,
+    #[serde(default)]
+    pub importance_weights: ImportanceWeights,
+    #[serde(default)]
+    pub privacy: PrivacySettings,
+}
+
+fn default_engine_config_version() -> u32 {
+    ENGINE_CONFIG_VERSION
+}
+
+/// What the engine hides on its own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PrivacySettings {
+    /// Blur password fields and inputs with a sensitive `autocomplete` hint while they
+    /// have focus.
+    #[serde(default = "default_true")]
+    pub auto_blur: bool,
+    /// Extra blur before focus arrives and after it leaves (microseconds).
+    #[serde(default = "default_blur_margin")]
+    pub margin_us: u64,
+    /// `autocomplete` tokens that mark an input as sensitive.
+    #[serde(default = "default_sensitive_autocomplete")]
+    pub sensitive_autocomplete: Vec<String>,
+}
+
+impl Default for PrivacySettings {
+    fn default() -> Self {
+        PrivacySettings {
+            auto_blur: default_true(),
+            margin_us: default_blur_margin(),
+            sensitive_autocomplete: default_sensitive_autocomplete(),
+        }
+    }
+}
+
+fn default_blur_margin() -> u64 {
+    500_000
+}
+
+fn default_sensitive_autocomplete() -> Vec<String> {
+    [
+        "current-password",
+        "new-password",
+        "one-time-code",
+        "cc-number",
+        "cc-csc",
+        "cc-exp",
+        "cc-exp-month",
+        "cc-exp-year",
+    ]
+    .iter()
+    .map(|token| token.to_string())
+    .collect()
+}
+
+/// Multipliers on the importance of each kind of focus region. Regions at 0.8 or
+/// above are what the camera zooms to, so lower weights give a calmer camera and
+/// higher ones a more eager one. Results are clamped to 0.0-1.0.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ImportanceWeights {
+    /// Click sessions, drags, pinches, and shakes.
+    #[serde(default = "default_weight")]
+    pub clicks: f32,
+    /// Focused elements and text selections.
+    #[serde(default = "default_weight")]
+    pub focus_changes: f32,
+    /// Sustained typing into the focused element.
+    #[serde(default = "default_weight")]
+    pub typing: f32,
+    /// Where the cursor rests (scaled by cursor confidence).
+    #[serde(default = "default_weight")]
+    pub dwell: f32,
+    /// Motion/contrast saliency in Tier B captures.
+    #[serde(default = "default_weight")]
+    pub saliency: f32,
+}
+
+impl Default for ImportanceWeights {
+    fn default() -> Self {
+        ImportanceWeights {
+            clicks: default_weight(),
+            focus_changes: default_weight(),
+            typing: default_weight(),
+            dwell: default_weight(),
+            saliency: default_weight(),
+        }
+    }
+}
+
+fn default_weight() -> f32 {
+    1.0
+}
+
+/// How clicks are grouped into interaction sessions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SessionSettings {
+    /// Longest pause between clicks of one session (microseconds).
+    #[serde(default = "default_max_click_gap")]
+    pub max_click_gap_us: u64,
+    /// Largest area (normalized width or height) one session may cover.
+    #[serde(default = "default_max_session_span")]
+    pub max_span: f32,
+}
+
+impl Default for SessionSettings {
+    fn default() -> Self {
+        SessionSettings {
+            max_click_gap_us: default_max_click_gap(),
+            max_span: default_max_session_span(),
+        }
+    }
+}
+
+fn default_max_click_gap() -> u64 {
+    2_000_000
+}
+
+fn default_max_session_span() -> f32 {
+    0.35
+}
+
+/// How scroll events relate input coordinates to the captured pixels, and how the
+/// camera follows long scrolls.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScrollSettings {
+    #[serde(default)]
+    pub compensation: ScrollCompensation,
+    /// Scroll `delta_y` units per viewport height (e.g. the viewport height in CSS
+    /// pixels when deltas are pixels). 1.0 means deltas are already normalized.
+    #[serde(default = "default_delta_scale")]
+    pub delta_scale: f32,
+    /// How far the camera leans toward incoming content during a long scroll, 0.0-1.0.
+    /// 0.0 leaves the camera on whatever it was framing.
+    #[serde(default = "default_pan_sensitivity")]
+    pub pan_sensitivity: f32,
+    /// Zoom out to full view during long scrolls instead of panning.
+    #[serde(default)]
+    pub zoom_out: bool,
+}
+
+impl Default for ScrollSettings {
+    fn default() -> Self {
+        ScrollSettings {
+            compensation: ScrollCompensation::default(),
+            delta_scale: default_delta_scale(),
+            pan_sensitivity: default_pan_sensitivity(),
+            zoom_out: false,
+        }
+    }
+}
+
+fn default_delta_scale() -> f32 {
+    1.0
+}
+
+fn default_pan_sensitivity() -> f32 {
+    0.5
+}
+
+/// Which way input coordinates are shifted by the cumulative scroll offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ScrollCompensation {
+    /// Inputs are already in the capture's coordinates.
+    #[default]
+    None,
+    /// Inputs are page-relative; the capture shows the viewport.
+    PageToViewport,
+    /// Inputs are viewport-relative; the capture shows the whole page.
+    ViewportToPage,
+}
+
+/// Cursor tracking settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CursorSettings {
+    /// Smoothing filter run over direct-input cursor positions.
+    #[serde(default)]
+    pub filter: CursorFilter,
+    /// Which position the camera follows.
+    #[serde(default)]
+    pub camera_input: CursorInput,
+    /// Direct-input jumps faster than this (normalized units per second) are outliers.
+    /// 0 disables outlier detection.
+    #[serde(default = "default_max_cursor_speed")]
+    pub max_cursor_speed: f32,
+    /// Drop outliers from the track instead of keeping them at low confidence.
+    #[serde(default)]
+    pub drop_outliers: bool,
+    /// Resample the track to this fixed rate (Hz) with spline interpolation. Outliers
+    /// are left out. `None` keeps the points as reported.
+    #[serde(default)]
+    pub resample_hz: Option<f32>,
+    /// Gaps between reported points longer than this (microseconds) are left empty
+    /// when resampling rather than filled.
+    #[serde(default = "default_max_gap_fill")]
+    pub max_gap_fill_us: u64,
+    /// Mouse moves closer than this (microseconds) to the last kept point are dropped,
+    /// bounding memory and result size for high-rate mice. 0 keeps every move.
+    #[serde(default)]
+    pub min_move_interval_us: u64,
+    /// Mouse moves closer than this (normalized distance) to the last kept point are
+    /// dropped. 0 keeps every move.
+    #[serde(default)]
+    pub min_move_distance: f32,
+    /// When both input events and frame detection report the cursor, each point is
+    /// fused from both, weighted by confidence and by recency with this half-life
+    /// (microseconds). 0 disables fusion (the latest report wins).
+    #[serde(default = "default_fusion_half_life")]
+    pub fusion_half_life_us: u64,
+    /// A press and release whose path spans less than this (normalized) is a click,
+    /// not a drag.
+    #[serde(default = "default_min_drag_distance")]
+    pub min_drag_distance: f32,
+    /// Pinches that change the finger spread by less than this factor (either way)
+    /// are ignored.
+    #[serde(default = "default_min_pinch_scale")]
+    pub min_pinch_scale: f32,
+    /// Direction reversals within a short window that make a "look here" shake.
+    /// 0 disables shake detection.
+    #[serde(default = "default_shake_min_reversals")]
+    pub shake_min_reversals: u32,
+}
+
+impl Default for CursorSettings {
+    fn default() -> Self {
+        CursorSettings {
+            filter: CursorFilter::default(),
+            camera_input: CursorInput::default(),
+            max_cursor_speed: default_max_cursor_speed(),
+            drop_outliers: false,
+            resample_hz: None,
+            max_gap_fill_us: default_max_gap_fill(),
+            min_move_interval_us: 0,
+            min_move_distance: 0.0,
+            fusion_half_life_us: default_fusion_half_life(),
+            min_drag_distance: default_min_drag_distance(),
+            min_pinch_scale: default_min_pinch_scale(),
+            shake_min_reversals: default_shake_min_reversals(),
+        }
+    }
+}
+
+fn default_shake_min_reversals() -> u32 {
+    4
+}
+
+fn default_min_pinch_scale() -> f32 {
+    1.2
+}
+
+fn default_min_drag_distance() -> f32 {
+    0.02
+}
+
+fn default_fusion_half_life() -> u64 {
+    100_000
+}
+
+fn default_max_gap_fill() -> u64 {
+    2_000_000
+}
+
+fn default_max_cursor_speed() -> f32 {
+    // A hard flick crosses the screen in ~100ms; bogus jumps are effectively instant.
+    30.0
+}
+
+/// Smoothing filter for raw mouse positions.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CursorFilter {
+    #[default]
+    None,
+    /// One-Euro filter: the cutoff rises with speed, so slow motion is smoothed hard
+    /// and fast motion has little lag. Cutoffs in Hz.
+    OneEuro {
+        min_cutoff: f32,
+        beta: f32,
+        d_cutoff: f32,
+    },
+    /// Per-axis Kalman filter with a constant-position model. Process noise is per
+    /// second; both are in normalized units squared.
+    Kalman {
+        process_noise: f32,
+        measurement_noise: f32,
+    },
+}
+
+/// Cursor position fed to the camera.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CursorInput {
+    /// Positions as reported.
+    #[default]
+    Raw,
+    /// Filtered positions, where available.
+    Smoothed,
 }
 
 /// Camera behavior settings.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CameraSettings {
     /// Minimum time to hold a zoom before moving (microseconds).
     #[serde(default = "default_min_hold_time")]
@@ -134,6 +485,140 @@ pub struct CameraSettings {
     /// Zoom strength multiplier.
     #[serde(default = "default_zoom_strength")]
     pub zoom_strength: f32,
+    /// How viewports that would show past the frame edge are brought back inside it.
+    #[serde(default)]
+    pub bounds: ViewportBounds,
+    /// Screen regions the camera never centers on (e.g. the notification corner or
+    /// browser chrome). Targets inside a zone are ignored; centers pushed into one by
+    /// smoothing are moved to its nearest edge.
+    #[serde(default)]
+    pub exclusion_zones: Vec<NormalizedRect>,
+    /// Cursor speed (normalized units per second) above which the camera pulls back to
+    /// full view. 0 disables the rule.
+    #[serde(default = "default_fast_motion_speed")]
+    pub fast_motion_speed: f32,
+    /// After pulling back, the camera re-zooms once the cursor is slower than this.
+    #[serde(default = "default_settle_speed")]
+    pub settle_speed: f32,
+    /// How far ahead of a click (microseconds) the camera starts moving toward its
+    /// target. Analysis is offline, so the camera can anticipate. 0 disables.
+    #[serde(default = "default_look_ahead")]
+    pub look_ahead_us: u64,
+    /// Minimum time at a zoom level before the zoom changes again (microseconds).
+    #[serde(default = "default_min_zoom_dwell")]
+    pub min_zoom_dwell_us: u64,
+    /// After zooming out, no zooming back in for this long (microseconds).
+    #[serde(default = "default_zoom_in_cooldown")]
+    pub zoom_in_cooldown_us: u64,
+    /// Zoom changes smaller than this are ignored (the camera only pans).
+    #[serde(default = "default_min_zoom_delta")]
+    pub min_zoom_delta: f32,
+    /// Keyframe compaction drops keyframes the curve passes within this distance of
+    /// anyway (normalized center distance, or relative zoom difference).
+    #[serde(default = "default_keyframe_epsilon")]
+    pub keyframe_epsilon: f32,
+    /// Keyframe budget for compaction; the least significant keyframes are dropped
+    /// beyond it. `None` means no budget.
+    #[serde(default)]
+    pub max_keyframes: Option<usize>,
+    /// Frame the cursor and the active focus region together instead of whichever
+    /// signal came last.
+    #[serde(default = "default_true")]
+    pub smart_framing: bool,
+    /// Padding (normalized) around the cursor and focus region when framing both.
+    #[serde(default = "default_framing_padding")]
+    pub framing_padding: f32,
+    /// How long a focus region stays active for smart framing (microseconds).
+    #[serde(default = "default_focus_hold")]
+    pub focus_hold_us: u64,
+    /// If framing both would zoom out further than this, frame the latest signal alone.
+    #[serde(default = "default_min_framing_zoom")]
+    pub min_framing_zoom: f32,
+    /// When several important focus regions are active at once the camera fits them
+    /// all, zooming out at most this factor from the newest region's own zoom.
+    /// 1.0 disables multi-region fitting.
+    #[serde(default = "default_max_fit_zoom_out")]
+    pub max_fit_zoom_out: f32,
+    /// Where the cursor sits inside a zoomed cursor-following viewport.
+    #[serde(default)]
+    pub composition: CompositionAnchor,
+    /// Zoom levels generated keyframes snap to after smoothing (e.g. `[1.0, 1.5, 2.0]`),
+    /// so the export doesn't shimmer through continuously varying scales. Empty disables.
+    #[serde(default)]
+    pub zoom_levels: Vec<f32>,
+    /// Only snap zooms within this distance of a level. `None` always snaps.
+    #[serde(default)]
+    pub zoom_snap_tolerance: Option<f32>,
+    /// Slow drift applied to zoomed-in viewports so long static zooms don't look
+    /// frozen, as a fraction of the visible size. 0 (the default) disables it.
+    #[serde(default)]
+    pub breathing_amplitude: f32,
+    /// Period of the breathing drift (microseconds).
+    #[serde(default = "default_breathing_period")]
+    pub breathing_period_us: u64,
+    /// Seed for the drift's phases; the same seed always gives the same drift.
+    #[serde(default)]
+    pub breathing_seed: u64,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        CameraSettings {
+            min_hold_time_us: default_min_hold_time(),
+            max_pan_speed: default_max_pan_speed(),
+            dead_zone: default_dead_zone(),
+            zoom_strength: default_zoom_strength(),
+            bounds: ViewportBounds::default(),
+            exclusion_zones: Vec::new(),
+            fast_motion_speed: default_fast_motion_speed(),
+            settle_speed: default_settle_speed(),
+            look_ahead_us: default_look_ahead(),
+            min_zoom_dwell_us: default_min_zoom_dwell(),
+            zoom_in_cooldown_us: default_zoom_in_cooldown(),
+            min_zoom_delta: default_min_zoom_delta(),
+            keyframe_epsilon: default_keyframe_epsilon(),
+            max_keyframes: None,
+            smart_framing: true,
+            framing_padding: default_framing_padding(),
+            focus_hold_us: default_focus_hold(),
+            min_framing_zoom: default_min_framing_zoom(),
+            max_fit_zoom_out: default_max_fit_zoom_out(),
+            composition: CompositionAnchor::default(),
+            zoom_levels: Vec::new(),
+            zoom_snap_tolerance: None,
+            breathing_amplitude: 0.0,
+            breathing_period_us: default_breathing_period(),
+            breathing_seed: 0,
+        }
+    }
+}
+
+/// Keeping the visible rect inside the frame when zoomed near an edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ViewportBounds {
+    /// Clip the visible rect at the frame edge and zoom in on what remains, keeping
+    /// the target as close to center as possible.
+    #[default]
+    Clip,
+    /// Keep the zoom and shift the center inward until the rect fits.
+    BiasCenter,
+}
+
+/// Placement of the zoom target within the viewport. Offsets are fractions of the
+/// visible width/height from the viewport center (-0.5..0.5).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CompositionAnchor {
+    /// Target dead center.
+    #[default]
+    Center,
+    /// Target on the rule-of-thirds intersection nearest its position on screen.
+    RuleOfThirds,
+    /// Target behind center along its direction of travel, leaving room ahead of it.
+    Lead { amount: f32 },
+    /// Target at a fixed offset from center.
+    Offset { x: f32, y: f32 },
 }
 
 fn default_min_hold_time() -> u64 {
@@ -152,8 +637,57 @@ fn default_zoom_strength() -> f32 {
     1.5
 }
 
+fn default_fast_motion_speed() -> f32 {
+    1.5
+}
+
+fn default_settle_speed() -> f32 {
+    0.3
+}
+
+fn default_look_ahead() -> u64 {
+    250_000 // 250ms
+}
+
+fn default_min_zoom_dwell() -> u64 {
+    750_000 // 750ms
+}
+
+fn default_zoom_in_cooldown() -> u64 {
+    1_500_000 // 1.5s
+}
+
+fn default_min_zoom_delta() -> f32 {
+    0.1
+}
+
+fn default_keyframe_epsilon() -> f32 {
+    0.005
+}
+
+fn default_framing_padding() -> f32 {
+    0.05
+}
+
+fn default_focus_hold() -> u64 {
+    2_000_000 // 2s
+}
+
+fn default_min_framing_zoom() -> f32 {
+    1.2
+}
+
+fn default_max_fit_zoom_out() -> f32 {
+    2.0
+}
+
+fn default_breathing_period() -> u64 {
+    8_000_000
+}
+
 /// Effect generation settings.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EffectSettings {
     /// Enable click ring effect.
     #[serde(default = "default_true")]
@@ -161,6 +695,269 @@ pub struct EffectSettings {
     /// Enable cursor highlight.
     #[serde(default = "default_true")]
     pub cursor_highlight: bool,
+    /// Spotlight the spot the presenter shook the cursor at.
+    #[serde(default)]
+    pub shake_spotlight: bool,
+    /// Show pressed keys and shortcuts as overlay chips.
+    #[serde(default)]
+    pub keystroke_overlay: bool,
+    /// How long a keystroke chip stays up after its last key (microseconds).
+    #[serde(default = "default_keystroke_duration")]
+    pub keystroke_duration_us: u64,
+    #[serde(default)]
+    pub keystroke_position: OverlayPosition,
+    /// Draw a fading trail behind the cursor.
+    #[serde(default)]
+    pub cursor_trail: bool,
+    /// How far back the trail reaches (microseconds).
+    #[serde(default = "default_trail_length")]
+    pub trail_length_us: u64,
+    /// Draw the path of each drag as a ribbon from press to release.
+    #[serde(default)]
+    pub drag_ribbons: bool,
+    /// Branding overlay; `None` draws none.
+    #[serde(default)]
+    pub watermark: Option<WatermarkSettings>,
+    /// Label clicks with the clicked element's accessible name ("Save", "Deploy").
+    #[serde(default)]
+    pub click_labels: bool,
+    /// How long a click label stays up (microseconds).
+    #[serde(default = "default_click_label_duration")]
+    pub click_label_duration_us: u64,
+    /// Thin bar showing how far into the export playback is; `None` draws none.
+    #[serde(default)]
+    pub progress_bar: Option<ProgressBarSettings>,
+    /// Color scheme for effects without an override below.
+    #[serde(default)]
+    pub theme: EffectTheme,
+    /// Accent color of the `Brand` theme.
+    #[serde(default = "default_brand_color")]
+    pub brand_color: String,
+    /// Appearance overrides; `None` uses the theme (`EffectType::themed_style`).
+    #[serde(default)]
+    pub click_ring_style: Option<EffectStyle>,
+    #[serde(default)]
+    pub cursor_highlight_style: Option<EffectStyle>,
+    #[serde(default)]
+    pub spotlight_style: Option<EffectStyle>,
+    #[serde(default)]
+    pub keystroke_style: Option<EffectStyle>,
+    #[serde(default)]
+    pub trail_style: Option<EffectStyle>,
+    #[serde(default)]
+    pub drag_ribbon_style: Option<EffectStyle>,
+    #[serde(default)]
+    pub progress_bar_style: Option<EffectStyle>,
+    #[serde(default)]
+    pub click_label_style: Option<EffectStyle>,
+    /// Click rings for right and middle clicks; `None` recolors the click ring.
+    #[serde(default)]
+    pub right_click_style: Option<EffectStyle>,
+    #[serde(default)]
+    pub middle_click_style: Option<EffectStyle>,
+}
+
+impl Default for EffectSettings {
+    fn default() -> Self {
+        EffectSettings {
+            click_rings: default_true(),
+            cursor_highlight: default_true(),
+            shake_spotlight: false,
+            keystroke_overlay: false,
+            keystroke_duration_us: default_keystroke_duration(),
+            keystroke_position: OverlayPosition::default(),
+            cursor_trail: false,
+            trail_length_us: default_trail_length(),
+            drag_ribbons: false,
+            watermark: None,
+            click_labels: false,
+            click_label_duration_us: default_click_label_duration(),
+            progress_bar: None,
+            theme: EffectTheme::default(),
+            brand_color: default_brand_color(),
+            click_ring_style: None,
+            cursor_highlight_style: None,
+            spotlight_style: None,
+            keystroke_style: None,
+            trail_style: None,
+            drag_ribbon_style: None,
+            progress_bar_style: None,
+            click_label_style: None,
+            right_click_style: None,
+            middle_click_style: None,
+        }
+    }
+}
+
+fn default_keystroke_duration() -> u64 {
+    1_500_000
+}
+
+fn default_trail_length() -> u64 {
+    250_000
+}
+
+fn default_click_label_duration() -> u64 {
+    1_200_000
+}
+
+fn default_brand_color() -> String {
+    "#0a84ff".to_string()
+}
+
+/// Named color scheme for built-in effects. Resolved to concrete colors and
+/// opacities when effects are generated, so effect tracks carry their final styles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum EffectTheme {
+    /// The built-in styles (`EffectType::default_style`).
+    #[default]
+    Standard,
+    /// Dark strokes and light chips, for light page content.
+    Light,
+    /// Light strokes and dark chips, for dark page content.
+    Dark,
+    /// Accents (rings, highlights, trails, ribbons, progress) in
+    /// `EffectSettings::brand_color`; chips and spotlights as built in.
+    Brand,
+}
+
+/// A logo or text watermark. Sizes are fractions of the output frame.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WatermarkSettings {
+    /// Image or text the renderer looks up; the engine doesn't interpret it.
+    pub asset_id: String,
+    /// Preferred spot; the watermark moves to another corner while content is under it.
+    #[serde(default = "default_watermark_anchor")]
+    pub anchor: OverlayPosition,
+    /// Gap between the watermark and the frame edge.
+    #[serde(default = "default_watermark_margin")]
+    pub margin: f32,
+    #[serde(default = "default_watermark_width")]
+    pub width: f32,
+    #[serde(default = "default_watermark_height")]
+    pub height: f32,
+    #[serde(default = "default_watermark_opacity")]
+    pub opacity: f32,
+}
+
+impl Default for WatermarkSettings {
+    fn default() -> Self {
+        WatermarkSettings {
+            asset_id: String::new(),
+            anchor: default_watermark_anchor(),
+            margin: default_watermark_margin(),
+            width: default_watermark_width(),
+            height: default_watermark_height(),
+            opacity: default_watermark_opacity(),
+        }
+    }
+}
+
+fn default_watermark_anchor() -> OverlayPosition {
+    OverlayPosition::BottomRight
+}
+
+fn default_watermark_margin() -> f32 {
+    0.03
+}
+
+fn default_watermark_width() -> f32 {
+    0.12
+}
+
+fn default_watermark_height() -> f32 {
+    0.05
+}
+
+fn default_watermark_opacity() -> f32 {
+    0.6
+}
+
+/// Playback progress bar along the top or bottom edge of the output.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ProgressBarSettings {
+    #[serde(default)]
+    pub edge: FrameEdge,
+    /// Bar height as a fraction of the output height.
+    #[serde(default = "default_progress_thickness")]
+    pub thickness: f32,
+}
+
+impl Default for ProgressBarSettings {
+    fn default() -> Self {
+        ProgressBarSettings {
+            edge: FrameEdge::default(),
+            thickness: default_progress_thickness(),
+        }
+    }
+}
+
+fn default_progress_thickness() -> f32 {
+    0.008
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum FrameEdge {
+    Top,
+    #[default]
+    Bottom,
+}
+
+/// Where overlay chips (e.g. keystrokes) sit in the frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum OverlayPosition {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomCenter,
+    BottomRight,
+}
+
+impl OverlayPosition {
+    /// Anchor point of a chip at this position: the center of its edge nearest the
+    /// frame edge, inset from the frame.
+    pub fn anchor(&self) -> NormalizedCoord {
+        let (x, y) = match self {
+            OverlayPosition::TopLeft => (0.1, 0.1),
+            OverlayPosition::TopCenter => (0.5, 0.1),
+            OverlayPosition::TopRight => (0.9, 0.1),
+            OverlayPosition::BottomLeft => (0.1, 0.9),
+            OverlayPosition::BottomCenter => (0.5, 0.9),
+            OverlayPosition::BottomRight => (0.9, 0.9),
+        };
+        NormalizedCoord::new(x, y)
+    }
+}
+
+/// How an effect looks. Sizes are fractions of the frame width.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EffectStyle {
+    /// CSS color string, e.g. "#ff8800".
+    pub color: String,
+    pub radius: f32,
+    /// Outline width; 0.0 draws a filled shape.
+    pub stroke_width: f32,
+    /// 0.0-1.0.
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+}
+
+impl Default for EffectStyle {
+    fn default() -> Self {
+        EffectType::ClickRing.default_style()
+    }
+}
+
+fn default_opacity() -> f32 {
+    1.0
 }
 
 fn default_true() -> bool {
@@ -168,39 +965,182 @@ fn default_true() -> bool {
 }
 
 /// Batch of input signals from JS (minimizes JS↔WASM crossings).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SignalBatch {
     pub events: Vec<InputEvent>,
 }
 
 /// Single input event from capture.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct InputEvent {
     pub timestamp: Timestamp,
     pub event_type: EventType,
 }
 
 /// Type of input event.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum EventType {
     /// Mouse move (Tab Mode).
     MouseMove { position: NormalizedCoord },
     /// Mouse click (Tab Mode).
     MouseClick {
+        position: NormalizedCoord,
+        /// DOM `MouseEvent.button`: 0 left, 1 middle, 2 right (see `MouseButton`).
+        button: u8,
+        /// What was clicked, when the content script could tell.
+        #[serde(default)]
+        element: Option<ElementInfo>,
+    },
+    /// Mouse button pressed (Tab Mode).
+    MouseDown {
+        position: NormalizedCoord,
+        button: u8,
+    },
+    /// Mouse button released (Tab Mode).
+    MouseUp {
         position: NormalizedCoord,
         button: u8,
     },
     /// Focused element changed (Tab Mode).
-    FocusChange { bounds: NormalizedRect },
+    FocusChange {
+        bounds: NormalizedRect,
+        /// What the element is, when the content script could tell.
+        #[serde(default)]
+        element: Option<ElementInfo>,
+    },
     /// Scroll event (Tab Mode).
     Scroll { delta_y: f32 },
     /// Frame captured (for Desktop Mode analysis).
     FrameCaptured { frame_index: FrameIndex },
+    /// Cursor found in a captured frame by video-based detection (confidence 0-100).
+    CursorDetected {
+        position: NormalizedCoord,
+        confidence: u8,
+    },
+    /// Key typed (Tab Mode). `key` is the DOM `KeyboardEvent.key` value.
+    KeyPress {
+        key: String,
+        #[serde(default)]
+        modifiers: KeyModifiers,
+    },
+    /// Key pressed down (Tab Mode).
+    KeyDown {
+        key: String,
+        #[serde(default)]
+        modifiers: KeyModifiers,
+    },
+    /// Key released (Tab Mode).
+    KeyUp {
+        key: String,
+        #[serde(default)]
+        modifiers: KeyModifiers,
+    },
+    /// Finger contact (Tab Mode). Each finger keeps its `pointer_id` from down to up.
+    Touch {
+        position: NormalizedCoord,
+        pointer_id: u32,
+        /// 0.0-1.0.
+        pressure: f32,
+        phase: PointerPhase,
+    },
+    /// Stylus input (Tab Mode). Moves with zero pressure are hovering.
+    Pen {
+        position: NormalizedCoord,
+        pointer_id: u32,
+        /// 0.0-1.0.
+        pressure: f32,
+        phase: PointerPhase,
+    },
+    /// Recording paused. The media has no gap, so signal time up to the next
+    /// `Resume` is collapsed away.
+    Pause,
+    /// Recording resumed after a `Pause`.
+    Resume,
+    /// Text selected (Tab Mode), sent once the selection is complete. Empty bounds
+    /// mean the selection was cleared.
+    TextSelection { bounds: NormalizedRect },
+    /// A `<video>` (or similar) element started or stopped playing (Tab Mode).
+    /// Motion inside a playing video is not a reason to zoom.
+    VideoPlaying {
+        bounds: NormalizedRect,
+        playing: bool,
+    },
+    /// The recorded window moved on screen by (`dx`, `dy`), in normalized capture units
+    /// (Screen Mode).
+    WindowMoved { dx: f32, dy: f32 },
+}
+
+/// Stage of a touch or pen contact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PointerPhase {
+    Down,
+    Move,
+    Up,
+}
+
+/// DOM metadata about a focused element.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ElementInfo {
+    /// Lowercase tag name, e.g. `input`.
+    pub tag: String,
+    /// ARIA role, explicit or implied.
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Accessible name (label, aria-label, or text).
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub is_password: bool,
+    /// The `autocomplete` attribute, e.g. `cc-number` or `shipping email`.
+    #[serde(default)]
+    pub autocomplete: Option<String>,
+}
+
+/// Mouse button of a click.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+    /// Back, forward, and other extra buttons.
+    Other,
+}
+
+impl MouseButton {
+    /// Button for a DOM `MouseEvent.button` code.
+    pub fn from_code(code: u8) -> MouseButton {
+        match code {
+            0 => MouseButton::Left,
+            1 => MouseButton::Middle,
+            2 => MouseButton::Right,
+            _ => MouseButton::Other,
+        }
+    }
+}
+
+/// Modifier keys held during a key event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct KeyModifiers {
+    #[serde(default)]
+    pub shift: bool,
+    #[serde(default)]
+    pub ctrl: bool,
+    #[serde(default)]
+    pub alt: bool,
+    #[serde(default)]
+    pub meta: bool,
 }
 
 /// Normalized rectangle (0-1 coordinates).
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct NormalizedRect {
     pub x: f32,
     pub y: f32,
@@ -221,41 +1161,338 @@ impl NormalizedRect {
     pub fn center(&self) -> NormalizedCoord {
         NormalizedCoord::new(self.x + self.width / 2.0, self.y + self.height / 2.0)
     }
+
+    /// Smallest rectangle containing both.
+    pub fn union(&self, other: &NormalizedRect) -> NormalizedRect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        NormalizedRect::new(
+            x,
+            y,
+            (self.x + self.width).max(other.x + other.width) - x,
+            (self.y + self.height).max(other.y + other.height) - y,
+        )
+    }
+
+    /// Intersection over union, 0.0 (disjoint) to 1.0 (identical).
+    pub fn overlap(&self, other: &NormalizedRect) -> f32 {
+        let width = (self.x + self.width).min(other.x + other.width) - self.x.max(other.x);
+        let height = (self.y + self.height).min(other.y + other.height) - self.y.max(other.y);
+        if width <= 0.0 || height <= 0.0 {
+            return 0.0;
+        }
+        let intersection = width * height;
+        intersection / (self.width * self.height + other.width * other.height - intersection)
+    }
+
+    /// The area both cover, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &NormalizedRect) -> Option<NormalizedRect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let width = (self.x + self.width).min(other.x + other.width) - x;
+        let height = (self.y + self.height).min(other.y + other.height) - y;
+        (width > 0.0 && height > 0.0).then(|| NormalizedRect::new(x, y, width, height))
+    }
+
+    /// Whether a point lies strictly inside the rectangle (edges are outside).
+    pub fn contains(&self, point: &NormalizedCoord) -> bool {
+        point.x > self.x
+            && point.x < self.x + self.width
+            && point.y > self.y
+            && point.y < self.y + self.height
+    }
 }
 
 /// A point on the cursor track with confidence.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CursorTrackPoint {
     pub timestamp: Timestamp,
     pub position: NormalizedCoord,
     pub state: CursorState,
     pub confidence: u8, // 0-100
     pub reason: InferenceReason,
+    /// Position after the tracker's smoothing filter (`CursorSettings::filter`).
+    /// `None` when filtering is off or the point isn't direct input.
+    #[serde(default)]
+    pub smoothed: Option<NormalizedCoord>,
+}
+
+/// Cursor velocity and acceleration at a track point, derived from positions.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CursorMotion {
+    pub timestamp: Timestamp,
+    /// Normalized units per second.
+    pub vx: f32,
+    pub vy: f32,
+    pub speed: f32,
+    /// Normalized units per second squared.
+    pub ax: f32,
+    pub ay: f32,
+}
+
+/// A press-move-release gesture with one mouse button.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DragSegment {
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub button: u8,
+    pub start_position: NormalizedCoord,
+    pub end_position: NormalizedCoord,
+    /// Bounding box of the whole path, including both ends.
+    pub bounds: NormalizedRect,
+}
+
+/// A two-finger pinch. `scale` is the final finger spread over the starting one:
+/// above 1 the presenter zoomed in, below 1 out.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PinchGesture {
+    pub start: Timestamp,
+    pub end: Timestamp,
+    /// Midpoint of the fingers when the pinch started.
+    pub center: NormalizedCoord,
+    /// Bounding box of the fingers when the pinch started.
+    pub bounds: NormalizedRect,
+    pub scale: f32,
+}
+
+/// Rapid back-and-forth cursor motion over a small area. `end` is when the shake was
+/// recognized; a shake that keeps going is not reported again.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ShakeGesture {
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub center: NormalizedCoord,
+    /// Area covered by the shake.
+    pub bounds: NormalizedRect,
+}
+
+/// Clicks close together in time and space, treated as one piece of work.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct InteractionSession {
+    /// First and last click.
+    pub start: Timestamp,
+    pub end: Timestamp,
+    /// Bounding box of the click positions.
+    pub bounds: NormalizedRect,
+    pub clicks: u32,
 }
 
 /// A detected focus region.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FocusRegion {
     pub timestamp: Timestamp,
     pub bounds: NormalizedRect,
     pub importance: f32, // 0.0-1.0
+    /// The focused element behind the region, for labels and auto-blur.
+    #[serde(default)]
+    pub element: Option<ElementInfo>,
+    /// Id of the `TrackedRegion` this sample belongs to, once tracked.
+    #[serde(default)]
+    pub track_id: Option<u32>,
+}
+
+/// The same focus region seen over a span of time (e.g. a dialog while it's open),
+/// built from the point-in-time regions that keep landing on it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackedRegion {
+    /// Stable for the life of the analysis; reset with the engine.
+    pub id: u32,
+    pub start: Timestamp,
+    /// Timestamp of the latest region in the track.
+    pub end: Timestamp,
+    /// Bounds of the latest region in the track.
+    pub bounds: NormalizedRect,
+    /// Highest importance seen in the track.
+    pub importance: f32,
+    /// Number of regions merged into the track.
+    pub samples: u32,
+}
+
+impl TrackedRegion {
+    pub fn duration_us(&self) -> u64 {
+        self.end.as_micros() - self.start.as_micros()
+    }
 }
 
 /// Camera keyframe for zoom/pan.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CameraKeyframe {
     pub timestamp: Timestamp,
     pub viewport: Viewport,
     pub easing: EasingType,
+    /// Placed by hand in the editor. Manual keyframes survive regeneration and are
+    /// never moved by smoothing.
+    #[serde(default)]
+    pub is_manual: bool,
+    /// Why the camera moved here (for "why did it zoom here?" tooltips and debugging).
+    #[serde(default)]
+    pub reason: KeyframeReason,
+    /// The camera stays perfectly still at this viewport for this long (microseconds)
+    /// before moving on to the next keyframe. 0 means it starts moving right away.
+    #[serde(default)]
+    pub hold_us: u64,
+}
+
+/// What produced a camera keyframe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum KeyframeReason {
+    /// Full view at the start of the recording.
+    Initial,
+    /// Following the cursor.
+    #[default]
+    Cursor,
+    /// Moving toward a click target (started early by the look-ahead).
+    ClickTarget,
+    /// Zooming to an important focus region.
+    FocusRegion,
+    /// Fitting several simultaneously active focus regions.
+    MultiRegionFit,
+    /// Framing the cursor and the active focus region together.
+    CursorAndFocus,
+    /// Pulled back to full view because the cursor moved fast.
+    VelocityZoomOut,
+    /// Placed by hand in the editor.
+    Manual,
+    /// Target moved by the max pan speed limit.
+    Smoothing,
+    /// Back to full view at a scene change.
+    SceneChange,
 }
 
 /// Viewport definition (what the camera shows).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Viewport {
     pub center: NormalizedCoord,
     pub zoom: f32, // 1.0 = no zoom, 2.0 = 2x zoom
 }
 
+impl Viewport {
+    /// The part of the frame this viewport shows.
+    pub fn visible_rect(&self) -> NormalizedRect {
+        let size = 1.0 / self.zoom;
+        NormalizedRect::new(
+            self.center.x - size / 2.0,
+            self.center.y - size / 2.0,
+            size,
+            size,
+        )
+    }
+
+    /// This viewport adjusted so its visible rect lies within [0,1] on both axes.
+    /// Zoom is never below 1.0 afterwards.
+    pub fn clamped_to_frame(&self, bounds: ViewportBounds) -> Viewport {
+        let zoom = if self.zoom.is_finite() {
+            self.zoom.max(1.0)
+        } else {
+            1.0
+        };
+        let half = 0.5 / zoom;
+        match bounds {
+            ViewportBounds::BiasCenter => Viewport {
+                center: NormalizedCoord::new(
+                    self.center.x.min(1.0 - half).max(half),
+                    self.center.y.min(1.0 - half).max(half),
+                ),
+                zoom,
+            },
+            ViewportBounds::Clip => {
+                let inside = |c: f32| c - half >= 0.0 && c + half <= 1.0;
+                if inside(self.center.x) && inside(self.center.y) {
+                    return Viewport {
+                        center: self.center,
+                        zoom,
+                    };
+                }
+                // Intersect with the frame, then take the largest square (the frame's
+                // aspect) that fits in the intersection.
+                let clip = |c: f32| ((c - half).max(0.0), (c + half).min(1.0));
+                let (x0, x1) = clip(self.center.x);
+                let (y0, y1) = clip(self.center.y);
+                let size = (x1 - x0).min(y1 - y0).max(f32::EPSILON);
+                let half = size / 2.0;
+                let fit = |c: f32, lo: f32, hi: f32| c.min(hi - half).max(lo + half);
+                Viewport {
+                    center: NormalizedCoord::new(
+                        fit(self.center.x, x0, x1),
+                        fit(self.center.y, y0, y1),
+                    ),
+                    zoom: 1.0 / size,
+                }
+            }
+        }
+    }
+
+    /// Column-major 3x3 affine matrix mapping output UVs (0-1) to source UVs, ready for
+    /// `uniformMatrix3fv`. Viewports have no rotation, so it is scale plus translation.
+    pub fn uv_matrix(&self) -> [f32; 9] {
+        let scale = 1.0 / self.zoom;
+        let tx = self.center.x - scale / 2.0;
+        let ty = self.center.y - scale / 2.0;
+        [scale, 0.0, 0.0, 0.0, scale, 0.0, tx, ty, 1.0]
+    }
+
+    /// Source-pixel crop for this viewport, for scaling to an `out_w`x`out_h` output.
+    /// The crop is the largest rect of the output's aspect inside the visible rect,
+    /// with even size and offset (required by 4:2:0 encoders), kept inside the source.
+    /// `None` if any dimension is too small for a 2x2 crop.
+    pub fn crop_rect(
+        &self,
+        source_w: u32,
+        source_h: u32,
+        out_w: u32,
+        out_h: u32,
+    ) -> Option<CropRect> {
+        if source_w < 2 || source_h < 2 || out_w == 0 || out_h == 0 {
+            return None;
+        }
+        let even = |v: f64| (v.max(0.0) / 2.0).floor() as u32 * 2;
+        let viewport = self.clamped_to_frame(ViewportBounds::BiasCenter);
+        let aspect = out_w as f64 / out_h as f64;
+        let visible_w = source_w as f64 / viewport.zoom as f64;
+        let visible_h = source_h as f64 / viewport.zoom as f64;
+
+        // Fix the limiting side, then derive the other from it so rounding to even
+        // can't push the aspect the wrong way.
+        let (width, height) = if visible_w / visible_h > aspect {
+            let height = even(visible_h).max(2);
+            (even(height as f64 * aspect).max(2), height)
+        } else {
+            let width = even(visible_w).max(2);
+            (width, even(width as f64 / aspect).max(2))
+        };
+        let width = width.min(source_w & !1);
+        let height = height.min(source_h & !1);
+
+        let offset = |center: f32, size: u32, limit: u32| {
+            even(center as f64 * limit as f64 - size as f64 / 2.0).min((limit - size) & !1)
+        };
+        Some(CropRect {
+            x: offset(viewport.center.x, width, source_w),
+            y: offset(viewport.center.y, height, source_h),
+            width,
+            height,
+        })
+    }
+}
+
+/// A crop in source pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CropRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
 impl Default for Viewport {
     fn default() -> Self {
         Viewport {
@@ -266,43 +1503,459 @@ impl Default for Viewport {
 }
 
 /// Easing function for camera transitions.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EasingType {
     Linear,
     EaseOut,
     EaseInOut,
     Spring,
+    /// CSS-style `cubic-bezier(x1, y1, x2, y2)`; x1 and x2 are clamped to 0..=1.
+    /// JSON: `{"CubicBezier": [0.25, 0.1, 0.25, 1.0]}`.
+    CubicBezier(f32, f32, f32, f32),
 }
 
 /// Effect track (click rings, highlights).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EffectTrack {
     pub effects: Vec<Effect>,
 }
 
-/// Single effect instance.
+impl EffectTrack {
+    /// Effects active at a timestamp (start inclusive, end exclusive), with progress and
+    /// animated state.
+    pub fn active_at(&self, timestamp: Timestamp) -> Vec<ActiveEffect> {
+        self.effects
+            .iter()
+            .filter_map(|effect| {
+                let state = effect.state_at(timestamp)?;
+                Some(ActiveEffect {
+                    effect: effect.clone(),
+                    progress: state.progress,
+                    opacity: state.opacity,
+                    scale: state.scale,
+                })
+            })
+            .collect()
+    }
+}
+
+/// An effect that is active at a query time.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveEffect {
+    pub effect: Effect,
+    /// Normalized progress through the effect (0.0 at start, approaching 1.0 at end).
+    pub progress: f32,
+    /// Animated opacity and scale at the query time (see `Effect::state_at`).
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+/// Everything the renderer needs for one frame, from a single WASM call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderState {
+    pub timestamp: Timestamp,
+    pub viewport: Viewport,
+    pub active_effects: Vec<ActiveEffect>,
+}
+
+/// Single effect instance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Effect {
     pub timestamp: Timestamp,
     pub duration_us: u64,
     pub effect_type: EffectType,
     pub position: NormalizedCoord,
+    /// Resolved appearance, so the renderer doesn't need any defaults of its own.
+    #[serde(default)]
+    pub style: EffectStyle,
+    /// How opacity and scale animate; see `Effect::state_at`.
+    #[serde(default)]
+    pub envelope: EffectEnvelope,
+}
+
+impl Effect {
+    /// Animated state at `timestamp` (start inclusive, end exclusive), or `None` when
+    /// the effect isn't showing.
+    pub fn state_at(&self, timestamp: Timestamp) -> Option<EffectState> {
+        let t = timestamp.as_micros();
+        let start = self.timestamp.as_micros();
+        if t < start || t >= start + self.duration_us {
+            return None;
+        }
+        let elapsed = t - start;
+        let at = match self.envelope.period_us {
+            Some(period) if period > 0 => (elapsed % period) as f32 / period as f32,
+            _ => elapsed as f32 / self.duration_us as f32,
+        };
+        Some(EffectState {
+            progress: elapsed as f32 / self.duration_us as f32,
+            opacity: self.style.opacity * sample_envelope(&self.envelope.opacity, at),
+            scale: sample_envelope(&self.envelope.scale, at),
+        })
+    }
+}
+
+/// One key of an envelope: `value` at `at`, a fraction (0.0-1.0) of the effect's
+/// duration, or of one period for repeating envelopes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EnvelopeKey {
+    pub at: f32,
+    pub value: f32,
+}
+
+/// Opacity and scale over an effect's lifetime, as keys interpolated linearly and held
+/// past the ends. Opacity multiplies `EffectStyle::opacity`; scale multiplies the
+/// effect's size. An empty key list holds 1.0.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EffectEnvelope {
+    #[serde(default)]
+    pub opacity: Vec<EnvelopeKey>,
+    #[serde(default)]
+    pub scale: Vec<EnvelopeKey>,
+    /// Repeat the keys every `period_us` (e.g. a pulse) instead of spreading them over
+    /// the whole effect.
+    #[serde(default)]
+    pub period_us: Option<u64>,
+}
+
+impl EffectEnvelope {
+    fn keys(keys: &[(f32, f32)]) -> Vec<EnvelopeKey> {
+        keys.iter()
+            .map(|&(at, value)| EnvelopeKey { at, value })
+            .collect()
+    }
+}
+
+fn sample_envelope(keys: &[EnvelopeKey], at: f32) -> f32 {
+    let Some(first) = keys.first() else {
+        return 1.0;
+    };
+    if at <= first.at {
+        return first.value;
+    }
+    for pair in keys.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if at <= b.at {
+            let span = b.at - a.at;
+            if span <= 0.0 {
+                return b.value;
+            }
+            return a.value + (b.value - a.value) * (at - a.at) / span;
+        }
+    }
+    keys[keys.len() - 1].value
+}
+
+/// An effect's animated state at one moment.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EffectState {
+    /// 0.0 at the start, approaching 1.0 at the end.
+    pub progress: f32,
+    /// Final opacity, style and envelope combined.
+    pub opacity: f32,
+    pub scale: f32,
 }
 
 /// Type of visual effect.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EffectType {
     ClickRing,
     CursorHighlight,
+    /// Dims everything outside `style.radius` around the effect's position.
+    Spotlight,
+    /// Keys typed in quick succession, as shown on the chip (e.g. "hello", "⌘S").
+    Keystrokes {
+        text: String,
+    },
+    /// Recent cursor path for one frame, oldest first.
+    Trail {
+        points: Vec<TrailPoint>,
+    },
+    /// Path of a drag; the ribbon grows along it over the effect's duration.
+    DragRibbon {
+        points: Vec<RibbonPoint>,
+    },
+    /// An extension-defined effect (e.g. confetti, arrow callouts). The engine only
+    /// schedules and retimes it; `params_json` is passed through to the renderer.
+    Custom {
+        name: String,
+        params_json: String,
+    },
+    /// Branding overlay. Unlike other effects, `position` (its center) and the size are
+    /// in output-frame coordinates, above the camera.
+    Watermark {
+        asset_id: String,
+        width: f32,
+        height: f32,
+    },
+    /// Bar filled to the effect's progress. Projected onto the export timeline it spans
+    /// the whole export, so progress is export progress. Drawn in output-frame
+    /// coordinates like the watermark.
+    ProgressBar {
+        edge: FrameEdge,
+        thickness: f32,
+    },
+    /// Accessible name of the clicked element, shown beside the click.
+    ClickLabel {
+        text: String,
+    },
+}
+
+/// One instance of a custom effect, as registered from the extension.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomEffectSpec {
+    pub timestamp: Timestamp,
+    pub duration_us: u64,
+    pub position: NormalizedCoord,
+    #[serde(default)]
+    pub params_json: String,
+    /// `None` uses `EffectType::default_style`.
+    #[serde(default)]
+    pub style: Option<EffectStyle>,
+    #[serde(default)]
+    pub envelope: EffectEnvelope,
+}
+
+/// One vertex of a drag ribbon, reached `offset_us` after the press.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RibbonPoint {
+    pub offset_us: u64,
+    pub position: NormalizedCoord,
+}
+
+/// One point of a cursor trail. `weight` falls from 1.0 at the cursor to 0.0 at the
+/// trail's end; renderers scale opacity and width by it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TrailPoint {
+    pub position: NormalizedCoord,
+    pub weight: f32,
+}
+
+impl EffectType {
+    /// Built-in animation: rings expand and fade, highlights pulse, spotlights and
+    /// keystroke chips fade in and out. Trails, ribbons, and custom effects hold still.
+    pub fn default_envelope(&self) -> EffectEnvelope {
+        match self {
+            EffectType::ClickRing => EffectEnvelope {
+                opacity: EffectEnvelope::keys(&[(0.0, 1.0), (1.0, 0.0)]),
+                scale: EffectEnvelope::keys(&[(0.0, 0.3), (1.0, 1.0)]),
+                period_us: None,
+            },
+            EffectType::CursorHighlight => EffectEnvelope {
+                opacity: EffectEnvelope::keys(&[(0.0, 0.8), (0.5, 1.0), (1.0, 0.8)]),
+                scale: EffectEnvelope::keys(&[(0.0, 1.0), (0.5, 1.1), (1.0, 1.0)]),
+                period_us: Some(1_000_000),
+            },
+            EffectType::Spotlight
+            | EffectType::Keystrokes { .. }
+            | EffectType::ClickLabel { .. } => EffectEnvelope {
+                opacity: EffectEnvelope::keys(&[(0.0, 0.0), (0.1, 1.0), (0.9, 1.0), (1.0, 0.0)]),
+                scale: Vec::new(),
+                period_us: None,
+            },
+            EffectType::Trail { .. }
+            | EffectType::DragRibbon { .. }
+            | EffectType::Custom { .. }
+            | EffectType::Watermark { .. }
+            | EffectType::ProgressBar { .. } => EffectEnvelope::default(),
+        }
+    }
+
+    /// Appearance under `theme`: the built-in style with the theme's color and
+    /// opacity. Custom effects and watermarks are not themed.
+    pub fn themed_style(&self, theme: EffectTheme, brand_color: &str) -> EffectStyle {
+        let style = self.default_style();
+        let themed = match (theme, self) {
+            (EffectTheme::Standard, _)
+            | (_, EffectType::Custom { .. } | EffectType::Watermark { .. }) => None,
+            (EffectTheme::Light, _) => Some(match self {
+                EffectType::CursorHighlight | EffectType::DragRibbon { .. } => ("#0a84ff", 0.25),
+                EffectType::Spotlight => ("#000000", 0.4),
+                EffectType::Keystrokes { .. } | EffectType::ClickLabel { .. } => ("#f2f2f7", 0.95),
+                _ => ("#1c1c1e", 0.8),
+            }),
+            (EffectTheme::Dark, _) => Some(match self {
+                EffectType::CursorHighlight => ("#ffd60a", 0.3),
+                EffectType::DragRibbon { .. } => ("#64d2ff", 0.7),
+                EffectType::Spotlight => ("#000000", 0.7),
+                EffectType::Keystrokes { .. } | EffectType::ClickLabel { .. } => ("#2c2c2e", 0.9),
+                _ => ("#ffffff", 0.9),
+            }),
+            (EffectTheme::Brand, _) => match self {
+                EffectType::Spotlight
+                | EffectType::Keystrokes { .. }
+                | EffectType::ClickLabel { .. } => None,
+                _ => Some((brand_color, style.opacity)),
+            },
+        };
+        match themed {
+            Some((color, opacity)) => EffectStyle {
+                color: color.to_string(),
+                opacity,
+                ..style
+            },
+            None => style,
+        }
+    }
+
+    /// Built-in appearance, used when `EffectSettings` has no override or theme.
+    pub fn default_style(&self) -> EffectStyle {
+        let (color, radius, stroke_width, opacity) = match self {
+            EffectType::ClickRing => ("#ffffff", 0.03, 0.004, 0.9),
+            EffectType::CursorHighlight => ("#ffd60a", 0.04, 0.0, 0.35),
+            EffectType::Spotlight => ("#000000", 0.15, 0.0, 0.6),
+            // Chip background; `radius` is the corner radius.
+            EffectType::Keystrokes { .. } => ("#1c1c1e", 0.008, 0.0, 0.85),
+            // `stroke_width` is the width at the cursor end.
+            EffectType::Trail { .. } => ("#ffffff", 0.0, 0.006, 0.5),
+            EffectType::DragRibbon { .. } => ("#0a84ff", 0.0, 0.008, 0.7),
+            EffectType::Custom { .. } => ("#ffffff", 0.05, 0.0, 1.0),
+            EffectType::Watermark { .. } => ("#ffffff", 0.0, 0.0, 0.6),
+            EffectType::ProgressBar { .. } => ("#0a84ff", 0.0, 0.0, 0.9),
+            // Label background; `radius` is the corner radius.
+            EffectType::ClickLabel { .. } => ("#1c1c1e", 0.006, 0.0, 0.85),
+        };
+        EffectStyle {
+            color: color.to_string(),
+            radius,
+            stroke_width,
+            opacity,
+        }
+    }
+}
+
+/// What signal sanitization had to fix in a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum SignalWarningKind {
+    /// Events arrived out of timestamp order and were sorted.
+    OutOfOrder,
+    /// Identical events (same timestamp and payload) were dropped.
+    Duplicate,
+    /// Events older than an already-processed chunk were dropped.
+    Late,
+    /// Events recorded while paused were dropped.
+    DuringPause,
+    /// Events from overlapping takes were dropped when merging.
+    TakeOverlap,
+}
+
+/// One kind of fix applied to a batch, with how many events it touched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SignalWarning {
+    pub kind: SignalWarningKind,
+    pub count: u32,
+    /// Human-readable description for display.
+    pub message: String,
+}
+
+/// A user-drawn area to blur, e.g. over an email address or token. `bounds` is where
+/// the area sits at `start` (or at the start of the recording); from there it moves
+/// with the window and, if `follow_scroll`, with the page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlurRegion {
+    pub id: u32,
+    pub bounds: NormalizedRect,
+    /// Blur from here on; `None` blurs from the start.
+    #[serde(default)]
+    pub start: Option<Timestamp>,
+    /// Stop blurring here; `None` blurs to the end.
+    #[serde(default)]
+    pub end: Option<Timestamp>,
+    /// Page content moves with scrolling; fixed UI (toolbars, overlays) doesn't.
+    #[serde(default = "default_true")]
+    pub follow_scroll: bool,
+}
+
+/// One blurred rectangle, in capture space.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BlurRect {
+    /// Id of the `BlurRegion` it comes from.
+    pub id: u32,
+    pub bounds: NormalizedRect,
+}
+
+/// The rectangles to blur from `timestamp` until the next sample.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BlurSample {
+    pub timestamp: Timestamp,
+    pub rects: Vec<BlurRect>,
+}
+
+/// Sound to mix in under an effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum AudioCueKind {
+    Click,
+    /// Second click of a double click.
+    DoubleClick,
+    /// Button release at the end of a drag.
+    DragEnd,
+}
+
+/// A sound cue aligned with the visual effects, for the exporter's audio mix.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AudioCue {
+    pub timestamp: Timestamp,
+    pub kind: AudioCueKind,
+    /// Playback volume, 0.0-1.0.
+    pub intensity: f32,
 }
 
 /// Complete analysis result returned to JS.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, Tsify)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct AnalysisResult {
     pub cursor_track: Vec<CursorTrackPoint>,
     pub focus_regions: Vec<FocusRegion>,
     pub camera_keyframes: Vec<CameraKeyframe>,
     pub effect_tracks: EffectTrack,
+    #[serde(default)]
+    pub drags: Vec<DragSegment>,
+    #[serde(default)]
+    pub pinches: Vec<PinchGesture>,
+    #[serde(default)]
+    pub shakes: Vec<ShakeGesture>,
+    #[serde(default)]
+    pub sessions: Vec<InteractionSession>,
+    /// Problems found in the input signals and how they were fixed.
+    #[serde(default)]
+    pub warnings: Vec<SignalWarning>,
+    #[serde(default)]
+    pub audio_cues: Vec<AudioCue>,
+    /// Privacy blur over time (see `Engine::set_blur_regions`). It covers the whole
+    /// recording, so chunk deltas leave it empty.
+    #[serde(default)]
+    pub blur_track: Vec<BlurSample>,
+}
+
+/// Incremental result for one chunk of signals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisChunk {
+    /// Only the points, regions, keyframes, and effects produced by this chunk.
+    pub delta: AnalysisResult,
+    /// Total events ingested across all chunks so far (for progress reporting).
+    pub events_processed: u64,
+    /// Timestamp of the latest event ingested so far.
+    pub processed_until: Timestamp,
 }
 
 #[cfg(test)]
@@ -316,6 +1969,162 @@ mod tests {
         assert!((ts.as_secs() - 1.5).abs() < 0.0001);
     }
 
+    #[test]
+    fn effect_track_active_at_reports_progress() {
+        let track = EffectTrack {
+            effects: vec![Effect {
+                timestamp: Timestamp::from_micros(1_000_000),
+                duration_us: 400_000,
+                effect_type: EffectType::ClickRing,
+                position: NormalizedCoord::center(),
+                style: EffectStyle::default(),
+                envelope: EffectEnvelope::default(),
+            }],
+        };
+
+        assert!(track.active_at(Timestamp::from_micros(999_999)).is_empty());
+        let active = track.active_at(Timestamp::from_micros(1_100_000));
+        assert_eq!(active.len(), 1);
+        assert!((active[0].progress - 0.25).abs() < 0.0001);
+        assert!(track
+            .active_at(Timestamp::from_micros(1_400_000))
+            .is_empty());
+    }
+
+    #[test]
+    fn envelopes_animate_effects() {
+        let effect = |effect_type: EffectType, duration_us: u64| Effect {
+            timestamp: Timestamp::from_micros(0),
+            duration_us,
+            envelope: effect_type.default_envelope(),
+            style: effect_type.default_style(),
+            effect_type,
+            position: NormalizedCoord::center(),
+        };
+
+        let ring = effect(EffectType::ClickRing, 400_000);
+        let state = ring.state_at(Timestamp::from_micros(200_000)).unwrap();
+        assert!((state.scale - 0.65).abs() < 1e-4);
+        assert!((state.opacity - 0.9 * 0.5).abs() < 1e-4);
+        assert!(ring.state_at(Timestamp::from_micros(400_000)).is_none());
+
+        // Highlights pulse once a second however long the span is.
+        let highlight = effect(EffectType::CursorHighlight, 3_000_000);
+        let peak = highlight
+            .state_at(Timestamp::from_micros(2_500_000))
+            .unwrap();
+        assert!((peak.scale - 1.1).abs() < 1e-4);
+        let trough = highlight
+            .state_at(Timestamp::from_micros(2_000_000))
+            .unwrap();
+        assert!((trough.scale - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn frame_conversions_round_trip_at_ntsc_rates() {
+        let fps = 30_000.0 / 1001.0;
+        for frame in [0, 1, 29, 30, 1799, 107_892] {
+            let ts = Timestamp::from_frame(frame, fps);
+            assert_eq!(ts.frame_at(fps), frame);
+            assert_eq!(
+                Timestamp::from_micros(ts.as_micros() + 1).frame_at(fps),
+                frame
+            );
+        }
+        assert_eq!(Timestamp::from_frame(1, 30.0).as_micros(), 33_333);
+    }
+
+    #[test]
+    fn viewport_clamps_to_frame() {
+        let edge = Viewport {
+            center: NormalizedCoord::new(0.95, 0.5),
+            zoom: 2.0,
+        };
+
+        let biased = edge.clamped_to_frame(ViewportBounds::BiasCenter);
+        assert_eq!(biased.zoom, 2.0);
+        assert!((biased.center.x - 0.75).abs() < 1e-6);
+
+        // Clipping keeps [0.7, 1.0] horizontally, so the zoom rises to fit it.
+        let clipped = edge.clamped_to_frame(ViewportBounds::Clip);
+        assert!((clipped.zoom - 1.0 / 0.3).abs() < 1e-4);
+        assert!((clipped.center.x - 0.85).abs() < 1e-6);
+
+        for viewport in [biased, clipped] {
+            let rect = viewport.visible_rect();
+            assert!(rect.x >= -1e-6 && rect.x + rect.width <= 1.0 + 1e-6);
+            assert!(rect.y >= -1e-6 && rect.y + rect.height <= 1.0 + 1e-6);
+        }
+
+        let centered = Viewport::default().clamped_to_frame(ViewportBounds::Clip);
+        assert_eq!(centered.zoom, 1.0);
+    }
+
+    #[test]
+    fn uv_matrix_maps_output_to_visible_rect() {
+        let viewport = Viewport {
+            center: NormalizedCoord::new(0.6, 0.4),
+            zoom: 2.0,
+        };
+        let m = viewport.uv_matrix();
+        let apply = |u: f32, v: f32| (m[0] * u + m[3] * v + m[6], m[1] * u + m[4] * v + m[7]);
+        let rect = viewport.visible_rect();
+        assert_eq!(apply(0.0, 0.0), (rect.x, rect.y));
+        assert_eq!(apply(1.0, 1.0), (rect.x + rect.width, rect.y + rect.height));
+    }
+
+    #[test]
+    fn crop_rect_is_even_and_keeps_aspect() {
+        let viewport = Viewport {
+            center: NormalizedCoord::new(0.25, 0.5),
+            zoom: 2.0,
+        };
+        assert_eq!(
+            viewport.crop_rect(1920, 1080, 1920, 1080),
+            Some(CropRect {
+                x: 0,
+                y: 270,
+                width: 960,
+                height: 540,
+            })
+        );
+
+        // Portrait output from an odd-sized landscape source.
+        let viewport = Viewport {
+            center: NormalizedCoord::center(),
+            zoom: 3.0,
+        };
+        let crop = viewport.crop_rect(1366, 768, 1080, 1920).unwrap();
+        assert_eq!(
+            crop,
+            CropRect {
+                x: 610,
+                y: 256,
+                width: 144,
+                height: 256
+            }
+        );
+        for value in [crop.x, crop.y, crop.width, crop.height] {
+            assert_eq!(value % 2, 0);
+        }
+        assert!(crop.x + crop.width <= 1366 && crop.y + crop.height <= 768);
+
+        assert_eq!(viewport.crop_rect(1920, 1080, 0, 1080), None);
+    }
+
+    #[test]
+    fn typescript_declarations_follow_serde() {
+        assert!(EngineConfig::DECL.contains("export interface EngineConfig {"));
+        assert!(EngineConfig::DECL.contains("    capture_mode: CaptureMode;\n"));
+        // Fields with serde defaults may be left out.
+        assert!(EngineConfig::DECL.contains("    privacy?: PrivacySettings;\n"));
+        assert_eq!(
+            Timestamp::DECL.lines().last(),
+            Some("export type Timestamp = number;")
+        );
+        assert!(EventType::DECL.contains("{ type: \"Scroll\"; delta_y: number }"));
+    }
+
     #[test]
     fn normalized_coord_clamps() {
         let coord = NormalizedCoord::new(1.5, -0.5);
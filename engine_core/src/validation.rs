@@ -0,0 +1,317 @@
+// Validation for TimeRemapper configs.
+// The constructor silently sorts, clamps, and tolerates overlaps; this reports what it tolerated
+// so the UI can show actionable errors instead of surprising export durations.
+// See design.md: TimeRemapper (Rust)
+
+use serde::{Deserialize, Serialize};
+
+use crate::time_remap::{TimeRangeConfig, TimeRemapperConfig};
+
+/// How serious a validation issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IssueSeverity {
+    /// The config is accepted but something was adjusted or ignored.
+    Warning,
+    /// The config is ambiguous or contradictory; strict construction rejects it.
+    Error,
+}
+
+/// What kind of problem a validation issue describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IssueKind {
+    /// `out_point_us` is before `in_point_us`.
+    OutBeforeIn,
+    /// A cut has `end_us <= start_us`.
+    EmptyCut,
+    /// A speed ramp has `end_us <= start_us`.
+    EmptySpeedRamp,
+    /// Two cuts overlap.
+    OverlappingCuts,
+    /// Two speed ramps overlap (only the earlier one applies in the overlap).
+    OverlappingSpeedRamps,
+    /// A speed ramp overlaps a cut (the overlapping part is never played).
+    SpeedRampOverlapsCut,
+    /// A cut lies entirely outside the trim range.
+    CutOutsideTrim,
+    /// A speed ramp lies entirely outside the trim range.
+    SpeedRampOutsideTrim,
+    /// A speed is outside the allowed range and will be clamped.
+    SpeedOutOfRange,
+    /// `min_speed` is not positive or is above `max_speed`.
+    InvalidSpeedRange,
+}
+
+/// One problem found in a TimeRemapper config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub kind: IssueKind,
+    pub severity: IssueSeverity,
+    /// Indices into `cuts` and/or `speed_ramps` as supplied, in the order named by `kind`
+    /// (e.g. `[ramp_index, cut_index]` for `SpeedRampOverlapsCut`).
+    pub indices: Vec<usize>,
+    /// The offending ranges, aligned with `indices`.
+    pub ranges: Vec<TimeRangeConfig>,
+    /// Human-readable description for display.
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn new(
+        kind: IssueKind,
+        severity: IssueSeverity,
+        indices: Vec<usize>,
+        ranges: Vec<TimeRangeConfig>,
+        message: String,
+    ) -> Self {
+        ValidationIssue {
+            kind,
+            severity,
+            indices,
+            ranges,
+            message,
+        }
+    }
+}
+
+/// Check a TimeRemapper config for overlaps, empty or out-of-range segments,
+/// and inverted trim points.
+pub fn validate_remapper_config(config: &TimeRemapperConfig) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let trim = TimeRangeConfig {
+        start_us: config.in_point_us,
+        end_us: config.out_point_us,
+    };
+
+    if config.out_point_us < config.in_point_us {
+        issues.push(ValidationIssue::new(
+            IssueKind::OutBeforeIn,
+            IssueSeverity::Error,
+            vec![],
+            vec![trim.clone()],
+            format!(
+                "Out point {} is before in point {}",
+                config.out_point_us, config.in_point_us
+            ),
+        ));
+    }
+
+    if !(config.min_speed > 0.0 && config.min_speed <= config.max_speed) {
+        issues.push(ValidationIssue::new(
+            IssueKind::InvalidSpeedRange,
+            IssueSeverity::Error,
+            vec![],
+            vec![],
+            format!(
+                "Speed range {}-{} is invalid",
+                config.min_speed, config.max_speed
+            ),
+        ));
+    }
+
+    let cuts = &config.cuts;
+    let ramps: Vec<TimeRangeConfig> = config
+        .speed_ramps
+        .iter()
+        .map(|r| TimeRangeConfig {
+            start_us: r.start_us,
+            end_us: r.end_us,
+        })
+        .collect();
+
+    for (i, cut) in cuts.iter().enumerate() {
+        if cut.end_us <= cut.start_us {
+            issues.push(ValidationIssue::new(
+                IssueKind::EmptyCut,
+                IssueSeverity::Error,
+                vec![i],
+                vec![cut.clone()],
+                format!("Cut {} has no duration", i),
+            ));
+        } else if !overlaps(cut, &trim) {
+            issues.push(ValidationIssue::new(
+                IssueKind::CutOutsideTrim,
+                IssueSeverity::Warning,
+                vec![i],
+                vec![cut.clone()],
+                format!("Cut {} is outside the trimmed range", i),
+            ));
+        }
+    }
+
+    for (i, (range, ramp)) in ramps.iter().zip(&config.speed_ramps).enumerate() {
+        if range.end_us <= range.start_us {
+            issues.push(ValidationIssue::new(
+                IssueKind::EmptySpeedRamp,
+                IssueSeverity::Error,
+                vec![i],
+                vec![range.clone()],
+                format!("Speed ramp {} has no duration", i),
+            ));
+        } else if !overlaps(range, &trim) {
+            issues.push(ValidationIssue::new(
+                IssueKind::SpeedRampOutsideTrim,
+                IssueSeverity::Warning,
+                vec![i],
+                vec![range.clone()],
+                format!("Speed ramp {} is outside the trimmed range", i),
+            ));
+        }
+
+        if !(config.min_speed..=config.max_speed).contains(&ramp.speed) {
+            issues.push(ValidationIssue::new(
+                IssueKind::SpeedOutOfRange,
+                IssueSeverity::Warning,
+                vec![i],
+                vec![range.clone()],
+                format!(
+                    "Speed ramp {} has speed {} outside {}-{} and will be clamped",
+                    i, ramp.speed, config.min_speed, config.max_speed
+                ),
+            ));
+        }
+    }
+
+    for (i, a) in cuts.iter().enumerate() {
+        for (j, b) in cuts.iter().enumerate().skip(i + 1) {
+            if overlaps(a, b) {
+                issues.push(ValidationIssue::new(
+                    IssueKind::OverlappingCuts,
+                    IssueSeverity::Error,
+                    vec![i, j],
+                    vec![a.clone(), b.clone()],
+                    format!("Cuts {} and {} overlap", i, j),
+                ));
+            }
+        }
+    }
+
+    for (i, a) in ramps.iter().enumerate() {
+        for (j, b) in ramps.iter().enumerate().skip(i + 1) {
+            if overlaps(a, b) {
+                issues.push(ValidationIssue::new(
+                    IssueKind::OverlappingSpeedRamps,
+                    IssueSeverity::Error,
+                    vec![i, j],
+                    vec![a.clone(), b.clone()],
+                    format!("Speed ramps {} and {} overlap", i, j),
+                ));
+            }
+        }
+
+        for (j, cut) in cuts.iter().enumerate() {
+            if overlaps(a, cut) {
+                issues.push(ValidationIssue::new(
+                    IssueKind::SpeedRampOverlapsCut,
+                    IssueSeverity::Warning,
+                    vec![i, j],
+                    vec![a.clone(), cut.clone()],
+                    format!("Speed ramp {} overlaps cut {}", i, j),
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Whether any issue is severe enough for strict construction to reject the config.
+pub fn has_errors(issues: &[ValidationIssue]) -> bool {
+    issues
+        .iter()
+        .any(|issue| issue.severity == IssueSeverity::Error)
+}
+
+/// Half-open overlap test; empty ranges never overlap anything.
+fn overlaps(a: &TimeRangeConfig, b: &TimeRangeConfig) -> bool {
+    a.start_us < a.end_us && b.start_us < b.end_us && a.start_us < b.end_us && b.start_us < a.end_us
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migrate::REMAPPER_CONFIG_VERSION;
+    use crate::time_remap::SpeedRampConfig;
+
+    fn config(cuts: &[(u64, u64)], ramps: &[(u64, u64, f32)]) -> TimeRemapperConfig {
+        TimeRemapperConfig {
+            version: REMAPPER_CONFIG_VERSION,
+            cuts: cuts
+                .iter()
+                .map(|&(start_us, end_us)| TimeRangeConfig { start_us, end_us })
+                .collect(),
+            speed_ramps: ramps
+                .iter()
+                .map(|&(start_us, end_us, speed)| SpeedRampConfig {
+                    start_us,
+                    end_us,
+                    speed,
+                    ramp_in_us: 0,
+                    ramp_out_us: 0,
+                })
+                .collect(),
+            in_point_us: 0,
+            out_point_us: 10_000_000,
+            min_speed: 0.25,
+            max_speed: 4.0,
+            markers: vec![],
+            protected_ranges: vec![],
+        }
+    }
+
+    #[test]
+    fn valid_config_has_no_issues() {
+        let issues = validate_remapper_config(&config(
+            &[(1_000_000, 2_000_000)],
+            &[(3_000_000, 4_000_000, 2.0)],
+        ));
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn reports_overlaps_with_indices() {
+        let issues = validate_remapper_config(&config(
+            &[(1_000_000, 3_000_000), (2_000_000, 4_000_000)],
+            &[(3_500_000, 5_000_000, 2.0)],
+        ));
+
+        let cut_overlap = issues
+            .iter()
+            .find(|i| i.kind == IssueKind::OverlappingCuts)
+            .expect("Should report overlapping cuts");
+        assert_eq!(cut_overlap.indices, vec![0, 1]);
+        assert_eq!(cut_overlap.ranges[1].start_us, 2_000_000);
+
+        let ramp_cut = issues
+            .iter()
+            .find(|i| i.kind == IssueKind::SpeedRampOverlapsCut)
+            .expect("Should report ramp overlapping cut");
+        assert_eq!(ramp_cut.indices, vec![0, 1]);
+        assert!(has_errors(&issues));
+    }
+
+    #[test]
+    fn reports_inverted_trim_and_clamped_speed() {
+        let mut cfg = config(&[], &[(0, 1_000_000, 8.0)]);
+        cfg.in_point_us = 5_000_000;
+        cfg.out_point_us = 2_000_000;
+
+        let kinds: Vec<IssueKind> = validate_remapper_config(&cfg)
+            .iter()
+            .map(|i| i.kind)
+            .collect();
+        assert!(kinds.contains(&IssueKind::OutBeforeIn));
+        assert!(kinds.contains(&IssueKind::SpeedOutOfRange));
+    }
+
+    #[test]
+    fn speed_limits_come_from_config() {
+        let mut cfg = config(&[], &[(0, 1_000_000, 8.0)]);
+        cfg.max_speed = 16.0;
+        assert!(validate_remapper_config(&cfg).is_empty());
+
+        cfg.min_speed = 0.0;
+        let issues = validate_remapper_config(&cfg);
+        assert_eq!(issues[0].kind, IssueKind::InvalidSpeedRange);
+        assert!(has_errors(&issues));
+    }
+}
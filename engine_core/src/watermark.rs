@@ -0,0 +1,175 @@
+// Watermark placement: keeps a branding overlay in a corner of the output the content
+// isn't using. The camera decides what ends up on screen, so placement happens here
+// rather than in every renderer.
+// See steering.md: Rust Rules (Engine)
+
+use crate::camera::CameraEngine;
+use crate::types::*;
+
+/// How often placement is re-checked (microseconds).
+const WATERMARK_SAMPLE_US: u64 = 250_000;
+/// A focus region counts as on screen for this long after it appears.
+const WATERMARK_FOCUS_US: u64 = 2_000_000;
+
+/// Watermark spans from `start` to `end`. The watermark sits at `settings.anchor` unless
+/// the focus region on screen (as framed by the camera) would be under it, in which case
+/// it moves to the nearest clear corner. Positions are in output-frame coordinates,
+/// since the watermark is drawn over the camera rather than inside it.
+pub fn place_watermark(
+    settings: &WatermarkSettings,
+    camera: &CameraEngine,
+    focus_regions: &[FocusRegion],
+    start: Timestamp,
+    end: Timestamp,
+) -> Vec<Effect> {
+    let effect_type = EffectType::Watermark {
+        asset_id: settings.asset_id.clone(),
+        width: settings.width,
+        height: settings.height,
+    };
+    let style = EffectStyle {
+        opacity: settings.opacity,
+        ..effect_type.default_style()
+    };
+
+    let mut candidates = vec![settings.anchor];
+    candidates.extend(
+        [
+            OverlayPosition::BottomRight,
+            OverlayPosition::TopRight,
+            OverlayPosition::BottomLeft,
+            OverlayPosition::TopLeft,
+        ]
+        .into_iter()
+        .filter(|corner| *corner != settings.anchor),
+    );
+
+    let mut spans: Vec<(Timestamp, OverlayPosition)> = Vec::new();
+    let mut t = start.as_micros();
+    while t < end.as_micros() {
+        let ts = Timestamp::from_micros(t);
+        let on_screen = focus_on_screen(camera.get_viewport_at(ts), focus_regions, ts);
+        let current = spans.last().map(|(_, corner)| *corner);
+        let clear = |corner: &OverlayPosition| {
+            on_screen.is_none_or(|focus| {
+                watermark_rect(settings, *corner)
+                    .intersection(&focus)
+                    .is_none()
+            })
+        };
+        // Prefer the configured anchor, then staying put, then the first clear corner.
+        let corner = candidates
+            .iter()
+            .take(1)
+            .chain(current.iter())
+            .chain(candidates.iter())
+            .find(|corner| clear(corner))
+            .copied()
+            .unwrap_or(settings.anchor);
+        if current != Some(corner) {
+            spans.push((ts, corner));
+        }
+        t += WATERMARK_SAMPLE_US;
+    }
+
+    spans
+        .iter()
+        .enumerate()
+        .map(|(i, (at, corner))| {
+            let until = spans.get(i + 1).map_or(end, |(next, _)| *next);
+            Effect {
+                timestamp: *at,
+                duration_us: until.as_micros() - at.as_micros(),
+                effect_type: effect_type.clone(),
+                position: watermark_rect(settings, *corner).center(),
+                style: style.clone(),
+                envelope: EffectEnvelope::default(),
+            }
+        })
+        .collect()
+}
+
+/// The latest recent focus region, in output-frame coordinates under `viewport`.
+fn focus_on_screen(
+    viewport: Viewport,
+    focus_regions: &[FocusRegion],
+    ts: Timestamp,
+) -> Option<NormalizedRect> {
+    let region = focus_regions
+        .iter()
+        .filter(|region| {
+            region.timestamp <= ts
+                && ts.as_micros() - region.timestamp.as_micros() <= WATERMARK_FOCUS_US
+        })
+        .max_by_key(|region| region.timestamp)?;
+    let visible = viewport.visible_rect();
+    Some(NormalizedRect::new(
+        (region.bounds.x - visible.x) / visible.width,
+        (region.bounds.y - visible.y) / visible.height,
+        region.bounds.width / visible.width,
+        region.bounds.height / visible.height,
+    ))
+}
+
+/// Where the watermark sits at `anchor`, inset by the margin so it stays fully in frame.
+fn watermark_rect(settings: &WatermarkSettings, anchor: OverlayPosition) -> NormalizedRect {
+    let (w, h, m) = (settings.width, settings.height, settings.margin);
+    let x = match anchor {
+        OverlayPosition::TopLeft | OverlayPosition::BottomLeft => m,
+        OverlayPosition::TopCenter | OverlayPosition::BottomCenter => (1.0 - w) / 2.0,
+        OverlayPosition::TopRight | OverlayPosition::BottomRight => 1.0 - m - w,
+    };
+    let y = match anchor {
+        OverlayPosition::TopLeft | OverlayPosition::TopCenter | OverlayPosition::TopRight => m,
+        _ => 1.0 - m - h,
+    };
+    NormalizedRect::new(x, y, w, h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_away_from_focus_under_the_watermark() {
+        let settings = WatermarkSettings {
+            asset_id: "logo".to_string(),
+            ..Default::default()
+        };
+        let camera = CameraEngine::new(CameraSettings::default());
+        let region = FocusRegion {
+            timestamp: Timestamp::from_micros(1_000_000),
+            bounds: NormalizedRect::new(0.7, 0.8, 0.3, 0.2),
+            importance: 1.0,
+            element: None,
+            track_id: None,
+        };
+
+        let effects = place_watermark(
+            &settings,
+            &camera,
+            &[region],
+            Timestamp::from_micros(0),
+            Timestamp::from_micros(5_000_000),
+        );
+        let placements: Vec<(u64, u64, bool)> = effects
+            .iter()
+            .map(|effect| {
+                (
+                    effect.timestamp.as_micros(),
+                    effect.duration_us,
+                    effect.position.y > 0.5,
+                )
+            })
+            .collect();
+        // Bottom right until the region shows up there, top right while it's recent.
+        assert_eq!(
+            placements,
+            vec![
+                (0, 1_000_000, true),
+                (1_000_000, 2_250_000, false),
+                (3_250_000, 1_750_000, true),
+            ]
+        );
+    }
+}